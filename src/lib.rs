@@ -10,6 +10,10 @@ mod midi_modes;
 mod midi_devices;
 mod midi_learn;
 mod midi_clock;
+mod tempo;
+mod macro_controls;
+mod scenes;
+mod stems;
 
 use egui_taffy::taffy::{
     prelude::*,
@@ -21,17 +25,44 @@ use nih_plug_egui::{create_egui_editor, egui};
 use params::DeviceParams;
 use std::sync::Arc;
 use ui::{Page, SharedUiState};
-use synth::{SynthEngine, MasterLimiter, MasterHpf, BoxCutFilter, BrillianceFilter, StereoControl, PitchedLooper, LushReverb, Compressor};
+use synth::{SynthEngine, MasterLimiter, MasterHpf, BoxCutFilter, BrillianceFilter, StereoControl, PitchedLooper, LushReverb, StereoDelay, ModFx, Compressor};
 use synth::compressor::{ScHpfMode, LookaheadMode};
 use synth::master_hpf::{HpfMode, HpfBoost};
 use synth::box_cut::BoxCutMode;
 use synth::looper::LoopDirection;
+use synth::delay::{DelayMode, DelayParams};
+use synth::mod_fx::{ModFxMode, ModFxParams};
+use synth::fx_chain::FxModule;
 use synth::lfo::LfoSyncDivision;
 use midi::MidiProcessor;
 use midi_modes::{MidiInputMode, MidiModeProcessor, MidiModeResult};
+use sequencer::TransposeQuantize;
+use tempo::TempoTracker;
+use macro_controls::MacroRouter;
+use preset::MorphSnapshots;
+use scenes::{SceneClock, SceneMidi};
+use stems::{Stem, StemTaps, STEM_COUNT, STEM_NAMES};
+
+/// Smallest move of the morph control that re-blends the sequencer pattern.
+const MORPH_PATTERN_STEP: f32 = 1.0 / 64.0;
 
 pub struct PhaseBurn {
     params: Arc<DeviceParams>,
+    macros: MacroRouter,
+    morph: MorphSnapshots,
+    /// Morph position the sequencer pattern was last blended at; NaN until
+    /// the first block after new endpoints arrive.
+    morph_pattern_position: f32,
+    scene_clock: SceneClock,
+    /// Serial of the last recall taken from the scene clock.
+    scene_serial: u32,
+    /// False when the last recall hit an empty slot.
+    scene_following: bool,
+    /// Recall progress the sequencer pattern was last blended at; NaN once
+    /// the pattern has landed.
+    scene_pattern_progress: f32,
+    /// Whether the landed scene offsets have been handed to the editor.
+    scene_published: bool,
     synth_engine: Option<SynthEngine>,
     ui_state: Arc<SharedUiState>,
     midi_processor: MidiProcessor,
@@ -48,15 +79,24 @@ pub struct PhaseBurn {
     stereo_control: StereoControl,
     looper: PitchedLooper,
     reverb: LushReverb,
+    delay: StereoDelay,
+    mod_fx: ModFx,
     compressor: Compressor,
-    comp_pre_looper_l: Vec<f32>,
-    comp_pre_looper_r: Vec<f32>,
-    comp_pre_reverb_l: Vec<f32>,
-    comp_pre_reverb_r: Vec<f32>,
+    slot_dry_l: Vec<f32>,
+    slot_dry_r: Vec<f32>,
+    fx_return_l: Vec<f32>,
+    fx_return_r: Vec<f32>,
+    looper_return_l: Vec<f32>,
+    looper_return_r: Vec<f32>,
+    reverb_return_l: Vec<f32>,
+    reverb_return_r: Vec<f32>,
+    stems: StemTaps,
     midi_events_buffer: Vec<(bool, bool, u8, u8, usize)>,
     midi_mode_processor: MidiModeProcessor,
     midi_clock_pll: midi_clock::MidiClockPll,
-    midi_clock_out_phase: f64,
+    tempo_tracker: TempoTracker,
+    midi_out_backlog: Vec<midi_devices::ScheduledMidiMessage>,
+    midi_block_clock: midi::BlockClock,
     process_time_seconds: f64,
     transport_has_played: bool,
     was_playing: bool,
@@ -66,16 +106,33 @@ pub struct PhaseBurn {
     sub_buffer: Vec<f32>,
     reverb_send_l: Vec<f32>,
     reverb_send_r: Vec<f32>,
+    delay_send_l: Vec<f32>,
+    delay_send_r: Vec<f32>,
     looper_input_l: Vec<f32>,
     looper_input_r: Vec<f32>,
     cpu_measure_counter: u32,
     last_reported_latency: u32,
 }
 
+/// Work the audio thread hands to nih-plug's background thread.
+pub enum PhaseBurnTask {
+    /// Drop the table the last wavetable swap replaced.
+    FreeRetiredWavetable,
+}
+
 impl Default for PhaseBurn {
     fn default() -> Self {
+        let params = Arc::new(DeviceParams::default());
         Self {
-            params: Arc::new(DeviceParams::default()),
+            macros: MacroRouter::new(&*params),
+            morph: MorphSnapshots::default(),
+            morph_pattern_position: f32::NAN,
+            scene_clock: SceneClock::default(),
+            scene_serial: 0,
+            scene_following: false,
+            scene_pattern_progress: f32::NAN,
+            scene_published: false,
+            params,
             synth_engine: None,
             ui_state: Arc::new(SharedUiState::new()),
             midi_processor: MidiProcessor::new(),
@@ -92,15 +149,24 @@ impl Default for PhaseBurn {
             stereo_control: StereoControl::new(44100.0),
             looper: PitchedLooper::new(44100.0),
             reverb: LushReverb::new(44100.0),
+            delay: StereoDelay::new(44100.0),
+            mod_fx: ModFx::new(44100.0),
             compressor: Compressor::new(44100.0),
-            comp_pre_looper_l: Vec::new(),
-            comp_pre_looper_r: Vec::new(),
-            comp_pre_reverb_l: Vec::new(),
-            comp_pre_reverb_r: Vec::new(),
+            slot_dry_l: Vec::new(),
+            slot_dry_r: Vec::new(),
+            fx_return_l: Vec::new(),
+            fx_return_r: Vec::new(),
+            looper_return_l: Vec::new(),
+            looper_return_r: Vec::new(),
+            reverb_return_l: Vec::new(),
+            reverb_return_r: Vec::new(),
+            stems: StemTaps::default(),
             midi_events_buffer: Vec::with_capacity(64),
             midi_mode_processor: MidiModeProcessor::new(),
             midi_clock_pll: midi_clock::MidiClockPll::new(),
-            midi_clock_out_phase: 0.0,
+            tempo_tracker: TempoTracker::default(),
+            midi_out_backlog: Vec::with_capacity(512),
+            midi_block_clock: midi::BlockClock::default(),
             process_time_seconds: 0.0,
             transport_has_played: false,
             was_playing: false,
@@ -110,6 +176,8 @@ impl Default for PhaseBurn {
             sub_buffer: Vec::new(),
             reverb_send_l: Vec::new(),
             reverb_send_r: Vec::new(),
+            delay_send_l: Vec::new(),
+            delay_send_r: Vec::new(),
             looper_input_l: Vec::new(),
             looper_input_r: Vec::new(),
             cpu_measure_counter: 0,
@@ -118,6 +186,348 @@ impl Default for PhaseBurn {
     }
 }
 
+/// Per-block values shared by the master chain modules.
+struct FxChainContext {
+    hpf_mode: HpfMode,
+    hpf_boost: HpfBoost,
+    box_cut_mode: BoxCutMode,
+    tempo: f64,
+    bar_index: u64,
+    seq_playing: bool,
+    lp_premaster: bool,
+}
+
+impl PhaseBurn {
+    /// Scene recall messages are taken out before the sequencer and synth
+    /// see them.
+    fn route_incoming_event(&mut self, event: NoteEvent<()>, program_change: bool, note_channel: i32) {
+        match scenes::route_midi(&event, program_change, note_channel) {
+            SceneMidi::Recall(scene) => self.ui_state.scenes.request_recall(scene),
+            SceneMidi::Consumed => {}
+            SceneMidi::Pass => self.midi_processor.process_incoming_event(event),
+        }
+    }
+
+    /// Takes new A/B endpoints from the editor. The previous set goes back
+    /// in their place so it is freed off the audio thread.
+    fn take_morph_snapshots(&mut self) {
+        if !self.ui_state.morph_dirty.swap(false, std::sync::atomic::Ordering::AcqRel) {
+            return;
+        }
+        let Ok(mut snapshots) = self.ui_state.morph_snapshots.try_lock() else {
+            self.ui_state.mark_morph_dirty();
+            return;
+        };
+        std::mem::swap(&mut self.morph, &mut *snapshots);
+        // A running scene recall keeps blending from the pattern it started at
+        std::mem::swap(&mut self.morph.scene_from, &mut snapshots.scene_from);
+        match &self.morph.ab {
+            Some((a, b)) => self.macros.set_morph(&a.params, &b.params),
+            None => self.macros.clear_morph(),
+        }
+        self.morph_pattern_position = f32::NAN;
+    }
+
+    /// Crossfades the sequencer pattern along the A/B morph. It only follows
+    /// moves of the control, so the first block just records the position and
+    /// a pattern edited by hand stays until the next move.
+    fn apply_morph_pattern(&mut self, position: f32) {
+        let MorphSnapshots { ab: Some((a, b)), blended, .. } = &mut self.morph else { return };
+        let last = self.morph_pattern_position;
+        if last.is_nan() {
+            self.morph_pattern_position = position;
+            return;
+        }
+        let at_end = (position <= 0.0 || position >= 1.0) && position != last;
+        if (position - last).abs() < MORPH_PATTERN_STEP && !at_end {
+            return;
+        }
+        blended.blend_into(&a.pattern, &b.pattern, position);
+        if self.ui_state.try_store_pattern(blended) {
+            self.morph_pattern_position = position;
+        }
+    }
+
+    /// Follows the recall timed by the scene clock. A new recall starts the
+    /// router's scene offsets from the sound being heard, and the sequencer
+    /// pattern crossfades to the scene's as the recall runs. Returns the
+    /// progress the scene offsets are at for this block.
+    fn apply_scene_recall(&mut self) -> f32 {
+        if self.ui_state.scenes.take_reset() {
+            self.macros.clear_scene();
+            self.scene_pattern_progress = f32::NAN;
+            self.scene_published = false;
+        }
+
+        let scene = self.scene_clock.state().map(|(scene, _)| scene);
+        if self.scene_clock.serial() != self.scene_serial {
+            self.scene_serial = self.scene_clock.serial();
+            let MorphSnapshots { scenes, scene_from, .. } = &mut self.morph;
+            let target = scene.and_then(|scene| scenes.get(scene)).and_then(Option::as_ref);
+            // An empty slot leaves the current sound alone
+            self.scene_following = target.is_some();
+            if let Some(target) = target {
+                self.macros.start_scene(&target.params);
+                let ui = &self.ui_state;
+                match (
+                    ui.strength_values.try_lock(),
+                    ui.note_pool.try_lock(),
+                    ui.scale.try_lock(),
+                    ui.stability_pattern.try_lock(),
+                    ui.octave_randomization.try_lock(),
+                    ui.style_config.try_lock(),
+                ) {
+                    (Ok(strength), Ok(pool), Ok(scale), Ok(stability), Ok(octave), Ok(style)) => {
+                        scene_from.read_from(&strength, &pool, *scale, *stability, &octave, &style);
+                    }
+                    // The editor holds the pattern; start from the scene's own
+                    _ => scene_from.clone_from(&target.pattern),
+                }
+                self.scene_pattern_progress = -1.0;
+                self.scene_published = false;
+            }
+        }
+
+        let progress = match self.scene_clock.state() {
+            Some((_, progress)) if self.scene_following => progress,
+            _ => 1.0,
+        };
+
+        let last = self.scene_pattern_progress;
+        if !last.is_nan() && progress >= 0.0 && (progress >= 1.0 || progress - last >= MORPH_PATTERN_STEP) {
+            let MorphSnapshots { scenes, blended, scene_from, .. } = &mut self.morph;
+            if let Some(target) = scene.and_then(|scene| scenes.get(scene)).and_then(Option::as_ref) {
+                blended.blend_into(scene_from, &target.pattern, progress);
+                if self.ui_state.try_store_pattern(blended) {
+                    self.scene_pattern_progress = if progress >= 1.0 { f32::NAN } else { progress };
+                }
+            }
+        }
+
+        if progress >= 1.0 && !self.scene_published {
+            // The editor reserved room for every routed param
+            if let Ok(mut offsets) = self.ui_state.scenes.offsets.try_lock() {
+                offsets.clear();
+                offsets.extend(self.macros.scene_offsets());
+                self.scene_published = true;
+            }
+        }
+        progress
+    }
+
+    fn process_mod_fx(&mut self, num_samples: usize, tempo: f64) {
+        let rate_hz = if self.macros.plain(&self.params.mod_fx_sync) {
+            let div = LfoSyncDivision::from_index(self.macros.plain(&self.params.mod_fx_division));
+            tempo / 60.0 / div.beats()
+        } else {
+            self.macros.plain(&self.params.mod_fx_rate) as f64
+        };
+        self.mod_fx.set_params(&ModFxParams {
+            mode: ModFxMode::from_index(self.macros.plain(&self.params.mod_fx_mode)),
+            rate_hz,
+            depth: self.macros.plain(&self.params.mod_fx_depth) as f64,
+            feedback: self.macros.plain(&self.params.mod_fx_feedback) as f64,
+            mix: self.macros.plain(&self.params.mod_fx_mix) as f64,
+            voices: self.macros.plain(&self.params.mod_fx_voices),
+            stages: self.macros.plain(&self.params.mod_fx_stages),
+            spread: self.macros.plain(&self.params.mod_fx_spread) as f64,
+        });
+        let (rate_mod, depth_mod, feedback_mod) = match &self.synth_engine {
+            Some(synth) => synth.mod_fx_modulation(),
+            None => (&[][..], &[][..], &[][..]),
+        };
+        self.mod_fx.process_block(
+            &mut self.output_buffer_l[..num_samples],
+            &mut self.output_buffer_r[..num_samples],
+            rate_mod,
+            depth_mod,
+            feedback_mod,
+        );
+    }
+
+    /// Runs one movable module of the master chain in place on the output bus.
+    fn process_fx_module(&mut self, module: FxModule, num_samples: usize, ctx: &FxChainContext) {
+        match module {
+            FxModule::MasterHpf => {
+                self.master_hpf.set_mode(ctx.hpf_mode);
+                self.master_hpf.set_boost(ctx.hpf_boost);
+                self.master_hpf.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
+            }
+            FxModule::BoxCut => {
+                self.box_cut.set_mode(ctx.box_cut_mode);
+                self.box_cut.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
+            }
+            FxModule::Brilliance => {
+                let brill_amount = self.macros.plain(&self.params.brilliance_amount) as f64;
+                self.brilliance.set_amount(brill_amount);
+                self.brilliance.set_drive(brill_amount);
+                self.brilliance.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
+            }
+            FxModule::Looper => {
+                if ctx.lp_premaster {
+                    self.looper_input_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
+                    self.looper_input_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
+                }
+                // Keep the looper's own contribution for the reverb's looper send
+                self.looper_return_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
+                self.looper_return_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
+
+                let current_freq = self.synth_engine.as_ref()
+                    .map(|s| s.current_frequency())
+                    .unwrap_or(440.0);
+                let looper_length_beats = LfoSyncDivision::from_index(self.macros.plain(&self.params.looper_length)).beats();
+                let auto_rec_beats = LfoSyncDivision::from_index(self.macros.plain(&self.params.looper_auto_rec_len)).beats();
+                self.looper.process_block(
+                    &mut self.output_buffer_l[..num_samples],
+                    &mut self.output_buffer_r[..num_samples],
+                    &self.looper_input_l[..num_samples],
+                    &self.looper_input_r[..num_samples],
+                    self.macros.plain(&self.params.looper_enabled),
+                    self.macros.plain(&self.params.looper_pitch) as f64,
+                    looper_length_beats,
+                    self.macros.plain(&self.params.looper_start) as f64,
+                    LoopDirection::from_index(self.macros.plain(&self.params.looper_direction)),
+                    self.macros.plain(&self.params.looper_mix) as f64,
+                    self.macros.plain(&self.params.looper_decay) as f64,
+                    self.macros.plain(&self.params.looper_stutter),
+                    self.macros.plain(&self.params.looper_key_track),
+                    self.macros.plain(&self.params.looper_freeze),
+                    ctx.bar_index,
+                    ctx.tempo,
+                    current_freq,
+                    ctx.seq_playing,
+                    auto_rec_beats,
+                    self.macros.plain(&self.params.looper_auto_rec_interval),
+                    self.macros.plain(&self.params.looper_doppler) as f64,
+                );
+
+                for i in 0..num_samples {
+                    self.looper_return_l[i] = self.output_buffer_l[i] - self.looper_return_l[i];
+                    self.looper_return_r[i] = self.output_buffer_r[i] - self.looper_return_r[i];
+                }
+            }
+            FxModule::ModFx => {
+                if self.macros.plain(&self.params.mod_fx_enable) {
+                    self.process_mod_fx(num_samples, ctx.tempo);
+                }
+            }
+            FxModule::Delay => {
+                if self.macros.plain(&self.params.synth_delay_enable) {
+                    let delay_ms = if self.macros.plain(&self.params.synth_delay_sync) {
+                        let div = LfoSyncDivision::from_index(self.macros.plain(&self.params.synth_delay_division));
+                        (div.beats() / ctx.tempo * 60000.0).min(synth::delay::MAX_DELAY_MS)
+                    } else {
+                        self.macros.plain(&self.params.synth_delay_time) as f64
+                    };
+                    self.delay.set_params(&DelayParams {
+                        mode: DelayMode::from_index(self.macros.plain(&self.params.synth_delay_mode)),
+                        mix: self.macros.plain(&self.params.synth_delay_mix) as f64,
+                        time_ms: delay_ms,
+                        spread: self.macros.plain(&self.params.synth_delay_spread) as f64,
+                        feedback: self.macros.plain(&self.params.synth_delay_feedback) as f64,
+                        lpf_hz: self.macros.plain(&self.params.synth_delay_lpf) as f64,
+                        hpf_hz: self.macros.plain(&self.params.synth_delay_hpf) as f64,
+                        saturation: self.macros.plain(&self.params.synth_delay_saturation) as f64,
+                        mod_rate: self.macros.plain(&self.params.synth_delay_mod_rate) as f64,
+                        mod_depth: self.macros.plain(&self.params.synth_delay_mod_depth) as f64,
+                        ducking: self.macros.plain(&self.params.synth_delay_ducking) as f64,
+                    });
+                    self.delay.process_block(
+                        &mut self.output_buffer_l[..num_samples],
+                        &mut self.output_buffer_r[..num_samples],
+                        &self.delay_send_l[..num_samples],
+                        &self.delay_send_r[..num_samples],
+                    );
+                }
+            }
+            FxModule::Reverb => {
+                if self.macros.plain(&self.params.synth_reverb_enable) {
+                    // Looper output only reaches the reverb when the looper runs earlier in the chain
+                    if self.macros.plain(&self.params.synth_reverb_send_looper) && self.macros.plain(&self.params.looper_enabled) {
+                        for i in 0..num_samples {
+                            self.reverb_send_l[i] += self.looper_return_l[i];
+                            self.reverb_send_r[i] += self.looper_return_r[i];
+                        }
+                    }
+
+                    let duck_div = LfoSyncDivision::from_index(self.macros.plain(&self.params.synth_reverb_duck_division));
+                    let duck_release_ms = duck_div.beats() / ctx.tempo * 60000.0;
+                    let pre_delay_ms = if self.macros.plain(&self.params.synth_reverb_pre_delay_sync) {
+                        let div = LfoSyncDivision::from_index(self.macros.plain(&self.params.synth_reverb_pre_delay_division));
+                        (div.beats() / ctx.tempo * 60000.0).min(500.0)
+                    } else {
+                        self.macros.plain(&self.params.synth_reverb_pre_delay) as f64
+                    };
+                    self.reverb.set_params(
+                        self.macros.plain(&self.params.synth_reverb_mix) as f64,
+                        pre_delay_ms,
+                        self.macros.plain(&self.params.synth_reverb_time_scale) as f64,
+                        self.macros.plain(&self.params.synth_reverb_input_hpf) as f64,
+                        self.macros.plain(&self.params.synth_reverb_input_lpf) as f64,
+                        self.macros.plain(&self.params.synth_reverb_hpf) as f64,
+                        self.macros.plain(&self.params.synth_reverb_lpf) as f64,
+                        self.macros.plain(&self.params.synth_reverb_mod_speed) as f64,
+                        self.macros.plain(&self.params.synth_reverb_mod_depth) as f64,
+                        self.macros.plain(&self.params.synth_reverb_mod_shape) as f64,
+                        self.macros.plain(&self.params.synth_reverb_diffusion_mix) as f64,
+                        self.macros.plain(&self.params.synth_reverb_diffusion) as f64,
+                        self.macros.plain(&self.params.synth_reverb_decay) as f64,
+                        self.macros.plain(&self.params.synth_reverb_ducking) as f64,
+                        duck_release_ms,
+                        self.macros.plain(&self.params.synth_reverb_stereo_width) as f64,
+                        self.macros.plain(&self.params.synth_reverb_saturation) as f64,
+                    );
+                    let rhythm_div = LfoSyncDivision::from_index(self.macros.plain(&self.params.synth_reverb_rhythm_duck_division));
+                    let rhythm_duck_freq = ctx.tempo / 60.0 / rhythm_div.beats();
+                    self.reverb.set_rhythm_duck_params(
+                        self.macros.plain(&self.params.synth_reverb_rhythm_duck_depth) as f64,
+                        rhythm_duck_freq,
+                        self.macros.plain(&self.params.synth_reverb_rhythm_duck_smooth) as f64,
+                    );
+                    self.reverb_return_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
+                    self.reverb_return_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
+                    self.reverb.process_block(
+                        &mut self.output_buffer_l[..num_samples],
+                        &mut self.output_buffer_r[..num_samples],
+                        &self.reverb_send_l[..num_samples],
+                        &self.reverb_send_r[..num_samples],
+                    );
+                    for i in 0..num_samples {
+                        self.reverb_return_l[i] = self.output_buffer_l[i] - self.reverb_return_l[i];
+                        self.reverb_return_r[i] = self.output_buffer_r[i] - self.reverb_return_r[i];
+                    }
+                }
+            }
+            FxModule::Compressor => {
+                if self.macros.plain(&self.params.comp_enable) {
+                    self.compressor.set_params(
+                        self.macros.plain(&self.params.comp_threshold) as f64,
+                        self.macros.plain(&self.params.comp_ratio) as f64,
+                        self.macros.plain(&self.params.comp_attack) as f64,
+                        self.macros.plain(&self.params.comp_release) as f64,
+                        self.macros.plain(&self.params.comp_makeup) as f64,
+                        self.macros.plain(&self.params.comp_mix) as f64,
+                        ScHpfMode::from_index(self.macros.plain(&self.params.comp_sc_hpf)),
+                        LookaheadMode::from_index(self.macros.plain(&self.params.comp_lookahead)),
+                        self.macros.plain(&self.params.comp_knee) as f64,
+                        self.macros.plain(&self.params.comp_stereo_link) as f64,
+                        self.macros.plain(&self.params.comp_auto_makeup),
+                    );
+                    self.compressor.process_block(
+                        &mut self.output_buffer_l[..num_samples],
+                        &mut self.output_buffer_r[..num_samples],
+                    );
+                }
+            }
+            FxModule::StereoControl => {
+                self.stereo_control.set_crossover_hz(self.macros.plain(&self.params.stereo_mono_bass) as f64);
+                self.stereo_control.set_width(self.macros.plain(&self.params.stereo_width) as f64);
+                self.stereo_control.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
+            }
+        }
+    }
+}
+
 impl Plugin for PhaseBurn {
     const NAME: &'static str = "PhaseBurn";
     const VENDOR: &'static str = "PhaseBurn Audio";
@@ -125,25 +535,49 @@ impl Plugin for PhaseBurn {
     const EMAIL: &'static str = "info@example.com";
     const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
-    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[AudioIOLayout {
-        main_input_channels: None,
-        main_output_channels: NonZeroU32::new(2),
-        aux_input_ports: &[],
-        aux_output_ports: &[],
-        names: PortNames::const_default(),
-    }];
+    // The first layout is the default; hosts that support it can pick the
+    // stem layout to route sources and effect returns separately
+    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[
+        AudioIOLayout {
+            main_input_channels: None,
+            main_output_channels: NonZeroU32::new(2),
+            aux_input_ports: &[],
+            aux_output_ports: &[],
+            names: PortNames::const_default(),
+        },
+        AudioIOLayout {
+            main_input_channels: None,
+            main_output_channels: NonZeroU32::new(2),
+            aux_input_ports: &[],
+            aux_output_ports: &[new_nonzero_u32(2); STEM_COUNT],
+            names: PortNames {
+                layout: Some("Stereo + Stems"),
+                main_input: None,
+                main_output: Some("Main"),
+                aux_inputs: &[],
+                aux_outputs: &STEM_NAMES,
+            },
+        },
+    ];
 
     const MIDI_INPUT: MidiConfig = MidiConfig::MidiCCs;
     const MIDI_OUTPUT: MidiConfig = MidiConfig::MidiCCs;
     const SAMPLE_ACCURATE_AUTOMATION: bool = true;
 
     type SysExMessage = ();
-    type BackgroundTask = ();
+    type BackgroundTask = PhaseBurnTask;
 
     fn params(&self) -> Arc<dyn Params> {
         self.params.clone()
     }
 
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        let ui_state = self.ui_state.clone();
+        Box::new(move |task| match task {
+            PhaseBurnTask::FreeRetiredWavetable => ui_state.wavetable.free_retired(),
+        })
+    }
+
     fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         let params = self.params.clone();
         let ui_state = self.ui_state.clone();
@@ -219,7 +653,10 @@ impl Plugin for PhaseBurn {
                         });
 
                     if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
-                        mgr.flush_output();
+                        mgr.sync_manual_bpm(
+                            ui_state.transport.get_manual_bpm(),
+                            ui_state.transport.ramping.load(std::sync::atomic::Ordering::Relaxed),
+                        );
                     }
                 });
             },
@@ -228,7 +665,7 @@ impl Plugin for PhaseBurn {
 
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
+        audio_io_layout: &AudioIOLayout,
         buffer_config: &BufferConfig,
         _context: &mut impl InitContext<Self>,
     ) -> bool {
@@ -257,7 +694,25 @@ impl Plugin for PhaseBurn {
             self.stereo_control.set_sample_rate(new_sample_rate);
             self.looper.set_sample_rate(new_sample_rate);
             self.reverb.set_sample_rate(new_sample_rate);
+            self.delay.set_sample_rate(new_sample_rate);
+            self.mod_fx.set_sample_rate(new_sample_rate);
             self.compressor.set_sample_rate(new_sample_rate as f64);
+            self.midi_block_clock.reset();
+            self.ui_state.wavetable_dirty.store(true, std::sync::atomic::Ordering::Release);
+        }
+
+        // Breakpoints, macro targets and the morph pair are persisted with the plugin state, outside the param system
+        self.ui_state.mark_mseg_dirty();
+        self.ui_state.mark_macro_dirty();
+        ui::pages::presets::update_morph_snapshots(&self.params, &self.ui_state);
+
+        // Restore the session's table; the name is persisted with the plugin state
+        let wanted = self.params.wavetable_name.read().map(|n| n.clone()).unwrap_or_default();
+        let loaded = self.ui_state.wavetable_name();
+        if !wanted.is_empty() && wanted != loaded {
+            if let Ok(table) = synth::wavetable::load_wavetable(&wanted) {
+                self.ui_state.set_wavetable(table);
+            }
         }
 
         let max_block = buffer_config.max_buffer_size as usize;
@@ -266,12 +721,23 @@ impl Plugin for PhaseBurn {
         self.sub_buffer.resize(max_block, 0.0);
         self.reverb_send_l.resize(max_block, 0.0);
         self.reverb_send_r.resize(max_block, 0.0);
+        self.delay_send_l.resize(max_block, 0.0);
+        self.delay_send_r.resize(max_block, 0.0);
         self.looper_input_l.resize(max_block, 0.0);
         self.looper_input_r.resize(max_block, 0.0);
-        self.comp_pre_looper_l.resize(max_block, 0.0);
-        self.comp_pre_looper_r.resize(max_block, 0.0);
-        self.comp_pre_reverb_l.resize(max_block, 0.0);
-        self.comp_pre_reverb_r.resize(max_block, 0.0);
+        self.slot_dry_l.resize(max_block, 0.0);
+        self.slot_dry_r.resize(max_block, 0.0);
+        self.fx_return_l.resize(max_block, 0.0);
+        self.fx_return_r.resize(max_block, 0.0);
+        self.looper_return_l.resize(max_block, 0.0);
+        self.looper_return_r.resize(max_block, 0.0);
+        self.reverb_return_l.resize(max_block, 0.0);
+        self.reverb_return_r.resize(max_block, 0.0);
+        self.stems.resize(max_block, new_sample_rate);
+        self.ui_state.stems_active.store(
+            audio_io_layout.aux_output_ports.len() == STEM_COUNT,
+            std::sync::atomic::Ordering::Relaxed,
+        );
 
         true
     }
@@ -282,6 +748,7 @@ impl Plugin for PhaseBurn {
         }
 
         self.midi_processor.clear_all();
+        self.midi_block_clock.reset();
         self.was_playing = false;
         self.volume_slew = 0.0;
         self.output_level_smoothed = 0.0;
@@ -290,13 +757,15 @@ impl Plugin for PhaseBurn {
     fn process(
         &mut self,
         buffer: &mut Buffer,
-        _aux: &mut AuxiliaryBuffers,
+        aux: &mut AuxiliaryBuffers,
         context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
         self.midi_processor.begin_buffer();
 
+        let scene_program_change = self.macros.plain(&self.params.scene_program_change);
+        let scene_note_channel = self.macros.plain(&self.params.scene_note_channel);
         while let Some(event) = context.next_event() {
-            self.midi_processor.process_incoming_event(event);
+            self.route_incoming_event(event, scene_program_change, scene_note_channel);
         }
 
         let clock_in_enabled = self.ui_state.midi_clock_in.load(std::sync::atomic::Ordering::Relaxed);
@@ -328,7 +797,7 @@ impl Plugin for PhaseBurn {
                     }
                 }
                 if let Some(event) = midi_devices::raw_midi_to_note_event(&raw) {
-                    self.midi_processor.process_incoming_event(event);
+                    self.route_incoming_event(event, scene_program_change, scene_note_channel);
                 }
             }
         }
@@ -350,26 +819,47 @@ impl Plugin for PhaseBurn {
         self.midi_clock_pll.advance_samples(num_samples as u32, self.sample_rate);
         self.process_time_seconds += num_samples as f64 / self.sample_rate as f64;
 
-        let tempo = if clock_in_enabled && self.midi_clock_pll.is_locked() {
-            self.midi_clock_pll.bpm()
+        let was_internal_playing = self.tempo_tracker.is_playing();
+        self.tempo_tracker.set_host_available(context.plugin_api() != PluginApi::Standalone);
+        self.tempo_tracker.apply_controls(&self.ui_state.transport, self.process_time_seconds);
+        self.tempo_tracker.set_host_tempo(transport.tempo, transport.playing);
+        self.tempo_tracker.set_clock_tempo(if clock_in_enabled && self.midi_clock_pll.is_locked() {
+            Some(self.midi_clock_pll.bpm())
         } else {
-            transport.tempo.unwrap_or(120.0)
-        };
-        self.ui_state.current_tempo.store((tempo * 100.0) as u32, std::sync::atomic::Ordering::Relaxed);
+            None
+        });
+        let internal_transport = self.tempo_tracker.uses_internal_transport();
 
-        let mut is_playing = transport.playing;
+        let mut is_playing = if internal_transport {
+            self.tempo_tracker.is_playing()
+        } else {
+            transport.playing
+        };
 
         if transport_in_enabled {
             if self.ui_state.midi_transport_start.swap(false, std::sync::atomic::Ordering::Relaxed) {
+                if internal_transport {
+                    self.tempo_tracker.play();
+                }
                 is_playing = true;
                 self.transport_has_played = true;
             }
             if self.ui_state.midi_transport_stop.swap(false, std::sync::atomic::Ordering::Relaxed) {
+                if internal_transport {
+                    self.tempo_tracker.stop();
+                }
                 is_playing = false;
             }
         }
 
-        if is_playing {
+        let internal_started = internal_transport && !was_internal_playing && self.tempo_tracker.is_playing();
+        let internal_pos_beats = self.tempo_tracker.position_beats();
+        let tempo = self.tempo_tracker.get_bpm();
+        self.tempo_tracker.advance(num_samples, self.sample_rate);
+        self.tempo_tracker.publish(&self.ui_state.transport);
+        self.ui_state.current_tempo.store((tempo * 100.0) as u32, std::sync::atomic::Ordering::Relaxed);
+
+        if is_playing || internal_transport {
             self.transport_has_played = true;
         }
 
@@ -390,8 +880,39 @@ impl Plugin for PhaseBurn {
             }
         }
 
+        if self.ui_state.macro_dirty.swap(false, std::sync::atomic::Ordering::AcqRel) {
+            if let Ok(targets) = self.params.macro_targets.try_read() {
+                self.macros.set_targets(&targets);
+            } else {
+                self.ui_state.mark_macro_dirty();
+            }
+        }
+        let scene_position = is_playing.then(|| if internal_transport {
+            internal_pos_beats
+        } else {
+            transport.pos_beats().unwrap_or(0.0)
+        });
+        self.scene_clock.apply_controls(
+            &self.ui_state.scenes,
+            scene_position,
+            scenes::quantize_beats(self.macros.plain(&self.params.scene_quantize)),
+            scenes::morph_time_beats(self.macros.plain(&self.params.scene_morph_time)),
+        );
+        self.scene_clock.advance(tempo / 60.0 * num_samples as f64 / self.sample_rate as f64);
+        self.scene_clock.publish(&self.ui_state.scenes);
+
+        self.take_morph_snapshots();
+        let scene_progress = self.apply_scene_recall();
+        let morph_position = self.macros.plain(&self.params.morph_position);
+        self.apply_morph_pattern(morph_position);
+        let macro_values = self.params.macros().map(|p| p.modulated_plain_value());
+        self.macros.update(&macro_values, morph_position, scene_progress);
+
         if let Some(synth) = &mut self.synth_engine {
             synth.set_bpm(tempo);
+            if internal_started {
+                synth.restart_transport();
+            }
 
             if self.ui_state.take_seq_dirty() {
                 if let Ok(note_pool) = self.ui_state.note_pool.try_lock() {
@@ -415,6 +936,9 @@ impl Plugin for PhaseBurn {
                 if let Ok(links) = self.ui_state.beat_links.try_lock() {
                     synth.update_beat_links(links.clone());
                 }
+                if let Ok(scale) = self.ui_state.scale.try_lock() {
+                    synth.update_scale(*scale);
+                }
             }
 
             if self.ui_state.ml_dataset_dirty.swap(false, std::sync::atomic::Ordering::AcqRel) {
@@ -423,151 +947,236 @@ impl Plugin for PhaseBurn {
                 }
             }
 
+            if self.ui_state.wavetable_dirty.swap(false, std::sync::atomic::Ordering::AcqRel) {
+                // The replaced table is parked and freed by a background task
+                if self.ui_state.wavetable.swap(|table| synth.set_wavetable(table)) {
+                    context.execute_background(PhaseBurnTask::FreeRetiredWavetable);
+                } else {
+                    self.ui_state.wavetable_dirty.store(true, std::sync::atomic::Ordering::Release);
+                }
+            }
+
+            if self.ui_state.mseg_dirty.swap(false, std::sync::atomic::Ordering::AcqRel) {
+                if let Ok(shapes) = self.params.mseg_shapes.try_read() {
+                    for (i, shape) in shapes.iter().enumerate() {
+                        synth.set_mseg_shape(i, shape);
+                    }
+                } else {
+                    self.ui_state.mark_mseg_dirty();
+                }
+            }
+
             synth.set_osc_params(
-                self.params.synth_osc_d.modulated_plain_value(),
-                self.params.synth_osc_v.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_osc_d),
+                self.macros.plain(&self.params.synth_osc_v),
             );
 
-            synth.set_osc_volume(self.params.synth_osc_volume.modulated_plain_value());
+            synth.set_osc_volume(self.macros.plain(&self.params.synth_osc_volume));
 
-            synth.set_osc_octave(self.params.synth_osc_octave.value());
+            synth.set_osc_octave(self.macros.plain(&self.params.synth_osc_octave));
             synth.set_osc_tune(
-                self.params.synth_osc_tune.value(),
-                self.params.synth_osc_fine.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_osc_tune),
+                self.macros.plain(&self.params.synth_osc_fine),
             );
-            synth.set_osc_fold(self.params.synth_osc_fold.modulated_plain_value());
+            synth.set_osc_fold(self.macros.plain(&self.params.synth_osc_fold));
 
-            synth.set_vps_stereo_v_offset(self.params.synth_osc_stereo_v_offset.modulated_plain_value());
-            synth.set_vps_stereo_d_offset(self.params.synth_osc_stereo_d_offset.modulated_plain_value());
+            synth.set_vps_stereo_v_offset(self.macros.plain(&self.params.synth_osc_stereo_v_offset));
+            synth.set_vps_stereo_d_offset(self.macros.plain(&self.params.synth_osc_stereo_d_offset));
             synth.set_vps_shape(
-                self.params.synth_vps_shape_type.value(),
-                self.params.synth_vps_shape_amount.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_vps_shape_type),
+                self.macros.plain(&self.params.synth_vps_shape_amount),
             );
-            synth.set_vps_fold_range(self.params.synth_vps_fold_range.value());
+            synth.set_vps_fold_range(self.macros.plain(&self.params.synth_vps_fold_range));
 
 
-            synth.set_sub_volume(self.params.synth_sub_volume.modulated_plain_value());
-            synth.set_sub_filter_route(self.params.synth_sub_filter_route.value());
+            synth.set_sub_volume(self.macros.plain(&self.params.synth_sub_volume));
+            synth.set_sub_filter_route(self.macros.plain(&self.params.synth_sub_filter_route));
 
-            synth.set_saw_volume(self.params.synth_saw_volume.modulated_plain_value());
-            synth.set_saw_octave(self.params.synth_saw_octave.value());
+            synth.set_saw_volume(self.macros.plain(&self.params.synth_saw_volume));
+            synth.set_saw_octave(self.macros.plain(&self.params.synth_saw_octave));
             synth.set_saw_tune(
-                self.params.synth_saw_tune.value(),
-                self.params.synth_saw_fine.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_saw_tune),
+                self.macros.plain(&self.params.synth_saw_fine),
             );
-            synth.set_saw_fold(self.params.synth_saw_fold.modulated_plain_value());
-            synth.set_saw_fold_range(self.params.synth_saw_fold_range.value());
-            synth.set_saw_tight(self.params.synth_saw_tight.modulated_plain_value());
+            synth.set_saw_fold(self.macros.plain(&self.params.synth_saw_fold));
+            synth.set_saw_fold_range(self.macros.plain(&self.params.synth_saw_fold_range));
+            synth.set_saw_tight(self.macros.plain(&self.params.synth_saw_tight));
             synth.set_saw_shape(
-                self.params.synth_saw_shape_type.value(),
-                self.params.synth_saw_shape_amount.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_saw_shape_type),
+                self.macros.plain(&self.params.synth_saw_shape_amount),
+            );
+
+            synth.set_wt_volume(self.macros.plain(&self.params.synth_wt_volume));
+            synth.set_wt_octave(self.macros.plain(&self.params.synth_wt_octave));
+            synth.set_wt_tune(
+                self.macros.plain(&self.params.synth_wt_tune),
+                self.macros.plain(&self.params.synth_wt_fine),
             );
+            synth.set_wt_position(self.macros.plain(&self.params.synth_wt_position));
 
-            synth.set_filter_enabled(self.params.synth_filter_enable.value());
+            synth.set_filter_enabled(self.macros.plain(&self.params.synth_filter_enable));
             synth.set_filter_params(
-                self.params.synth_filter_cutoff.modulated_plain_value(),
-                self.params.synth_filter_resonance.modulated_plain_value(),
-                self.params.synth_filter_drive.modulated_plain_value(),
-                self.params.synth_filter_mode.value(),
+                self.macros.plain(&self.params.synth_filter_cutoff),
+                self.macros.plain(&self.params.synth_filter_resonance),
+                self.macros.plain(&self.params.synth_filter_drive),
+                self.macros.plain(&self.params.synth_filter_mode),
             );
-            synth.set_filter_key_track(self.params.synth_filter_key_track.modulated_plain_value());
-            synth.set_filter_env_amount(self.params.synth_filter_env_amount.modulated_plain_value());
-            synth.set_filter_stereo_sep(self.params.synth_filter_stereo_sep.modulated_plain_value());
+            synth.set_filter_key_track(self.macros.plain(&self.params.synth_filter_key_track));
+            synth.set_filter_env_amount(self.macros.plain(&self.params.synth_filter_env_amount));
+            synth.set_filter_stereo_sep(self.macros.plain(&self.params.synth_filter_stereo_sep));
             let fe_div_to_ms = |div_idx: i32| -> f32 {
                 let div = LfoSyncDivision::from_index(div_idx);
                 (div.beats() as f64 / tempo * 60000.0).max(0.5) as f32
             };
-            let fe_a = if self.params.synth_filter_env_attack_sync.value() {
-                fe_div_to_ms(self.params.synth_filter_env_attack_div.value())
+            let fe_a = if self.macros.plain(&self.params.synth_filter_env_attack_sync) {
+                fe_div_to_ms(self.macros.plain(&self.params.synth_filter_env_attack_div))
             } else {
-                self.params.synth_filter_env_attack.modulated_plain_value()
+                self.macros.plain(&self.params.synth_filter_env_attack)
             };
-            let fe_h = if self.params.synth_filter_env_hold_sync.value() {
-                fe_div_to_ms(self.params.synth_filter_env_hold_div.value())
+            let fe_h = if self.macros.plain(&self.params.synth_filter_env_hold_sync) {
+                fe_div_to_ms(self.macros.plain(&self.params.synth_filter_env_hold_div))
             } else {
-                self.params.synth_filter_env_hold.modulated_plain_value()
+                self.macros.plain(&self.params.synth_filter_env_hold)
             };
-            let fe_d = if self.params.synth_filter_env_decay_sync.value() {
-                fe_div_to_ms(self.params.synth_filter_env_decay_div.value())
+            let fe_d = if self.macros.plain(&self.params.synth_filter_env_decay_sync) {
+                fe_div_to_ms(self.macros.plain(&self.params.synth_filter_env_decay_div))
             } else {
-                self.params.synth_filter_env_decay.modulated_plain_value()
+                self.macros.plain(&self.params.synth_filter_env_decay)
             };
-            let fe_r = if self.params.synth_filter_env_release_sync.value() {
-                fe_div_to_ms(self.params.synth_filter_env_release_div.value())
+            let fe_r = if self.macros.plain(&self.params.synth_filter_env_release_sync) {
+                fe_div_to_ms(self.macros.plain(&self.params.synth_filter_env_release_div))
             } else {
-                self.params.synth_filter_env_release.modulated_plain_value()
+                self.macros.plain(&self.params.synth_filter_env_release)
             };
             synth.set_filter_envelope(
                 fe_a,
-                self.params.synth_filter_env_attack_shape.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_filter_env_attack_shape),
                 fe_d,
-                self.params.synth_filter_env_decay_shape.modulated_plain_value(),
-                self.params.synth_filter_env_sustain.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_filter_env_decay_shape),
+                self.macros.plain(&self.params.synth_filter_env_sustain),
                 fe_r,
-                self.params.synth_filter_env_release_shape.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_filter_env_release_shape),
             );
-            synth.set_filter_env_dip(self.params.synth_filter_env_dip.modulated_plain_value());
-            synth.set_filter_env_range(self.params.synth_filter_env_range.modulated_plain_value());
+            synth.set_filter_env_dip(self.macros.plain(&self.params.synth_filter_env_dip));
+            synth.set_filter_env_range(self.macros.plain(&self.params.synth_filter_env_range));
             synth.set_filt_env_hold(fe_h);
-            synth.set_filt_env_loop_mode(self.params.synth_filter_env_loop_mode.value());
+            synth.set_filt_env_loop_mode(self.macros.plain(&self.params.synth_filter_env_loop_mode));
             synth.set_filt_env_s_curves(
-                self.params.synth_filter_env_attack_s.value(),
-                self.params.synth_filter_env_decay_s.value(),
-                self.params.synth_filter_env_release_s.value(),
+                self.macros.plain(&self.params.synth_filter_env_attack_s),
+                self.macros.plain(&self.params.synth_filter_env_decay_s),
+                self.macros.plain(&self.params.synth_filter_env_release_s),
+            );
+            synth.set_filter_drive_boost(self.macros.plain(&self.params.synth_filter_drive_boost));
+            synth.set_filter_sat_type(self.macros.plain(&self.params.synth_filter_sat_type));
+            synth.set_filter_morph(self.macros.plain(&self.params.synth_filter_morph));
+            synth.set_filter_fm(self.macros.plain(&self.params.synth_filter_fm));
+            synth.set_filter_feedback(self.macros.plain(&self.params.synth_filter_feedback));
+            synth.set_filter_bass_lock(self.macros.plain(&self.params.synth_filter_bass_lock));
+            synth.set_filter_pole_spread(self.macros.plain(&self.params.synth_filter_pole_spread));
+            synth.set_filter_res_character(self.macros.plain(&self.params.synth_filter_res_character));
+            synth.set_filter_res_tilt(self.macros.plain(&self.params.synth_filter_res_tilt));
+            synth.set_filter_cutoff_slew(self.macros.plain(&self.params.synth_filter_cutoff_slew));
+            synth.set_filter_poles(self.macros.plain(&self.params.synth_filter_poles));
+            synth.set_filter2_enabled(self.macros.plain(&self.params.synth_filter2_enable));
+            synth.set_filter2_params(
+                self.macros.plain(&self.params.synth_filter2_type),
+                self.macros.plain(&self.params.synth_filter2_cutoff),
+                self.macros.plain(&self.params.synth_filter2_resonance),
+                self.macros.plain(&self.params.synth_filter2_morph),
+            );
+            synth.set_filter2_tracking(
+                self.macros.plain(&self.params.synth_filter2_key_track),
+                self.macros.plain(&self.params.synth_filter2_env_amount),
+            );
+            synth.set_filter_routing(self.macros.plain(&self.params.synth_filter_routing));
+            synth.set_resonator_enabled(self.macros.plain(&self.params.synth_resonator_enable));
+            synth.set_resonator_params(
+                self.macros.plain(&self.params.synth_resonator_model),
+                self.macros.plain(&self.params.synth_resonator_excite),
+                self.macros.plain(&self.params.synth_resonator_decay),
+                self.macros.plain(&self.params.synth_resonator_brightness),
+                self.macros.plain(&self.params.synth_resonator_position),
+                self.macros.plain(&self.params.synth_resonator_mix),
+            );
+
+            synth.set_lofi_routing(
+                self.macros.plain(&self.params.lofi_enable),
+                self.macros.plain(&self.params.lofi_post_filter),
+                self.macros.plain(&self.params.lofi_vps),
+                self.macros.plain(&self.params.lofi_pll),
+                self.macros.plain(&self.params.lofi_saw),
+            );
+            synth.set_lofi_params(
+                self.macros.plain(&self.params.lofi_shaper),
+                self.macros.plain(&self.params.lofi_bits),
+                self.macros.plain(&self.params.lofi_rate),
+                self.macros.plain(&self.params.lofi_anti_alias),
+                self.macros.plain(&self.params.lofi_jitter),
+                self.macros.plain(&self.params.lofi_drive),
+                self.macros.plain(&self.params.lofi_mix),
+            );
+
+            synth.set_noise_params(
+                self.macros.plain(&self.params.synth_noise_type),
+                self.macros.plain(&self.params.synth_noise_rate),
+                self.macros.plain(&self.params.synth_noise_lpf),
+                self.macros.plain(&self.params.synth_noise_hpf),
+                self.macros.plain(&self.params.synth_noise_env) == 1,
+            );
+            synth.set_noise_level(self.macros.plain(&self.params.synth_noise_level));
+            synth.set_sync(
+                self.macros.plain(&self.params.synth_sync_source) == 1,
+                self.macros.plain(&self.params.synth_vps_sync),
+                self.macros.plain(&self.params.synth_saw_sync),
+                self.macros.plain(&self.params.synth_pll_sync),
+                self.macros.plain(&self.params.synth_sync_soft),
+            );
+            synth.set_noise_injection(
+                self.macros.plain(&self.params.synth_noise_inject),
+                self.macros.plain(&self.params.synth_noise_inject_amount),
             );
-            synth.set_filter_drive_boost(self.params.synth_filter_drive_boost.value());
-            synth.set_filter_sat_type(self.params.synth_filter_sat_type.value());
-            synth.set_filter_morph(self.params.synth_filter_morph.modulated_plain_value());
-            synth.set_filter_fm(self.params.synth_filter_fm.modulated_plain_value());
-            synth.set_filter_feedback(self.params.synth_filter_feedback.modulated_plain_value());
-            synth.set_filter_bass_lock(self.params.synth_filter_bass_lock.modulated_plain_value());
-            synth.set_filter_pole_spread(self.params.synth_filter_pole_spread.modulated_plain_value());
-            synth.set_filter_res_character(self.params.synth_filter_res_character.modulated_plain_value());
-            synth.set_filter_res_tilt(self.params.synth_filter_res_tilt.modulated_plain_value());
-            synth.set_filter_cutoff_slew(self.params.synth_filter_cutoff_slew.modulated_plain_value());
-            synth.set_filter_poles(self.params.synth_filter_poles.value());
 
             synth.set_pll_fm_params(
-                self.params.synth_pll_fm_amount.modulated_plain_value(),
-                self.params.synth_pll_fm_ratio_float.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_pll_fm_amount),
+                self.macros.plain(&self.params.synth_pll_fm_ratio_float),
             );
 
             synth.set_pll_experimental_params(
-                self.params.synth_pll_retrigger.modulated_plain_value(),
-                self.params.synth_pll_burst_threshold.modulated_plain_value(),
-                self.params.synth_pll_burst_amount.modulated_plain_value(),
-                self.params.synth_pll_loop_saturation.modulated_plain_value(),
-                self.params.synth_pll_color_amount.modulated_plain_value(),
-                self.params.synth_pll_edge_sensitivity.modulated_plain_value(),
-                self.params.synth_pll_range.modulated_plain_value(),
-                self.params.synth_pll_stereo_track_offset.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_pll_retrigger),
+                self.macros.plain(&self.params.synth_pll_burst_threshold),
+                self.macros.plain(&self.params.synth_pll_burst_amount),
+                self.macros.plain(&self.params.synth_pll_loop_saturation),
+                self.macros.plain(&self.params.synth_pll_color_amount),
+                self.macros.plain(&self.params.synth_pll_edge_sensitivity),
+                self.macros.plain(&self.params.synth_pll_range),
+                self.macros.plain(&self.params.synth_pll_stereo_track_offset),
             );
 
-            synth.set_pll_stereo_phase(self.params.synth_pll_stereo_phase.modulated_plain_value());
-            synth.set_pll_fm_env_amount(self.params.synth_pll_fm_env_amount.modulated_plain_value());
+            synth.set_pll_stereo_phase(self.macros.plain(&self.params.synth_pll_stereo_phase));
+            synth.set_pll_fm_env_amount(self.macros.plain(&self.params.synth_pll_fm_env_amount));
 
             synth.set_coloration_params(
-                self.params.synth_drift_amount.modulated_plain_value(),
-                self.params.synth_drift_rate.modulated_plain_value(),
-                self.params.synth_tube_drive.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_drift_amount),
+                self.macros.plain(&self.params.synth_drift_rate),
+                self.macros.plain(&self.params.synth_tube_drive),
             );
 
-            synth.set_bypass_switches(true, true, true, true);
-            synth.set_vps_formant(self.params.synth_vps_formant.value());
-            synth.set_oversampling(self.params.synth_oversampling.value());
+            synth.set_bypass_switches(true, true, true, true, true);
+            synth.set_vps_formant(self.macros.plain(&self.params.synth_vps_formant));
+            synth.set_oversampling(self.macros.plain(&self.params.synth_oversampling));
 
-            synth.set_base_rate(self.params.synth_base_rate.value());
+            synth.set_base_rate(self.macros.plain(&self.params.synth_base_rate));
 
             synth.set_pll_ref_params(
-                self.params.synth_pll_ref_octave.value(),
-                self.params.synth_pll_ref_pulse_width.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_pll_ref_octave),
+                self.macros.plain(&self.params.synth_pll_ref_pulse_width),
             );
             synth.set_pll_ref_tune(
-                self.params.synth_pll_ref_tune.value(),
-                self.params.synth_pll_ref_fine.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_pll_ref_tune),
+                self.macros.plain(&self.params.synth_pll_ref_fine),
             );
 
-            let pll_mult = match self.params.synth_pll_mult.value() {
+            let mult_from_index = |index: i32| match index {
                 0 => 1.0,
                 1 => 2.0,
                 2 => 4.0,
@@ -577,36 +1186,60 @@ impl Plugin for PhaseBurn {
                 6 => 64.0,
                 _ => 1.0,
             };
+            let pll_mult = mult_from_index(self.macros.plain(&self.params.synth_pll_mult));
 
             synth.set_pll_params(
-                self.params.synth_pll_track_speed.modulated_plain_value(),
-                self.params.synth_pll_damping.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_pll_track_speed),
+                self.macros.plain(&self.params.synth_pll_damping),
                 pll_mult,
-                self.params.synth_pll_influence.modulated_plain_value(),
-                self.params.synth_pll_colored.value(),
-                self.params.synth_pll_mode.value(),
+                self.macros.plain(&self.params.synth_pll_influence),
+                self.macros.plain(&self.params.synth_pll_colored),
+                self.macros.plain(&self.params.synth_pll_mode),
             );
-            synth.set_pll_mult_slew_time(self.params.synth_pll_mult_slew_time.modulated_plain_value());
-            synth.set_pll_precision(self.params.synth_pll_precision.value());
+            synth.set_pll_topology(
+                self.macros.plain(&self.params.synth_pll_topology),
+                self.macros.plain(&self.params.synth_pll2_track_speed),
+                self.macros.plain(&self.params.synth_pll2_damping),
+                mult_from_index(self.macros.plain(&self.params.synth_pll2_mult)),
+                self.macros.plain(&self.params.synth_pll_coupling),
+            );
+            synth.set_pll_ref_source(
+                self.macros.plain(&self.params.synth_pll_ref_source),
+                self.macros.plain(&self.params.synth_pll_ref_mix),
+            );
+            synth.set_ring_mod(
+                self.macros.plain(&self.params.synth_ring_pll_enable),
+                self.macros.plain(&self.params.synth_ring_pll),
+                self.macros.plain(&self.params.synth_ring_vps_saw_enable),
+                self.macros.plain(&self.params.synth_ring_vps_saw),
+            );
+            synth.set_cross_fm(
+                self.macros.plain(&self.params.synth_xfm_vps_saw_enable),
+                self.macros.plain(&self.params.synth_xfm_vps_saw),
+                self.macros.plain(&self.params.synth_xfm_saw_ref_enable),
+                self.macros.plain(&self.params.synth_xfm_saw_ref),
+            );
+            synth.set_pll_mult_slew_time(self.macros.plain(&self.params.synth_pll_mult_slew_time));
+            synth.set_pll_precision(self.macros.plain(&self.params.synth_pll_precision));
             synth.set_pll_advanced_params(
-                self.params.synth_pll_anti_alias.value(),
-                self.params.synth_pll_injection_amount.modulated_plain_value(),
-                self.params.synth_pll_injection_x4.value(),
+                self.macros.plain(&self.params.synth_pll_anti_alias),
+                self.macros.plain(&self.params.synth_pll_injection_amount),
+                self.macros.plain(&self.params.synth_pll_injection_x4),
             );
 
-            synth.set_pll_volume(self.params.synth_pll_volume.modulated_plain_value());
+            synth.set_pll_volume(self.macros.plain(&self.params.synth_pll_volume));
 
-            synth.set_pll_stereo_damp_offset(self.params.synth_pll_stereo_damp_offset.modulated_plain_value());
+            synth.set_pll_stereo_damp_offset(self.macros.plain(&self.params.synth_pll_stereo_damp_offset));
 
-            synth.set_pll_glide(self.params.synth_pll_glide.modulated_plain_value());
+            synth.set_pll_glide(self.macros.plain(&self.params.synth_pll_glide));
 
-            synth.set_legato_mode(self.params.legato_mode.value());
-            if self.params.legato_mode.value() {
-                synth.set_legato_time(self.params.legato_time.modulated_plain_value());
+            synth.set_legato_mode(self.macros.plain(&self.params.legato_mode));
+            if self.macros.plain(&self.params.legato_mode) {
+                synth.set_legato_time(self.macros.plain(&self.params.legato_time));
             }
-            synth.set_legato_velocity_lock(self.params.legato_velocity_lock.value());
-            synth.set_vca_mode(self.params.vca_mode.value());
-            synth.set_note_priority(self.params.note_priority.value());
+            synth.set_legato_velocity_lock(self.macros.plain(&self.params.legato_velocity_lock));
+            synth.set_vca_mode(self.macros.plain(&self.params.vca_mode));
+            synth.set_note_priority(self.macros.plain(&self.params.note_priority));
 
 
             synth.set_volume(1.0);
@@ -615,136 +1248,144 @@ impl Plugin for PhaseBurn {
                 let div = LfoSyncDivision::from_index(div_idx);
                 (div.beats() as f64 / tempo * 60000.0).max(0.5) as f32
             };
-            let vol_a = if self.params.synth_vol_attack_sync.value() {
-                div_to_ms(self.params.synth_vol_attack_div.value())
+            let vol_a = if self.macros.plain(&self.params.synth_vol_attack_sync) {
+                div_to_ms(self.macros.plain(&self.params.synth_vol_attack_div))
             } else {
-                self.params.synth_vol_attack.modulated_plain_value()
+                self.macros.plain(&self.params.synth_vol_attack)
             };
-            let vol_h = if self.params.synth_vol_hold_sync.value() {
-                div_to_ms(self.params.synth_vol_hold_div.value())
+            let vol_h = if self.macros.plain(&self.params.synth_vol_hold_sync) {
+                div_to_ms(self.macros.plain(&self.params.synth_vol_hold_div))
             } else {
-                self.params.synth_vol_hold.modulated_plain_value()
+                self.macros.plain(&self.params.synth_vol_hold)
             };
-            let vol_d = if self.params.synth_vol_decay_sync.value() {
-                div_to_ms(self.params.synth_vol_decay_div.value())
+            let vol_d = if self.macros.plain(&self.params.synth_vol_decay_sync) {
+                div_to_ms(self.macros.plain(&self.params.synth_vol_decay_div))
             } else {
-                self.params.synth_vol_decay.modulated_plain_value()
+                self.macros.plain(&self.params.synth_vol_decay)
             };
-            let vol_r = if self.params.synth_vol_release_sync.value() {
-                div_to_ms(self.params.synth_vol_release_div.value())
+            let vol_r = if self.macros.plain(&self.params.synth_vol_release_sync) {
+                div_to_ms(self.macros.plain(&self.params.synth_vol_release_div))
             } else {
-                self.params.synth_vol_release.modulated_plain_value()
+                self.macros.plain(&self.params.synth_vol_release)
             };
             synth.set_volume_envelope(
                 vol_a,
-                self.params.synth_vol_attack_shape.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_vol_attack_shape),
                 vol_d,
-                self.params.synth_vol_decay_shape.modulated_plain_value(),
-                self.params.synth_vol_sustain.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_vol_decay_shape),
+                self.macros.plain(&self.params.synth_vol_sustain),
                 vol_r,
-                self.params.synth_vol_release_shape.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_vol_release_shape),
             );
             synth.set_vol_env_hold(vol_h);
-            synth.set_vol_env_depth(self.params.synth_vol_depth.modulated_plain_value());
-            synth.set_vol_env_loop_mode(self.params.synth_vol_loop_mode.value());
+            synth.set_vol_env_depth(self.macros.plain(&self.params.synth_vol_depth));
+            synth.set_vol_env_loop_mode(self.macros.plain(&self.params.synth_vol_loop_mode));
             synth.set_vol_env_s_curves(
-                self.params.synth_vol_attack_s.value(),
-                self.params.synth_vol_decay_s.value(),
-                self.params.synth_vol_release_s.value(),
+                self.macros.plain(&self.params.synth_vol_attack_s),
+                self.macros.plain(&self.params.synth_vol_decay_s),
+                self.macros.plain(&self.params.synth_vol_release_s),
             );
-            synth.set_env_key_track(self.params.synth_env_key_track.modulated_plain_value());
-            synth.set_env_vel_to_attack(self.params.synth_env_vel_to_attack.modulated_plain_value());
-            synth.set_env_vel_to_decay(self.params.synth_env_vel_to_decay.modulated_plain_value());
-            synth.set_env_vel_to_sustain(self.params.synth_env_vel_to_sustain.modulated_plain_value());
-            synth.set_retrigger_dip(self.params.synth_retrigger_dip.modulated_plain_value());
-            let pll_tail_amount = self.params.synth_pll_tail_amount.modulated_plain_value();
+            synth.set_env_key_track(self.macros.plain(&self.params.synth_env_key_track));
+            synth.set_env_vel_to_attack(self.macros.plain(&self.params.synth_env_vel_to_attack));
+            synth.set_env_vel_to_decay(self.macros.plain(&self.params.synth_env_vel_to_decay));
+            synth.set_env_vel_to_sustain(self.macros.plain(&self.params.synth_env_vel_to_sustain));
+            synth.set_retrigger_dip(self.macros.plain(&self.params.synth_retrigger_dip));
+            let pll_tail_amount = self.macros.plain(&self.params.synth_pll_tail_amount);
             synth.set_pll_tail(
                 pll_tail_amount > 0.001,
-                self.params.synth_pll_tail_time.modulated_plain_value(),
+                self.macros.plain(&self.params.synth_pll_tail_time),
                 pll_tail_amount,
             );
 
             synth.set_lfo_params(
                 0,
-                self.params.lfo1_rate.modulated_plain_value(),
-                self.params.lfo1_waveform.value(),
-                self.params.lfo1_tempo_sync.value(),
-                self.params.lfo1_sync_division.value(),
-                self.params.lfo1_sync_source.value(),
-                self.params.lfo1_phase_mod.modulated_plain_value(),
+                self.macros.plain(&self.params.lfo1_rate),
+                self.macros.plain(&self.params.lfo1_waveform),
+                self.macros.plain(&self.params.lfo1_tempo_sync),
+                self.macros.plain(&self.params.lfo1_sync_division),
+                self.macros.plain(&self.params.lfo1_sync_source),
+                self.macros.plain(&self.params.lfo1_phase_mod),
             );
-            synth.set_lfo_modulation(0, 0, self.params.lfo1_dest1.value(), self.params.lfo1_amount1.modulated_plain_value());
-            synth.set_lfo_modulation(0, 1, self.params.lfo1_dest2.value(), self.params.lfo1_amount2.modulated_plain_value());
+            synth.set_lfo_modulation(0, 0, self.macros.plain(&self.params.lfo1_dest1), self.macros.plain(&self.params.lfo1_amount1));
+            synth.set_lfo_modulation(0, 1, self.macros.plain(&self.params.lfo1_dest2), self.macros.plain(&self.params.lfo1_amount2));
 
             synth.set_lfo_params(
                 1,
-                self.params.lfo2_rate.modulated_plain_value(),
-                self.params.lfo2_waveform.value(),
-                self.params.lfo2_tempo_sync.value(),
-                self.params.lfo2_sync_division.value(),
-                self.params.lfo2_sync_source.value(),
-                self.params.lfo2_phase_mod.modulated_plain_value(),
+                self.macros.plain(&self.params.lfo2_rate),
+                self.macros.plain(&self.params.lfo2_waveform),
+                self.macros.plain(&self.params.lfo2_tempo_sync),
+                self.macros.plain(&self.params.lfo2_sync_division),
+                self.macros.plain(&self.params.lfo2_sync_source),
+                self.macros.plain(&self.params.lfo2_phase_mod),
             );
-            synth.set_lfo_modulation(1, 0, self.params.lfo2_dest1.value(), self.params.lfo2_amount1.modulated_plain_value());
-            synth.set_lfo_modulation(1, 1, self.params.lfo2_dest2.value(), self.params.lfo2_amount2.modulated_plain_value());
+            synth.set_lfo_modulation(1, 0, self.macros.plain(&self.params.lfo2_dest1), self.macros.plain(&self.params.lfo2_amount1));
+            synth.set_lfo_modulation(1, 1, self.macros.plain(&self.params.lfo2_dest2), self.macros.plain(&self.params.lfo2_amount2));
 
             synth.set_lfo_params(
                 2,
-                self.params.lfo3_rate.modulated_plain_value(),
-                self.params.lfo3_waveform.value(),
-                self.params.lfo3_tempo_sync.value(),
-                self.params.lfo3_sync_division.value(),
-                self.params.lfo3_sync_source.value(),
-                self.params.lfo3_phase_mod.modulated_plain_value(),
+                self.macros.plain(&self.params.lfo3_rate),
+                self.macros.plain(&self.params.lfo3_waveform),
+                self.macros.plain(&self.params.lfo3_tempo_sync),
+                self.macros.plain(&self.params.lfo3_sync_division),
+                self.macros.plain(&self.params.lfo3_sync_source),
+                self.macros.plain(&self.params.lfo3_phase_mod),
             );
-            synth.set_lfo_modulation(2, 0, self.params.lfo3_dest1.value(), self.params.lfo3_amount1.modulated_plain_value());
-            synth.set_lfo_modulation(2, 1, self.params.lfo3_dest2.value(), self.params.lfo3_amount2.modulated_plain_value());
-
-            synth.set_mod_seq_step(0, self.params.mseq_step_1.value());
-            synth.set_mod_seq_step(1, self.params.mseq_step_2.value());
-            synth.set_mod_seq_step(2, self.params.mseq_step_3.value());
-            synth.set_mod_seq_step(3, self.params.mseq_step_4.value());
-            synth.set_mod_seq_step(4, self.params.mseq_step_5.value());
-            synth.set_mod_seq_step(5, self.params.mseq_step_6.value());
-            synth.set_mod_seq_step(6, self.params.mseq_step_7.value());
-            synth.set_mod_seq_step(7, self.params.mseq_step_8.value());
-            synth.set_mod_seq_step(8, self.params.mseq_step_9.value());
-            synth.set_mod_seq_step(9, self.params.mseq_step_10.value());
-            synth.set_mod_seq_step(10, self.params.mseq_step_11.value());
-            synth.set_mod_seq_step(11, self.params.mseq_step_12.value());
-            synth.set_mod_seq_step(12, self.params.mseq_step_13.value());
-            synth.set_mod_seq_step(13, self.params.mseq_step_14.value());
-            synth.set_mod_seq_step(14, self.params.mseq_step_15.value());
-            synth.set_mod_seq_step(15, self.params.mseq_step_16.value());
-            synth.set_mod_seq_step(16, self.params.mseq_step_17.value());
-            synth.set_mod_seq_step(17, self.params.mseq_step_18.value());
-            synth.set_mod_seq_step(18, self.params.mseq_step_19.value());
-            synth.set_mod_seq_step(19, self.params.mseq_step_20.value());
-            synth.set_mod_seq_step(20, self.params.mseq_step_21.value());
-            synth.set_mod_seq_step(21, self.params.mseq_step_22.value());
-            synth.set_mod_seq_step(22, self.params.mseq_step_23.value());
-            synth.set_mod_seq_step(23, self.params.mseq_step_24.value());
-            synth.set_mod_seq_step(24, self.params.mseq_step_25.value());
-            synth.set_mod_seq_step(25, self.params.mseq_step_26.value());
-            synth.set_mod_seq_step(26, self.params.mseq_step_27.value());
-            synth.set_mod_seq_step(27, self.params.mseq_step_28.value());
-            synth.set_mod_seq_step(28, self.params.mseq_step_29.value());
-            synth.set_mod_seq_step(29, self.params.mseq_step_30.value());
-            synth.set_mod_seq_step(30, self.params.mseq_step_31.value());
-            synth.set_mod_seq_step(31, self.params.mseq_step_32.value());
+            synth.set_lfo_modulation(2, 0, self.macros.plain(&self.params.lfo3_dest1), self.macros.plain(&self.params.lfo3_amount1));
+            synth.set_lfo_modulation(2, 1, self.macros.plain(&self.params.lfo3_dest2), self.macros.plain(&self.params.lfo3_amount2));
+
+            synth.set_mod_seq_step(0, self.macros.plain(&self.params.mseq_step_1));
+            synth.set_mod_seq_step(1, self.macros.plain(&self.params.mseq_step_2));
+            synth.set_mod_seq_step(2, self.macros.plain(&self.params.mseq_step_3));
+            synth.set_mod_seq_step(3, self.macros.plain(&self.params.mseq_step_4));
+            synth.set_mod_seq_step(4, self.macros.plain(&self.params.mseq_step_5));
+            synth.set_mod_seq_step(5, self.macros.plain(&self.params.mseq_step_6));
+            synth.set_mod_seq_step(6, self.macros.plain(&self.params.mseq_step_7));
+            synth.set_mod_seq_step(7, self.macros.plain(&self.params.mseq_step_8));
+            synth.set_mod_seq_step(8, self.macros.plain(&self.params.mseq_step_9));
+            synth.set_mod_seq_step(9, self.macros.plain(&self.params.mseq_step_10));
+            synth.set_mod_seq_step(10, self.macros.plain(&self.params.mseq_step_11));
+            synth.set_mod_seq_step(11, self.macros.plain(&self.params.mseq_step_12));
+            synth.set_mod_seq_step(12, self.macros.plain(&self.params.mseq_step_13));
+            synth.set_mod_seq_step(13, self.macros.plain(&self.params.mseq_step_14));
+            synth.set_mod_seq_step(14, self.macros.plain(&self.params.mseq_step_15));
+            synth.set_mod_seq_step(15, self.macros.plain(&self.params.mseq_step_16));
+            synth.set_mod_seq_step(16, self.macros.plain(&self.params.mseq_step_17));
+            synth.set_mod_seq_step(17, self.macros.plain(&self.params.mseq_step_18));
+            synth.set_mod_seq_step(18, self.macros.plain(&self.params.mseq_step_19));
+            synth.set_mod_seq_step(19, self.macros.plain(&self.params.mseq_step_20));
+            synth.set_mod_seq_step(20, self.macros.plain(&self.params.mseq_step_21));
+            synth.set_mod_seq_step(21, self.macros.plain(&self.params.mseq_step_22));
+            synth.set_mod_seq_step(22, self.macros.plain(&self.params.mseq_step_23));
+            synth.set_mod_seq_step(23, self.macros.plain(&self.params.mseq_step_24));
+            synth.set_mod_seq_step(24, self.macros.plain(&self.params.mseq_step_25));
+            synth.set_mod_seq_step(25, self.macros.plain(&self.params.mseq_step_26));
+            synth.set_mod_seq_step(26, self.macros.plain(&self.params.mseq_step_27));
+            synth.set_mod_seq_step(27, self.macros.plain(&self.params.mseq_step_28));
+            synth.set_mod_seq_step(28, self.macros.plain(&self.params.mseq_step_29));
+            synth.set_mod_seq_step(29, self.macros.plain(&self.params.mseq_step_30));
+            synth.set_mod_seq_step(30, self.macros.plain(&self.params.mseq_step_31));
+            synth.set_mod_seq_step(31, self.macros.plain(&self.params.mseq_step_32));
             synth.set_mod_seq_params(
-                self.params.mseq_ties.value(),
-                self.params.mseq_ties_hi.value(),
-                self.params.mseq_division.value(),
-                self.params.mseq_slew.modulated_plain_value(),
-                self.params.mseq_length.value(),
-                self.params.mseq_retrigger.value(),
-                self.params.mseq_bipolar.value(),
+                self.macros.plain(&self.params.mseq_ties),
+                self.macros.plain(&self.params.mseq_ties_hi),
+                self.macros.plain(&self.params.mseq_division),
+                self.macros.plain(&self.params.mseq_slew),
+                self.macros.plain(&self.params.mseq_length),
+                self.macros.plain(&self.params.mseq_retrigger),
+                self.macros.plain(&self.params.mseq_bipolar),
             );
-            synth.set_mod_seq_modulation(0, self.params.mseq_dest1.value(), self.params.mseq_amount1.modulated_plain_value());
-            synth.set_mod_seq_modulation(1, self.params.mseq_dest2.value(), self.params.mseq_amount2.modulated_plain_value());
-            synth.set_mod_seq_modulation(2, self.params.mseq_dest3.value(), self.params.mseq_amount3.modulated_plain_value());
-            synth.set_mod_seq_modulation(3, self.params.mseq_dest4.value(), self.params.mseq_amount4.modulated_plain_value());
+            synth.set_mod_seq_modulation(0, self.macros.plain(&self.params.mseq_dest1), self.macros.plain(&self.params.mseq_amount1));
+            synth.set_mod_seq_modulation(1, self.macros.plain(&self.params.mseq_dest2), self.macros.plain(&self.params.mseq_amount2));
+            synth.set_mod_seq_modulation(2, self.macros.plain(&self.params.mseq_dest3), self.macros.plain(&self.params.mseq_amount3));
+            synth.set_mod_seq_modulation(3, self.macros.plain(&self.params.mseq_dest4), self.macros.plain(&self.params.mseq_amount4));
+
+            synth.set_mseg_params(0, self.macros.plain(&self.params.mseg1_tempo_sync), self.macros.plain(&self.params.mseg1_retrigger));
+            synth.set_mseg_modulation(0, 0, self.macros.plain(&self.params.mseg1_dest1), self.macros.plain(&self.params.mseg1_amount1));
+            synth.set_mseg_modulation(0, 1, self.macros.plain(&self.params.mseg1_dest2), self.macros.plain(&self.params.mseg1_amount2));
+            synth.set_mseg_params(1, self.macros.plain(&self.params.mseg2_tempo_sync), self.macros.plain(&self.params.mseg2_retrigger));
+            synth.set_mseg_modulation(1, 0, self.macros.plain(&self.params.mseg2_dest1), self.macros.plain(&self.params.mseg2_amount1));
+            synth.set_mseg_modulation(1, 1, self.macros.plain(&self.params.mseg2_dest2), self.macros.plain(&self.params.mseg2_amount2));
+            synth.set_pitch_mod_quantize(self.macros.plain(&self.params.mod_pitch_quantize));
 
             let num_samples = buffer.samples();
             self.output_buffer_l.resize(num_samples, 0.0);
@@ -752,25 +1393,31 @@ impl Plugin for PhaseBurn {
             self.sub_buffer.resize(num_samples, 0.0);
             self.reverb_send_l.resize(num_samples, 0.0);
             self.reverb_send_r.resize(num_samples, 0.0);
+            self.delay_send_l.resize(num_samples, 0.0);
+            self.delay_send_r.resize(num_samples, 0.0);
             self.looper_input_l.resize(num_samples, 0.0);
             self.looper_input_r.resize(num_samples, 0.0);
-            self.comp_pre_looper_l.resize(num_samples, 0.0);
-            self.comp_pre_looper_r.resize(num_samples, 0.0);
-            self.comp_pre_reverb_l.resize(num_samples, 0.0);
-            self.comp_pre_reverb_r.resize(num_samples, 0.0);
+            self.slot_dry_l.resize(num_samples, 0.0);
+            self.slot_dry_r.resize(num_samples, 0.0);
+            self.fx_return_l.resize(num_samples, 0.0);
+            self.fx_return_r.resize(num_samples, 0.0);
+            self.looper_return_l.resize(num_samples, 0.0);
+            self.looper_return_r.resize(num_samples, 0.0);
             self.output_buffer_l.fill(0.0);
             self.output_buffer_r.fill(0.0);
             self.sub_buffer.fill(0.0);
             self.reverb_send_l.fill(0.0);
             self.reverb_send_r.fill(0.0);
+            self.delay_send_l.fill(0.0);
+            self.delay_send_r.fill(0.0);
 
-            let pll_feedback_amt = self.params.synth_pll_feedback.modulated_plain_value();
+            let pll_feedback_amt = self.macros.plain(&self.params.synth_pll_feedback);
             let base_freq = 220.0;
 
             let seq_playing = if self.transport_has_played {
-                self.params.sequencer_enable.value() && is_playing
+                self.macros.plain(&self.params.sequencer_enable) && is_playing
             } else {
-                self.params.sequencer_enable.value()
+                self.macros.plain(&self.params.sequencer_enable)
             };
 
             synth.set_mod_seq_playing(seq_playing);
@@ -786,29 +1433,58 @@ impl Plugin for PhaseBurn {
                 self.ui_state.midi_mode.load(std::sync::atomic::Ordering::Relaxed),
             );
             self.midi_mode_processor.set_mode(midi_mode);
+            self.midi_mode_processor.set_phrase_bars(
+                self.ui_state.call_response_bars.load(std::sync::atomic::Ordering::Relaxed) as u32,
+            );
+            if midi_mode != MidiInputMode::CallResponse {
+                synth.clear_phrase();
+            }
+            if midi_mode == MidiInputMode::Transpose {
+                synth.set_transpose_options(
+                    self.ui_state.transpose_scale_aware.load(std::sync::atomic::Ordering::Relaxed),
+                    TransposeQuantize::from_index(
+                        self.ui_state.transpose_quantize.load(std::sync::atomic::Ordering::Relaxed),
+                    ),
+                    self.ui_state.transpose_gate.load(std::sync::atomic::Ordering::Relaxed),
+                );
+            } else {
+                synth.clear_transpose();
+            }
 
             if self.ui_state.midi_clear_memory.swap(false, std::sync::atomic::Ordering::Relaxed) {
                 self.midi_mode_processor.clear_accompaniment();
+                self.midi_mode_processor.clear_phrase();
+                synth.clear_phrase();
             }
 
-            let pos_beats = transport.pos_beats().unwrap_or(0.0);
+            let pos_beats = if internal_transport {
+                internal_pos_beats
+            } else {
+                transport.pos_beats().unwrap_or(0.0)
+            };
             let bar_index = (pos_beats / 4.0).floor().max(0.0) as u64;
             let bar_position = ((pos_beats % 4.0) / 4.0) as f32;
 
+            let bar_length_samples = 4.0 * 60.0 / tempo * self.sample_rate as f64;
+
             let external_notes = &self.midi_processor.input.external_notes;
             let mode_result = self.midi_mode_processor.process_events(
                 external_notes,
                 bar_index,
                 bar_position,
+                bar_length_samples,
             );
 
             let passthrough_notes: &[midi::ExternalNoteEvent] = match midi_mode {
-                MidiInputMode::Passthrough => external_notes,
+                MidiInputMode::Passthrough | MidiInputMode::CallResponse => external_notes,
                 _ => &[],
             };
 
-            if let MidiModeResult::NotePoolUpdate(pool) = mode_result {
-                synth.update_note_pool(pool);
+            match mode_result {
+                MidiModeResult::NotePoolUpdate(pool) => synth.update_note_pool(pool),
+                MidiModeResult::PhraseAnswer(answer) => synth.update_phrase(&answer.note_pool, answer.bars()),
+                MidiModeResult::TransposeKey(key) => synth.set_transpose_key(key),
+                MidiModeResult::Passthrough | MidiModeResult::NoChange => {}
             }
 
             if let Ok(mut display) = self.ui_state.midi_mode_display.try_lock() {
@@ -820,19 +1496,32 @@ impl Plugin for PhaseBurn {
 
             let start_time = if measure_cpu { Some(std::time::Instant::now()) } else { None };
             synth.set_reverb_sends(
-                if self.params.synth_reverb_send_vps.value() { 1.0 } else { 0.0 },
-                if self.params.synth_reverb_send_pll.value() { 1.0 } else { 0.0 },
-                if self.params.synth_reverb_send_saw.value() { 1.0 } else { 0.0 },
-                if self.params.synth_reverb_send_sub.value() { 1.0 } else { 0.0 },
-                if self.params.synth_reverb_send_filter.value() { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_reverb_send_vps) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_reverb_send_pll) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_reverb_send_saw) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_reverb_send_wt) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_reverb_send_sub) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_reverb_send_filter) { 1.0 } else { 0.0 },
+            );
+            synth.set_delay_sends(
+                if self.macros.plain(&self.params.synth_delay_send_vps) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_delay_send_pll) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_delay_send_saw) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_delay_send_wt) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_delay_send_sub) { 1.0 } else { 0.0 },
+                if self.macros.plain(&self.params.synth_delay_send_filter) { 1.0 } else { 0.0 },
             );
+            let seq_bar_position = synth.sequencer_bar_position();
             synth.process_block(
                 &mut self.output_buffer_l,
                 &mut self.output_buffer_r,
                 &mut self.sub_buffer,
                 &mut self.reverb_send_l,
                 &mut self.reverb_send_r,
+                &mut self.delay_send_l,
+                &mut self.delay_send_r,
                 &self.params,
+                &self.macros,
                 pll_feedback_amt,
                 base_freq,
                 &mut self.midi_events_buffer,
@@ -844,32 +1533,42 @@ impl Plugin for PhaseBurn {
                 synth.mod_seq_current_step() as u8,
                 std::sync::atomic::Ordering::Relaxed,
             );
+            for (i, position) in self.ui_state.mseg_position.iter().enumerate() {
+                position.store((synth.mseg_position(i) as f32).to_bits(), std::sync::atomic::Ordering::Relaxed);
+            }
 
-            let lp_premaster = self.params.looper_input_premaster.value();
+            let lp_premaster = self.macros.plain(&self.params.looper_input_premaster);
             if lp_premaster {
                 for i in 0..num_samples {
                     self.looper_input_l[i] = 0.0;
                     self.looper_input_r[i] = 0.0;
                 }
-            } else if self.params.looper_input_filter.value() {
+            } else if self.macros.plain(&self.params.looper_input_filter) {
                 self.looper_input_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
                 self.looper_input_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
             } else {
-                let (vps_l, vps_r, pll_l, pll_r, saw) = synth.source_buffers();
-                let lp_vps = self.params.looper_input_vps.value();
-                let lp_pll = self.params.looper_input_pll.value();
-                let lp_saw = self.params.looper_input_saw.value();
+                let (vps_l, vps_r, pll_l, pll_r, saw, wt) = synth.source_buffers();
+                let lp_vps = self.macros.plain(&self.params.looper_input_vps);
+                let lp_pll = self.macros.plain(&self.params.looper_input_pll);
+                let lp_saw = self.macros.plain(&self.params.looper_input_saw);
+                let lp_wt = self.macros.plain(&self.params.looper_input_wt);
                 for i in 0..num_samples {
                     let mut l = 0.0f32;
                     let mut r = 0.0f32;
                     if lp_vps { l += vps_l[i]; r += vps_r[i]; }
                     if lp_pll { l += pll_l[i]; r += pll_r[i]; }
                     if lp_saw { l += saw[i]; r += saw[i]; }
+                    if lp_wt { l += wt[i]; r += wt[i]; }
                     self.looper_input_l[i] = l;
                     self.looper_input_r[i] = r;
                 }
             }
 
+            let (vps_l, vps_r, pll_l, pll_r, saw, _) = synth.source_buffers();
+            self.stems.capture(Stem::Vps, vps_l, vps_r, num_samples);
+            self.stems.capture(Stem::Pll, pll_l, pll_r, num_samples);
+            self.stems.capture(Stem::Saw, saw, saw, num_samples);
+
             for (is_note_on, is_note_off, midi_note, velocity, sample_idx) in &self.midi_events_buffer {
                 if *is_note_on {
                     self.midi_processor.note_on_from_sequencer(*midi_note, *velocity, *sample_idx as u32);
@@ -878,48 +1577,49 @@ impl Plugin for PhaseBurn {
                 }
             }
 
+            let clock_settings = midi::ClockOutSettings {
+                clock: self.ui_state.midi_clock_out.load(std::sync::atomic::Ordering::Relaxed),
+                transport: self.ui_state.midi_transport_out.load(std::sync::atomic::Ordering::Relaxed),
+                offset_ms: f32::from_bits(self.ui_state.clock_offset_ms.load(std::sync::atomic::Ordering::Relaxed)),
+                din_sync: self.ui_state.clock_din_sync.load(std::sync::atomic::Ordering::Relaxed),
+                start_on_bar: self.ui_state.clock_start_on_bar.load(std::sync::atomic::Ordering::Relaxed),
+                swing: if self.ui_state.clock_swing.load(std::sync::atomic::Ordering::Relaxed) {
+                    Some(self.macros.plain(&self.params.swing_amount))
+                } else {
+                    None
+                },
+            };
+
             self.midi_processor.send_output::<PhaseBurn>(
                 context,
-                is_playing,
+                if seq_playing { Some(seq_bar_position) } else { None },
                 buffer.samples(),
                 self.sample_rate,
                 tempo,
+                &clock_settings,
             );
 
-            let transport_out_enabled = self.ui_state.midi_transport_out.load(std::sync::atomic::Ordering::Relaxed);
-            let clock_out_enabled = self.ui_state.midi_clock_out.load(std::sync::atomic::Ordering::Relaxed);
-
-            if transport_out_enabled {
-                let seq_just_started = seq_playing && !prev_seq_playing;
-                let seq_just_stopped = !seq_playing && prev_seq_playing;
-                if seq_just_started {
-                    if let Ok(mut q) = self.ui_state.midi_device_output_queue.try_lock() {
-                        q.push_back(midi_devices::RawMidiMessage { data: [0xFA, 0, 0], len: 1 });
-                    }
-                    self.midi_clock_out_phase = 0.0;
-                }
-                if seq_just_stopped {
-                    if let Ok(mut q) = self.ui_state.midi_device_output_queue.try_lock() {
-                        q.push_back(midi_devices::RawMidiMessage { data: [0xFC, 0, 0], len: 1 });
-                    }
+            let block_start = self.midi_block_clock.block_start(std::time::Instant::now(), buffer.samples(), self.sample_rate);
+            for event in self.midi_processor.output.clock_events() {
+                if self.midi_out_backlog.len() >= self.midi_out_backlog.capacity() {
+                    break;
                 }
+                let delay = std::time::Duration::from_secs_f64(event.sample_offset as f64 / self.sample_rate as f64);
+                self.midi_out_backlog.push(midi_devices::ScheduledMidiMessage {
+                    msg: match event.status {
+                        // Song position 0
+                        0xF2 => midi_devices::RawMidiMessage { data: [0xF2, 0, 0], len: 3 },
+                        status => midi_devices::RawMidiMessage { data: [status, 0, 0], len: 1 },
+                    },
+                    send_at: Some(block_start + delay),
+                    clock_tick: (event.status == 0xF8).then_some(event.tick),
+                });
             }
-
-            if clock_out_enabled && seq_playing {
-                let ticks_per_second = tempo / 60.0 * 24.0;
-                let block_ticks = ticks_per_second * (num_samples as f64 / self.sample_rate as f64);
-                let prev_phase = self.midi_clock_out_phase;
-                self.midi_clock_out_phase += block_ticks;
-                let ticks_to_send = self.midi_clock_out_phase.floor() as u32 - prev_phase.floor() as u32;
-                if ticks_to_send > 0 {
-                    if let Ok(mut q) = self.ui_state.midi_device_output_queue.try_lock() {
-                        for _ in 0..ticks_to_send.min(24) {
-                            q.push_back(midi_devices::RawMidiMessage { data: [0xF8, 0, 0], len: 1 });
-                        }
-                    }
-                }
-            } else if !seq_playing {
-                self.midi_clock_out_phase = 0.0;
+            if !self.midi_out_backlog.is_empty() {
+                // Whatever doesn't fit in the queue waits for the next block
+                let queue = &self.ui_state.midi_device_output_queue;
+                let sent = self.midi_out_backlog.iter().take_while(|m| queue.try_send(**m).is_ok()).count();
+                self.midi_out_backlog.drain(..sent);
             }
 
             if let Some(start) = start_time {
@@ -933,189 +1633,56 @@ impl Plugin for PhaseBurn {
                 self.ui_state.set_cpu_load(self.cpu_load_smoothed);
             }
 
-            let hpf_mode = HpfMode::from_index(self.params.master_hpf.value());
-            let hpf_boost = HpfBoost::from_index(self.params.master_hpf_boost.value());
-            let box_cut_mode = BoxCutMode::from_index(self.params.box_cut_mode.value());
+            let hpf_mode = HpfMode::from_index(self.macros.plain(&self.params.master_hpf));
+            let hpf_boost = HpfBoost::from_index(self.macros.plain(&self.params.master_hpf_boost));
+            let box_cut_mode = BoxCutMode::from_index(self.macros.plain(&self.params.box_cut_mode));
 
-            self.master_hpf.set_mode(hpf_mode);
-            self.master_hpf.set_boost(hpf_boost);
-            self.master_hpf.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
-
-            self.box_cut.set_mode(box_cut_mode);
-            self.box_cut.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
-
-            let brill_amount = self.params.brilliance_amount.modulated_plain_value() as f64;
-            self.brilliance.set_amount(brill_amount);
-            self.brilliance.set_drive(brill_amount);
-            self.brilliance.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
-
-            if lp_premaster {
-                self.looper_input_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
-                self.looper_input_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
-            }
-
-            let comp_enabled = self.params.comp_enable.value();
-            let reverb_send_looper = self.params.synth_reverb_send_looper.value()
-                && self.params.looper_enabled.value();
-            if comp_enabled || reverb_send_looper {
-                self.comp_pre_looper_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
-                self.comp_pre_looper_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
-            }
-
-            let current_freq = self.synth_engine.as_ref()
-                .map(|s| s.current_frequency())
-                .unwrap_or(440.0);
-            let looper_length_beats = LfoSyncDivision::from_index(self.params.looper_length.value()).beats();
-            let auto_rec_beats = LfoSyncDivision::from_index(self.params.looper_auto_rec_len.value()).beats();
-            self.looper.process_block(
-                &mut self.output_buffer_l[..num_samples],
-                &mut self.output_buffer_r[..num_samples],
-                &self.looper_input_l[..num_samples],
-                &self.looper_input_r[..num_samples],
-                self.params.looper_enabled.value(),
-                self.params.looper_pitch.modulated_plain_value() as f64,
-                looper_length_beats,
-                self.params.looper_start.modulated_plain_value() as f64,
-                LoopDirection::from_index(self.params.looper_direction.value()),
-                self.params.looper_mix.modulated_plain_value() as f64,
-                self.params.looper_decay.modulated_plain_value() as f64,
-                self.params.looper_stutter.value(),
-                self.params.looper_key_track.value(),
-                self.params.looper_freeze.value(),
-                bar_index,
+            let comp_enabled = self.macros.plain(&self.params.comp_enable);
+            let chain_ctx = FxChainContext {
+                hpf_mode,
+                hpf_boost,
+                box_cut_mode,
                 tempo,
-                current_freq,
+                bar_index,
                 seq_playing,
-                auto_rec_beats,
-                self.params.looper_auto_rec_interval.value(),
-                self.params.looper_doppler.modulated_plain_value() as f64,
-            );
-
-            if comp_enabled {
-                self.comp_pre_reverb_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
-                self.comp_pre_reverb_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
-            }
+                lp_premaster,
+            };
 
-            if self.params.synth_reverb_enable.value() {
-                if reverb_send_looper {
-                    for i in 0..num_samples {
-                        self.reverb_send_l[i] += self.output_buffer_l[i] - self.comp_pre_looper_l[i];
-                        self.reverb_send_r[i] += self.output_buffer_r[i] - self.comp_pre_looper_r[i];
-                    }
+            self.fx_return_l[..num_samples].fill(0.0);
+            self.fx_return_r[..num_samples].fill(0.0);
+            self.looper_return_l[..num_samples].fill(0.0);
+            self.looper_return_r[..num_samples].fill(0.0);
+            self.reverb_return_l[..num_samples].fill(0.0);
+            self.reverb_return_r[..num_samples].fill(0.0);
+
+            for module in self.params.fx_order() {
+                let as_send = self.params.fx_send_param(module).is_some_and(|p| self.macros.plain(p));
+                if as_send {
+                    self.slot_dry_l[..num_samples].copy_from_slice(&self.output_buffer_l[..num_samples]);
+                    self.slot_dry_r[..num_samples].copy_from_slice(&self.output_buffer_r[..num_samples]);
                 }
 
-                let duck_div = LfoSyncDivision::from_index(self.params.synth_reverb_duck_division.value());
-                let duck_release_ms = duck_div.beats() / tempo * 60000.0;
-                let pre_delay_ms = if self.params.synth_reverb_pre_delay_sync.value() {
-                    let div = LfoSyncDivision::from_index(self.params.synth_reverb_pre_delay_division.value());
-                    (div.beats() / tempo * 60000.0).min(500.0)
-                } else {
-                    self.params.synth_reverb_pre_delay.modulated_plain_value() as f64
-                };
-                self.reverb.set_params(
-                    self.params.synth_reverb_mix.modulated_plain_value() as f64,
-                    pre_delay_ms,
-                    self.params.synth_reverb_time_scale.modulated_plain_value() as f64,
-                    self.params.synth_reverb_input_hpf.modulated_plain_value() as f64,
-                    self.params.synth_reverb_input_lpf.modulated_plain_value() as f64,
-                    self.params.synth_reverb_hpf.modulated_plain_value() as f64,
-                    self.params.synth_reverb_lpf.modulated_plain_value() as f64,
-                    self.params.synth_reverb_mod_speed.modulated_plain_value() as f64,
-                    self.params.synth_reverb_mod_depth.modulated_plain_value() as f64,
-                    self.params.synth_reverb_mod_shape.modulated_plain_value() as f64,
-                    self.params.synth_reverb_diffusion_mix.modulated_plain_value() as f64,
-                    self.params.synth_reverb_diffusion.modulated_plain_value() as f64,
-                    self.params.synth_reverb_decay.modulated_plain_value() as f64,
-                    self.params.synth_reverb_ducking.modulated_plain_value() as f64,
-                    duck_release_ms,
-                    self.params.synth_reverb_stereo_width.modulated_plain_value() as f64,
-                    self.params.synth_reverb_saturation.modulated_plain_value() as f64,
-                );
-                let rhythm_div = LfoSyncDivision::from_index(self.params.synth_reverb_rhythm_duck_division.value());
-                let rhythm_duck_freq = tempo / 60.0 / rhythm_div.beats();
-                self.reverb.set_rhythm_duck_params(
-                    self.params.synth_reverb_rhythm_duck_depth.modulated_plain_value() as f64,
-                    rhythm_duck_freq,
-                    self.params.synth_reverb_rhythm_duck_smooth.modulated_plain_value() as f64,
-                );
-                self.reverb.process_block(
-                    &mut self.output_buffer_l[..num_samples],
-                    &mut self.output_buffer_r[..num_samples],
-                    &self.reverb_send_l[..num_samples],
-                    &self.reverb_send_r[..num_samples],
-                );
-            }
-
-            if comp_enabled {
-                self.compressor.set_params(
-                    self.params.comp_threshold.modulated_plain_value() as f64,
-                    self.params.comp_ratio.modulated_plain_value() as f64,
-                    self.params.comp_attack.modulated_plain_value() as f64,
-                    self.params.comp_release.modulated_plain_value() as f64,
-                    self.params.comp_makeup.modulated_plain_value() as f64,
-                    self.params.comp_mix.modulated_plain_value() as f64,
-                    ScHpfMode::from_index(self.params.comp_sc_hpf.value()),
-                    LookaheadMode::from_index(self.params.comp_lookahead.value()),
-                    self.params.comp_knee.modulated_plain_value() as f64,
-                    self.params.comp_stereo_link.modulated_plain_value() as f64,
-                    self.params.comp_auto_makeup.value(),
-                );
-
-                let route_master = self.params.comp_route_master.value();
-                let route_looper = self.params.comp_route_looper.value();
-                let route_reverb = self.params.comp_route_reverb.value();
-
-                if route_master && route_looper && route_reverb {
-                    self.compressor.process_block(
-                        &mut self.output_buffer_l[..num_samples],
-                        &mut self.output_buffer_r[..num_samples],
-                    );
-                } else {
-                    for i in 0..num_samples {
-                        let master_l = self.comp_pre_looper_l[i];
-                        let master_r = self.comp_pre_looper_r[i];
-                        let looper_l = self.comp_pre_reverb_l[i] - self.comp_pre_looper_l[i];
-                        let looper_r = self.comp_pre_reverb_r[i] - self.comp_pre_looper_r[i];
-                        let reverb_l = self.output_buffer_l[i] - self.comp_pre_reverb_l[i];
-                        let reverb_r = self.output_buffer_r[i] - self.comp_pre_reverb_r[i];
-
-                        let mut comp_l = 0.0_f32;
-                        let mut comp_r = 0.0_f32;
-                        let mut bypass_l = 0.0_f32;
-                        let mut bypass_r = 0.0_f32;
-
-                        if route_master { comp_l += master_l; comp_r += master_r; }
-                        else { bypass_l += master_l; bypass_r += master_r; }
-
-                        if route_looper { comp_l += looper_l; comp_r += looper_r; }
-                        else { bypass_l += looper_l; bypass_r += looper_r; }
-
-                        if route_reverb { comp_l += reverb_l; comp_r += reverb_r; }
-                        else { bypass_l += reverb_l; bypass_r += reverb_r; }
-
-                        self.comp_pre_looper_l[i] = comp_l;
-                        self.comp_pre_looper_r[i] = comp_r;
-                        self.comp_pre_reverb_l[i] = bypass_l;
-                        self.comp_pre_reverb_r[i] = bypass_r;
-                    }
-
-                    self.compressor.process_block(
-                        &mut self.comp_pre_looper_l[..num_samples],
-                        &mut self.comp_pre_looper_r[..num_samples],
-                    );
+                self.process_fx_module(module, num_samples, &chain_ctx);
 
+                // A send keeps only its wet contribution, which rejoins the bus after the last slot
+                if as_send {
                     for i in 0..num_samples {
-                        self.output_buffer_l[i] = self.comp_pre_looper_l[i] + self.comp_pre_reverb_l[i];
-                        self.output_buffer_r[i] = self.comp_pre_looper_r[i] + self.comp_pre_reverb_r[i];
+                        self.fx_return_l[i] += self.output_buffer_l[i] - self.slot_dry_l[i];
+                        self.fx_return_r[i] += self.output_buffer_r[i] - self.slot_dry_r[i];
+                        self.output_buffer_l[i] = self.slot_dry_l[i];
+                        self.output_buffer_r[i] = self.slot_dry_r[i];
                     }
                 }
             }
 
-            self.stereo_control.set_crossover_hz(self.params.stereo_mono_bass.modulated_plain_value() as f64);
-            self.stereo_control.set_width(self.params.stereo_width.modulated_plain_value() as f64);
-            self.stereo_control.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
+            for i in 0..num_samples {
+                self.output_buffer_l[i] += self.fx_return_l[i];
+                self.output_buffer_r[i] += self.fx_return_r[i];
+            }
+            self.stems.capture(Stem::Reverb, &self.reverb_return_l, &self.reverb_return_r, num_samples);
+            self.stems.capture(Stem::Looper, &self.looper_return_l, &self.looper_return_r, num_samples);
 
-            if self.params.master_hpf_sub.value() == 1 {
+            if self.macros.plain(&self.params.master_hpf_sub) == 1 {
                 self.sub_hpf.set_mode(hpf_mode);
                 self.sub_hpf.set_boost(hpf_boost);
                 self.sub_hpf.process_mono(&mut self.sub_buffer[..num_samples]);
@@ -1123,20 +1690,23 @@ impl Plugin for PhaseBurn {
                 self.sub_box_cut.process_mono(&mut self.sub_buffer[..num_samples]);
             }
 
+            self.stems.capture(Stem::Sub, &self.sub_buffer, &self.sub_buffer, num_samples);
             for i in 0..num_samples {
                 self.output_buffer_l[i] += self.sub_buffer[i];
                 self.output_buffer_r[i] += self.sub_buffer[i];
             }
 
-            let linear_volume = self.params.global_volume.modulated_plain_value();
+            let linear_volume = self.macros.plain(&self.params.global_volume);
             let target_volume = linear_volume * linear_volume * linear_volume;
             let slew_coeff = 1.0 - (-1.0 / (self.sample_rate * 0.04)).exp();
 
-            for i in 0..num_samples {
-                self.volume_slew += (target_volume - self.volume_slew) * slew_coeff;
-                self.output_buffer_l[i] *= self.volume_slew;
-                self.output_buffer_r[i] *= self.volume_slew;
-            }
+            self.stems.apply_master_volume(
+                &mut self.output_buffer_l[..num_samples],
+                &mut self.output_buffer_r[..num_samples],
+                &mut self.volume_slew,
+                target_volume,
+                slew_coeff,
+            );
 
             let comp_latency = if comp_enabled {
                 self.ui_state.comp_gr_db.store(
@@ -1153,7 +1723,7 @@ impl Plugin for PhaseBurn {
                 std::sync::atomic::Ordering::Relaxed,
             );
 
-            if self.params.limiter_enable.value() {
+            if self.macros.plain(&self.params.limiter_enable) {
                 self.limiter.process_block(&mut self.output_buffer_l, &mut self.output_buffer_r);
                 self.ui_state.limiter_latency_samples.store(
                     self.limiter.lookahead_samples() as u32,
@@ -1163,7 +1733,7 @@ impl Plugin for PhaseBurn {
                 self.ui_state.limiter_latency_samples.store(0, std::sync::atomic::Ordering::Relaxed);
             }
 
-            let limiter_latency = if self.params.limiter_enable.value() {
+            let limiter_latency = if self.macros.plain(&self.params.limiter_enable) {
                 self.limiter.lookahead_samples()
             } else {
                 0
@@ -1174,6 +1744,11 @@ impl Plugin for PhaseBurn {
                 context.set_latency_samples(total_latency);
             }
 
+            let stems_master_volume = self.macros.plain(&self.params.stems_post_master);
+            for (stem, port) in Stem::ALL.into_iter().zip(aux.outputs.iter_mut()) {
+                self.stems.write(stem, port.as_slice(), num_samples, stems_master_volume, total_latency as usize);
+            }
+
             let mut peak: f32 = 0.0;
             for (i, channel_samples) in buffer.iter_samples().enumerate() {
                 peak = peak.max(self.output_buffer_l[i].abs()).max(self.output_buffer_r[i].abs());
@@ -1202,6 +1777,11 @@ impl Plugin for PhaseBurn {
                     *sample_out = 0.0;
                 }
             }
+            for port in aux.outputs.iter_mut() {
+                for channel in port.as_slice() {
+                    channel.fill(0.0);
+                }
+            }
         }
 
         ProcessStatus::Normal
@@ -1282,9 +1862,9 @@ fn apply_midi_learn(
                         data: [0xB0 | out_channel, value_cc, cc_value],
                         len: 3,
                     };
-                    if let Ok(mut q) = ui_state.midi_device_output_queue.try_lock() {
-                        q.push_back(msg);
-                    }
+                    let _ = ui_state
+                        .midi_device_output_queue
+                        .try_send(midi_devices::ScheduledMidiMessage { msg, send_at: None, clock_tick: None });
                 }
             }
         }
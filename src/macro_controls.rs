@@ -0,0 +1,384 @@
+//! Macro controls: eight host-visible knobs that each offset a list of other
+//! parameters. Offsets are added where the audio thread reads parameters, so
+//! target knobs keep their own values and macros stack with host automation
+//! and the LFOs. The A/B preset morph and scene recall ride the same offsets.
+
+use nih_plug::prelude::{Param, ParamPtr, Params};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const MACRO_COUNT: usize = 8;
+pub const MACRO_MAX_TARGETS: usize = 16;
+
+const CURVE_RANGE: f32 = 6.0;
+
+/// Parameters the audio thread reads without the router: the macros
+/// themselves, the FX chain order and its Mod FX shortcut, and the
+/// sequencer's velocity, length and position modifiers.
+const UNROUTED_PREFIXES: &[&str] = &["macro_", "fx_slot_", "mod_fx_position", "vel_", "len_mod_", "pos_mod_"];
+
+/// Whether a macro can target this parameter.
+pub fn is_macro_target(param_id: &str) -> bool {
+    !UNROUTED_PREFIXES.iter().any(|prefix| param_id.starts_with(prefix))
+}
+
+/// One macro destination. `min` and `max` are normalized offsets (-1..1)
+/// applied at macro 0 and macro 1; `curve` bends the travel between them
+/// (negative rushes, positive eases in).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MacroTarget {
+    pub param_id: String,
+    pub min: f32,
+    pub max: f32,
+    #[serde(default)]
+    pub curve: f32,
+}
+
+impl MacroTarget {
+    pub fn new(param_id: String) -> Self {
+        Self { param_id, min: 0.0, max: 0.5, curve: 0.0 }
+    }
+
+    pub fn offset(&self, value: f32) -> f32 {
+        curve_offset(self.min, self.max, self.curve, value)
+    }
+}
+
+pub type MacroTargets = [Vec<MacroTarget>; MACRO_COUNT];
+
+fn curve_offset(min: f32, max: f32, curve: f32, value: f32) -> f32 {
+    let t = value.clamp(0.0, 1.0);
+    let shaped = if curve.abs() < 1e-3 {
+        t
+    } else {
+        let k = curve * CURVE_RANGE;
+        (k * t).exp_m1() / k.exp_m1()
+    };
+    min + (max - min) * shaped
+}
+
+/// A preset's value for one parameter, normalized.
+#[derive(Clone, Copy)]
+pub struct SnapshotValue {
+    pub param: ParamPtr,
+    pub value: f32,
+    /// Stepped parameters switch at the morph midpoint instead of gliding.
+    pub discrete: bool,
+}
+
+struct MorphRoute {
+    param: ParamPtr,
+    delta: f32,
+    discrete: bool,
+}
+
+impl MorphRoute {
+    fn offset(&self, t: f32) -> f32 {
+        if !self.discrete {
+            self.delta * t
+        } else if t < 0.5 {
+            0.0
+        } else {
+            self.delta
+        }
+    }
+}
+
+/// A scene recall's offset for one parameter, moving from where the
+/// previous recall left it to the scene's value over the morph.
+struct SceneRoute {
+    param: ParamPtr,
+    start: f32,
+    end: f32,
+    discrete: bool,
+}
+
+impl SceneRoute {
+    /// `progress` is negative while the recall waits for its beat or bar.
+    fn offset(&self, progress: f32) -> f32 {
+        if progress < 0.0 || (self.discrete && progress < 0.5) {
+            self.start
+        } else if self.discrete {
+            self.end
+        } else {
+            self.start + (self.end - self.start) * progress.min(1.0)
+        }
+    }
+}
+
+struct Route {
+    macro_idx: usize,
+    param: ParamPtr,
+    min: f32,
+    max: f32,
+    curve: f32,
+}
+
+/// Audio-thread side of the macros: target ids resolved to parameter
+/// pointers, and the summed offset per parameter for the current block.
+pub struct MacroRouter {
+    lookup: Vec<(String, ParamPtr)>,
+    routes: Vec<Route>,
+    morph: Vec<MorphRoute>,
+    scene: Vec<SceneRoute>,
+    scene_spare: Vec<SceneRoute>,
+    scene_progress: f32,
+    offsets: HashMap<ParamPtr, f32>,
+}
+
+impl MacroRouter {
+    /// Resolves every parameter `is_macro_target` accepts.
+    pub fn new(params: &impl Params) -> Self {
+        let lookup: Vec<(String, ParamPtr)> = params
+            .param_map()
+            .into_iter()
+            .filter(|(id, _, _)| is_macro_target(id))
+            .map(|(id, ptr, _)| (id, ptr))
+            .collect();
+        Self {
+            routes: Vec::with_capacity(MACRO_COUNT * MACRO_MAX_TARGETS),
+            morph: Vec::with_capacity(lookup.len()),
+            scene: Vec::with_capacity(lookup.len()),
+            scene_spare: Vec::with_capacity(lookup.len()),
+            scene_progress: 1.0,
+            offsets: HashMap::with_capacity(lookup.len()),
+            lookup,
+        }
+    }
+
+    /// Re-resolves the target lists. Ids that no longer exist are skipped.
+    pub fn set_targets(&mut self, targets: &MacroTargets) {
+        self.routes.clear();
+        for (macro_idx, list) in targets.iter().enumerate() {
+            for target in list.iter().take(MACRO_MAX_TARGETS) {
+                if let Some((_, param)) = self.lookup.iter().find(|(id, _)| *id == target.param_id) {
+                    self.routes.push(Route {
+                        macro_idx,
+                        param: *param,
+                        min: target.min,
+                        max: target.max,
+                        curve: target.curve,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Sets the A/B morph endpoints. Both snapshots come from the same
+    /// preset writer, so values pair up by index; only parameters that
+    /// differ between A and B are kept.
+    pub fn set_morph(&mut self, a: &[SnapshotValue], b: &[SnapshotValue]) {
+        self.morph.clear();
+        for (a, b) in a.iter().zip(b) {
+            if a.param == b.param && a.value != b.value {
+                self.morph.push(MorphRoute { param: a.param, delta: b.value - a.value, discrete: a.discrete });
+            }
+        }
+    }
+
+    pub fn clear_morph(&mut self) {
+        self.morph.clear();
+    }
+
+    /// Starts a scene recall towards `target`. Each parameter's offset moves
+    /// from its current scene offset to whatever takes the knob to the
+    /// scene's value; the knobs themselves are never written.
+    pub fn start_scene(&mut self, target: &[SnapshotValue]) {
+        self.scene_spare.clear();
+        for (i, value) in target.iter().enumerate() {
+            // Scene snapshots share one writer order, so the previous route is
+            // normally at the same index
+            let previous = match self.scene.get(i) {
+                Some(route) if route.param == value.param => Some(route),
+                _ => self.scene.iter().find(|route| route.param == value.param),
+            };
+            let start = previous.map_or(0.0, |route| route.offset(self.scene_progress));
+            // SAFETY: the pointers come from the params object this router was
+            // built from, which the plugin keeps alive alongside it.
+            let knob = unsafe { value.param.unmodulated_normalized_value() };
+            self.scene_spare.push(SceneRoute { param: value.param, start, end: value.value - knob, discrete: value.discrete });
+        }
+        std::mem::swap(&mut self.scene, &mut self.scene_spare);
+        self.scene_progress = -1.0;
+    }
+
+    /// Drops the scene offsets, e.g. once a preset has been written to the
+    /// knobs.
+    pub fn clear_scene(&mut self) {
+        self.scene.clear();
+    }
+
+    /// Offsets a landed recall holds each parameter at.
+    pub fn scene_offsets(&self) -> impl Iterator<Item = (ParamPtr, f32)> + '_ {
+        self.scene.iter().filter(|route| route.end != 0.0).map(|route| (route.param, route.end))
+    }
+
+    /// Sums the offsets of every macro route, the A/B morph at position
+    /// `morph` and the scene recall at `scene_progress` per target for this
+    /// block.
+    pub fn update(&mut self, values: &[f32; MACRO_COUNT], morph: f32, scene_progress: f32) {
+        self.offsets.clear();
+        for route in &self.routes {
+            let offset = curve_offset(route.min, route.max, route.curve, values[route.macro_idx]);
+            *self.offsets.entry(route.param).or_insert(0.0) += offset;
+        }
+        let t = morph.clamp(0.0, 1.0);
+        for route in &self.morph {
+            *self.offsets.entry(route.param).or_insert(0.0) += route.offset(t);
+        }
+        self.scene_progress = scene_progress;
+        for route in &self.scene {
+            let offset = route.offset(scene_progress);
+            if offset != 0.0 {
+                *self.offsets.entry(route.param).or_insert(0.0) += offset;
+            }
+        }
+    }
+
+    /// The parameter's modulated value with macro, morph and scene offsets
+    /// added in the normalized domain.
+    pub fn plain<P: Param>(&self, param: &P) -> P::Plain {
+        if self.offsets.is_empty() {
+            return param.modulated_plain_value();
+        }
+        match self.offsets.get(&param.as_ptr()) {
+            Some(&offset) if offset != 0.0 => {
+                param.preview_plain((param.modulated_normalized_value() + offset).clamp(0.0, 1.0))
+            }
+            _ => param.modulated_plain_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nih_plug::prelude::{FloatParam, FloatRange, IntParam, IntRange};
+
+    #[derive(Params)]
+    struct TestParams {
+        #[id = "cutoff"]
+        cutoff: FloatParam,
+        #[id = "mode"]
+        mode: IntParam,
+        #[id = "macro_1"]
+        macro_1: FloatParam,
+    }
+
+    fn test_params() -> TestParams {
+        TestParams {
+            cutoff: FloatParam::new("Cutoff", 0.25, FloatRange::Linear { min: 0.0, max: 1.0 }),
+            mode: IntParam::new("Mode", 0, IntRange::Linear { min: 0, max: 3 }),
+            macro_1: FloatParam::new("Macro 1", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 }),
+        }
+    }
+
+    fn targets(list: Vec<MacroTarget>) -> MacroTargets {
+        let mut targets = MacroTargets::default();
+        targets[0] = list;
+        targets
+    }
+
+    #[test]
+    fn curve_keeps_endpoints() {
+        let target = MacroTarget { param_id: String::new(), min: -0.5, max: 0.5, curve: 0.8 };
+        assert!((target.offset(0.0) + 0.5).abs() < 1e-6);
+        assert!((target.offset(1.0) - 0.5).abs() < 1e-6);
+        assert!(target.offset(0.5) < 0.0);
+    }
+
+    #[test]
+    fn offsets_stack_on_the_base_value_and_clamp() {
+        let params = test_params();
+        let mut router = MacroRouter::new(&params);
+        router.set_targets(&targets(vec![
+            MacroTarget::new("cutoff".to_string()),
+            MacroTarget::new("cutoff".to_string()),
+        ]));
+        let mut values = [0.0; MACRO_COUNT];
+        router.update(&values, 0.0, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.25).abs() < 1e-6);
+        values[0] = 0.5;
+        router.update(&values, 0.0, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.75).abs() < 1e-6);
+        values[0] = 1.0;
+        router.update(&values, 0.0, 1.0);
+        assert!((router.plain(&params.cutoff) - 1.0).abs() < 1e-6);
+        assert!((params.cutoff.value() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn unknown_and_macro_targets_are_skipped() {
+        let params = test_params();
+        let mut router = MacroRouter::new(&params);
+        router.set_targets(&targets(vec![
+            MacroTarget::new("gone".to_string()),
+            MacroTarget::new("macro_1".to_string()),
+        ]));
+        router.update(&[1.0; MACRO_COUNT], 0.0, 1.0);
+        assert!(router.offsets.is_empty());
+        assert!(router.plain(&params.macro_1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn morph_glides_continuous_params_and_switches_steps_at_midpoint() {
+        let params = test_params();
+        let mut router = MacroRouter::new(&params);
+        let snapshot = |cutoff: f32, mode: i32| [
+            SnapshotValue { param: params.cutoff.as_ptr(), value: cutoff, discrete: false },
+            SnapshotValue { param: params.mode.as_ptr(), value: params.mode.preview_normalized(mode), discrete: true },
+        ];
+        router.set_morph(&snapshot(0.25, 0), &snapshot(0.75, 3));
+
+        router.update(&[0.0; MACRO_COUNT], 0.25, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.375).abs() < 1e-6);
+        assert_eq!(router.plain(&params.mode), 0);
+        router.update(&[0.0; MACRO_COUNT], 0.75, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.625).abs() < 1e-6);
+        assert_eq!(router.plain(&params.mode), 3);
+
+        router.clear_morph();
+        router.update(&[0.0; MACRO_COUNT], 0.75, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scene_recall_moves_from_the_previous_scene_to_the_new_one() {
+        let params = test_params();
+        let mut router = MacroRouter::new(&params);
+        let scene = |cutoff: f32, mode: i32| [
+            SnapshotValue { param: params.cutoff.as_ptr(), value: cutoff, discrete: false },
+            SnapshotValue { param: params.mode.as_ptr(), value: params.mode.preview_normalized(mode), discrete: true },
+        ];
+
+        router.start_scene(&scene(0.75, 2));
+        router.update(&[0.0; MACRO_COUNT], 0.0, -1.0);
+        assert!((router.plain(&params.cutoff) - 0.25).abs() < 1e-6);
+        router.update(&[0.0; MACRO_COUNT], 0.0, 0.5);
+        assert!((router.plain(&params.cutoff) - 0.5).abs() < 1e-6);
+        assert_eq!(router.plain(&params.mode), 2);
+        router.update(&[0.0; MACRO_COUNT], 0.0, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.75).abs() < 1e-6);
+        assert_eq!(router.scene_offsets().count(), 2);
+
+        // The next recall starts from the landed scene, not the knobs
+        router.start_scene(&scene(0.25, 0));
+        router.update(&[0.0; MACRO_COUNT], 0.0, 0.25);
+        assert!((router.plain(&params.cutoff) - 0.625).abs() < 1e-6);
+        assert_eq!(router.plain(&params.mode), 2);
+        router.update(&[0.0; MACRO_COUNT], 0.0, 1.0);
+        assert!((router.plain(&params.cutoff) - 0.25).abs() < 1e-6);
+        assert_eq!(router.scene_offsets().count(), 0);
+
+        assert!((params.cutoff.value() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn only_routed_params_are_targets() {
+        assert!(is_macro_target("synth_filter_cutoff"));
+        assert!(is_macro_target("swing_amount"));
+        assert!(!is_macro_target("macro_3"));
+        assert!(!is_macro_target("fx_slot_4"));
+        assert!(!is_macro_target("vel_strength_amount"));
+    }
+}
@@ -3,6 +3,7 @@
 use nih_plug::midi::NoteEvent;
 use nih_plug::prelude::ProcessContext;
 use crate::sequencer::midi_to_frequency;
+use std::time::{Duration, Instant};
 
 pub struct MidiCCState {
     cc_msb: [u8; 32],
@@ -155,11 +156,111 @@ impl MidiState {
     }
 }
 
+/// Clock ticks are generated on a 48 PPQN grid; each output keeps every
+/// `CLOCK_GRID_PPQN / ppqn`-th tick.
+pub const CLOCK_GRID_PPQN: u32 = 48;
+const MAX_CLOCK_EVENTS: usize = 256;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClockOutSettings {
+    pub clock: bool,
+    pub transport: bool,
+    pub offset_ms: f32,
+    /// Keep clocking while stopped and send a position reset before Start.
+    pub din_sync: bool,
+    /// Hold Start back until the sequencer's next downbeat.
+    pub start_on_bar: bool,
+    /// Sequencer swing amount to apply to the clock, if enabled.
+    pub swing: Option<f32>,
+}
+
+/// Callback timing errors beyond this mean an xrun, a pause or offline
+/// rendering, and the block clock starts over.
+const BLOCK_CLOCK_MAX_ERROR: f64 = 0.05;
+/// Share of each block's timing error folded into the block clock: slow
+/// enough to average out callback jitter (about 1000 blocks), fast enough to
+/// follow drift between the audio and system clocks.
+const BLOCK_CLOCK_TRACKING: f64 = 0.001;
+
+/// System time of each block's first sample, for scheduling MIDI out. It
+/// advances by the samples processed rather than by when `process` happens
+/// to run, so callback jitter doesn't move messages: they keep their sample
+/// spacing, up to the MIDI thread's wake-up precision (typically well under
+/// a millisecond), and a fixed delay to the audio output. The schedule
+/// starts one block after the first callback so late callbacks still land
+/// in time; the clock offset setting trims the remaining fixed delay.
+#[derive(Default)]
+pub struct BlockClock {
+    anchor: Option<Instant>,
+    /// Seconds from the anchor to the current block.
+    elapsed: f64,
+    /// How far ahead of its callback a block is scheduled.
+    lead: f64,
+}
+
+impl BlockClock {
+    /// Start time of the next block; call once per block.
+    pub fn block_start(&mut self, now: Instant, num_samples: usize, sample_rate: f32) -> Instant {
+        let block = num_samples as f64 / sample_rate as f64;
+        let anchor = match self.anchor {
+            Some(anchor) => {
+                let since_anchor = match now.checked_duration_since(anchor) {
+                    Some(d) => d.as_secs_f64(),
+                    None => -anchor.duration_since(now).as_secs_f64(),
+                };
+                let error = since_anchor - (self.elapsed - self.lead);
+                if error.abs() > BLOCK_CLOCK_MAX_ERROR {
+                    self.start(now, block)
+                } else {
+                    self.elapsed += error * BLOCK_CLOCK_TRACKING;
+                    anchor
+                }
+            }
+            None => self.start(now, block),
+        };
+        let start = anchor + Duration::from_secs_f64(self.elapsed.max(0.0));
+        self.elapsed += block;
+        start
+    }
+
+    /// Drops the reference, e.g. after a sample-rate change.
+    pub fn reset(&mut self) {
+        self.anchor = None;
+    }
+
+    fn start(&mut self, now: Instant, block: f64) -> Instant {
+        self.anchor = Some(now);
+        self.lead = block;
+        self.elapsed = block;
+        now
+    }
+}
+
+/// The sequencer's place in its bar at the start of a block.
+#[derive(Clone, Copy, Debug)]
+pub struct BarPosition {
+    pub beats: f64,
+    pub bar_length_samples: usize,
+}
+
+/// A clock or transport message at a sample offset in the current block.
+/// For clock ticks, `tick` is the grid index within the bar.
+#[derive(Clone, Copy, Debug)]
+pub struct ClockEvent {
+    pub status: u8,
+    pub tick: u8,
+    pub sample_offset: u32,
+}
+
 pub struct MidiOutput {
     pending_notes: Vec<PendingMidiNote>,
-    clock_counter: f64,
-    samples_per_clock: f64,
-    last_was_playing: bool,
+    clock_events: Vec<ClockEvent>,
+    free_run_beats: f64,
+    was_running: bool,
+    /// Pending Start / Stop, in samples from the current block's start on
+    /// the (offset) tick timeline.
+    start_at: Option<f64>,
+    stop_at: Option<f64>,
     output_channel: u8,
 }
 
@@ -181,9 +282,11 @@ impl MidiOutput {
     pub fn new() -> Self {
         Self {
             pending_notes: Vec::with_capacity(32),
-            clock_counter: 0.0,
-            samples_per_clock: 0.0,
-            last_was_playing: false,
+            clock_events: Vec::with_capacity(MAX_CLOCK_EVENTS),
+            free_run_beats: 0.0,
+            was_running: false,
+            start_at: None,
+            stop_at: None,
             output_channel: 0,
         }
     }
@@ -192,13 +295,6 @@ impl MidiOutput {
         self.output_channel = channel.min(15);
     }
 
-    pub fn update_tempo(&mut self, sample_rate: f32, tempo: f64) {
-        let beats_per_second = tempo / 60.0;
-        let clocks_per_beat = 24.0;
-        let clocks_per_second = beats_per_second * clocks_per_beat;
-        self.samples_per_clock = sample_rate as f64 / clocks_per_second;
-    }
-
     pub fn queue_note_on(&mut self, note: u8, velocity: u8, sample_offset: u32) {
         self.pending_notes.push(PendingMidiNote {
             note,
@@ -217,21 +313,17 @@ impl MidiOutput {
         });
     }
 
+    /// Sends queued notes to the host and schedules this block's clock and
+    /// transport messages. `position` is `None` while the sequencer is stopped.
     pub fn send_pending<P: nih_plug::prelude::Plugin>(
         &mut self,
         context: &mut impl ProcessContext<P>,
-        is_playing: bool,
+        position: Option<BarPosition>,
         buffer_samples: usize,
         sample_rate: f32,
         tempo: f64,
+        clock: &ClockOutSettings,
     ) {
-        self.update_tempo(sample_rate, tempo);
-
-        if is_playing && !self.last_was_playing {
-            self.clock_counter = 0.0;
-        }
-        self.last_was_playing = is_playing;
-
         for pending in self.pending_notes.drain(..) {
             if pending.is_on {
                 context.send_event(NoteEvent::NoteOn {
@@ -252,25 +344,155 @@ impl MidiOutput {
             }
         }
 
-        if is_playing && self.samples_per_clock > 0.0 {
-            let mut sample_in_buffer = 0u32;
-            while sample_in_buffer < buffer_samples as u32 {
-                let samples_until_clock = self.samples_per_clock - self.clock_counter;
-                if samples_until_clock <= 0.0 {
-                    self.clock_counter = 0.0;
-                    continue;
+        self.schedule_clock(position, buffer_samples, sample_rate, tempo, clock);
+    }
+
+    fn schedule_clock(
+        &mut self,
+        position: Option<BarPosition>,
+        buffer_samples: usize,
+        sample_rate: f32,
+        tempo: f64,
+        clock: &ClockOutSettings,
+    ) {
+        self.clock_events.clear();
+        if tempo <= 0.0 || sample_rate <= 0.0 {
+            return;
+        }
+
+        // Follow the sequencer's own bar length so consecutive blocks tile
+        // exactly; while stopped the clock free-runs from wherever it was.
+        let running = position.is_some();
+        let (phase, samples_per_beat) = match position {
+            Some(pos) if pos.bar_length_samples > 0 => (pos.beats, pos.bar_length_samples as f64 / 4.0),
+            _ => (self.free_run_beats, sample_rate as f64 * 60.0 / tempo),
+        };
+        let block_beats = buffer_samples as f64 / samples_per_beat;
+        let offset_samples = clock.offset_ms as f64 * 0.001 * sample_rate as f64;
+        let offset_beats = offset_samples / samples_per_beat;
+        self.free_run_beats = (phase + block_beats).rem_euclid(4.0);
+
+        let just_started = running && !self.was_running;
+        let just_stopped = !running && self.was_running;
+        self.was_running = running;
+
+        // Transport rides the same timeline as the ticks: the sequencer's
+        // position now is `offset_samples` into the block, so a negative
+        // offset sends Start early along with the ticks.
+        if clock.transport {
+            if just_started {
+                if self.stop_at.take().is_some() {
+                    self.push_clock_event(0xFC, 0, 0);
                 }
+                self.start_at = Some(offset_samples.round());
+            }
+            if just_stopped {
+                self.start_at = None;
+                self.stop_at = Some(offset_samples.round());
+            }
+        } else {
+            self.start_at = None;
+            self.stop_at = None;
+        }
+        // Ticks the running sequencer still owes keep going until a delayed Stop is out
+        let ticking = |stop_at: Option<f64>| clock.clock && (running || clock.din_sync || stop_at.is_some());
+        if !ticking(self.stop_at) && self.start_at.is_none() && self.stop_at.is_none() {
+            return;
+        }
 
-                let next_clock_sample = sample_in_buffer + samples_until_clock as u32;
-                if next_clock_sample < buffer_samples as u32 {
-                    self.clock_counter = 0.0;
-                    sample_in_buffer = next_clock_sample + 1;
-                } else {
-                    self.clock_counter += (buffer_samples as u32 - sample_in_buffer) as f64;
-                    break;
+        // A tick at bar position t (beats) lands at (t - phase) + offset.
+        let window_start = phase - offset_beats;
+        let window_end = window_start + block_beats;
+        // A negative offset puts the start position before the window; the
+        // ticks in between were never sent and go out at once after Start.
+        let catch_up_from = match self.start_at {
+            Some(start) if just_started && start < 0.0 => Some(start),
+            _ => None,
+        };
+        let from = if catch_up_from.is_some() { phase } else { window_start };
+        let margin = if clock.swing.is_some() { 0.5 } else { 0.0 };
+        let grid = CLOCK_GRID_PPQN as f64;
+        let first = ((from - margin) * grid).floor() as i64 - 1;
+        let last = ((window_end + margin) * grid).ceil() as i64 + 1;
+
+        for k in first..last {
+            let straight = k as f64 / grid;
+            let t = match clock.swing {
+                Some(amount) => swing_beats(straight, amount),
+                None => straight,
+            };
+            // Rounding to whole samples keeps ticks on a block edge from
+            // being sent twice or dropped.
+            let exact = ((t - window_start) * samples_per_beat).round();
+            if exact >= buffer_samples as f64 {
+                continue;
+            }
+            if exact < 0.0 && !catch_up_from.is_some_and(|from| exact >= from) {
+                continue;
+            }
+            let sample = exact.max(0.0) as u32;
+            let tick = k.rem_euclid(4 * CLOCK_GRID_PPQN as i64) as u32;
+
+            if let Some(stop) = self.stop_at.filter(|&stop| exact >= stop) {
+                self.stop_at = None;
+                self.push_clock_event(0xFC, 0, stop.max(0.0) as u32);
+            }
+            if let Some(start) = self.start_at.filter(|&start| exact >= start) {
+                if !clock.start_on_bar {
+                    self.start_at = None;
+                    self.push_start(clock.din_sync, start.max(0.0) as u32);
+                } else if tick == 0 {
+                    self.start_at = None;
+                    self.push_start(clock.din_sync, sample);
                 }
             }
+            if ticking(self.stop_at) {
+                self.push_clock_event(0xF8, (tick % CLOCK_GRID_PPQN) as u8, sample);
+            }
+        }
+
+        // Transport due after the block's last tick, or with the clock off;
+        // anything later carries over to the next block.
+        let block = buffer_samples as f64;
+        if let Some(stop) = self.stop_at {
+            if stop < block {
+                self.stop_at = None;
+                self.push_clock_event(0xFC, 0, stop.max(0.0) as u32);
+            } else {
+                self.stop_at = Some(stop - block);
+            }
+        }
+        if let Some(start) = self.start_at {
+            if !clock.start_on_bar && start < block {
+                self.start_at = None;
+                self.push_start(clock.din_sync, start.max(0.0) as u32);
+            } else {
+                self.start_at = Some(start - block);
+            }
+        }
+    }
+
+    fn push_start(&mut self, din_sync: bool, sample_offset: u32) {
+        if din_sync {
+            // Song position 0: the reset pulse before the run line goes high.
+            self.push_clock_event(0xF2, 0, sample_offset);
         }
+        self.push_clock_event(0xFA, 0, sample_offset);
+    }
+
+    fn push_clock_event(&mut self, status: u8, tick: u8, sample_offset: u32) {
+        if self.clock_events.len() < MAX_CLOCK_EVENTS {
+            self.clock_events.push(ClockEvent {
+                status,
+                tick,
+                sample_offset,
+            });
+        }
+    }
+
+    /// Clock and transport messages scheduled by the last `send_pending`.
+    pub fn clock_events(&self) -> &[ClockEvent] {
+        &self.clock_events
     }
 
     pub fn clear(&mut self) {
@@ -278,6 +500,14 @@ impl MidiOutput {
     }
 }
 
+/// Applies the sequencer's 8th-note swing to a position in beats.
+fn swing_beats(beats: f64, swing_amount: f32) -> f64 {
+    let bar = (beats / 4.0).floor();
+    let in_bar = (beats / 4.0 - bar) as f32;
+    let swung = crate::params::DeviceParams::apply_swing(in_bar, swing_amount);
+    (bar + swung as f64) * 4.0
+}
+
 pub struct MidiProcessor {
     pub input: MidiState,
     pub output: MidiOutput,
@@ -324,12 +554,13 @@ impl MidiProcessor {
     pub fn send_output<P: nih_plug::prelude::Plugin>(
         &mut self,
         context: &mut impl ProcessContext<P>,
-        is_playing: bool,
+        position: Option<BarPosition>,
         buffer_samples: usize,
         sample_rate: f32,
         tempo: f64,
+        clock: &ClockOutSettings,
     ) {
-        self.output.send_pending(context, is_playing, buffer_samples, sample_rate, tempo);
+        self.output.send_pending(context, position, buffer_samples, sample_rate, tempo, clock);
     }
 
     pub fn get_cc(&self, cc: u8) -> f32 {
@@ -362,3 +593,127 @@ impl MidiProcessor {
         self.output.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_clock_ignores_callback_jitter() {
+        let mut clock = BlockClock::default();
+        let t0 = Instant::now();
+        let block = Duration::from_secs_f64(512.0 / 48000.0);
+        let first = clock.block_start(t0, 512, 48000.0);
+        assert_eq!(first, t0 + block);
+        for i in 1..50u32 {
+            // Callbacks wander by up to 3 ms either way
+            let jitter = Duration::from_micros(((i * 7919) % 6000) as u64);
+            let now = t0 + block * i + jitter - Duration::from_millis(3);
+            let start = clock.block_start(now, 512, 48000.0);
+            let expected = (first + block * i).duration_since(t0).as_secs_f64();
+            assert!((start.duration_since(t0).as_secs_f64() - expected).abs() < 1e-4);
+        }
+
+        // A callback far off the schedule starts over from it
+        let late = t0 + Duration::from_secs(10);
+        assert_eq!(clock.block_start(late, 512, 48000.0), late + block);
+    }
+
+    fn clock_only() -> ClockOutSettings {
+        ClockOutSettings {
+            clock: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn clock_ticks_cover_bar_once() {
+        let mut out = MidiOutput::new();
+        let sr = 48000.0;
+        let tempo = 120.0;
+        let block = 512usize;
+        let bar_samples = 96000usize;
+        let mut ticks = 0;
+        let mut pos = 0usize;
+        while pos < bar_samples {
+            let n = block.min(bar_samples - pos);
+            let position = BarPosition {
+                beats: pos as f64 / bar_samples as f64 * 4.0,
+                bar_length_samples: bar_samples,
+            };
+            out.schedule_clock(Some(position), n, sr, tempo, &clock_only());
+            ticks += out.clock_events().iter().filter(|e| e.status == 0xF8).count();
+            pos += n;
+        }
+        assert_eq!(ticks, 4 * CLOCK_GRID_PPQN as usize);
+    }
+
+    #[test]
+    fn start_on_bar_waits_for_downbeat() {
+        let mut out = MidiOutput::new();
+        let settings = ClockOutSettings {
+            clock: true,
+            transport: true,
+            start_on_bar: true,
+            din_sync: true,
+            ..Default::default()
+        };
+        // 120 BPM at 48 kHz: 24000 samples per beat; start on beat 4.
+        let at = |beats| Some(BarPosition { beats, bar_length_samples: 96000 });
+        out.schedule_clock(at(3.0), 12000, 48000.0, 120.0, &settings);
+        assert!(out.clock_events().iter().all(|e| e.status == 0xF8));
+
+        out.schedule_clock(at(3.5), 24000, 48000.0, 120.0, &settings);
+        let events = out.clock_events();
+        let start = events.iter().position(|e| e.status == 0xFA).expect("start sent on downbeat");
+        assert_eq!(events[start].sample_offset, 12000);
+        assert_eq!(events[start - 1].status, 0xF2);
+        assert_eq!(events[start + 1].status, 0xF8);
+        assert_eq!(events[start + 1].tick, 0);
+    }
+
+    #[test]
+    fn negative_offset_sends_start_before_first_tick() {
+        let mut out = MidiOutput::new();
+        let settings = ClockOutSettings {
+            clock: true,
+            transport: true,
+            offset_ms: -10.0,
+            ..Default::default()
+        };
+        // 480 samples early; a 48 PPQN tick every 500 samples
+        let at = |beats| Some(BarPosition { beats, bar_length_samples: 96000 });
+        out.schedule_clock(None, 512, 48000.0, 120.0, &settings);
+        assert!(out.clock_events().is_empty());
+
+        out.schedule_clock(at(0.0), 512, 48000.0, 120.0, &settings);
+        let events = out.clock_events();
+        assert_eq!(events[0].status, 0xFA);
+        assert_eq!((events[1].status, events[1].tick, events[1].sample_offset), (0xF8, 0, 0));
+        assert_eq!((events[2].status, events[2].tick, events[2].sample_offset), (0xF8, 1, 20));
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn positive_offset_delays_stop_with_the_ticks() {
+        let mut out = MidiOutput::new();
+        let settings = ClockOutSettings {
+            clock: true,
+            transport: true,
+            offset_ms: 20.0,
+            ..Default::default()
+        };
+        let at = |beats| Some(BarPosition { beats, bar_length_samples: 96000 });
+        out.schedule_clock(at(1.0), 512, 48000.0, 120.0, &settings);
+
+        // Stop belongs 960 samples in, i.e. in the block after next
+        out.schedule_clock(None, 512, 48000.0, 120.0, &settings);
+        assert!(out.clock_events().iter().all(|e| e.status == 0xF8));
+        out.schedule_clock(None, 512, 48000.0, 120.0, &settings);
+        let events = out.clock_events();
+        let stop = events.iter().position(|e| e.status == 0xFC).expect("stop sent");
+        assert_eq!(events[stop].sample_offset, 448);
+        assert!(events[..stop].iter().all(|e| e.status == 0xF8 && e.sample_offset <= 448));
+        assert!(events[stop + 1..].is_empty());
+    }
+}
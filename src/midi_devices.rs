@@ -1,8 +1,11 @@
 use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use midir::{MidiInput, MidiOutput, MidiInputConnection, MidiOutputConnection};
 use serde::{Deserialize, Serialize};
 use nih_plug::midi::NoteEvent;
+use crate::midi::CLOCK_GRID_PPQN;
 use crate::midi_learn::{CcMapping, MidiLearnMappings};
 
 #[derive(Clone, Copy)]
@@ -11,15 +14,36 @@ pub struct RawMidiMessage {
     pub len: u8,
 }
 
+/// An outgoing message, sent as soon as possible or at `send_at`.
+#[derive(Clone, Copy)]
+pub struct ScheduledMidiMessage {
+    pub msg: RawMidiMessage,
+    pub send_at: Option<Instant>,
+    /// Grid index of a clock tick (`CLOCK_GRID_PPQN` per quarter), used to
+    /// thin the clock to each output's PPQN.
+    pub clock_tick: Option<u8>,
+}
+
 pub type MidiInputQueue = Arc<Mutex<VecDeque<RawMidiMessage>>>;
-pub type MidiOutputQueue = Arc<Mutex<VecDeque<RawMidiMessage>>>;
+/// Bounded so the audio thread can `try_send` without allocating.
+pub type MidiOutputQueue = SyncSender<ScheduledMidiMessage>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SettingsConfig {
-    pub input_device_name: Option<String>,
-    pub output_device_name: Option<String>,
-    pub input_channel: MidiChannel,
-    pub output_channel: u8,
+    #[serde(default)]
+    pub inputs: Vec<MidiInputPort>,
+    #[serde(default)]
+    pub outputs: Vec<MidiOutputPort>,
+    // Single-device fields from older settings files, migrated into
+    // `inputs`/`outputs` on load.
+    #[serde(default, skip_serializing)]
+    input_device_name: Option<String>,
+    #[serde(default, skip_serializing)]
+    output_device_name: Option<String>,
+    #[serde(default, skip_serializing)]
+    input_channel: MidiChannel,
+    #[serde(default, skip_serializing)]
+    output_channel: u8,
     pub midi_mode: u8,
     #[serde(default)]
     pub midi_learn_mappings: Vec<CcMapping>,
@@ -39,14 +63,46 @@ pub struct SettingsConfig {
     pub midi_transport_out: bool,
     #[serde(default = "default_oversampling")]
     pub oversampling: i32,
+    #[serde(default = "default_call_response_bars")]
+    pub call_response_bars: u8,
+    #[serde(default)]
+    pub transpose_scale_aware: bool,
+    #[serde(default = "default_transpose_quantize")]
+    pub transpose_quantize: u8,
+    #[serde(default)]
+    pub transpose_gate: bool,
+    #[serde(default)]
+    pub virtual_input: bool,
+    #[serde(default)]
+    pub virtual_output: bool,
+    #[serde(default = "default_virtual_port_name")]
+    pub virtual_port_name: String,
+    #[serde(default)]
+    pub clock_offset_ms: f32,
+    #[serde(default)]
+    pub clock_din_sync: bool,
+    #[serde(default)]
+    pub clock_start_on_bar: bool,
+    #[serde(default)]
+    pub clock_swing: bool,
+    #[serde(default)]
+    pub tempo_mode: u8,
+    #[serde(default = "default_manual_bpm")]
+    pub manual_bpm: f32,
 }
 
 fn default_true() -> bool { true }
 fn default_oversampling() -> i32 { 1 }
+fn default_call_response_bars() -> u8 { 1 }
+fn default_transpose_quantize() -> u8 { 1 }
+fn default_virtual_port_name() -> String { "PhaseBurn".to_string() }
+fn default_manual_bpm() -> f32 { 120.0 }
 
 impl Default for SettingsConfig {
     fn default() -> Self {
         Self {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
             input_device_name: None,
             output_device_name: None,
             input_channel: MidiChannel::All,
@@ -61,6 +117,19 @@ impl Default for SettingsConfig {
             midi_transport_in: false,
             midi_transport_out: false,
             oversampling: 1,
+            call_response_bars: 1,
+            transpose_scale_aware: false,
+            transpose_quantize: 1,
+            transpose_gate: false,
+            virtual_input: false,
+            virtual_output: false,
+            virtual_port_name: default_virtual_port_name(),
+            clock_offset_ms: 0.0,
+            clock_din_sync: false,
+            clock_start_on_bar: false,
+            clock_swing: false,
+            tempo_mode: 0,
+            manual_bpm: 120.0,
         }
     }
 }
@@ -71,10 +140,33 @@ impl SettingsConfig {
             mappings: self.midi_learn_mappings.clone(),
         }
     }
+
+    fn migrate_legacy_devices(&mut self) {
+        if let Some(name) = self.input_device_name.take() {
+            if self.inputs.is_empty() {
+                self.inputs.push(MidiInputPort {
+                    name,
+                    channel: self.input_channel.clone(),
+                    roles: MidiRoles::default(),
+                });
+            }
+        }
+        if let Some(name) = self.output_device_name.take() {
+            if self.outputs.is_empty() {
+                self.outputs.push(MidiOutputPort {
+                    name,
+                    channel: self.output_channel,
+                    roles: MidiRoles::default(),
+                    clock_ppqn: default_clock_ppqn(),
+                });
+            }
+        }
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MidiChannel {
+    #[default]
     All,
     Channel(u8),
 }
@@ -103,31 +195,145 @@ impl MidiChannel {
     }
 }
 
+/// Which kinds of messages a port carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MidiRoles {
+    pub notes: bool,
+    pub cc: bool,
+    pub clock: bool,
+}
+
+impl Default for MidiRoles {
+    fn default() -> Self {
+        Self {
+            notes: true,
+            cc: true,
+            clock: true,
+        }
+    }
+}
+
+impl MidiRoles {
+    pub fn accepts(&self, status: u8) -> bool {
+        match status {
+            0xF2 | 0xF8 | 0xFA | 0xFB | 0xFC => self.clock,
+            0xB0..=0xCF => self.cc,
+            0x80..=0xEF => self.notes,
+            _ => false,
+        }
+    }
+
+    pub fn overlaps(&self, other: &MidiRoles) -> bool {
+        (self.notes && other.notes) || (self.cc && other.cc) || (self.clock && other.clock)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MidiInputPort {
+    pub name: String,
+    #[serde(default)]
+    pub channel: MidiChannel,
+    #[serde(default)]
+    pub roles: MidiRoles,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MidiOutputPort {
+    pub name: String,
+    #[serde(default)]
+    pub channel: u8,
+    #[serde(default)]
+    pub roles: MidiRoles,
+    #[serde(default = "default_clock_ppqn")]
+    pub clock_ppqn: u8,
+}
+
+fn default_clock_ppqn() -> u8 { 24 }
+
+pub const CLOCK_PPQN_OPTIONS: [u8; 4] = [4, 8, 24, 48];
+
+/// Open output connections, shared with the sender thread.
+struct OutputPorts {
+    connections: Vec<(MidiOutputPort, MidiOutputConnection)>,
+    virtual_output: Option<MidiOutputConnection>,
+}
+
+impl OutputPorts {
+    /// Sends to every output whose roles accept the message, re-channelling
+    /// channel messages and thinning clock ticks to each output's PPQN. The
+    /// virtual output gets channel messages unchanged and 24 PPQN clock.
+    fn send(&mut self, scheduled: &ScheduledMidiMessage) {
+        let msg = &scheduled.msg;
+        let len = msg.len as usize;
+        let status = msg.data[0];
+        let keep_tick = |ppqn: u8| {
+            let step = (CLOCK_GRID_PPQN / ppqn.clamp(1, CLOCK_GRID_PPQN as u8) as u32).max(1);
+            scheduled.clock_tick.is_none_or(|tick| (tick as u32).is_multiple_of(step))
+        };
+
+        if let Some(conn) = &mut self.virtual_output {
+            if status != 0xF8 || keep_tick(24) {
+                let _ = conn.send(&msg.data[..len]);
+            }
+        }
+
+        for (port, conn) in &mut self.connections {
+            if !port.roles.accepts(status) {
+                continue;
+            }
+            if status == 0xF8 && !keep_tick(port.clock_ppqn) {
+                continue;
+            }
+            let mut data = msg.data;
+            if status < 0xF0 {
+                data[0] = (status & 0xF0) | port.channel;
+            }
+            let _ = conn.send(&data[..len]);
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MidiDeviceInfo {
     pub name: String,
 }
 
+const HOTPLUG_INTERVAL: Duration = Duration::from_secs(1);
+const OUTPUT_QUEUE_CAPACITY: usize = 1024;
+const VIRTUAL_CLIENT_NAME: &str = "PhaseBurn Virtual";
+
 pub struct MidiDeviceManager {
     config: SettingsConfig,
     input_devices: Vec<MidiDeviceInfo>,
     output_devices: Vec<MidiDeviceInfo>,
-    input_connection: Option<MidiInputConnection<()>>,
-    output_connection: Option<MidiOutputConnection>,
+    input_connections: Vec<(String, MidiInputConnection<()>)>,
+    output_ports: Arc<Mutex<OutputPorts>>,
+    virtual_input: Option<MidiInputConnection<()>>,
     input_queue: MidiInputQueue,
     output_queue: MidiOutputQueue,
+    output_receiver: Option<Receiver<ScheduledMidiMessage>>,
+    last_hotplug_check: Option<Instant>,
+    probe: PortProbe,
 }
 
 impl MidiDeviceManager {
     pub fn new() -> Self {
+        let (output_queue, output_receiver) = mpsc::sync_channel(OUTPUT_QUEUE_CAPACITY);
         Self {
             config: SettingsConfig::default(),
             input_devices: Vec::new(),
             output_devices: Vec::new(),
-            input_connection: None,
-            output_connection: None,
+            input_connections: Vec::new(),
+            output_ports: Arc::new(Mutex::new(OutputPorts {
+                connections: Vec::new(),
+                virtual_output: None,
+            })),
+            virtual_input: None,
             input_queue: Arc::new(Mutex::new(VecDeque::with_capacity(256))),
-            output_queue: Arc::new(Mutex::new(VecDeque::with_capacity(256))),
+            output_queue,
+            output_receiver: Some(output_receiver),
+            last_hotplug_check: None,
+            probe: PortProbe::default(),
         }
     }
 
@@ -147,157 +353,366 @@ impl MidiDeviceManager {
         &self.output_devices
     }
 
-    pub fn input_channel(&self) -> &MidiChannel {
-        &self.config.input_channel
+    pub fn inputs(&self) -> &[MidiInputPort] {
+        &self.config.inputs
     }
 
-    pub fn output_channel(&self) -> u8 {
-        self.config.output_channel
+    pub fn outputs(&self) -> &[MidiOutputPort] {
+        &self.config.outputs
     }
 
-    pub fn connected_input_name(&self) -> Option<&str> {
-        self.config.input_device_name.as_deref()
+    pub fn is_input_connected(&self, name: &str) -> bool {
+        self.input_connections.iter().any(|(n, _)| n == name)
     }
 
-    pub fn connected_output_name(&self) -> Option<&str> {
-        self.config.output_device_name.as_deref()
+    pub fn is_output_connected(&self, name: &str) -> bool {
+        self.output_ports.lock()
+            .map(|ports| ports.connections.iter().any(|(p, _)| p.name == name))
+            .unwrap_or(false)
+    }
+
+    /// Channel used for CC feedback: the first output carrying CCs.
+    pub fn output_channel(&self) -> u8 {
+        self.config.outputs.iter()
+            .find(|p| p.roles.cc)
+            .map(|p| p.channel)
+            .unwrap_or(0)
     }
 
     pub fn refresh_devices(&mut self) {
-        self.input_devices.clear();
-        self.output_devices.clear();
+        let (inputs, outputs) = self.probe_ports();
+        self.input_devices = inputs;
+        self.output_devices = outputs;
+    }
 
-        if let Ok(midi_in) = MidiInput::new("PhaseBurn probe") {
-            for port in midi_in.ports() {
-                if let Ok(name) = midi_in.port_name(&port) {
-                    self.input_devices.push(MidiDeviceInfo { name });
-                }
+    /// Lists hardware and other software ports, hiding our own virtual ports
+    /// so they cannot be connected back to themselves.
+    fn probe_ports(&mut self) -> (Vec<MidiDeviceInfo>, Vec<MidiDeviceInfo>) {
+        let (mut inputs, mut outputs) = self.probe.list();
+        let own_in = self.virtual_input_port_name();
+        let own_out = self.virtual_output_port_name();
+        let is_own = |d: &MidiDeviceInfo| {
+            d.name.starts_with(VIRTUAL_CLIENT_NAME) || d.name == own_in || d.name == own_out
+        };
+        inputs.retain(|d| !is_own(d));
+        outputs.retain(|d| !is_own(d));
+        (inputs, outputs)
+    }
+
+    /// Re-probes ports at most once per second; when the device list changed,
+    /// drops connections to vanished ports and reconnects saved ones.
+    pub fn poll_hotplug(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last_hotplug_check {
+            if now.duration_since(last) < HOTPLUG_INTERVAL {
+                return;
             }
         }
+        self.last_hotplug_check = Some(now);
 
-        if let Ok(midi_out) = MidiOutput::new("PhaseBurn probe") {
-            for port in midi_out.ports() {
-                if let Ok(name) = midi_out.port_name(&port) {
-                    self.output_devices.push(MidiDeviceInfo { name });
-                }
-            }
+        let (inputs, outputs) = self.probe_ports();
+        let same = |a: &[MidiDeviceInfo], b: &[MidiDeviceInfo]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.name == y.name)
+        };
+        if same(&inputs, &self.input_devices) && same(&outputs, &self.output_devices) {
+            return;
         }
+        self.input_devices = inputs;
+        self.output_devices = outputs;
+
+        let input_devices = &self.input_devices;
+        self.input_connections.retain(|(name, _)| input_devices.iter().any(|d| &d.name == name));
+        let output_devices = &self.output_devices;
+        if let Ok(mut ports) = self.output_ports.lock() {
+            ports.connections.retain(|(port, _)| output_devices.iter().any(|d| d.name == port.name));
+        }
+
+        self.reconnect_saved_devices();
     }
 
+    /// Adds an input to the saved set (if new) and connects it.
     pub fn connect_input(&mut self, name: &str) -> bool {
-        self.disconnect_input();
+        if !self.config.inputs.iter().any(|p| p.name == name) {
+            self.config.inputs.push(MidiInputPort {
+                name: name.to_string(),
+                channel: MidiChannel::All,
+                roles: MidiRoles::default(),
+            });
+        }
+        self.open_input(name)
+    }
+
+    /// Adds an output to the saved set (if new) and connects it.
+    pub fn connect_output(&mut self, name: &str) -> bool {
+        if !self.config.outputs.iter().any(|p| p.name == name) {
+            self.config.outputs.push(MidiOutputPort {
+                name: name.to_string(),
+                channel: 0,
+                roles: MidiRoles::default(),
+                clock_ppqn: default_clock_ppqn(),
+            });
+        }
+        self.open_output(name)
+    }
+
+    fn open_input(&mut self, name: &str) -> bool {
+        self.close_input(name);
+
+        let Some(port_config) = self.config.inputs.iter().find(|p| p.name == name) else {
+            return false;
+        };
 
         let Ok(midi_in) = MidiInput::new("PhaseBurn input") else {
             return false;
         };
 
-        let port = midi_in.ports().into_iter().enumerate().find(|(i, _)| {
-            midi_in.port_name(&midi_in.ports()[*i]).map(|n| n == name).unwrap_or(false)
+        let port = midi_in.ports().into_iter().find(|port| {
+            midi_in.port_name(port).map(|n| n == name).unwrap_or(false)
         });
 
-        let Some((_, port)) = port else {
+        let Some(port) = port else {
             return false;
         };
 
-        let queue = self.input_queue.clone();
-        let channel_filter = self.config.input_channel.clone();
-
-        let result = midi_in.connect(
-            &port,
-            "PhaseBurn input",
-            move |_timestamp, data, _| {
-                if data.is_empty() {
-                    return;
-                }
-
-                let status = data[0];
-                let is_realtime = matches!(status, 0xF8 | 0xFA | 0xFB | 0xFC);
-                if !is_realtime && !(0x80..0xF0).contains(&status) {
-                    return;
-                }
-
-                if !is_realtime {
-                    let msg_channel = status & 0x0F;
-                    if !channel_filter.matches(msg_channel) {
-                        return;
-                    }
-                }
-
-                let mut msg = RawMidiMessage {
-                    data: [0; 3],
-                    len: data.len().min(3) as u8,
-                };
-                for (i, &byte) in data.iter().take(3).enumerate() {
-                    msg.data[i] = byte;
-                }
-
-                if let Ok(mut q) = queue.try_lock() {
-                    if q.len() < 512 {
-                        q.push_back(msg);
-                    }
-                }
-            },
-            (),
+        let callback = input_callback(
+            self.input_queue.clone(),
+            port_config.channel.clone(),
+            port_config.roles,
         );
+        let result = midi_in.connect(&port, "PhaseBurn input", callback, ());
 
         match result {
             Ok(conn) => {
-                self.input_connection = Some(conn);
-                self.config.input_device_name = Some(name.to_string());
+                self.input_connections.push((name.to_string(), conn));
                 true
             }
             Err(_) => false,
         }
     }
 
-    pub fn connect_output(&mut self, name: &str) -> bool {
-        self.disconnect_output();
+    fn open_output(&mut self, name: &str) -> bool {
+        self.close_output(name);
+
+        let Some(port_config) = self.config.outputs.iter().find(|p| p.name == name).cloned() else {
+            return false;
+        };
 
         let Ok(midi_out) = MidiOutput::new("PhaseBurn output") else {
             return false;
         };
 
-        let port = midi_out.ports().into_iter().enumerate().find(|(i, _)| {
-            midi_out.port_name(&midi_out.ports()[*i]).map(|n| n == name).unwrap_or(false)
+        let port = midi_out.ports().into_iter().find(|port| {
+            midi_out.port_name(port).map(|n| n == name).unwrap_or(false)
         });
 
-        let Some((_, port)) = port else {
+        let Some(port) = port else {
             return false;
         };
 
         match midi_out.connect(&port, "PhaseBurn output") {
             Ok(conn) => {
-                self.output_connection = Some(conn);
-                self.config.output_device_name = Some(name.to_string());
+                if let Ok(mut ports) = self.output_ports.lock() {
+                    ports.connections.push((port_config, conn));
+                }
                 true
             }
             Err(_) => false,
         }
     }
 
-    pub fn disconnect_input(&mut self) {
-        if let Some(conn) = self.input_connection.take() {
+    fn close_input(&mut self, name: &str) {
+        if let Some(idx) = self.input_connections.iter().position(|(n, _)| n == name) {
+            let (_, conn) = self.input_connections.remove(idx);
             conn.close();
         }
-        self.config.input_device_name = None;
     }
 
-    pub fn disconnect_output(&mut self) {
-        if let Some(conn) = self.output_connection.take() {
+    fn close_output(&mut self, name: &str) {
+        let Ok(mut ports) = self.output_ports.lock() else {
+            return;
+        };
+        if let Some(idx) = ports.connections.iter().position(|(p, _)| p.name == name) {
+            let (_, conn) = ports.connections.remove(idx);
             conn.close();
         }
-        self.config.output_device_name = None;
     }
 
-    pub fn set_input_channel(&mut self, channel: MidiChannel) {
-        self.config.input_channel = channel;
-        if let Some(name) = self.config.input_device_name.clone() {
-            self.connect_input(&name);
+    pub fn virtual_ports_supported(&self) -> bool {
+        cfg!(unix)
+    }
+
+    pub fn virtual_input_active(&self) -> bool {
+        self.virtual_input.is_some()
+    }
+
+    pub fn virtual_output_active(&self) -> bool {
+        self.output_ports.lock()
+            .map(|ports| ports.virtual_output.is_some())
+            .unwrap_or(false)
+    }
+
+    pub fn virtual_input_enabled(&self) -> bool {
+        self.config.virtual_input
+    }
+
+    pub fn virtual_output_enabled(&self) -> bool {
+        self.config.virtual_output
+    }
+
+    pub fn virtual_port_name(&self) -> &str {
+        &self.config.virtual_port_name
+    }
+
+    fn virtual_input_port_name(&self) -> String {
+        format!("{} In", self.config.virtual_port_name)
+    }
+
+    fn virtual_output_port_name(&self) -> String {
+        format!("{} Out", self.config.virtual_port_name)
+    }
+
+    pub fn set_virtual_input(&mut self, enabled: bool) {
+        self.config.virtual_input = enabled;
+        self.close_virtual_input();
+        if enabled {
+            self.open_virtual_input();
+        }
+    }
+
+    pub fn set_virtual_output(&mut self, enabled: bool) {
+        self.config.virtual_output = enabled;
+        self.close_virtual_output();
+        if enabled {
+            self.open_virtual_output();
+        }
+    }
+
+    /// Renames the virtual ports, recreating any that are open.
+    pub fn set_virtual_port_name(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() || name == self.config.virtual_port_name {
+            return;
+        }
+        self.config.virtual_port_name = name.to_string();
+        let (input, output) = (self.config.virtual_input, self.config.virtual_output);
+        self.set_virtual_input(input);
+        self.set_virtual_output(output);
+    }
+
+    #[cfg(unix)]
+    fn open_virtual_input(&mut self) -> bool {
+        use midir::os::unix::VirtualInput;
+
+        let Ok(midi_in) = MidiInput::new(VIRTUAL_CLIENT_NAME) else {
+            return false;
+        };
+        let callback = input_callback(self.input_queue.clone(), MidiChannel::All, MidiRoles::default());
+        match midi_in.create_virtual(&self.virtual_input_port_name(), callback, ()) {
+            Ok(conn) => {
+                self.virtual_input = Some(conn);
+                true
+            }
+            Err(_) => false,
         }
     }
 
-    pub fn set_output_channel(&mut self, channel: u8) {
-        self.config.output_channel = channel.min(15);
+    #[cfg(not(unix))]
+    fn open_virtual_input(&mut self) -> bool {
+        false
+    }
+
+    #[cfg(unix)]
+    fn open_virtual_output(&mut self) -> bool {
+        use midir::os::unix::VirtualOutput;
+
+        let Ok(midi_out) = MidiOutput::new(VIRTUAL_CLIENT_NAME) else {
+            return false;
+        };
+        match midi_out.create_virtual(&self.virtual_output_port_name()) {
+            Ok(conn) => {
+                if let Ok(mut ports) = self.output_ports.lock() {
+                    ports.virtual_output = Some(conn);
+                }
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn open_virtual_output(&mut self) -> bool {
+        false
+    }
+
+    fn close_virtual_input(&mut self) {
+        if let Some(conn) = self.virtual_input.take() {
+            conn.close();
+        }
+    }
+
+    fn close_virtual_output(&mut self) {
+        let Ok(mut ports) = self.output_ports.lock() else {
+            return;
+        };
+        if let Some(conn) = ports.virtual_output.take() {
+            conn.close();
+        }
+    }
+
+    /// Closes an input and removes it from the saved set.
+    pub fn disconnect_input(&mut self, name: &str) {
+        self.close_input(name);
+        self.config.inputs.retain(|p| p.name != name);
+    }
+
+    /// Closes an output and removes it from the saved set.
+    pub fn disconnect_output(&mut self, name: &str) {
+        self.close_output(name);
+        self.config.outputs.retain(|p| p.name != name);
+    }
+
+    pub fn set_input_channel(&mut self, name: &str, channel: MidiChannel) {
+        if let Some(port) = self.config.inputs.iter_mut().find(|p| p.name == name) {
+            port.channel = channel;
+            if self.is_input_connected(name) {
+                self.open_input(name);
+            }
+        }
+    }
+
+    pub fn set_input_roles(&mut self, name: &str, roles: MidiRoles) {
+        if let Some(port) = self.config.inputs.iter_mut().find(|p| p.name == name) {
+            port.roles = roles;
+            if self.is_input_connected(name) {
+                self.open_input(name);
+            }
+        }
+    }
+
+    pub fn set_output_channel(&mut self, name: &str, channel: u8) {
+        self.update_output_port(name, |port| port.channel = channel.min(15));
+    }
+
+    pub fn set_output_roles(&mut self, name: &str, roles: MidiRoles) {
+        self.update_output_port(name, |port| port.roles = roles);
+    }
+
+    pub fn set_output_clock_ppqn(&mut self, name: &str, ppqn: u8) {
+        if CLOCK_PPQN_OPTIONS.contains(&ppqn) {
+            self.update_output_port(name, |port| port.clock_ppqn = ppqn);
+        }
+    }
+
+    /// Applies `f` to the saved port and to its open connection's copy.
+    fn update_output_port(&mut self, name: &str, f: impl Fn(&mut MidiOutputPort)) {
+        if let Some(port) = self.config.outputs.iter_mut().find(|p| p.name == name) {
+            f(port);
+        }
+        if let Ok(mut ports) = self.output_ports.lock() {
+            if let Some((port, _)) = ports.connections.iter_mut().find(|(p, _)| p.name == name) {
+                f(port);
+            }
+        }
     }
 
     pub fn set_midi_mode(&mut self, mode: u8) {
@@ -336,44 +751,131 @@ impl MidiDeviceManager {
         self.config.midi_transport_out = enabled;
     }
 
+    pub fn set_clock_offset_ms(&mut self, offset_ms: f32) {
+        self.config.clock_offset_ms = offset_ms.clamp(-100.0, 100.0);
+    }
+
+    pub fn set_clock_din_sync(&mut self, enabled: bool) {
+        self.config.clock_din_sync = enabled;
+    }
+
+    pub fn set_clock_start_on_bar(&mut self, enabled: bool) {
+        self.config.clock_start_on_bar = enabled;
+    }
+
+    pub fn set_clock_swing(&mut self, enabled: bool) {
+        self.config.clock_swing = enabled;
+    }
+
+    pub fn set_tempo_mode(&mut self, mode: u8) {
+        self.config.tempo_mode = mode;
+    }
+
+    /// Persists the manual tempo once it settles; taps and ramps change it on
+    /// the audio thread, so the editor reports it back here.
+    pub fn sync_manual_bpm(&mut self, bpm: f32, ramping: bool) {
+        if !ramping && (bpm - self.config.manual_bpm).abs() > 0.005 {
+            self.config.manual_bpm = bpm.clamp(20.0, 300.0);
+            self.save_config();
+        }
+    }
+
     pub fn set_oversampling(&mut self, factor: i32) {
         self.config.oversampling = factor;
     }
 
+    pub fn set_call_response_bars(&mut self, bars: u8) {
+        self.config.call_response_bars = bars;
+    }
+
+    pub fn set_transpose_scale_aware(&mut self, enabled: bool) {
+        self.config.transpose_scale_aware = enabled;
+    }
+
+    pub fn set_transpose_quantize(&mut self, quantize: u8) {
+        self.config.transpose_quantize = quantize;
+    }
+
+    pub fn set_transpose_gate(&mut self, enabled: bool) {
+        self.config.transpose_gate = enabled;
+    }
+
+    /// True when an input and an output share a device, a role and a channel.
     pub fn has_feedback_risk(&self) -> bool {
-        let (Some(in_name), Some(out_name)) = (&self.config.input_device_name, &self.config.output_device_name) else {
-            return false;
-        };
-        if in_name != out_name {
-            return false;
-        }
-        match &self.config.input_channel {
-            MidiChannel::All => true,
-            MidiChannel::Channel(ch) => *ch == self.config.output_channel,
-        }
+        self.config.inputs.iter().any(|input| {
+            self.config.outputs.iter().any(|output| {
+                input.name == output.name
+                    && input.roles.overlaps(&output.roles)
+                    && input.channel.matches(output.channel)
+            })
+        })
     }
 
     pub fn auto_select_if_single(&mut self) {
-        if self.config.input_device_name.is_none() && self.input_devices.len() == 1 {
+        if self.config.inputs.is_empty() && self.input_devices.len() == 1 {
             let name = self.input_devices[0].name.clone();
             self.connect_input(&name);
         }
-        if self.config.output_device_name.is_none() && self.output_devices.len() == 1 {
+        if self.config.outputs.is_empty() && self.output_devices.len() == 1 {
             let name = self.output_devices[0].name.clone();
             self.connect_output(&name);
         }
     }
 
-    pub fn flush_output(&mut self) {
-        let Some(conn) = &mut self.output_connection else {
-            return;
-        };
-        if let Ok(mut q) = self.output_queue.try_lock() {
-            while let Some(msg) = q.pop_front() {
-                let len = msg.len as usize;
-                let _ = conn.send(&msg.data[..len]);
-            }
-        }
+    /// Starts the MIDI background thread: it delivers queued output messages
+    /// at their scheduled time and watches for devices being plugged in or
+    /// removed, whether or not the editor is open. Between deadlines it blocks
+    /// on the output queue. It exits once the manager is dropped.
+    pub fn start_background_thread(manager: &Arc<Mutex<MidiDeviceManager>>) {
+        let Ok(mut mgr) = manager.lock() else { return };
+        let Some(queue) = mgr.output_receiver.take() else { return };
+        let ports = Arc::downgrade(&mgr.output_ports);
+        drop(mgr);
+        let manager = Arc::downgrade(manager);
+        let _ = std::thread::Builder::new()
+            .name("PhaseBurn MIDI".to_string())
+            .spawn(move || {
+                // Messages go out in queue order, so a scheduled message also
+                // holds back everything queued after it
+                let mut pending: VecDeque<ScheduledMidiMessage> = VecDeque::with_capacity(256);
+                let mut next_hotplug = Instant::now() + HOTPLUG_INTERVAL;
+                while let Some(ports) = ports.upgrade() {
+                    let now = Instant::now();
+                    let due = pending.iter().take_while(|m| m.send_at.is_none_or(|t| t <= now)).count();
+                    if due > 0 {
+                        if let Ok(mut ports) = ports.lock() {
+                            for scheduled in pending.iter().take(due) {
+                                ports.send(scheduled);
+                            }
+                        }
+                        pending.drain(..due);
+                    }
+                    drop(ports);
+
+                    if now >= next_hotplug {
+                        next_hotplug = now + HOTPLUG_INTERVAL;
+                        // The settings page may hold the manager; try again next interval
+                        if let Some(manager) = manager.upgrade() {
+                            if let Ok(mut mgr) = manager.try_lock() {
+                                mgr.poll_hotplug();
+                            }
+                        }
+                    }
+
+                    let deadline = match pending.front() {
+                        Some(front) => front.send_at.map_or(now, |t| t.min(next_hotplug)),
+                        None => next_hotplug,
+                    };
+                    match queue.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(scheduled) => {
+                            pending.push_back(scheduled);
+                            pending.extend(queue.try_iter());
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+            });
     }
 
     pub fn save_config(&self) {
@@ -391,23 +893,126 @@ impl MidiDeviceManager {
             return self.config.clone();
         };
         if let Ok(data) = std::fs::read_to_string(&path) {
-            if let Ok(cfg) = serde_json::from_str::<SettingsConfig>(&data) {
+            if let Ok(mut cfg) = serde_json::from_str::<SettingsConfig>(&data) {
+                cfg.migrate_legacy_devices();
                 self.config = cfg;
             }
         }
         self.config.clone()
     }
 
+    /// Connects every saved port that is present but not yet connected, and
+    /// opens the enabled virtual ports.
     pub fn reconnect_saved_devices(&mut self) {
-        if let Some(name) = self.config.input_device_name.clone() {
-            self.connect_input(&name);
+        if self.config.virtual_input && self.virtual_input.is_none() {
+            self.open_virtual_input();
         }
-        if let Some(name) = self.config.output_device_name.clone() {
-            self.connect_output(&name);
+        if self.config.virtual_output && !self.virtual_output_active() {
+            self.open_virtual_output();
+        }
+
+        // Ports missing from the last listing are skipped, so hot-plug changes
+        // don't open a client per absent device
+        let inputs: Vec<String> = self.config.inputs.iter()
+            .filter(|p| self.input_devices.iter().any(|d| d.name == p.name))
+            .map(|p| p.name.clone())
+            .collect();
+        for name in inputs {
+            if !self.is_input_connected(&name) {
+                self.open_input(&name);
+            }
+        }
+        let outputs: Vec<String> = self.config.outputs.iter()
+            .filter(|p| self.output_devices.iter().any(|d| d.name == p.name))
+            .map(|p| p.name.clone())
+            .collect();
+        for name in outputs {
+            if !self.is_output_connected(&name) {
+                self.open_output(&name);
+            }
         }
     }
 }
 
+/// midir callback that filters by role and channel and pushes to `queue`.
+fn input_callback(
+    queue: MidiInputQueue,
+    channel_filter: MidiChannel,
+    roles: MidiRoles,
+) -> impl FnMut(u64, &[u8], &mut ()) + Send + 'static {
+    move |_timestamp, data, _| {
+        if data.is_empty() {
+            return;
+        }
+
+        let status = data[0];
+        if !roles.accepts(status) {
+            return;
+        }
+
+        if status < 0xF0 {
+            let msg_channel = status & 0x0F;
+            if !channel_filter.matches(msg_channel) {
+                return;
+            }
+        }
+
+        let mut msg = RawMidiMessage {
+            data: [0; 3],
+            len: data.len().min(3) as u8,
+        };
+        for (i, &byte) in data.iter().take(3).enumerate() {
+            msg.data[i] = byte;
+        }
+
+        if let Ok(mut q) = queue.try_lock() {
+            if q.len() < 512 {
+                q.push_back(msg);
+            }
+        }
+    }
+}
+
+/// One client per direction, kept for listing ports. Hot-plug polling lists
+/// every second, and on ALSA each new client registers (and removes) a
+/// sequencer client.
+#[derive(Default)]
+struct PortProbe {
+    input: Option<MidiInput>,
+    output: Option<MidiOutput>,
+}
+
+impl PortProbe {
+    fn list(&mut self) -> (Vec<MidiDeviceInfo>, Vec<MidiDeviceInfo>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+
+        if self.input.is_none() {
+            self.input = MidiInput::new("PhaseBurn probe").ok();
+        }
+        if let Some(midi_in) = &self.input {
+            for port in midi_in.ports() {
+                if let Ok(name) = midi_in.port_name(&port) {
+                    inputs.push(MidiDeviceInfo { name });
+                }
+            }
+        }
+
+        if self.output.is_none() {
+            self.output = MidiOutput::new("PhaseBurn probe").ok();
+        }
+        if let Some(midi_out) = &self.output {
+            for port in midi_out.ports() {
+                if let Ok(name) = midi_out.port_name(&port) {
+                    outputs.push(MidiDeviceInfo { name });
+                }
+            }
+        }
+
+        (inputs, outputs)
+    }
+}
+
 fn settings_file_path() -> Option<std::path::PathBuf> {
     dirs::data_local_dir().map(|mut path| {
         path.push("Device");
@@ -446,6 +1051,75 @@ pub fn raw_midi_to_note_event(msg: &RawMidiMessage) -> Option<NoteEvent<()>> {
             cc: note,
             value: velocity as f32 / 127.0,
         }),
+        0xC0 => Some(NoteEvent::MidiProgramChange {
+            timing: 0,
+            channel,
+            program: note,
+        }),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_single_device_settings_migrate_to_ports() {
+        let mut cfg = SettingsConfig {
+            input_device_name: Some("Keys".to_string()),
+            input_channel: MidiChannel::Channel(2),
+            output_device_name: Some("Synth".to_string()),
+            output_channel: 5,
+            ..Default::default()
+        };
+        cfg.migrate_legacy_devices();
+
+        assert_eq!(cfg.inputs.len(), 1);
+        assert_eq!(cfg.inputs[0].name, "Keys");
+        assert_eq!(cfg.inputs[0].channel, MidiChannel::Channel(2));
+        assert_eq!(cfg.inputs[0].roles, MidiRoles::default());
+        assert_eq!(cfg.outputs.len(), 1);
+        assert_eq!(cfg.outputs[0].name, "Synth");
+        assert_eq!(cfg.outputs[0].channel, 5);
+        assert_eq!(cfg.outputs[0].clock_ppqn, 24);
+        assert!(cfg.input_device_name.is_none() && cfg.output_device_name.is_none());
+    }
+
+    #[test]
+    fn legacy_device_does_not_override_saved_ports() {
+        let mut cfg = SettingsConfig {
+            inputs: vec![MidiInputPort { name: "Pads".to_string(), channel: MidiChannel::All, roles: MidiRoles::default() }],
+            input_device_name: Some("Keys".to_string()),
+            ..Default::default()
+        };
+        cfg.migrate_legacy_devices();
+
+        assert_eq!(cfg.inputs.len(), 1);
+        assert_eq!(cfg.inputs[0].name, "Pads");
+        assert!(cfg.input_device_name.is_none());
+    }
+
+    #[test]
+    fn roles_filter_by_message_kind() {
+        let notes = MidiRoles { notes: true, cc: false, clock: false };
+        assert!(notes.accepts(0x93));
+        assert!(notes.accepts(0x80));
+        assert!(notes.accepts(0xE0));
+        assert!(!notes.accepts(0xB0));
+        assert!(!notes.accepts(0xC1));
+        assert!(!notes.accepts(0xF8));
+
+        let cc = MidiRoles { notes: false, cc: true, clock: false };
+        assert!(cc.accepts(0xB5));
+        assert!(cc.accepts(0xC0));
+        assert!(!cc.accepts(0x90));
+
+        let clock = MidiRoles { notes: false, cc: false, clock: true };
+        for status in [0xF2, 0xF8, 0xFA, 0xFB, 0xFC] {
+            assert!(clock.accepts(status));
+        }
+        assert!(!clock.accepts(0x90));
+        assert!(!MidiRoles::default().accepts(0xF0));
+    }
+}
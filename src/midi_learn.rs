@@ -27,6 +27,11 @@ pub const SOUND_PARAMS: &[&str] = &[
     "synth_pll_enable", "synth_pll_colored", "synth_pll_mode",
     "synth_pll_precision", "synth_pll_injection_x4", "synth_pll_fm_expand",
     "synth_saw_enable", "synth_vps_enable",
+    // Macros
+    "macro_1", "macro_2", "macro_3", "macro_4",
+    "macro_5", "macro_6", "macro_7", "macro_8",
+    // Morph
+    "morph_position",
 ];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
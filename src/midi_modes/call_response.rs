@@ -0,0 +1,324 @@
+use crate::params::DeviceParams;
+use crate::sequencer::NotePool;
+use crate::sequencer::{PhraseBar, MAX_PHRASE_BARS, MAX_PHRASE_NOTES};
+use crate::sequencer::melodic_engine::MelodicNote;
+use crate::sequencer::ml_suggest::{normalize_beat_constraints, DIVISIONS};
+
+const PICKUP_WINDOW: f64 = 1.0 / 16.0;
+const MIN_NOTE_BARS: f32 = 1.0 / 128.0;
+
+#[derive(Clone, Copy)]
+struct CapturedNote {
+    note: u8,
+    velocity: u8,
+    onset: f64,
+    duration: f64,
+}
+
+/// The transcription of the last phrase. Its buffers are allocated once and
+/// refilled in place, since phrases close on the audio thread.
+pub struct PhraseAnswer {
+    pub note_pool: NotePool,
+    bars: Vec<PhraseBar>,
+    len: usize,
+}
+
+impl PhraseAnswer {
+    fn new() -> Self {
+        let mut note_pool = NotePool::new();
+        note_pool.notes.reserve(128);
+        Self {
+            note_pool,
+            bars: (0..MAX_PHRASE_BARS).map(|_| PhraseBar::with_capacity()).collect(),
+            len: 0,
+        }
+    }
+
+    pub fn bars(&self) -> &[PhraseBar] {
+        &self.bars[..self.len]
+    }
+}
+
+/// Records a phrase of `phrase_bars` bars starting at the first played note,
+/// then transcribes it into per-bar beat probabilities and a melody.
+pub struct PhraseListener {
+    phrase_bars: u32,
+    notes: Vec<CapturedNote>,
+    held: [Option<usize>; 128],
+    phrase_start_bar: Option<u64>,
+    answer_notes: usize,
+    answer: PhraseAnswer,
+}
+
+impl PhraseListener {
+    pub fn new() -> Self {
+        Self {
+            phrase_bars: 1,
+            notes: Vec::with_capacity(MAX_PHRASE_NOTES),
+            held: [None; 128],
+            phrase_start_bar: None,
+            answer_notes: 0,
+            answer: PhraseAnswer::new(),
+        }
+    }
+
+    pub fn set_phrase_bars(&mut self, bars: u32) {
+        self.phrase_bars = bars.clamp(1, MAX_PHRASE_BARS as u32);
+    }
+
+    pub fn note_on(&mut self, note: u8, velocity: u8, bar_index: u64, bar_position: f64) {
+        let start = match self.phrase_start_bar {
+            Some(start) => start,
+            None => {
+                // A note just before the downbeat is a pickup into the next bar.
+                let start = if bar_position > 1.0 - PICKUP_WINDOW {
+                    bar_index + 1
+                } else {
+                    bar_index
+                };
+                self.phrase_start_bar = Some(start);
+                self.notes.clear();
+                self.held = [None; 128];
+                start
+            }
+        };
+
+        if self.notes.len() >= MAX_PHRASE_NOTES {
+            return;
+        }
+
+        let onset = (bar_index as f64 - start as f64 + bar_position).max(0.0);
+        if onset >= self.phrase_bars as f64 {
+            return;
+        }
+
+        self.held[note as usize] = Some(self.notes.len());
+        self.notes.push(CapturedNote {
+            note,
+            velocity,
+            onset,
+            duration: 0.0,
+        });
+    }
+
+    pub fn note_off(&mut self, note: u8, bar_index: u64, bar_position: f64) {
+        let Some(start) = self.phrase_start_bar else {
+            return;
+        };
+        if let Some(idx) = self.held[note as usize].take() {
+            let end = (bar_index as f64 - start as f64 + bar_position).min(self.phrase_bars as f64);
+            let captured = &mut self.notes[idx];
+            captured.duration = (end - captured.onset).max(0.0);
+        }
+    }
+
+    /// Closes the phrase once its window has elapsed and returns the answer.
+    pub fn check_phrase_end(&mut self, bar_index: u64) -> Option<&PhraseAnswer> {
+        let start = self.phrase_start_bar?;
+        if bar_index < start + self.phrase_bars as u64 {
+            return None;
+        }
+
+        let phrase_len = self.phrase_bars as f64;
+        for idx in self.held.iter_mut().filter_map(|h| h.take()) {
+            let captured = &mut self.notes[idx];
+            captured.duration = phrase_len - captured.onset;
+        }
+
+        self.phrase_start_bar = None;
+        if self.notes.is_empty() {
+            return None;
+        }
+
+        self.build_answer();
+        self.answer_notes = self.notes.len();
+        self.notes.clear();
+        Some(&self.answer)
+    }
+
+    fn build_answer(&mut self) {
+        let Self { notes, answer, phrase_bars, .. } = self;
+        let root = notes[0].note;
+        answer.len = *phrase_bars as usize;
+        let bars = &mut answer.bars[..answer.len];
+        for bar in bars.iter_mut() {
+            bar.clear();
+        }
+
+        let mut counts = [0u32; 128];
+        let mut strong_sum = [0.0f32; 128];
+        let mut duration_sum = [0.0f32; 128];
+        let mut total_duration = 0.0f32;
+
+        for captured in notes.iter() {
+            let bar = (captured.onset.floor() as usize).min(bars.len() - 1);
+            let position = (captured.onset - bar as f64) as f32;
+            let duration = (captured.duration as f32).max(MIN_NOTE_BARS);
+
+            let slot = nearest_slot(position, duration);
+            bars[bar].beats[slot] = 127.0;
+
+            let relative = (captured.note as i16 - root as i16).clamp(-36, 36);
+            bars[bar].melody.push(MelodicNote {
+                relative_pitch: relative as i8,
+                start_time: position,
+                duration: duration.min(1.0),
+                velocity: captured.velocity,
+            });
+
+            let n = captured.note as usize;
+            counts[n] += 1;
+            strong_sum[n] += position_strength(position);
+            duration_sum[n] += duration;
+            total_duration += duration;
+        }
+
+        for bar in bars.iter_mut() {
+            normalize_beat_constraints(&mut bar.beats);
+            // Unstable sort: the stable one allocates a merge buffer
+            bar.melody.sort_unstable_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal));
+        }
+
+        let note_pool = &mut answer.note_pool;
+        note_pool.notes.clear();
+        note_pool.set_root_note(root);
+
+        let max_count = counts.iter().copied().max().unwrap_or(1).max(1) as f32;
+        let mean_duration = total_duration / notes.len() as f32;
+
+        for note in 0..128u8 {
+            let count = counts[note as usize];
+            if count == 0 || note == root {
+                continue;
+            }
+            let chance = 0.25 + 0.75 * count as f32 / max_count;
+            let strength_bias = strong_sum[note as usize] / count as f32;
+            let avg_duration = duration_sum[note as usize] / count as f32;
+            let length_bias = (avg_duration / mean_duration.max(MIN_NOTE_BARS)).log2().clamp(-1.0, 1.0);
+            note_pool.set_note_full(note, 0, chance, strength_bias, length_bias);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.notes.clear();
+        self.held = [None; 128];
+        self.phrase_start_bar = None;
+        self.answer_notes = 0;
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.phrase_start_bar.is_some()
+    }
+
+    pub fn notes_captured(&self) -> usize {
+        self.notes.len()
+    }
+
+    pub fn answer_notes(&self) -> usize {
+        self.answer_notes
+    }
+
+    pub fn phrase_bars(&self) -> u32 {
+        self.phrase_bars
+    }
+
+    pub fn bars_elapsed(&self, bar_index: u64) -> u32 {
+        match self.phrase_start_bar {
+            Some(start) if bar_index >= start => (bar_index - start) as u32 + 1,
+            _ => 0,
+        }
+    }
+}
+
+/// +1 on quarter beats, 0 on eighths, -1 elsewhere.
+fn position_strength(position: f32) -> f32 {
+    let near = |grid: f32| {
+        let scaled = position / grid;
+        (scaled - scaled.round()).abs() * grid < 1.0 / 64.0
+    };
+    if near(0.25) {
+        1.0
+    } else if near(0.125) {
+        0.0
+    } else {
+        -1.0
+    }
+}
+
+/// Picks the beat slot whose start is closest to `position` and whose length
+/// best matches `duration` (both in bars).
+fn nearest_slot(position: f32, duration: f32) -> usize {
+    let mut best = 0;
+    let mut best_cost = f32::MAX;
+    let mut flat = 0;
+    for &(mode, count) in &DIVISIONS {
+        for index in 0..count {
+            let (start, end) = DeviceParams::get_beat_time_span(mode, count, index);
+            let cost = (start - position).abs() * 16.0 + ((end - start) / duration).log2().abs();
+            if cost < best_cost {
+                best_cost = cost;
+                best = flat;
+            }
+            flat += 1;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::BeatMode;
+    use crate::sequencer::ml_suggest::flat_index;
+
+    #[test]
+    fn quarter_note_phrase_maps_to_quarter_slots() {
+        let mut listener = PhraseListener::new();
+        let pitches = [48u8, 51, 55, 58];
+        for (i, &note) in pitches.iter().enumerate() {
+            let pos = i as f64 * 0.25;
+            listener.note_on(note, 100, 4, pos);
+            listener.note_off(note, 4, pos + 0.25);
+        }
+        assert!(listener.check_phrase_end(4).is_none());
+
+        let answer = listener.check_phrase_end(5).expect("phrase should close");
+        assert_eq!(answer.bars().len(), 1);
+        for i in 0..4 {
+            assert!(answer.bars()[0].beats[flat_index(BeatMode::Straight, 4, i)] > 100.0);
+        }
+        assert_eq!(answer.note_pool.root_note, Some(48));
+        assert_eq!(answer.bars()[0].melody.len(), 4);
+        assert_eq!(answer.bars()[0].melody[3].relative_pitch, 10);
+        assert!(!listener.is_listening());
+    }
+
+    #[test]
+    fn answers_reuse_their_buffers() {
+        let mut listener = PhraseListener::new();
+        listener.set_phrase_bars(2);
+        let mut melody_buffer = None;
+        for phrase in 0..3u64 {
+            let bar = phrase * 2;
+            for i in 0..8 {
+                listener.note_on(60 + i as u8, 100, bar, i as f64 / 8.0);
+                listener.note_off(60 + i as u8, bar, (i + 1) as f64 / 8.0);
+            }
+            let answer = listener.check_phrase_end(bar + 2).expect("phrase should close");
+            assert_eq!(answer.bars().len(), 2);
+            assert_eq!(answer.bars()[0].melody.len(), 8);
+            assert!(answer.bars()[1].melody.is_empty());
+            let buffer = answer.bars()[0].melody.as_ptr();
+            assert_eq!(*melody_buffer.get_or_insert(buffer), buffer);
+        }
+    }
+
+    #[test]
+    fn pickup_note_starts_next_bar() {
+        let mut listener = PhraseListener::new();
+        listener.note_on(60, 100, 2, 0.97);
+        assert_eq!(listener.bars_elapsed(3), 1);
+        assert!(listener.check_phrase_end(3).is_none());
+        assert!(listener.check_phrase_end(4).is_some());
+    }
+}
@@ -25,7 +25,7 @@ impl ChordFollowState {
         }
     }
 
-    pub fn is_dirty(&mut self) -> bool {
+    pub fn take_dirty(&mut self) -> bool {
         let d = self.dirty;
         self.dirty = false;
         d
@@ -1,6 +1,8 @@
 pub mod chord_follow;
 pub mod accompaniment;
 pub mod scale_detect;
+pub mod call_response;
+pub mod transpose;
 
 use serde::{Deserialize, Serialize};
 use crate::midi::ExternalNoteEvent;
@@ -8,6 +10,8 @@ use crate::sequencer::NotePool;
 use crate::sequencer::scales::Scale;
 use chord_follow::ChordFollowState;
 use accompaniment::HarmonicMemory;
+use call_response::{PhraseAnswer, PhraseListener};
+use transpose::TransposeKeys;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
 pub enum MidiInputMode {
@@ -15,14 +19,18 @@ pub enum MidiInputMode {
     Passthrough,
     ChordFollow,
     Accompaniment,
+    CallResponse,
+    Transpose,
 }
 
 impl MidiInputMode {
-    pub fn all() -> [MidiInputMode; 3] {
+    pub fn all() -> [MidiInputMode; 5] {
         [
             MidiInputMode::Passthrough,
             MidiInputMode::ChordFollow,
             MidiInputMode::Accompaniment,
+            MidiInputMode::CallResponse,
+            MidiInputMode::Transpose,
         ]
     }
 
@@ -31,6 +39,8 @@ impl MidiInputMode {
             MidiInputMode::Passthrough => "Passthrough",
             MidiInputMode::ChordFollow => "Chord Follow",
             MidiInputMode::Accompaniment => "Accompaniment",
+            MidiInputMode::CallResponse => "Call & Response",
+            MidiInputMode::Transpose => "Transpose",
         }
     }
 
@@ -39,6 +49,8 @@ impl MidiInputMode {
             0 => MidiInputMode::Passthrough,
             1 => MidiInputMode::ChordFollow,
             2 => MidiInputMode::Accompaniment,
+            3 => MidiInputMode::CallResponse,
+            4 => MidiInputMode::Transpose,
             _ => MidiInputMode::Passthrough,
         }
     }
@@ -48,13 +60,19 @@ impl MidiInputMode {
             MidiInputMode::Passthrough => 0,
             MidiInputMode::ChordFollow => 1,
             MidiInputMode::Accompaniment => 2,
+            MidiInputMode::CallResponse => 3,
+            MidiInputMode::Transpose => 4,
         }
     }
 }
 
-pub enum MidiModeResult {
+pub enum MidiModeResult<'a> {
     Passthrough,
     NotePoolUpdate(NotePool),
+    /// Borrowed from the listener, which reuses its buffers for the next phrase.
+    PhraseAnswer(&'a PhraseAnswer),
+    /// Last held key, or `None` once all keys are released.
+    TransposeKey(Option<u8>),
     NoChange,
 }
 
@@ -62,6 +80,9 @@ pub struct MidiModeProcessor {
     mode: MidiInputMode,
     chord_follow: ChordFollowState,
     accompaniment: HarmonicMemory,
+    phrase_listener: PhraseListener,
+    transpose_keys: TransposeKeys,
+    last_bar_index: u64,
 }
 
 impl MidiModeProcessor {
@@ -70,6 +91,9 @@ impl MidiModeProcessor {
             mode: MidiInputMode::Passthrough,
             chord_follow: ChordFollowState::new(),
             accompaniment: HarmonicMemory::new(),
+            phrase_listener: PhraseListener::new(),
+            transpose_keys: TransposeKeys::new(),
+            last_bar_index: 0,
         }
     }
 
@@ -77,15 +101,23 @@ impl MidiModeProcessor {
         if self.mode != mode {
             self.mode = mode;
             self.chord_follow.clear();
+            self.phrase_listener.clear();
+            self.transpose_keys.clear();
         }
     }
 
+    pub fn set_phrase_bars(&mut self, bars: u32) {
+        self.phrase_listener.set_phrase_bars(bars);
+    }
+
     pub fn process_events(
         &mut self,
         events: &[ExternalNoteEvent],
         bar_index: u64,
         bar_position: f32,
-    ) -> MidiModeResult {
+        bar_length_samples: f64,
+    ) -> MidiModeResult<'_> {
+        self.last_bar_index = bar_index;
         match self.mode {
             MidiInputMode::Passthrough => {
                 MidiModeResult::Passthrough
@@ -98,7 +130,7 @@ impl MidiModeProcessor {
                         self.chord_follow.note_off(event.note);
                     }
                 }
-                if self.chord_follow.is_dirty() {
+                if self.chord_follow.take_dirty() {
                     MidiModeResult::NotePoolUpdate(self.chord_follow.build_note_pool())
                 } else {
                     MidiModeResult::NoChange
@@ -121,6 +153,43 @@ impl MidiModeProcessor {
                     MidiModeResult::NoChange
                 }
             }
+            MidiInputMode::CallResponse => {
+                for event in events {
+                    let position = if bar_length_samples > 0.0 {
+                        bar_position as f64 + event.timing as f64 / bar_length_samples
+                    } else {
+                        bar_position as f64
+                    };
+                    let (bar, position) = if position >= 1.0 {
+                        (bar_index + 1, position - 1.0)
+                    } else {
+                        (bar_index, position)
+                    };
+                    if event.is_note_on {
+                        self.phrase_listener.note_on(event.note, event.velocity, bar, position);
+                    } else {
+                        self.phrase_listener.note_off(event.note, bar, position);
+                    }
+                }
+                match self.phrase_listener.check_phrase_end(bar_index) {
+                    Some(answer) => MidiModeResult::PhraseAnswer(answer),
+                    None => MidiModeResult::NoChange,
+                }
+            }
+            MidiInputMode::Transpose => {
+                for event in events {
+                    if event.is_note_on {
+                        self.transpose_keys.note_on(event.note);
+                    } else {
+                        self.transpose_keys.note_off(event.note);
+                    }
+                }
+                if self.transpose_keys.take_dirty() {
+                    MidiModeResult::TransposeKey(self.transpose_keys.held_key())
+                } else {
+                    MidiModeResult::NoChange
+                }
+            }
         }
     }
 
@@ -128,12 +197,23 @@ impl MidiModeProcessor {
         self.accompaniment.clear();
     }
 
+    pub fn clear_phrase(&mut self) {
+        self.phrase_listener.clear();
+    }
+
     pub fn get_display(&self) -> MidiModeDisplay {
         MidiModeDisplay {
             held_notes: self.chord_follow.get_held_notes(),
             detected_key: self.accompaniment.detected_key(),
             bars_analyzed: self.accompaniment.bars_analyzed(),
             confidence: self.accompaniment.key_confidence(),
+            phrase_listening: self.phrase_listener.is_listening(),
+            phrase_bar: self.phrase_listener.bars_elapsed(self.last_bar_index),
+            phrase_bars: self.phrase_listener.phrase_bars(),
+            phrase_notes: self.phrase_listener.notes_captured(),
+            answer_notes: self.phrase_listener.answer_notes(),
+            transpose_key: self.transpose_keys.last_key(),
+            transpose_held: self.transpose_keys.held_key().is_some(),
         }
     }
 }
@@ -144,6 +224,13 @@ pub struct MidiModeDisplay {
     pub detected_key: Option<(u8, Scale)>,
     pub bars_analyzed: u32,
     pub confidence: f32,
+    pub phrase_listening: bool,
+    pub phrase_bar: u32,
+    pub phrase_bars: u32,
+    pub phrase_notes: usize,
+    pub answer_notes: usize,
+    pub transpose_key: Option<u8>,
+    pub transpose_held: bool,
 }
 
 impl Default for MidiModeDisplay {
@@ -153,6 +240,13 @@ impl Default for MidiModeDisplay {
             detected_key: None,
             bars_analyzed: 0,
             confidence: 0.0,
+            phrase_listening: false,
+            phrase_bar: 0,
+            phrase_bars: 1,
+            phrase_notes: 0,
+            answer_notes: 0,
+            transpose_key: None,
+            transpose_held: false,
         }
     }
 }
@@ -0,0 +1,62 @@
+const MAX_HELD: usize = 16;
+
+/// Tracks held keys in press order; the most recent one is the transpose key.
+pub struct TransposeKeys {
+    held: Vec<u8>,
+    last_key: Option<u8>,
+    dirty: bool,
+}
+
+impl TransposeKeys {
+    pub fn new() -> Self {
+        Self {
+            held: Vec::with_capacity(MAX_HELD),
+            last_key: None,
+            dirty: false,
+        }
+    }
+
+    pub fn note_on(&mut self, note: u8) {
+        self.held.retain(|&n| n != note);
+        if self.held.len() >= MAX_HELD {
+            self.held.remove(0);
+        }
+        self.held.push(note);
+        self.last_key = Some(note);
+        self.dirty = true;
+    }
+
+    pub fn note_off(&mut self, note: u8) {
+        let before = self.held.len();
+        self.held.retain(|&n| n != note);
+        if self.held.len() != before {
+            // Falling back to a still-held key re-transposes to it.
+            if let Some(&prev) = self.held.last() {
+                self.last_key = Some(prev);
+            }
+            self.dirty = true;
+        }
+    }
+
+    pub fn take_dirty(&mut self) -> bool {
+        let d = self.dirty;
+        self.dirty = false;
+        d
+    }
+
+    /// The key currently held, or `None` once every key is released.
+    pub fn held_key(&self) -> Option<u8> {
+        self.held.last().copied()
+    }
+
+    /// The last key played, latched after release.
+    pub fn last_key(&self) -> Option<u8> {
+        self.last_key
+    }
+
+    pub fn clear(&mut self) {
+        self.held.clear();
+        self.last_key = None;
+        self.dirty = true;
+    }
+}
@@ -1,6 +1,11 @@
 use nih_plug::prelude::*;
 use nih_plug_egui::EguiState;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use crate::synth::fx_chain::{sanitize_order, FxModule, FX_SLOT_COUNT};
+use crate::synth::mseg::{MsegShape, MSEG_COUNT};
+use crate::macro_controls::{MacroTargets, MACRO_COUNT};
+use crate::preset::{MorphPair, PresetData, RandomizerSettings};
+use crate::scenes::{MORPH_TIME_LABELS, QUANTIZE_LABELS, SCENE_COUNT};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BeatMode {
@@ -70,6 +75,24 @@ pub fn random_up_to(amount: f32, rng: &mut impl rand::Rng) -> f32 {
 pub struct DeviceParams {
     #[persist = "editor-state"]
     pub editor_state: Arc<EguiState>,
+    /// Wavetable file stem, restored into the oscillator on initialize.
+    #[persist = "wavetable-name"]
+    pub wavetable_name: Arc<RwLock<String>>,
+    /// MSEG breakpoints; the editor writes here and flags the engine to re-read.
+    #[persist = "mseg-shapes"]
+    pub mseg_shapes: Arc<RwLock<[MsegShape; MSEG_COUNT]>>,
+    /// Macro target lists; the engine re-resolves them when flagged dirty.
+    #[persist = "macro-targets"]
+    pub macro_targets: Arc<RwLock<MacroTargets>>,
+    /// Presets at either end of the morph control.
+    #[persist = "morph-pair"]
+    pub morph_pair: Arc<RwLock<MorphPair>>,
+    /// Scene snapshots of the current preset, one per slot.
+    #[persist = "scenes"]
+    pub scenes: Arc<RwLock<Vec<Option<PresetData>>>>,
+    /// Randomizer amount and locks.
+    #[persist = "randomizer"]
+    pub randomizer: Arc<RwLock<RandomizerSettings>>,
 
     #[id = "div1_beat1"]
     pub div1_beat1: FloatParam,
@@ -444,6 +467,77 @@ pub struct DeviceParams {
     #[id = "synth_saw_shape_amount"]
     pub synth_saw_shape_amount: FloatParam,
 
+    #[id = "synth_wt_volume"]
+    pub synth_wt_volume: FloatParam,
+    #[id = "synth_wt_octave"]
+    pub synth_wt_octave: IntParam,
+    #[id = "synth_wt_tune"]
+    pub synth_wt_tune: IntParam,
+    #[id = "synth_wt_fine"]
+    pub synth_wt_fine: FloatParam,
+    #[id = "synth_wt_position"]
+    pub synth_wt_position: FloatParam,
+
+    #[id = "synth_noise_level"]
+    pub synth_noise_level: FloatParam,
+    #[id = "synth_noise_type"]
+    pub synth_noise_type: IntParam,
+    #[id = "synth_noise_rate"]
+    pub synth_noise_rate: FloatParam,
+    #[id = "synth_noise_lpf"]
+    pub synth_noise_lpf: FloatParam,
+    #[id = "synth_noise_hpf"]
+    pub synth_noise_hpf: FloatParam,
+    #[id = "synth_noise_env"]
+    pub synth_noise_env: IntParam,
+    #[id = "synth_noise_inject"]
+    pub synth_noise_inject: IntParam,
+    #[id = "synth_noise_inject_amount"]
+    pub synth_noise_inject_amount: FloatParam,
+
+    #[id = "synth_sync_source"]
+    pub synth_sync_source: IntParam,
+    #[id = "synth_vps_sync"]
+    pub synth_vps_sync: IntParam,
+    #[id = "synth_saw_sync"]
+    pub synth_saw_sync: IntParam,
+    #[id = "synth_pll_sync"]
+    pub synth_pll_sync: IntParam,
+    #[id = "synth_sync_soft"]
+    pub synth_sync_soft: FloatParam,
+
+    #[id = "synth_pll_topology"]
+    pub synth_pll_topology: IntParam,
+    #[id = "synth_pll2_track_speed"]
+    pub synth_pll2_track_speed: FloatParam,
+    #[id = "synth_pll2_damping"]
+    pub synth_pll2_damping: FloatParam,
+    #[id = "synth_pll2_mult"]
+    pub synth_pll2_mult: IntParam,
+    #[id = "synth_pll_coupling"]
+    pub synth_pll_coupling: FloatParam,
+    #[id = "synth_pll_ref_source"]
+    pub synth_pll_ref_source: IntParam,
+    #[id = "synth_pll_ref_mix"]
+    pub synth_pll_ref_mix: FloatParam,
+
+    #[id = "synth_ring_pll_enable"]
+    pub synth_ring_pll_enable: BoolParam,
+    #[id = "synth_ring_pll"]
+    pub synth_ring_pll: FloatParam,
+    #[id = "synth_ring_vps_saw_enable"]
+    pub synth_ring_vps_saw_enable: BoolParam,
+    #[id = "synth_ring_vps_saw"]
+    pub synth_ring_vps_saw: FloatParam,
+    #[id = "synth_xfm_vps_saw_enable"]
+    pub synth_xfm_vps_saw_enable: BoolParam,
+    #[id = "synth_xfm_vps_saw"]
+    pub synth_xfm_vps_saw: FloatParam,
+    #[id = "synth_xfm_saw_ref_enable"]
+    pub synth_xfm_saw_ref_enable: BoolParam,
+    #[id = "synth_xfm_saw_ref"]
+    pub synth_xfm_saw_ref: FloatParam,
+
     #[id = "synth_filter_cutoff"]
     pub synth_filter_cutoff: FloatParam,
     #[id = "synth_filter_resonance"]
@@ -512,6 +606,64 @@ pub struct DeviceParams {
     #[id = "synth_filter_poles"]
     pub synth_filter_poles: IntParam,
 
+    #[id = "synth_filter2_enable"]
+    pub synth_filter2_enable: BoolParam,
+    #[id = "synth_filter2_type"]
+    pub synth_filter2_type: IntParam,
+    #[id = "synth_filter2_cutoff"]
+    pub synth_filter2_cutoff: FloatParam,
+    #[id = "synth_filter2_resonance"]
+    pub synth_filter2_resonance: FloatParam,
+    #[id = "synth_filter2_morph"]
+    pub synth_filter2_morph: FloatParam,
+    #[id = "synth_filter2_key_track"]
+    pub synth_filter2_key_track: FloatParam,
+    #[id = "synth_filter2_env_amount"]
+    pub synth_filter2_env_amount: FloatParam,
+    #[id = "synth_filter_routing"]
+    pub synth_filter_routing: IntParam,
+
+    #[id = "synth_resonator_enable"]
+    pub synth_resonator_enable: BoolParam,
+    #[id = "synth_resonator_model"]
+    pub synth_resonator_model: IntParam,
+    #[id = "synth_resonator_excite"]
+    pub synth_resonator_excite: IntParam,
+    #[id = "synth_resonator_decay"]
+    pub synth_resonator_decay: FloatParam,
+    #[id = "synth_resonator_brightness"]
+    pub synth_resonator_brightness: FloatParam,
+    #[id = "synth_resonator_position"]
+    pub synth_resonator_position: FloatParam,
+    #[id = "synth_resonator_mix"]
+    pub synth_resonator_mix: FloatParam,
+
+    // ===== Lo-fi =====
+    #[id = "lofi_enable"]
+    pub lofi_enable: BoolParam,
+    #[id = "lofi_post_filter"]
+    pub lofi_post_filter: BoolParam,
+    #[id = "lofi_vps"]
+    pub lofi_vps: BoolParam,
+    #[id = "lofi_pll"]
+    pub lofi_pll: BoolParam,
+    #[id = "lofi_saw"]
+    pub lofi_saw: BoolParam,
+    #[id = "lofi_shaper"]
+    pub lofi_shaper: IntParam,
+    #[id = "lofi_bits"]
+    pub lofi_bits: FloatParam,
+    #[id = "lofi_rate"]
+    pub lofi_rate: FloatParam,
+    #[id = "lofi_anti_alias"]
+    pub lofi_anti_alias: BoolParam,
+    #[id = "lofi_jitter"]
+    pub lofi_jitter: FloatParam,
+    #[id = "lofi_drive"]
+    pub lofi_drive: FloatParam,
+    #[id = "lofi_mix"]
+    pub lofi_mix: FloatParam,
+
     #[id = "synth_pll_fm_amount"]
     pub synth_pll_fm_amount: FloatParam,
     #[id = "synth_pll_fm_ratio"]
@@ -678,6 +830,8 @@ pub struct DeviceParams {
     pub looper_input_pll: BoolParam,
     #[id = "looper_input_saw"]
     pub looper_input_saw: BoolParam,
+    #[id = "looper_input_wt"]
+    pub looper_input_wt: BoolParam,
     #[id = "looper_input_filter"]
     pub looper_input_filter: BoolParam,
     #[id = "looper_input_premaster"]
@@ -685,6 +839,8 @@ pub struct DeviceParams {
 
     #[id = "limiter_enable"]
     pub limiter_enable: BoolParam,
+    #[id = "stems_post_master"]
+    pub stems_post_master: BoolParam,
 
     #[id = "comp_enable"]
     pub comp_enable: BoolParam,
@@ -710,12 +866,6 @@ pub struct DeviceParams {
     pub comp_stereo_link: FloatParam,
     #[id = "comp_auto_makeup"]
     pub comp_auto_makeup: BoolParam,
-    #[id = "comp_route_master"]
-    pub comp_route_master: BoolParam,
-    #[id = "comp_route_looper"]
-    pub comp_route_looper: BoolParam,
-    #[id = "comp_route_reverb"]
-    pub comp_route_reverb: BoolParam,
 
     #[id = "synth_vol_attack"]
     pub synth_vol_attack: FloatParam,
@@ -847,6 +997,8 @@ pub struct DeviceParams {
     pub synth_reverb_send_pll: BoolParam,
     #[id = "synth_reverb_send_saw"]
     pub synth_reverb_send_saw: BoolParam,
+    #[id = "synth_reverb_send_wt"]
+    pub synth_reverb_send_wt: BoolParam,
     #[id = "synth_reverb_send_sub"]
     pub synth_reverb_send_sub: BoolParam,
     #[id = "synth_reverb_send_filter"]
@@ -854,6 +1006,101 @@ pub struct DeviceParams {
     #[id = "synth_reverb_send_looper"]
     pub synth_reverb_send_looper: BoolParam,
 
+    // ===== Delay =====
+    #[id = "synth_delay_enable"]
+    pub synth_delay_enable: BoolParam,
+    #[id = "synth_delay_mode"]
+    pub synth_delay_mode: IntParam,  // 0=Stereo, 1=Ping-Pong, 2=Tape
+    #[id = "synth_delay_sync"]
+    pub synth_delay_sync: BoolParam,
+    #[id = "synth_delay_division"]
+    pub synth_delay_division: IntParam,
+    #[id = "synth_delay_time"]
+    pub synth_delay_time: FloatParam,
+    #[id = "synth_delay_mix"]
+    pub synth_delay_mix: FloatParam,
+    #[id = "synth_delay_feedback"]
+    pub synth_delay_feedback: FloatParam,
+    #[id = "synth_delay_spread"]
+    pub synth_delay_spread: FloatParam,
+    #[id = "synth_delay_lpf"]
+    pub synth_delay_lpf: FloatParam,
+    #[id = "synth_delay_hpf"]
+    pub synth_delay_hpf: FloatParam,
+    #[id = "synth_delay_saturation"]
+    pub synth_delay_saturation: FloatParam,
+    #[id = "synth_delay_mod_rate"]
+    pub synth_delay_mod_rate: FloatParam,
+    #[id = "synth_delay_mod_depth"]
+    pub synth_delay_mod_depth: FloatParam,
+    #[id = "synth_delay_ducking"]
+    pub synth_delay_ducking: FloatParam,
+    #[id = "synth_delay_send_vps"]
+    pub synth_delay_send_vps: BoolParam,
+    #[id = "synth_delay_send_pll"]
+    pub synth_delay_send_pll: BoolParam,
+    #[id = "synth_delay_send_saw"]
+    pub synth_delay_send_saw: BoolParam,
+    #[id = "synth_delay_send_wt"]
+    pub synth_delay_send_wt: BoolParam,
+    #[id = "synth_delay_send_sub"]
+    pub synth_delay_send_sub: BoolParam,
+    #[id = "synth_delay_send_filter"]
+    pub synth_delay_send_filter: BoolParam,
+
+    // ===== Modulation FX (chorus / flanger / phaser) =====
+    #[id = "mod_fx_enable"]
+    pub mod_fx_enable: BoolParam,
+    #[id = "mod_fx_mode"]
+    pub mod_fx_mode: IntParam,  // 0=Chorus, 1=Flanger, 2=Phaser
+    #[id = "mod_fx_position"]
+    pub mod_fx_position: IntParam,  // 0=Pre-Looper, 1=Post-Looper, 2=Post-Reverb, 3=Post-Comp
+    #[id = "mod_fx_rate"]
+    pub mod_fx_rate: FloatParam,
+    #[id = "mod_fx_sync"]
+    pub mod_fx_sync: BoolParam,
+    #[id = "mod_fx_division"]
+    pub mod_fx_division: IntParam,
+    #[id = "mod_fx_depth"]
+    pub mod_fx_depth: FloatParam,
+    #[id = "mod_fx_feedback"]
+    pub mod_fx_feedback: FloatParam,
+    #[id = "mod_fx_mix"]
+    pub mod_fx_mix: FloatParam,
+    #[id = "mod_fx_voices"]
+    pub mod_fx_voices: IntParam,
+    #[id = "mod_fx_stages"]
+    pub mod_fx_stages: IntParam,
+    #[id = "mod_fx_spread"]
+    pub mod_fx_spread: FloatParam,
+
+    // ===== Master FX chain =====
+    // Each slot holds an FxModule index; the limiter always runs last
+    #[id = "fx_slot_1"]
+    pub fx_slot_1: IntParam,
+    #[id = "fx_slot_2"]
+    pub fx_slot_2: IntParam,
+    #[id = "fx_slot_3"]
+    pub fx_slot_3: IntParam,
+    #[id = "fx_slot_4"]
+    pub fx_slot_4: IntParam,
+    #[id = "fx_slot_5"]
+    pub fx_slot_5: IntParam,
+    #[id = "fx_slot_6"]
+    pub fx_slot_6: IntParam,
+    #[id = "fx_slot_7"]
+    pub fx_slot_7: IntParam,
+    #[id = "fx_slot_8"]
+    pub fx_slot_8: IntParam,
+    #[id = "fx_slot_9"]
+    pub fx_slot_9: IntParam,
+    #[id = "fx_send_looper"]
+    pub fx_send_looper: BoolParam,
+    #[id = "fx_send_delay"]
+    pub fx_send_delay: BoolParam,
+    #[id = "fx_send_reverb"]
+    pub fx_send_reverb: BoolParam,
+
     // ===== LFO 1 =====
     #[id = "lfo1_rate"]
     pub lfo1_rate: FloatParam,
@@ -1016,6 +1263,67 @@ pub struct DeviceParams {
     #[id = "mseq_amount4"]
     pub mseq_amount4: FloatParam,
 
+    #[id = "mseg1_tempo_sync"]
+    pub mseg1_tempo_sync: BoolParam,
+    #[id = "mseg1_retrigger"]
+    pub mseg1_retrigger: IntParam,
+    #[id = "mseg1_dest1"]
+    pub mseg1_dest1: IntParam,
+    #[id = "mseg1_amount1"]
+    pub mseg1_amount1: FloatParam,
+    #[id = "mseg1_dest2"]
+    pub mseg1_dest2: IntParam,
+    #[id = "mseg1_amount2"]
+    pub mseg1_amount2: FloatParam,
+
+    #[id = "mseg2_tempo_sync"]
+    pub mseg2_tempo_sync: BoolParam,
+    #[id = "mseg2_retrigger"]
+    pub mseg2_retrigger: IntParam,
+    #[id = "mseg2_dest1"]
+    pub mseg2_dest1: IntParam,
+    #[id = "mseg2_amount1"]
+    pub mseg2_amount1: FloatParam,
+    #[id = "mseg2_dest2"]
+    pub mseg2_dest2: IntParam,
+    #[id = "mseg2_amount2"]
+    pub mseg2_amount2: FloatParam,
+
+    #[id = "mod_pitch_quantize"]
+    pub mod_pitch_quantize: BoolParam,
+
+    // ===== Macros =====
+    #[id = "macro_1"]
+    pub macro_1: FloatParam,
+    #[id = "macro_2"]
+    pub macro_2: FloatParam,
+    #[id = "macro_3"]
+    pub macro_3: FloatParam,
+    #[id = "macro_4"]
+    pub macro_4: FloatParam,
+    #[id = "macro_5"]
+    pub macro_5: FloatParam,
+    #[id = "macro_6"]
+    pub macro_6: FloatParam,
+    #[id = "macro_7"]
+    pub macro_7: FloatParam,
+    #[id = "macro_8"]
+    pub macro_8: FloatParam,
+
+    /// Position between morph presets A (0) and B (1).
+    #[id = "morph_position"]
+    pub morph_position: FloatParam,
+
+    #[id = "scene_morph_time"]
+    pub scene_morph_time: IntParam,
+    #[id = "scene_quantize"]
+    pub scene_quantize: IntParam,
+    #[id = "scene_program_change"]
+    pub scene_program_change: BoolParam,
+    /// MIDI channel (1-16) whose notes recall scenes; 0 = off.
+    #[id = "scene_note_channel"]
+    pub scene_note_channel: IntParam,
+
     #[id = "note_length_percent"]
     pub note_length_percent: FloatParam,
 
@@ -1121,6 +1429,50 @@ impl DeviceParams {
         }
     }
 
+    /// Master FX slot params in chain order
+    pub fn fx_slots(&self) -> [&IntParam; FX_SLOT_COUNT] {
+        [
+            &self.fx_slot_1,
+            &self.fx_slot_2,
+            &self.fx_slot_3,
+            &self.fx_slot_4,
+            &self.fx_slot_5,
+            &self.fx_slot_6,
+            &self.fx_slot_7,
+            &self.fx_slot_8,
+            &self.fx_slot_9,
+        ]
+    }
+
+    /// Macro knobs in order
+    pub fn macros(&self) -> [&FloatParam; MACRO_COUNT] {
+        [
+            &self.macro_1,
+            &self.macro_2,
+            &self.macro_3,
+            &self.macro_4,
+            &self.macro_5,
+            &self.macro_6,
+            &self.macro_7,
+            &self.macro_8,
+        ]
+    }
+
+    /// Current master FX order, repaired if the slots hold duplicates
+    pub fn fx_order(&self) -> [FxModule; FX_SLOT_COUNT] {
+        sanitize_order(&self.fx_slots().map(|p| p.value()))
+    }
+
+    /// Insert/send switch for modules that can run as a send
+    pub fn fx_send_param(&self, module: FxModule) -> Option<&BoolParam> {
+        match module {
+            FxModule::Looper => Some(&self.fx_send_looper),
+            FxModule::Delay => Some(&self.fx_send_delay),
+            FxModule::Reverb => Some(&self.fx_send_reverb),
+            _ => None,
+        }
+    }
+
     /// Get all length modifiers as (target, amount, probability) tuples
     pub fn get_length_modifiers(&self) -> [(f32, f32, f32); 2] {
         [
@@ -1686,6 +2038,12 @@ impl Default for DeviceParams {
     fn default() -> Self {
         Self {
             editor_state: EguiState::from_size(1280, 720),
+            wavetable_name: Arc::new(RwLock::new(crate::synth::wavetable::BUILTIN_NAME.to_string())),
+            mseg_shapes: Arc::new(RwLock::new([MsegShape::default(); MSEG_COUNT])),
+            macro_targets: Arc::new(RwLock::new(MacroTargets::default())),
+            morph_pair: Arc::new(RwLock::new(MorphPair::default())),
+            scenes: Arc::new(RwLock::new(vec![None; SCENE_COUNT])),
+            randomizer: Arc::new(RwLock::new(RandomizerSettings::default())),
 
             div1_beat1: Self::create_param("1/1 Beat 1".to_string(), 0.0),
 
@@ -1974,6 +2332,166 @@ impl Default for DeviceParams {
                 FloatRange::Linear { min: 0.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(50.0)),
 
+            synth_wt_volume: FloatParam::new(
+                "WT Volume".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_wt_octave: IntParam::new(
+                "WT Octave".to_string(),
+                0,
+                IntRange::Linear { min: -3, max: 3 }
+            ),
+            synth_wt_tune: IntParam::new(
+                "WT Tune".to_string(),
+                0,
+                IntRange::Linear { min: -12, max: 12 }
+            ),
+            synth_wt_fine: FloatParam::new(
+                "WT Fine".to_string(),
+                0.0,
+                FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+            synth_wt_position: FloatParam::new(
+                "WT Position".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+
+            synth_noise_level: FloatParam::new(
+                "Noise Level".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_noise_type: IntParam::new(
+                "Noise Type",
+                0,
+                IntRange::Linear { min: 0, max: 5 }
+            ),
+            synth_noise_rate: FloatParam::new(
+                "Noise Rate".to_string(),
+                1000.0,
+                FloatRange::Skewed { min: 1.0, max: 20000.0, factor: FloatRange::skew_factor(-2.0) }
+            )
+            .with_smoother(SmoothingStyle::Linear(50.0))
+            .with_unit(" Hz"),
+            synth_noise_lpf: FloatParam::new(
+                "Noise LPF".to_string(),
+                20000.0,
+                FloatRange::Skewed { min: 20.0, max: 20000.0, factor: FloatRange::skew_factor(-2.0) }
+            )
+            .with_smoother(SmoothingStyle::Linear(50.0))
+            .with_unit(" Hz"),
+            synth_noise_hpf: FloatParam::new(
+                "Noise HPF".to_string(),
+                20.0,
+                FloatRange::Skewed { min: 20.0, max: 20000.0, factor: FloatRange::skew_factor(-2.0) }
+            )
+            .with_smoother(SmoothingStyle::Linear(50.0))
+            .with_unit(" Hz"),
+            synth_noise_env: IntParam::new(
+                "Noise Env",
+                0,
+                IntRange::Linear { min: 0, max: 1 }
+            ),
+            synth_noise_inject: IntParam::new(
+                "Noise Inject",
+                0,
+                IntRange::Linear { min: 0, max: 3 }
+            ),
+            synth_noise_inject_amount: FloatParam::new(
+                "Noise Inject Amount".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+
+            synth_sync_source: IntParam::new(
+                "Sync Source",
+                0,
+                IntRange::Linear { min: 0, max: 1 }
+            ),
+            synth_vps_sync: IntParam::new(
+                "VPS Sync",
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+            synth_saw_sync: IntParam::new(
+                "Saw Sync",
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+            synth_pll_sync: IntParam::new(
+                "PLL Sync",
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+            synth_sync_soft: FloatParam::new(
+                "Soft Sync".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+
+            synth_pll_topology: IntParam::new(
+                "PLL Topology",
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+            synth_pll2_track_speed: FloatParam::new(
+                "PLL2 Track Speed".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_pll2_damping: FloatParam::new(
+                "PLL2 Damping".to_string(),
+                0.3,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_pll2_mult: IntParam::new(
+                "PLL2 Multiplier".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: 6 }
+            ),
+            synth_pll_coupling: FloatParam::new(
+                "PLL Coupling".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_pll_ref_source: IntParam::new(
+                "PLL Ref Source",
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+            synth_pll_ref_mix: FloatParam::new(
+                "PLL Ref Mix".to_string(),
+                1.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+
+            synth_ring_pll_enable: BoolParam::new("Ring PLL Enable".to_string(), false),
+            synth_ring_pll: FloatParam::new(
+                "Ring PLL".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+            synth_ring_vps_saw_enable: BoolParam::new("Ring VPS Saw Enable".to_string(), false),
+            synth_ring_vps_saw: FloatParam::new(
+                "Ring VPS Saw".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+            synth_xfm_vps_saw_enable: BoolParam::new("XFM VPS Saw Enable".to_string(), false),
+            synth_xfm_vps_saw: FloatParam::new(
+                "XFM VPS Saw".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+            synth_xfm_saw_ref_enable: BoolParam::new("XFM Saw Ref Enable".to_string(), false),
+            synth_xfm_saw_ref: FloatParam::new(
+                "XFM Saw Ref".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+
             synth_filter_cutoff: FloatParam::new(
                 "Filter Cutoff".to_string(),
                 20000.0,
@@ -2125,6 +2643,114 @@ impl Default for DeviceParams {
                 IntRange::Linear { min: 0, max: 1 }
             ),
 
+            synth_filter2_enable: BoolParam::new("Filter 2 Enable".to_string(), false),
+            synth_filter2_type: IntParam::new(
+                "Filter 2 Type".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: 4 }
+            ),
+            synth_filter2_cutoff: FloatParam::new(
+                "Filter 2 Cutoff".to_string(),
+                1000.0,
+                FloatRange::Skewed { min: 20.0, max: 20000.0, factor: FloatRange::skew_factor(-2.0) }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_filter2_resonance: FloatParam::new(
+                "Filter 2 Resonance".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_filter2_morph: FloatParam::new(
+                "Filter 2 Morph".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_filter2_key_track: FloatParam::new(
+                "Filter 2 Key Track".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_filter2_env_amount: FloatParam::new(
+                "Filter 2 Env Amount".to_string(),
+                0.0,
+                FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_filter_routing: IntParam::new(
+                "Filter Routing".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+
+            synth_resonator_enable: BoolParam::new("Resonator Enable".to_string(), false),
+            synth_resonator_model: IntParam::new(
+                "Resonator Model".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: 3 }
+            ),
+            synth_resonator_excite: IntParam::new(
+                "Resonator Excite".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: 2 }
+            ),
+            synth_resonator_decay: FloatParam::new(
+                "Resonator Decay".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_resonator_brightness: FloatParam::new(
+                "Resonator Brightness".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_resonator_position: FloatParam::new(
+                "Resonator Position".to_string(),
+                0.3,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_resonator_mix: FloatParam::new(
+                "Resonator Mix".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+
+            lofi_enable: BoolParam::new("LoFi Enable".to_string(), false),
+            lofi_post_filter: BoolParam::new("LoFi Post Filter".to_string(), false),
+            lofi_vps: BoolParam::new("LoFi VPS".to_string(), true),
+            lofi_pll: BoolParam::new("LoFi PLL".to_string(), true),
+            lofi_saw: BoolParam::new("LoFi Saw".to_string(), true),
+            lofi_shaper: IntParam::new(
+                "LoFi Shaper",
+                0,
+                IntRange::Linear { min: 0, max: 7 }
+            ),
+            lofi_bits: FloatParam::new(
+                "LoFi Bits".to_string(),
+                16.0,
+                FloatRange::Linear { min: 1.0, max: 16.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            lofi_rate: FloatParam::new(
+                "LoFi Rate".to_string(),
+                48000.0,
+                FloatRange::Skewed { min: 100.0, max: 48000.0, factor: FloatRange::skew_factor(-2.0) }
+            )
+            .with_smoother(SmoothingStyle::Linear(50.0))
+            .with_unit(" Hz"),
+            lofi_anti_alias: BoolParam::new("LoFi Anti-Alias".to_string(), true),
+            lofi_jitter: FloatParam::new(
+                "LoFi Jitter".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            lofi_drive: FloatParam::new(
+                "LoFi Drive".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            lofi_mix: FloatParam::new(
+                "LoFi Mix".to_string(),
+                1.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+
             synth_pll_fm_amount: FloatParam::new(
                 "PLL FM Amount".to_string(),
                 0.0,
@@ -2453,10 +3079,12 @@ impl Default for DeviceParams {
             looper_input_vps: BoolParam::new("Looper Input VPS".to_string(), false),
             looper_input_pll: BoolParam::new("Looper Input PLL".to_string(), false),
             looper_input_saw: BoolParam::new("Looper Input SAW".to_string(), false),
+            looper_input_wt: BoolParam::new("Looper Input WT".to_string(), false),
             looper_input_filter: BoolParam::new("Looper Input Filter".to_string(), true),
             looper_input_premaster: BoolParam::new("Looper Input Premaster".to_string(), false),
 
             limiter_enable: BoolParam::new("Limiter".to_string(), true),
+            stems_post_master: BoolParam::new("Stems Master Volume".to_string(), false),
 
             comp_enable: BoolParam::new("Compressor".to_string(), false),
             comp_threshold: FloatParam::new(
@@ -2510,9 +3138,6 @@ impl Default for DeviceParams {
                 FloatRange::Linear { min: 0.0, max: 1.0 },
             ).with_step_size(0.01),
             comp_auto_makeup: BoolParam::new("Comp Auto Makeup".to_string(), false),
-            comp_route_master: BoolParam::new("Comp Route Master".to_string(), true),
-            comp_route_looper: BoolParam::new("Comp Route Looper".to_string(), false),
-            comp_route_reverb: BoolParam::new("Comp Route Reverb".to_string(), false),
 
             synth_vol_attack: FloatParam::new(
                 "Vol Attack".to_string(),
@@ -2762,10 +3387,131 @@ impl Default for DeviceParams {
             synth_reverb_send_vps: BoolParam::new("Reverb Send VPS".to_string(), false),
             synth_reverb_send_pll: BoolParam::new("Reverb Send PLL".to_string(), false),
             synth_reverb_send_saw: BoolParam::new("Reverb Send SAW".to_string(), false),
+            synth_reverb_send_wt: BoolParam::new("Reverb Send WT".to_string(), false),
             synth_reverb_send_sub: BoolParam::new("Reverb Send SUB".to_string(), false),
             synth_reverb_send_filter: BoolParam::new("Reverb Send Filter".to_string(), false),
             synth_reverb_send_looper: BoolParam::new("Reverb Send Looper".to_string(), false),
 
+            // Delay
+            synth_delay_enable: BoolParam::new("Delay Enable".to_string(), false),
+            synth_delay_mode: IntParam::new("Delay Mode".to_string(), 0, IntRange::Linear { min: 0, max: 2 }),
+            synth_delay_sync: BoolParam::new("Delay Sync".to_string(), true),
+            synth_delay_division: IntParam::new(
+                "Delay Division",
+                8,
+                IntRange::Linear { min: 0, max: 17 }
+            ),
+            synth_delay_time: FloatParam::new(
+                "Delay Time".to_string(),
+                375.0,
+                FloatRange::Skewed { min: 1.0, max: 2000.0, factor: FloatRange::skew_factor(-1.5) }
+            ).with_smoother(SmoothingStyle::Linear(50.0))
+            .with_unit(" ms"),
+            synth_delay_mix: FloatParam::new(
+                "Delay Dry/Wet".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_feedback: FloatParam::new(
+                "Delay Feedback".to_string(),
+                0.4,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_spread: FloatParam::new(
+                "Delay Spread".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_lpf: FloatParam::new(
+                "Delay LPF".to_string(),
+                8000.0,
+                FloatRange::Skewed { min: 20.0, max: 22000.0, factor: 0.25 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_hpf: FloatParam::new(
+                "Delay HPF".to_string(),
+                80.0,
+                FloatRange::Skewed { min: 20.0, max: 22000.0, factor: 0.25 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_saturation: FloatParam::new(
+                "Delay Saturation".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_mod_rate: FloatParam::new(
+                "Delay Mod Rate".to_string(),
+                0.3,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_mod_depth: FloatParam::new(
+                "Delay Mod Depth".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_ducking: FloatParam::new(
+                "Delay Ducking".to_string(),
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            synth_delay_send_vps: BoolParam::new("Delay Send VPS".to_string(), false),
+            synth_delay_send_pll: BoolParam::new("Delay Send PLL".to_string(), false),
+            synth_delay_send_saw: BoolParam::new("Delay Send SAW".to_string(), false),
+            synth_delay_send_wt: BoolParam::new("Delay Send WT".to_string(), false),
+            synth_delay_send_sub: BoolParam::new("Delay Send SUB".to_string(), false),
+            synth_delay_send_filter: BoolParam::new("Delay Send Filter".to_string(), true),
+
+            // Modulation FX
+            mod_fx_enable: BoolParam::new("Mod FX Enable".to_string(), false),
+            mod_fx_mode: IntParam::new("Mod FX Mode".to_string(), 0, IntRange::Linear { min: 0, max: 2 }),
+            mod_fx_position: IntParam::new("Mod FX Position".to_string(), 1, IntRange::Linear { min: 0, max: 3 }),
+            mod_fx_rate: FloatParam::new(
+                "Mod FX Rate".to_string(),
+                0.5,
+                FloatRange::Skewed { min: 0.01, max: 10.0, factor: 0.3 }
+            ).with_smoother(SmoothingStyle::Linear(50.0))
+            .with_unit(" Hz"),
+            mod_fx_sync: BoolParam::new("Mod FX Sync".to_string(), false),
+            mod_fx_division: IntParam::new(
+                "Mod FX Division",
+                0,
+                IntRange::Linear { min: 0, max: 17 }
+            ),
+            mod_fx_depth: FloatParam::new(
+                "Mod FX Depth".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            mod_fx_feedback: FloatParam::new(
+                "Mod FX Feedback".to_string(),
+                0.0,
+                FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            mod_fx_mix: FloatParam::new(
+                "Mod FX Dry/Wet".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+            mod_fx_voices: IntParam::new("Mod FX Chorus Voices".to_string(), 3, IntRange::Linear { min: 1, max: 4 }),
+            mod_fx_stages: IntParam::new("Mod FX Phaser Stages".to_string(), 6, IntRange::Linear { min: 4, max: 12 }),
+            mod_fx_spread: FloatParam::new(
+                "Mod FX Stereo Spread".to_string(),
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(50.0)),
+
+            // Master FX chain (defaults follow DEFAULT_FX_ORDER)
+            fx_slot_1: IntParam::new("FX Slot 1".to_string(), 0, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_2: IntParam::new("FX Slot 2".to_string(), 1, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_3: IntParam::new("FX Slot 3".to_string(), 2, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_4: IntParam::new("FX Slot 4".to_string(), 7, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_5: IntParam::new("FX Slot 5".to_string(), 3, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_6: IntParam::new("FX Slot 6".to_string(), 4, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_7: IntParam::new("FX Slot 7".to_string(), 5, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_8: IntParam::new("FX Slot 8".to_string(), 6, IntRange::Linear { min: 0, max: 8 }),
+            fx_slot_9: IntParam::new("FX Slot 9".to_string(), 8, IntRange::Linear { min: 0, max: 8 }),
+            fx_send_looper: BoolParam::new("FX Send Looper".to_string(), false),
+            fx_send_delay: BoolParam::new("FX Send Delay".to_string(), false),
+            fx_send_reverb: BoolParam::new("FX Send Reverb".to_string(), false),
+
             // LFO 1
             lfo1_rate: FloatParam::new(
                 "LFO 1 Rate".to_string(),
@@ -2781,13 +3527,13 @@ impl Default for DeviceParams {
                 0.0,
                 FloatRange::Linear { min: 0.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            lfo1_dest1: IntParam::new("LFO 1 Dest 1".to_string(), 0, IntRange::Linear { min: 0, max: 59 }),
+            lfo1_dest1: IntParam::new("LFO 1 Dest 1".to_string(), 0, IntRange::Linear { min: 0, max: 81 }),
             lfo1_amount1: FloatParam::new(
                 "LFO 1 Amount 1".to_string(),
                 0.0,
                 FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            lfo1_dest2: IntParam::new("LFO 1 Dest 2".to_string(), 0, IntRange::Linear { min: 0, max: 59 }),
+            lfo1_dest2: IntParam::new("LFO 1 Dest 2".to_string(), 0, IntRange::Linear { min: 0, max: 81 }),
             lfo1_amount2: FloatParam::new(
                 "LFO 1 Amount 2".to_string(),
                 0.0,
@@ -2809,13 +3555,13 @@ impl Default for DeviceParams {
                 0.0,
                 FloatRange::Linear { min: 0.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            lfo2_dest1: IntParam::new("LFO 2 Dest 1".to_string(), 0, IntRange::Linear { min: 0, max: 59 }),
+            lfo2_dest1: IntParam::new("LFO 2 Dest 1".to_string(), 0, IntRange::Linear { min: 0, max: 81 }),
             lfo2_amount1: FloatParam::new(
                 "LFO 2 Amount 1".to_string(),
                 0.0,
                 FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            lfo2_dest2: IntParam::new("LFO 2 Dest 2".to_string(), 0, IntRange::Linear { min: 0, max: 59 }),
+            lfo2_dest2: IntParam::new("LFO 2 Dest 2".to_string(), 0, IntRange::Linear { min: 0, max: 81 }),
             lfo2_amount2: FloatParam::new(
                 "LFO 2 Amount 2".to_string(),
                 0.0,
@@ -2837,13 +3583,13 @@ impl Default for DeviceParams {
                 0.0,
                 FloatRange::Linear { min: 0.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            lfo3_dest1: IntParam::new("LFO 3 Dest 1".to_string(), 0, IntRange::Linear { min: 0, max: 59 }),
+            lfo3_dest1: IntParam::new("LFO 3 Dest 1".to_string(), 0, IntRange::Linear { min: 0, max: 81 }),
             lfo3_amount1: FloatParam::new(
                 "LFO 3 Amount 1".to_string(),
                 0.0,
                 FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            lfo3_dest2: IntParam::new("LFO 3 Dest 2".to_string(), 0, IntRange::Linear { min: 0, max: 59 }),
+            lfo3_dest2: IntParam::new("LFO 3 Dest 2".to_string(), 0, IntRange::Linear { min: 0, max: 81 }),
             lfo3_amount2: FloatParam::new(
                 "LFO 3 Amount 2".to_string(),
                 0.0,
@@ -2888,26 +3634,86 @@ impl Default for DeviceParams {
             mseq_slew: FloatParam::new(
                 "MSeq Slew", 5.0, FloatRange::Linear { min: 0.0, max: 200.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            mseq_dest1: IntParam::new("MSeq Dest 1", 0, IntRange::Linear { min: 0, max: 59 }),
+            mseq_dest1: IntParam::new("MSeq Dest 1", 0, IntRange::Linear { min: 0, max: 81 }),
             mseq_amount1: FloatParam::new(
                 "MSeq Amount 1", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            mseq_dest2: IntParam::new("MSeq Dest 2", 0, IntRange::Linear { min: 0, max: 59 }),
+            mseq_dest2: IntParam::new("MSeq Dest 2", 0, IntRange::Linear { min: 0, max: 81 }),
             mseq_amount2: FloatParam::new(
                 "MSeq Amount 2", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
             mseq_length: IntParam::new("MSeq Length", 16, IntRange::Linear { min: 1, max: 32 }),
             mseq_retrigger: BoolParam::new("MSeq Retrigger", false),
             mseq_bipolar: BoolParam::new("MSeq Bipolar", true),
-            mseq_dest3: IntParam::new("MSeq Dest 3", 0, IntRange::Linear { min: 0, max: 59 }),
+            mseq_dest3: IntParam::new("MSeq Dest 3", 0, IntRange::Linear { min: 0, max: 81 }),
             mseq_amount3: FloatParam::new(
                 "MSeq Amount 3", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
-            mseq_dest4: IntParam::new("MSeq Dest 4", 0, IntRange::Linear { min: 0, max: 59 }),
+            mseq_dest4: IntParam::new("MSeq Dest 4", 0, IntRange::Linear { min: 0, max: 81 }),
             mseq_amount4: FloatParam::new(
                 "MSeq Amount 4", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
             ).with_smoother(SmoothingStyle::Linear(20.0)),
 
+            mseg1_tempo_sync: BoolParam::new("MSEG 1 Tempo Sync", true),
+            mseg1_retrigger: IntParam::new("MSEG 1 Retrigger", 1, IntRange::Linear { min: 0, max: 2 }),
+            mseg1_dest1: IntParam::new("MSEG 1 Dest 1", 0, IntRange::Linear { min: 0, max: 81 }),
+            mseg1_amount1: FloatParam::new(
+                "MSEG 1 Amount 1", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+            mseg1_dest2: IntParam::new("MSEG 1 Dest 2", 0, IntRange::Linear { min: 0, max: 81 }),
+            mseg1_amount2: FloatParam::new(
+                "MSEG 1 Amount 2", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+
+            mseg2_tempo_sync: BoolParam::new("MSEG 2 Tempo Sync", true),
+            mseg2_retrigger: IntParam::new("MSEG 2 Retrigger", 1, IntRange::Linear { min: 0, max: 2 }),
+            mseg2_dest1: IntParam::new("MSEG 2 Dest 1", 0, IntRange::Linear { min: 0, max: 81 }),
+            mseg2_amount1: FloatParam::new(
+                "MSEG 2 Amount 1", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+            mseg2_dest2: IntParam::new("MSEG 2 Dest 2", 0, IntRange::Linear { min: 0, max: 81 }),
+            mseg2_amount2: FloatParam::new(
+                "MSEG 2 Amount 2", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 }
+            ).with_smoother(SmoothingStyle::Linear(20.0)),
+
+            mod_pitch_quantize: BoolParam::new("Pitch Mod Quantize", false),
+
+            macro_1: FloatParam::new("Macro 1", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_2: FloatParam::new("Macro 2", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_3: FloatParam::new("Macro 3", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_4: FloatParam::new("Macro 4", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_5: FloatParam::new("Macro 5", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_6: FloatParam::new("Macro 6", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_7: FloatParam::new("Macro 7", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+            macro_8: FloatParam::new("Macro 8", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0)),
+
+            morph_position: FloatParam::new("Morph", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 }),
+
+            scene_morph_time: IntParam::new(
+                "Scene Morph Time".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: MORPH_TIME_LABELS.len() as i32 - 1 },
+            ),
+            scene_quantize: IntParam::new(
+                "Scene Quantize".to_string(),
+                2,
+                IntRange::Linear { min: 0, max: QUANTIZE_LABELS.len() as i32 - 1 },
+            ),
+            scene_program_change: BoolParam::new("Scene Program Change", true),
+            scene_note_channel: IntParam::new(
+                "Scene Note Channel".to_string(),
+                0,
+                IntRange::Linear { min: 0, max: 16 },
+            ),
+
             note_length_percent: FloatParam::new(
                 "Note Length %".to_string(),
                 95.0,
@@ -3078,6 +3884,39 @@ impl DeviceParams {
             "synth_saw_tight" => set_float!(self.synth_saw_tight),
             "synth_saw_shape_type" => set_int!(self.synth_saw_shape_type),
             "synth_saw_shape_amount" => set_float!(self.synth_saw_shape_amount),
+            "synth_wt_volume" => set_float!(self.synth_wt_volume),
+            "synth_wt_octave" => set_int!(self.synth_wt_octave),
+            "synth_wt_tune" => set_int!(self.synth_wt_tune),
+            "synth_wt_fine" => set_float!(self.synth_wt_fine),
+            "synth_wt_position" => set_float!(self.synth_wt_position),
+            "synth_noise_level" => set_float!(self.synth_noise_level),
+            "synth_noise_type" => set_int!(self.synth_noise_type),
+            "synth_noise_rate" => set_float!(self.synth_noise_rate),
+            "synth_noise_lpf" => set_float!(self.synth_noise_lpf),
+            "synth_noise_hpf" => set_float!(self.synth_noise_hpf),
+            "synth_noise_env" => set_int!(self.synth_noise_env),
+            "synth_noise_inject" => set_int!(self.synth_noise_inject),
+            "synth_noise_inject_amount" => set_float!(self.synth_noise_inject_amount),
+            "synth_sync_source" => set_int!(self.synth_sync_source),
+            "synth_vps_sync" => set_int!(self.synth_vps_sync),
+            "synth_saw_sync" => set_int!(self.synth_saw_sync),
+            "synth_pll_sync" => set_int!(self.synth_pll_sync),
+            "synth_sync_soft" => set_float!(self.synth_sync_soft),
+            "synth_pll_topology" => set_int!(self.synth_pll_topology),
+            "synth_pll2_track_speed" => set_float!(self.synth_pll2_track_speed),
+            "synth_pll2_damping" => set_float!(self.synth_pll2_damping),
+            "synth_pll2_mult" => set_int!(self.synth_pll2_mult),
+            "synth_pll_coupling" => set_float!(self.synth_pll_coupling),
+            "synth_pll_ref_source" => set_int!(self.synth_pll_ref_source),
+            "synth_pll_ref_mix" => set_float!(self.synth_pll_ref_mix),
+            "synth_ring_pll_enable" => set_bool!(self.synth_ring_pll_enable),
+            "synth_ring_pll" => set_float!(self.synth_ring_pll),
+            "synth_ring_vps_saw_enable" => set_bool!(self.synth_ring_vps_saw_enable),
+            "synth_ring_vps_saw" => set_float!(self.synth_ring_vps_saw),
+            "synth_xfm_vps_saw_enable" => set_bool!(self.synth_xfm_vps_saw_enable),
+            "synth_xfm_vps_saw" => set_float!(self.synth_xfm_vps_saw),
+            "synth_xfm_saw_ref_enable" => set_bool!(self.synth_xfm_saw_ref_enable),
+            "synth_xfm_saw_ref" => set_float!(self.synth_xfm_saw_ref),
             "synth_filter_cutoff" => set_float!(self.synth_filter_cutoff),
             "synth_filter_resonance" => set_float!(self.synth_filter_resonance),
             "synth_filter_drive" => set_float!(self.synth_filter_drive),
@@ -3108,6 +3947,28 @@ impl DeviceParams {
             "synth_filter_res_tilt" => set_float!(self.synth_filter_res_tilt),
             "synth_filter_cutoff_slew" => set_float!(self.synth_filter_cutoff_slew),
             "synth_filter_poles" => set_int!(self.synth_filter_poles),
+            "synth_filter2_enable" => set_bool!(self.synth_filter2_enable),
+            "synth_filter2_type" => set_int!(self.synth_filter2_type),
+            "synth_filter2_cutoff" => set_float!(self.synth_filter2_cutoff),
+            "synth_filter2_resonance" => set_float!(self.synth_filter2_resonance),
+            "synth_filter2_morph" => set_float!(self.synth_filter2_morph),
+            "synth_filter2_key_track" => set_float!(self.synth_filter2_key_track),
+            "synth_filter2_env_amount" => set_float!(self.synth_filter2_env_amount),
+            "synth_filter_routing" => set_int!(self.synth_filter_routing),
+            "synth_resonator_enable" => set_bool!(self.synth_resonator_enable),
+            "synth_resonator_model" => set_int!(self.synth_resonator_model),
+            "synth_resonator_excite" => set_int!(self.synth_resonator_excite),
+            "synth_resonator_decay" => set_float!(self.synth_resonator_decay),
+            "synth_resonator_brightness" => set_float!(self.synth_resonator_brightness),
+            "synth_resonator_position" => set_float!(self.synth_resonator_position),
+            "synth_resonator_mix" => set_float!(self.synth_resonator_mix),
+            "lofi_enable" => set_bool!(self.lofi_enable),
+            "lofi_shaper" => set_int!(self.lofi_shaper),
+            "lofi_bits" => set_float!(self.lofi_bits),
+            "lofi_rate" => set_float!(self.lofi_rate),
+            "lofi_jitter" => set_float!(self.lofi_jitter),
+            "lofi_drive" => set_float!(self.lofi_drive),
+            "lofi_mix" => set_float!(self.lofi_mix),
             "synth_pll_fm_amount" => set_float!(self.synth_pll_fm_amount),
             "synth_pll_fm_ratio_float" => set_float!(self.synth_pll_fm_ratio_float),
             "synth_pll_track_speed" => set_float!(self.synth_pll_track_speed),
@@ -3180,6 +4041,15 @@ impl DeviceParams {
             "synth_pll_fm_expand" => set_bool!(self.synth_pll_fm_expand),
             "synth_saw_enable" => set_bool!(self.synth_saw_enable),
             "synth_vps_enable" => set_bool!(self.synth_vps_enable),
+            "macro_1" => set_float!(self.macro_1),
+            "macro_2" => set_float!(self.macro_2),
+            "macro_3" => set_float!(self.macro_3),
+            "macro_4" => set_float!(self.macro_4),
+            "macro_5" => set_float!(self.macro_5),
+            "macro_6" => set_float!(self.macro_6),
+            "macro_7" => set_float!(self.macro_7),
+            "macro_8" => set_float!(self.macro_8),
+            "morph_position" => set_float!(self.morph_position),
             _ => false,
         }
     }
@@ -3217,6 +4087,39 @@ impl DeviceParams {
             "synth_saw_tight" => read_float!(self.synth_saw_tight),
             "synth_saw_shape_type" => read_int!(self.synth_saw_shape_type),
             "synth_saw_shape_amount" => read_float!(self.synth_saw_shape_amount),
+            "synth_wt_volume" => read_float!(self.synth_wt_volume),
+            "synth_wt_octave" => read_int!(self.synth_wt_octave),
+            "synth_wt_tune" => read_int!(self.synth_wt_tune),
+            "synth_wt_fine" => read_float!(self.synth_wt_fine),
+            "synth_wt_position" => read_float!(self.synth_wt_position),
+            "synth_noise_level" => read_float!(self.synth_noise_level),
+            "synth_noise_type" => read_int!(self.synth_noise_type),
+            "synth_noise_rate" => read_float!(self.synth_noise_rate),
+            "synth_noise_lpf" => read_float!(self.synth_noise_lpf),
+            "synth_noise_hpf" => read_float!(self.synth_noise_hpf),
+            "synth_noise_env" => read_int!(self.synth_noise_env),
+            "synth_noise_inject" => read_int!(self.synth_noise_inject),
+            "synth_noise_inject_amount" => read_float!(self.synth_noise_inject_amount),
+            "synth_sync_source" => read_int!(self.synth_sync_source),
+            "synth_vps_sync" => read_int!(self.synth_vps_sync),
+            "synth_saw_sync" => read_int!(self.synth_saw_sync),
+            "synth_pll_sync" => read_int!(self.synth_pll_sync),
+            "synth_sync_soft" => read_float!(self.synth_sync_soft),
+            "synth_pll_topology" => read_int!(self.synth_pll_topology),
+            "synth_pll2_track_speed" => read_float!(self.synth_pll2_track_speed),
+            "synth_pll2_damping" => read_float!(self.synth_pll2_damping),
+            "synth_pll2_mult" => read_int!(self.synth_pll2_mult),
+            "synth_pll_coupling" => read_float!(self.synth_pll_coupling),
+            "synth_pll_ref_source" => read_int!(self.synth_pll_ref_source),
+            "synth_pll_ref_mix" => read_float!(self.synth_pll_ref_mix),
+            "synth_ring_pll_enable" => read_bool!(self.synth_ring_pll_enable),
+            "synth_ring_pll" => read_float!(self.synth_ring_pll),
+            "synth_ring_vps_saw_enable" => read_bool!(self.synth_ring_vps_saw_enable),
+            "synth_ring_vps_saw" => read_float!(self.synth_ring_vps_saw),
+            "synth_xfm_vps_saw_enable" => read_bool!(self.synth_xfm_vps_saw_enable),
+            "synth_xfm_vps_saw" => read_float!(self.synth_xfm_vps_saw),
+            "synth_xfm_saw_ref_enable" => read_bool!(self.synth_xfm_saw_ref_enable),
+            "synth_xfm_saw_ref" => read_float!(self.synth_xfm_saw_ref),
             "synth_filter_cutoff" => read_float!(self.synth_filter_cutoff),
             "synth_filter_resonance" => read_float!(self.synth_filter_resonance),
             "synth_filter_drive" => read_float!(self.synth_filter_drive),
@@ -3255,6 +4158,28 @@ impl DeviceParams {
             "synth_filter_res_tilt" => read_float!(self.synth_filter_res_tilt),
             "synth_filter_cutoff_slew" => read_float!(self.synth_filter_cutoff_slew),
             "synth_filter_poles" => read_int!(self.synth_filter_poles),
+            "synth_filter2_enable" => read_bool!(self.synth_filter2_enable),
+            "synth_filter2_type" => read_int!(self.synth_filter2_type),
+            "synth_filter2_cutoff" => read_float!(self.synth_filter2_cutoff),
+            "synth_filter2_resonance" => read_float!(self.synth_filter2_resonance),
+            "synth_filter2_morph" => read_float!(self.synth_filter2_morph),
+            "synth_filter2_key_track" => read_float!(self.synth_filter2_key_track),
+            "synth_filter2_env_amount" => read_float!(self.synth_filter2_env_amount),
+            "synth_filter_routing" => read_int!(self.synth_filter_routing),
+            "synth_resonator_enable" => read_bool!(self.synth_resonator_enable),
+            "synth_resonator_model" => read_int!(self.synth_resonator_model),
+            "synth_resonator_excite" => read_int!(self.synth_resonator_excite),
+            "synth_resonator_decay" => read_float!(self.synth_resonator_decay),
+            "synth_resonator_brightness" => read_float!(self.synth_resonator_brightness),
+            "synth_resonator_position" => read_float!(self.synth_resonator_position),
+            "synth_resonator_mix" => read_float!(self.synth_resonator_mix),
+            "lofi_enable" => read_bool!(self.lofi_enable),
+            "lofi_shaper" => read_int!(self.lofi_shaper),
+            "lofi_bits" => read_float!(self.lofi_bits),
+            "lofi_rate" => read_float!(self.lofi_rate),
+            "lofi_jitter" => read_float!(self.lofi_jitter),
+            "lofi_drive" => read_float!(self.lofi_drive),
+            "lofi_mix" => read_float!(self.lofi_mix),
             "synth_pll_fm_amount" => read_float!(self.synth_pll_fm_amount),
             "synth_pll_fm_ratio_float" => read_float!(self.synth_pll_fm_ratio_float),
             "synth_pll_track_speed" => read_float!(self.synth_pll_track_speed),
@@ -3283,6 +4208,15 @@ impl DeviceParams {
             "synth_vps_enable" => read_bool!(self.synth_vps_enable),
             "stereo_mono_bass" => read_float!(self.stereo_mono_bass),
             "stereo_width" => read_float!(self.stereo_width),
+            "macro_1" => read_float!(self.macro_1),
+            "macro_2" => read_float!(self.macro_2),
+            "macro_3" => read_float!(self.macro_3),
+            "macro_4" => read_float!(self.macro_4),
+            "macro_5" => read_float!(self.macro_5),
+            "macro_6" => read_float!(self.macro_6),
+            "macro_7" => read_float!(self.macro_7),
+            "macro_8" => read_float!(self.macro_8),
+            "morph_position" => read_float!(self.morph_position),
             _ => None,
         }
     }
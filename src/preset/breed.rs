@@ -0,0 +1,147 @@
+//! Preset breeding. A child takes each top-level `PresetData` field from a
+//! random parent, so sound and sequencer sections mix freely. Mutation then
+//! moves the sound parameters the randomizer may change by the same rules
+//! (`mutate_normalized`, with its safety ranges), and nudges the sequencer
+//! grids.
+
+use nih_plug::prelude::{ParamPtr, Params};
+use rand::Rng;
+use serde_json::{Map, Value};
+use super::data::PresetData;
+use super::randomize::{mutate_normalized, mutate_sequencer, RandomGroup};
+
+/// Sequencer grids mutate more gently than the randomizer at the same amount.
+const SEQUENCER_SCALE: f32 = 0.25;
+
+/// Breeds one child of `parents`. `params` provides the ranges the sound
+/// parameters are mutated in.
+pub fn breed<R: Rng>(parents: &[&PresetData], amount: f32, params: &impl Params, rng: &mut R) -> PresetData {
+    let Some(first) = parents.first() else { return PresetData::default() };
+    let values: Vec<Map<String, Value>> = parents.iter()
+        .filter_map(|p| match serde_json::to_value(p) {
+            Ok(Value::Object(mut fields)) => {
+                fields.remove("scenes");
+                Some(fields)
+            }
+            _ => None,
+        })
+        .collect();
+    if values.len() != parents.len() {
+        return (*first).clone();
+    }
+
+    let amount = amount.clamp(0.0, 1.0);
+    let mut child = Map::new();
+    for key in values[0].keys() {
+        let candidates: Vec<&Value> = values.iter().filter_map(|v| v.get(key)).collect();
+        child.insert(key.clone(), candidates[rng.gen_range(0..candidates.len())].clone());
+    }
+
+    // Only what the randomizer touches: no enables, and the safety ranges hold
+    let ptrs = params.param_map();
+    for id in RandomGroup::ALL.into_iter().flat_map(RandomGroup::params) {
+        let (Some(value), Some((_, ptr, _))) = (child.get_mut(id), ptrs.iter().find(|(p, _, _)| p == id)) else {
+            continue;
+        };
+        if rng.gen::<f32>() < amount {
+            mutate_value(id, value, *ptr, amount, rng.gen());
+        }
+    }
+
+    let mut data = serde_json::from_value::<PresetData>(Value::Object(child))
+        .unwrap_or_else(|_| (*first).clone());
+    data.scenes.clear();
+    mutate_sequencer(&mut data, amount * SEQUENCER_SCALE, rng);
+    data
+}
+
+/// Mutates one sound parameter in its normalized range, keeping the JSON
+/// type the preset stores it as.
+fn mutate_value(id: &str, value: &mut Value, ptr: ParamPtr, amount: f32, r: f32) {
+    // SAFETY: `ptr` comes from the params object borrowed by `breed` for the
+    // duration of the call.
+    let to_normalized = |plain: f32| unsafe { ptr.preview_normalized(plain) };
+    let to_plain = |normalized: f32| unsafe { ptr.preview_plain(normalized) };
+    match value {
+        Value::Bool(on) => {
+            *on = mutate_normalized(id, if *on { 1.0 } else { 0.0 }, amount, r) >= 0.5;
+        }
+        Value::Number(n) => {
+            let (Some(plain), is_float) = (n.as_f64(), n.is_f64()) else { return };
+            let plain = to_plain(mutate_normalized(id, to_normalized(plain as f32), amount, r));
+            if !is_float {
+                *value = Value::from(plain.round() as i64);
+            } else if let Some(n) = serde_json::Number::from_f64(plain as f64) {
+                *value = Value::Number(n);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nih_plug::prelude::{BoolParam, FloatParam, FloatRange, IntParam, IntRange};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[derive(Params)]
+    struct TestParams {
+        #[id = "synth_pll_damping"]
+        damping: FloatParam,
+        #[id = "synth_pll_mult"]
+        mult: IntParam,
+        #[id = "synth_pll_volume"]
+        volume: FloatParam,
+        #[id = "synth_pll_enable"]
+        enable: BoolParam,
+    }
+
+    fn test_params() -> TestParams {
+        TestParams {
+            damping: FloatParam::new("Damping", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 }),
+            mult: IntParam::new("Mult", 1, IntRange::Linear { min: 1, max: 16 }),
+            volume: FloatParam::new("Volume", 0.7, FloatRange::Linear { min: 0.0, max: 1.0 }),
+            enable: BoolParam::new("Enable", true),
+        }
+    }
+
+    #[test]
+    fn children_take_every_field_from_a_parent() {
+        let a = PresetData { synth_pll_damping: 0.1, synth_pll_mult: 2, ..Default::default() };
+        let b = PresetData { synth_pll_damping: 0.9, synth_pll_mult: 7, ..Default::default() };
+
+        let params = test_params();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut seen_a = false;
+        let mut seen_b = false;
+        for _ in 0..32 {
+            let child = breed(&[&a, &b], 0.0, &params, &mut rng);
+            assert!(child.synth_pll_damping == 0.1 || child.synth_pll_damping == 0.9);
+            assert!(child.synth_pll_mult == 2 || child.synth_pll_mult == 7);
+            seen_a |= child.synth_pll_mult == 2;
+            seen_b |= child.synth_pll_mult == 7;
+        }
+        assert!(seen_a && seen_b);
+    }
+
+    #[test]
+    fn mutation_follows_the_randomizer_rules() {
+        let a = PresetData { synth_pll_volume: 0.35, synth_pll_mult: 3, synth_pll_enable: false, ..Default::default() };
+        let b = PresetData { synth_pll_volume: 0.8, synth_pll_mult: 5, synth_pll_enable: false, ..Default::default() };
+
+        let params = test_params();
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut mutated = false;
+        for _ in 0..32 {
+            let child = breed(&[&a, &b], 1.0, &params, &mut rng);
+            assert!((0.3..=0.85).contains(&child.synth_pll_volume));
+            assert!((1..=16).contains(&child.synth_pll_mult));
+            assert!(!child.synth_pll_enable);
+            assert!(child.scenes.is_empty());
+            mutated |= ![3, 5].contains(&child.synth_pll_mult);
+        }
+        assert!(mutated);
+    }
+}
@@ -2,6 +2,10 @@ use serde::{Deserialize, Serialize};
 use crate::sequencer::scales::{Scale, StabilityPattern, OctaveDirection};
 use crate::sequencer::styles::{StylePattern, StyleMode};
 use crate::sequencer::multi_bar::BarOrderMode;
+use crate::synth::fx_chain::DEFAULT_FX_ORDER;
+use crate::synth::wavetable::BUILTIN_NAME;
+use crate::synth::mseg::{MsegShape, MSEG_COUNT};
+use crate::macro_controls::MacroTarget;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NotePresetData {
@@ -310,6 +314,79 @@ pub struct PresetData {
     #[serde(default)]
     pub synth_saw_shape_amount: f32,
 
+    #[serde(default = "default_wavetable_name")]
+    pub wavetable_name: String,
+    #[serde(default)]
+    pub synth_wt_volume: f32,
+    #[serde(default)]
+    pub synth_wt_octave: i32,
+    #[serde(default)]
+    pub synth_wt_tune: i32,
+    #[serde(default)]
+    pub synth_wt_fine: f32,
+    #[serde(default)]
+    pub synth_wt_position: f32,
+
+    #[serde(default)]
+    pub synth_noise_level: f32,
+    #[serde(default)]
+    pub synth_noise_type: i32,
+    #[serde(default = "default_noise_rate")]
+    pub synth_noise_rate: f32,
+    #[serde(default = "default_noise_lpf")]
+    pub synth_noise_lpf: f32,
+    #[serde(default = "default_noise_hpf")]
+    pub synth_noise_hpf: f32,
+    #[serde(default)]
+    pub synth_noise_env: i32,
+    #[serde(default)]
+    pub synth_noise_inject: i32,
+    #[serde(default)]
+    pub synth_noise_inject_amount: f32,
+
+    #[serde(default)]
+    pub synth_sync_source: i32,
+    #[serde(default)]
+    pub synth_vps_sync: i32,
+    #[serde(default)]
+    pub synth_saw_sync: i32,
+    #[serde(default)]
+    pub synth_pll_sync: i32,
+    #[serde(default = "default_sync_soft")]
+    pub synth_sync_soft: f32,
+
+    #[serde(default)]
+    pub synth_pll_topology: i32,
+    #[serde(default = "default_pll_track_speed")]
+    pub synth_pll2_track_speed: f32,
+    #[serde(default = "default_pll_damping")]
+    pub synth_pll2_damping: f32,
+    #[serde(default)]
+    pub synth_pll2_mult: i32,
+    #[serde(default = "default_pll_coupling")]
+    pub synth_pll_coupling: f32,
+    #[serde(default)]
+    pub synth_pll_ref_source: i32,
+    #[serde(default = "default_pll_ref_mix")]
+    pub synth_pll_ref_mix: f32,
+
+    #[serde(default)]
+    pub synth_ring_pll_enable: bool,
+    #[serde(default)]
+    pub synth_ring_pll: f32,
+    #[serde(default)]
+    pub synth_ring_vps_saw_enable: bool,
+    #[serde(default)]
+    pub synth_ring_vps_saw: f32,
+    #[serde(default)]
+    pub synth_xfm_vps_saw_enable: bool,
+    #[serde(default)]
+    pub synth_xfm_vps_saw: f32,
+    #[serde(default)]
+    pub synth_xfm_saw_ref_enable: bool,
+    #[serde(default)]
+    pub synth_xfm_saw_ref: f32,
+
     #[serde(default)]
     pub synth_filter_enable: bool,
     #[serde(default = "default_filter_cutoff")]
@@ -393,6 +470,38 @@ pub struct PresetData {
     #[serde(default)]
     pub synth_filter_poles: i32,
 
+    #[serde(default)]
+    pub synth_filter2_enable: bool,
+    #[serde(default)]
+    pub synth_filter2_type: i32,
+    #[serde(default = "default_filter2_cutoff")]
+    pub synth_filter2_cutoff: f32,
+    #[serde(default)]
+    pub synth_filter2_resonance: f32,
+    #[serde(default)]
+    pub synth_filter2_morph: f32,
+    #[serde(default)]
+    pub synth_filter2_key_track: f32,
+    #[serde(default)]
+    pub synth_filter2_env_amount: f32,
+    #[serde(default)]
+    pub synth_filter_routing: i32,
+
+    #[serde(default)]
+    pub synth_resonator_enable: bool,
+    #[serde(default)]
+    pub synth_resonator_model: i32,
+    #[serde(default)]
+    pub synth_resonator_excite: i32,
+    #[serde(default = "default_half")]
+    pub synth_resonator_decay: f32,
+    #[serde(default = "default_half")]
+    pub synth_resonator_brightness: f32,
+    #[serde(default = "default_resonator_position")]
+    pub synth_resonator_position: f32,
+    #[serde(default = "default_half")]
+    pub synth_resonator_mix: f32,
+
     pub synth_vol_attack: f32,
     #[serde(default)]
     pub synth_vol_attack_shape: f32,
@@ -476,6 +585,108 @@ pub struct PresetData {
     #[serde(default = "default_rhythm_duck_smooth")]
     pub synth_reverb_rhythm_duck_smooth: f32,
 
+    #[serde(default)]
+    pub synth_delay_enable: bool,
+    #[serde(default)]
+    pub synth_delay_mode: i32,
+    #[serde(default = "default_true")]
+    pub synth_delay_sync: bool,
+    #[serde(default = "default_delay_division")]
+    pub synth_delay_division: i32,
+    #[serde(default = "default_delay_time")]
+    pub synth_delay_time: f32,
+    #[serde(default)]
+    pub synth_delay_mix: f32,
+    #[serde(default = "default_delay_feedback")]
+    pub synth_delay_feedback: f32,
+    #[serde(default)]
+    pub synth_delay_spread: f32,
+    #[serde(default = "default_delay_lpf")]
+    pub synth_delay_lpf: f32,
+    #[serde(default = "default_delay_hpf")]
+    pub synth_delay_hpf: f32,
+    #[serde(default)]
+    pub synth_delay_saturation: f32,
+    #[serde(default = "default_delay_mod_rate")]
+    pub synth_delay_mod_rate: f32,
+    #[serde(default)]
+    pub synth_delay_mod_depth: f32,
+    #[serde(default)]
+    pub synth_delay_ducking: f32,
+    #[serde(default)]
+    pub synth_delay_send_vps: bool,
+    #[serde(default)]
+    pub synth_delay_send_pll: bool,
+    #[serde(default)]
+    pub synth_delay_send_saw: bool,
+    #[serde(default)]
+    pub synth_delay_send_wt: bool,
+    #[serde(default)]
+    pub synth_delay_send_sub: bool,
+    #[serde(default = "default_true")]
+    pub synth_delay_send_filter: bool,
+
+    #[serde(default)]
+    pub mod_fx_enable: bool,
+    #[serde(default)]
+    pub mod_fx_mode: i32,
+    #[serde(default = "default_mod_fx_position")]
+    pub mod_fx_position: i32,
+    #[serde(default = "default_mod_fx_rate")]
+    pub mod_fx_rate: f32,
+    #[serde(default)]
+    pub mod_fx_sync: bool,
+    #[serde(default)]
+    pub mod_fx_division: i32,
+    #[serde(default = "default_half")]
+    pub mod_fx_depth: f32,
+    #[serde(default)]
+    pub mod_fx_feedback: f32,
+    #[serde(default = "default_half")]
+    pub mod_fx_mix: f32,
+    #[serde(default = "default_mod_fx_voices")]
+    pub mod_fx_voices: i32,
+    #[serde(default = "default_mod_fx_stages")]
+    pub mod_fx_stages: i32,
+    #[serde(default = "default_half")]
+    pub mod_fx_spread: f32,
+
+    /// Empty in presets saved before the chain existed; those place Mod FX
+    /// from `mod_fx_position` in the default order.
+    #[serde(default)]
+    pub fx_chain: Vec<i32>,
+    #[serde(default)]
+    pub fx_send_looper: bool,
+    #[serde(default)]
+    pub fx_send_delay: bool,
+    #[serde(default)]
+    pub fx_send_reverb: bool,
+
+    #[serde(default)]
+    pub lofi_enable: bool,
+    #[serde(default)]
+    pub lofi_post_filter: bool,
+    #[serde(default = "default_true")]
+    pub lofi_vps: bool,
+    #[serde(default = "default_true")]
+    pub lofi_pll: bool,
+    #[serde(default = "default_true")]
+    pub lofi_saw: bool,
+    #[serde(default)]
+    pub lofi_shaper: i32,
+    #[serde(default = "default_lofi_bits")]
+    pub lofi_bits: f32,
+    #[serde(default = "default_lofi_rate")]
+    pub lofi_rate: f32,
+    #[serde(default = "default_true")]
+    pub lofi_anti_alias: bool,
+    #[serde(default)]
+    pub lofi_jitter: f32,
+    #[serde(default)]
+    pub lofi_drive: f32,
+    #[serde(default = "default_lofi_mix")]
+    pub lofi_mix: f32,
+
     #[serde(default)]
     pub lfo1_rate: f32,
     #[serde(default)]
@@ -652,6 +863,58 @@ pub struct PresetData {
     pub mseq_dest2: i32,
     #[serde(default)]
     pub mseq_amount2: f32,
+
+    #[serde(default = "default_mseg_shapes")]
+    pub mseg_shapes: Vec<MsegShape>,
+    #[serde(default = "default_true")]
+    pub mseg1_tempo_sync: bool,
+    #[serde(default = "default_mseg_retrigger")]
+    pub mseg1_retrigger: i32,
+    #[serde(default)]
+    pub mseg1_dest1: i32,
+    #[serde(default)]
+    pub mseg1_amount1: f32,
+    #[serde(default)]
+    pub mseg1_dest2: i32,
+    #[serde(default)]
+    pub mseg1_amount2: f32,
+    #[serde(default = "default_true")]
+    pub mseg2_tempo_sync: bool,
+    #[serde(default = "default_mseg_retrigger")]
+    pub mseg2_retrigger: i32,
+    #[serde(default)]
+    pub mseg2_dest1: i32,
+    #[serde(default)]
+    pub mseg2_amount1: f32,
+    #[serde(default)]
+    pub mseg2_dest2: i32,
+    #[serde(default)]
+    pub mseg2_amount2: f32,
+
+    #[serde(default)]
+    pub mod_pitch_quantize: bool,
+
+    #[serde(default)]
+    pub macro_1: f32,
+    #[serde(default)]
+    pub macro_2: f32,
+    #[serde(default)]
+    pub macro_3: f32,
+    #[serde(default)]
+    pub macro_4: f32,
+    #[serde(default)]
+    pub macro_5: f32,
+    #[serde(default)]
+    pub macro_6: f32,
+    #[serde(default)]
+    pub macro_7: f32,
+    #[serde(default)]
+    pub macro_8: f32,
+    #[serde(default)]
+    pub macro_targets: Vec<Vec<MacroTarget>>,
+    /// Performance snapshots; a snapshot's own `scenes` is always empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scenes: Vec<Option<PresetData>>,
 }
 
 fn default_swing() -> f32 { 50.0 }
@@ -672,8 +935,33 @@ fn default_reverb_mod_shape() -> f32 { 0.5 }
 fn default_rhythm_duck_division() -> i32 { 2 }
 fn default_reverb_stereo_width() -> f32 { 1.0 }
 fn default_rhythm_duck_smooth() -> f32 { 75.0 }
+fn default_delay_division() -> i32 { 8 }
+fn default_delay_time() -> f32 { 375.0 }
+fn default_delay_feedback() -> f32 { 0.4 }
+fn default_delay_lpf() -> f32 { 8000.0 }
+fn default_delay_hpf() -> f32 { 80.0 }
+fn default_delay_mod_rate() -> f32 { 0.3 }
+fn default_mod_fx_position() -> i32 { 1 }
+fn default_mod_fx_rate() -> f32 { 0.5 }
+fn default_half() -> f32 { 0.5 }
+fn default_mod_fx_voices() -> i32 { 3 }
+fn default_mod_fx_stages() -> i32 { 6 }
+fn default_fx_chain() -> Vec<i32> { DEFAULT_FX_ORDER.iter().map(|m| m.to_index()).collect() }
+fn default_lofi_bits() -> f32 { 16.0 }
+fn default_lofi_rate() -> f32 { 48000.0 }
+fn default_wavetable_name() -> String { BUILTIN_NAME.to_string() }
+fn default_noise_rate() -> f32 { 1000.0 }
+fn default_noise_lpf() -> f32 { 20000.0 }
+fn default_noise_hpf() -> f32 { 20.0 }
+fn default_sync_soft() -> f32 { 0.5 }
+fn default_pll_track_speed() -> f32 { 0.5 }
+fn default_pll_damping() -> f32 { 0.3 }
+fn default_pll_coupling() -> f32 { 0.5 }
+fn default_pll_ref_mix() -> f32 { 1.0 }
 fn default_stereo_width() -> f32 { 1.0 }
 fn default_filter_cutoff() -> f32 { 20000.0 }
+fn default_filter2_cutoff() -> f32 { 1000.0 }
+fn default_resonator_position() -> f32 { 0.3 }
 fn default_filter_env_attack() -> f32 { 10.0 }
 fn default_filter_env_decay() -> f32 { 100.0 }
 fn default_filter_env_sustain() -> f32 { 0.7 }
@@ -682,12 +970,15 @@ fn default_filter_env_range() -> f32 { 4.0 }
 fn default_mseq_division() -> i32 { 3 }
 fn default_fm_ratio_float() -> f32 { 1.0 }
 fn default_mseq_slew() -> f32 { 5.0 }
+fn default_mseg_shapes() -> Vec<MsegShape> { vec![MsegShape::default(); MSEG_COUNT] }
+fn default_mseg_retrigger() -> i32 { 1 }
 fn default_mult_slew_time() -> f32 { 0.15 }
 fn default_phase_reset() -> bool { true }
 fn default_env_range() -> f32 { 500.0 }
 fn default_pll_tail_time() -> f32 { 500.0 }
 fn default_pll_tail_amount() -> f32 { 0.3 }
 fn default_vol_depth() -> f32 { 1.0 }
+fn default_lofi_mix() -> f32 { 1.0 }
 fn default_div() -> i32 { 3 }
 
 impl Default for PresetData {
@@ -808,6 +1099,45 @@ impl Default for PresetData {
             synth_saw_shape_type: 0,
             synth_saw_shape_amount: 0.0,
 
+            wavetable_name: default_wavetable_name(),
+            synth_wt_volume: 0.0,
+            synth_wt_octave: 0,
+            synth_wt_tune: 0,
+            synth_wt_fine: 0.0,
+            synth_wt_position: 0.0,
+
+            synth_noise_level: 0.0,
+            synth_noise_type: 0,
+            synth_noise_rate: 1000.0,
+            synth_noise_lpf: 20000.0,
+            synth_noise_hpf: 20.0,
+            synth_noise_env: 0,
+            synth_noise_inject: 0,
+            synth_noise_inject_amount: 0.0,
+
+            synth_sync_source: 0,
+            synth_vps_sync: 0,
+            synth_saw_sync: 0,
+            synth_pll_sync: 0,
+            synth_sync_soft: 0.5,
+
+            synth_pll_topology: 0,
+            synth_pll2_track_speed: 0.5,
+            synth_pll2_damping: 0.3,
+            synth_pll2_mult: 0,
+            synth_pll_coupling: 0.5,
+            synth_pll_ref_source: 0,
+            synth_pll_ref_mix: 1.0,
+
+            synth_ring_pll_enable: false,
+            synth_ring_pll: 0.0,
+            synth_ring_vps_saw_enable: false,
+            synth_ring_vps_saw: 0.0,
+            synth_xfm_vps_saw_enable: false,
+            synth_xfm_vps_saw: 0.0,
+            synth_xfm_saw_ref_enable: false,
+            synth_xfm_saw_ref: 0.0,
+
             synth_filter_enable: false,
             synth_filter_cutoff: 20000.0,
             synth_filter_resonance: 0.0,
@@ -850,6 +1180,23 @@ impl Default for PresetData {
             synth_filter_cutoff_slew: 0.0,
             synth_filter_poles: 0,
 
+            synth_filter2_enable: false,
+            synth_filter2_type: 0,
+            synth_filter2_cutoff: 1000.0,
+            synth_filter2_resonance: 0.0,
+            synth_filter2_morph: 0.0,
+            synth_filter2_key_track: 0.0,
+            synth_filter2_env_amount: 0.0,
+            synth_filter_routing: 0,
+
+            synth_resonator_enable: false,
+            synth_resonator_model: 0,
+            synth_resonator_excite: 0,
+            synth_resonator_decay: 0.5,
+            synth_resonator_brightness: 0.5,
+            synth_resonator_position: 0.3,
+            synth_resonator_mix: 0.5,
+
             synth_vol_attack: 10.0,
             synth_vol_attack_shape: 0.0,
             synth_vol_decay: 100.0,
@@ -899,6 +1246,58 @@ impl Default for PresetData {
             synth_reverb_rhythm_duck_division: 2,
             synth_reverb_rhythm_duck_smooth: 75.0,
 
+            synth_delay_enable: false,
+            synth_delay_mode: 0,
+            synth_delay_sync: true,
+            synth_delay_division: 8,
+            synth_delay_time: 375.0,
+            synth_delay_mix: 0.0,
+            synth_delay_feedback: 0.4,
+            synth_delay_spread: 0.0,
+            synth_delay_lpf: 8000.0,
+            synth_delay_hpf: 80.0,
+            synth_delay_saturation: 0.0,
+            synth_delay_mod_rate: 0.3,
+            synth_delay_mod_depth: 0.0,
+            synth_delay_ducking: 0.0,
+            synth_delay_send_vps: false,
+            synth_delay_send_pll: false,
+            synth_delay_send_saw: false,
+            synth_delay_send_wt: false,
+            synth_delay_send_sub: false,
+            synth_delay_send_filter: true,
+
+            mod_fx_enable: false,
+            mod_fx_mode: 0,
+            mod_fx_position: 1,
+            mod_fx_rate: 0.5,
+            mod_fx_sync: false,
+            mod_fx_division: 0,
+            mod_fx_depth: 0.5,
+            mod_fx_feedback: 0.0,
+            mod_fx_mix: 0.5,
+            mod_fx_voices: 3,
+            mod_fx_stages: 6,
+            mod_fx_spread: 0.5,
+
+            fx_chain: default_fx_chain(),
+            fx_send_looper: false,
+            fx_send_delay: false,
+            fx_send_reverb: false,
+
+            lofi_enable: false,
+            lofi_post_filter: false,
+            lofi_vps: true,
+            lofi_pll: true,
+            lofi_saw: true,
+            lofi_shaper: 0,
+            lofi_bits: 16.0,
+            lofi_rate: 48000.0,
+            lofi_anti_alias: true,
+            lofi_jitter: 0.0,
+            lofi_drive: 0.0,
+            lofi_mix: 1.0,
+
             lfo1_rate: 1.0,
             lfo1_waveform: 0,
             lfo1_tempo_sync: false,
@@ -993,6 +1392,31 @@ impl Default for PresetData {
             mseq_amount1: 0.0,
             mseq_dest2: 0,
             mseq_amount2: 0.0,
+
+            mseg_shapes: default_mseg_shapes(),
+            mseg1_tempo_sync: true,
+            mseg1_retrigger: 1,
+            mseg1_dest1: 0,
+            mseg1_amount1: 0.0,
+            mseg1_dest2: 0,
+            mseg1_amount2: 0.0,
+            mseg2_tempo_sync: true,
+            mseg2_retrigger: 1,
+            mseg2_dest1: 0,
+            mseg2_amount1: 0.0,
+            mseg2_dest2: 0,
+            mseg2_amount2: 0.0,
+            mod_pitch_quantize: false,
+            macro_1: 0.0,
+            macro_2: 0.0,
+            macro_3: 0.0,
+            macro_4: 0.0,
+            macro_5: 0.0,
+            macro_6: 0.0,
+            macro_7: 0.0,
+            macro_8: 0.0,
+            macro_targets: Vec::new(),
+            scenes: Vec::new(),
         }
     }
 }
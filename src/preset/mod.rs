@@ -3,6 +3,12 @@
 mod data;
 pub mod manager;
 mod defaults;
+mod breed;
+mod morph;
+mod randomize;
 
 pub use data::{Preset, PresetData, NotePresetData, OctaveRandomizationPresetData, StyleConfigPresetData, MultiBarPresetData, BarSlotPresetData, NoteSlotPresetData, MelodicConfigPresetData};
 pub use manager::PresetManager;
+pub use breed::breed;
+pub use morph::{routed_params, MorphPair, MorphSnapshot, MorphSnapshots, ParamWriter, PatternData, SnapshotCapture};
+pub use randomize::{mutate_normalized, mutate_sequencer, RandomGroup, RandomizerSettings};
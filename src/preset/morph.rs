@@ -0,0 +1,376 @@
+//! A/B preset morphing and scene recall, run on the audio thread. Each
+//! preset or scene is reduced to a `MorphSnapshot`: the normalized values it
+//! writes to the params, captured through the same writer that loads presets,
+//! plus its sequencer pattern. The macro router turns the parameter
+//! differences into offsets, so continuous params glide and stepped ones
+//! switch at the midpoint; the pattern's strength grid and note chances
+//! crossfade and its scale, style and octave settings switch at the midpoint.
+//! Wavetable, MSEG shapes, macro targets, multi-bar and melodic settings stay
+//! as loaded.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use nih_plug::prelude::{Param, ParamPtr, ParamSetter, Params};
+use serde::{Deserialize, Serialize};
+use crate::macro_controls::{is_macro_target, SnapshotValue};
+use crate::sequencer::NotePool;
+use crate::sequencer::scales::{OctaveRandomization, Scale, StabilityPattern};
+use crate::sequencer::styles::StyleConfig;
+use super::data::{NotePresetData, OctaveRandomizationPresetData, Preset, PresetData, StyleConfigPresetData};
+
+/// The two presets the morph control moves between.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MorphPair {
+    pub a: Option<Preset>,
+    pub b: Option<Preset>,
+}
+
+/// Where preset loading sends parameter values: the host through a
+/// `ParamSetter`, or a `SnapshotCapture` that records them.
+pub trait ParamWriter {
+    fn set_parameter<P: Param>(&self, param: &P, value: P::Plain);
+}
+
+impl ParamWriter for ParamSetter<'_> {
+    fn set_parameter<P: Param>(&self, param: &P, value: P::Plain) {
+        ParamSetter::set_parameter(self, param, value);
+    }
+}
+
+/// Params the macro router can offset.
+pub fn routed_params(params: &impl Params) -> HashSet<ParamPtr> {
+    params
+        .param_map()
+        .into_iter()
+        .filter(|(id, _, _)| is_macro_target(id))
+        .map(|(_, ptr, _)| ptr)
+        .collect()
+}
+
+/// Records the values a preset writes without touching the params.
+#[derive(Default)]
+pub struct SnapshotCapture {
+    values: RefCell<Vec<SnapshotValue>>,
+}
+
+impl SnapshotCapture {
+    /// The recorded values of `routed` params, one per param with the last
+    /// write winning.
+    pub fn finish(self, routed: &HashSet<ParamPtr>) -> Vec<SnapshotValue> {
+        let mut seen = HashSet::new();
+        let mut values: Vec<SnapshotValue> = self.values.into_inner()
+            .into_iter()
+            .rev()
+            .filter(|v| routed.contains(&v.param) && seen.insert(v.param))
+            .collect();
+        values.reverse();
+        values
+    }
+}
+
+impl ParamWriter for SnapshotCapture {
+    fn set_parameter<P: Param>(&self, param: &P, value: P::Plain) {
+        self.values.borrow_mut().push(SnapshotValue {
+            param: param.as_ptr(),
+            value: param.preview_normalized(value),
+            discrete: param.step_count().is_some(),
+        });
+    }
+}
+
+/// The sequencer half of a morph endpoint, in preset units.
+#[derive(Clone, Debug)]
+pub struct PatternData {
+    pub strength_values: Vec<u8>,
+    pub root_note: u8,
+    pub notes: Vec<NotePresetData>,
+    pub scale: Scale,
+    pub stability_pattern: StabilityPattern,
+    pub octave_randomization: OctaveRandomizationPresetData,
+    pub style_config: StyleConfigPresetData,
+}
+
+impl PatternData {
+    pub fn from_preset(data: &PresetData) -> Self {
+        Self {
+            strength_values: data.strength_values.clone(),
+            root_note: data.root_note,
+            notes: data.notes.clone(),
+            scale: data.scale,
+            stability_pattern: data.stability_pattern,
+            octave_randomization: data.octave_randomization.clone(),
+            style_config: data.style_config.clone(),
+        }
+    }
+
+    /// Blends `a` towards `b` into `self`, reusing its buffers. Strength
+    /// cells and note chances crossfade; everything else switches at the
+    /// midpoint.
+    pub fn blend_into(&mut self, a: &Self, b: &Self, t: f32) {
+        let t = t.clamp(0.0, 1.0);
+        let near = if t < 0.5 { a } else { b };
+        blend_strength(&a.strength_values, &b.strength_values, t, &mut self.strength_values);
+        blend_notes(&a.notes, &b.notes, t, &mut self.notes);
+        self.root_note = near.root_note;
+        self.scale = near.scale;
+        self.stability_pattern = near.stability_pattern;
+        self.octave_randomization.clone_from(&near.octave_randomization);
+        self.style_config.clone_from(&near.style_config);
+    }
+
+    /// Reads the pattern back from the shared sequencer state, reusing this
+    /// pattern's buffers.
+    pub fn read_from(
+        &mut self,
+        strength_values: &[f32],
+        note_pool: &NotePool,
+        scale: Scale,
+        stability_pattern: StabilityPattern,
+        octave_randomization: &OctaveRandomization,
+        style_config: &StyleConfig,
+    ) {
+        self.strength_values.clear();
+        self.strength_values.extend(strength_values.iter().map(|&v| (v * 100.0).clamp(0.0, 100.0) as u8));
+
+        self.root_note = note_pool.root_note.unwrap_or(48);
+        self.notes.clear();
+        self.notes.extend(note_pool.notes.iter().map(|n| NotePresetData {
+            midi_note: n.midi_note,
+            chance: (n.chance * 127.0).clamp(0.0, 127.0) as u8,
+            beat: ((n.strength_bias * 63.0) + 64.0).clamp(0.0, 127.0) as u8,
+            beat_length: ((n.length_bias * 63.0) + 64.0).clamp(0.0, 127.0) as u8,
+            octave_offset: n.octave_offset,
+        }));
+
+        self.scale = scale;
+        self.stability_pattern = stability_pattern;
+
+        self.octave_randomization.chance = octave_randomization.chance;
+        self.octave_randomization.strength_pref = octave_randomization.strength_pref;
+        self.octave_randomization.length_pref = octave_randomization.length_pref;
+        self.octave_randomization.direction = octave_randomization.direction;
+
+        self.style_config.style = style_config.style;
+        self.style_config.chance = style_config.chance;
+        self.style_config.complexity = style_config.complexity;
+        self.style_config.max_notes = style_config.max_notes;
+        self.style_config.mode = style_config.mode;
+    }
+
+    /// Writes the pattern into the sequencer state the editor shares with
+    /// the audio thread.
+    pub fn write_into(
+        &self,
+        strength_values: &mut [f32],
+        note_pool: &mut NotePool,
+        scale: &mut Scale,
+        stability_pattern: &mut StabilityPattern,
+        octave_randomization: &mut OctaveRandomization,
+        style_config: &mut StyleConfig,
+    ) {
+        for (i, value) in strength_values.iter_mut().enumerate() {
+            *value = self.strength_values.get(i).map_or(0.0, |&v| v as f32 / 100.0);
+        }
+
+        note_pool.notes.clear();
+        note_pool.set_root_note(self.root_note);
+        for note_data in &self.notes {
+            let chance = note_data.chance as f32 / 127.0;
+            let strength_bias = (note_data.beat as f32 - 64.0) / 63.0;
+            let length_bias = (note_data.beat_length as f32 - 64.0) / 63.0;
+            note_pool.set_note_full(note_data.midi_note, note_data.octave_offset, chance, strength_bias, length_bias);
+        }
+
+        *scale = self.scale;
+        *stability_pattern = self.stability_pattern;
+
+        octave_randomization.chance = self.octave_randomization.chance;
+        octave_randomization.strength_pref = self.octave_randomization.strength_pref;
+        octave_randomization.length_pref = self.octave_randomization.length_pref;
+        octave_randomization.direction = self.octave_randomization.direction;
+
+        style_config.style = self.style_config.style;
+        style_config.chance = self.style_config.chance;
+        style_config.complexity = self.style_config.complexity;
+        style_config.max_notes = self.style_config.max_notes;
+        style_config.mode = self.style_config.mode;
+    }
+}
+
+/// One preset as the audio thread morphs it.
+#[derive(Clone)]
+pub struct MorphSnapshot {
+    pub params: Vec<SnapshotValue>,
+    pub pattern: PatternData,
+}
+
+/// Everything the audio thread morphs between, rebuilt off the audio thread
+/// whenever the A/B pair or the scenes change. `blended` and `scene_from` are
+/// the audio thread's scratch patterns, sized here so it never allocates.
+pub struct MorphSnapshots {
+    pub ab: Option<(MorphSnapshot, MorphSnapshot)>,
+    pub scenes: Vec<Option<MorphSnapshot>>,
+    pub blended: PatternData,
+    /// The pattern as it was when the running scene recall started.
+    pub scene_from: PatternData,
+}
+
+/// Room for the notes of a pattern read back from the sequencer.
+const PATTERN_NOTE_CAPACITY: usize = 128;
+
+impl MorphSnapshots {
+    pub fn new(ab: Option<(MorphSnapshot, MorphSnapshot)>, scenes: Vec<Option<MorphSnapshot>>) -> Self {
+        let patterns = || ab.iter().flat_map(|(a, b)| [a, b]).chain(scenes.iter().flatten()).map(|s| &s.pattern);
+        // The shared strength grid has 96 cells
+        let strength = patterns().map(|p| p.strength_values.len()).max().unwrap_or(0).max(96);
+        let notes = patterns().map(|p| p.notes.len()).max().unwrap_or(0).max(PATTERN_NOTE_CAPACITY);
+        let scratch = || {
+            let mut pattern = PatternData::from_preset(&PresetData::default());
+            pattern.strength_values.reserve(strength);
+            // A blend holds the notes of both sides
+            pattern.notes.reserve(notes * 2);
+            pattern
+        };
+        Self { ab, scenes, blended: scratch(), scene_from: scratch() }
+    }
+}
+
+impl Default for MorphSnapshots {
+    fn default() -> Self {
+        Self::new(None, Vec::new())
+    }
+}
+
+fn lerp_u8(x: u8, y: u8, t: f32) -> u8 {
+    (x as f32 + (y as f32 - x as f32) * t).round().clamp(0.0, 255.0) as u8
+}
+
+/// Strength cells missing on one side fade from or to zero.
+fn blend_strength(a: &[u8], b: &[u8], t: f32, out: &mut Vec<u8>) {
+    out.clear();
+    out.extend((0..a.len().max(b.len()))
+        .map(|i| lerp_u8(a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0), t)));
+}
+
+/// Notes in both pools blend their chance and biases; notes in only one fade
+/// their chance in or out and leave the pool once it reaches zero.
+fn blend_notes(a: &[NotePresetData], b: &[NotePresetData], t: f32, out: &mut Vec<NotePresetData>) {
+    let same = |x: &NotePresetData, y: &NotePresetData| {
+        x.midi_note == y.midi_note && x.octave_offset == y.octave_offset
+    };
+    out.clear();
+    for na in a {
+        match b.iter().find(|nb| same(na, nb)) {
+            Some(nb) => out.push(NotePresetData {
+                chance: lerp_u8(na.chance, nb.chance, t),
+                beat: lerp_u8(na.beat, nb.beat, t),
+                beat_length: lerp_u8(na.beat_length, nb.beat_length, t),
+                ..na.clone()
+            }),
+            None => {
+                let chance = lerp_u8(na.chance, 0, t);
+                if chance > 0 {
+                    out.push(NotePresetData { chance, ..na.clone() });
+                }
+            }
+        }
+    }
+    for nb in b.iter().filter(|nb| !a.iter().any(|na| same(na, nb))) {
+        let chance = lerp_u8(0, nb.chance, t);
+        if chance > 0 {
+            out.push(NotePresetData { chance, ..nb.clone() });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nih_plug::prelude::{FloatParam, FloatRange};
+
+    fn note(midi_note: u8, chance: u8) -> NotePresetData {
+        NotePresetData { midi_note, chance, beat: 64, beat_length: 64, octave_offset: 0 }
+    }
+
+    #[derive(Params)]
+    struct TestParams {
+        #[id = "cutoff"]
+        cutoff: FloatParam,
+        #[id = "macro_1"]
+        macro_1: FloatParam,
+    }
+
+    #[test]
+    fn capture_keeps_the_last_write_of_routed_params() {
+        let params = TestParams {
+            cutoff: FloatParam::new("Cutoff", 0.0, FloatRange::Linear { min: 0.0, max: 200.0 }),
+            macro_1: FloatParam::new("Macro 1", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 }),
+        };
+        let capture = SnapshotCapture::default();
+        capture.set_parameter(&params.cutoff, 20.0);
+        capture.set_parameter(&params.macro_1, 1.0);
+        capture.set_parameter(&params.cutoff, 50.0);
+
+        let values = capture.finish(&routed_params(&params));
+        assert_eq!(values.len(), 1);
+        assert!(values[0].param == params.cutoff.as_ptr());
+        assert!((values[0].value - 0.25).abs() < 1e-6);
+        assert!(!values[0].discrete);
+        assert_eq!(params.cutoff.value(), 0.0);
+    }
+
+    #[test]
+    fn pattern_crossfades_strength_and_switches_settings_at_midpoint() {
+        let a = PatternData::from_preset(&PresetData::default());
+        let b = PatternData {
+            strength_values: vec![100; 4],
+            root_note: a.root_note + 7,
+            ..a.clone()
+        };
+
+        let mut blended = a.clone();
+        blended.blend_into(&a, &b, 0.25);
+        assert_eq!(blended.strength_values[0], lerp_u8(a.strength_values[0], 100, 0.25));
+        assert_eq!(blended.strength_values.len(), a.strength_values.len().max(4));
+        assert_eq!(blended.root_note, a.root_note);
+        blended.blend_into(&a, &b, 0.75);
+        assert_eq!(blended.root_note, b.root_note);
+    }
+
+    #[test]
+    fn pattern_round_trips_through_the_sequencer_state() {
+        let pattern = PatternData {
+            notes: vec![note(55, 127)],
+            ..PatternData::from_preset(&PresetData::default())
+        };
+        let mut strength_values = vec![0.0; 96];
+        let mut note_pool = NotePool::new();
+        let (mut scale, mut stability_pattern) = (Scale::default(), StabilityPattern::default());
+        let (mut octave_randomization, mut style_config) = (OctaveRandomization::default(), StyleConfig::default());
+        pattern.write_into(&mut strength_values, &mut note_pool, &mut scale, &mut stability_pattern, &mut octave_randomization, &mut style_config);
+
+        let mut back = PatternData::from_preset(&PresetData::default());
+        back.read_from(&strength_values, &note_pool, scale, stability_pattern, &octave_randomization, &style_config);
+        for (x, y) in back.strength_values.iter().zip(&pattern.strength_values) {
+            assert!(x.abs_diff(*y) <= 1);
+        }
+        assert_eq!(back.root_note, pattern.root_note);
+        assert!(back.notes.iter().any(|n| n.midi_note == 55 && n.chance == 127));
+    }
+
+    #[test]
+    fn note_pools_crossfade() {
+        let a = vec![note(48, 100), note(55, 40)];
+        let b = vec![note(48, 20), note(60, 127)];
+
+        let mut mid = Vec::new();
+        blend_notes(&a, &b, 0.5, &mut mid);
+        let chance = |n: u8| mid.iter().find(|x| x.midi_note == n).map(|x| x.chance);
+        assert_eq!(chance(48), Some(60));
+        assert_eq!(chance(55), Some(20));
+        assert_eq!(chance(60), Some(64));
+
+        blend_notes(&a, &b, 0.0, &mut mid);
+        assert!(mid.iter().all(|n| n.midi_note != 60));
+    }
+}
@@ -0,0 +1,226 @@
+//! Constrained patch randomizer. Sound parameters are the MIDI-learnable
+//! `SOUND_PARAMS`, grouped by their section; the sequencer group covers beat
+//! probabilities, the strength grid and note chances. Every value moves
+//! towards a random target by the mutation amount, so small amounts nudge a
+//! patch and 1.0 replaces it.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::midi_learn::SOUND_PARAMS;
+use super::data::PresetData;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RandomGroup {
+    Pll,
+    Sub,
+    Saw,
+    Filter,
+    Vps,
+    Sequencer,
+}
+
+impl RandomGroup {
+    pub const ALL: [RandomGroup; 6] = [
+        RandomGroup::Pll,
+        RandomGroup::Sub,
+        RandomGroup::Saw,
+        RandomGroup::Filter,
+        RandomGroup::Vps,
+        RandomGroup::Sequencer,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RandomGroup::Pll => "PLL",
+            RandomGroup::Sub => "SUB",
+            RandomGroup::Saw => "SAW",
+            RandomGroup::Filter => "FILTER",
+            RandomGroup::Vps => "VPS",
+            RandomGroup::Sequencer => "SEQ",
+        }
+    }
+
+    /// Sound group of a `SOUND_PARAMS` id; macros and morph have none.
+    pub fn of(param_id: &str) -> Option<RandomGroup> {
+        let id = param_id.strip_prefix("synth_")?;
+        if id.starts_with("pll_") || id.starts_with("drift_") || id == "tube_drive" {
+            Some(RandomGroup::Pll)
+        } else if id.starts_with("sub_") {
+            Some(RandomGroup::Sub)
+        } else if id.starts_with("saw_") {
+            Some(RandomGroup::Saw)
+        } else if id.starts_with("filter_") {
+            Some(RandomGroup::Filter)
+        } else if id.starts_with("osc_") || id.starts_with("vps_") {
+            Some(RandomGroup::Vps)
+        } else {
+            None
+        }
+    }
+
+    /// Parameters of this group the randomizer may change.
+    pub fn params(self) -> impl Iterator<Item = &'static str> {
+        SOUND_PARAMS.iter()
+            .copied()
+            .filter(move |id| RandomGroup::of(id) == Some(self) && !NEVER_RANDOMIZED.contains(id))
+    }
+}
+
+/// Switching a source or the filter off is left to the player.
+const NEVER_RANDOMIZED: &[&str] = &[
+    "synth_pll_enable", "synth_saw_enable", "synth_vps_enable", "synth_filter_enable",
+];
+
+/// Normalized bounds that keep random patches audible and below painful
+/// levels: no silent oscillators, no closed or screaming filter, capped drive.
+const SAFE_RANGES: &[(&str, f32, f32)] = &[
+    ("synth_pll_volume", 0.3, 0.85),
+    ("synth_saw_volume", 0.3, 0.85),
+    ("synth_osc_volume", 0.3, 0.85),
+    ("synth_sub_volume", 0.0, 0.7),
+    ("synth_filter_cutoff", 0.4, 1.0),
+    ("synth_filter_resonance", 0.0, 0.85),
+    ("synth_filter_drive", 0.0, 0.6),
+    ("synth_tube_drive", 0.0, 0.6),
+];
+
+/// The likeliest note keeps at least this chance (out of 127).
+const MIN_NOTE_CHANCE: u8 = 64;
+/// Below this the busiest beat is treated as a silent pattern.
+const MIN_BEAT_PROBABILITY: f32 = 0.25;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RandomizerSettings {
+    pub amount: f32,
+    pub locked_groups: Vec<RandomGroup>,
+    pub locked_params: Vec<String>,
+}
+
+impl Default for RandomizerSettings {
+    fn default() -> Self {
+        Self { amount: 0.25, locked_groups: Vec::new(), locked_params: Vec::new() }
+    }
+}
+
+impl RandomizerSettings {
+    pub fn group_locked(&self, group: RandomGroup) -> bool {
+        self.locked_groups.contains(&group)
+    }
+
+    pub fn param_locked(&self, param_id: &str) -> bool {
+        self.locked_params.iter().any(|p| p == param_id)
+    }
+
+    pub fn toggle_group(&mut self, group: RandomGroup) {
+        match self.locked_groups.iter().position(|g| *g == group) {
+            Some(i) => { self.locked_groups.remove(i); }
+            None => self.locked_groups.push(group),
+        }
+    }
+
+    pub fn toggle_param(&mut self, param_id: &str) {
+        match self.locked_params.iter().position(|p| p == param_id) {
+            Some(i) => { self.locked_params.remove(i); }
+            None => self.locked_params.push(param_id.to_string()),
+        }
+    }
+
+    /// Sound parameter ids a randomize pass will change.
+    pub fn sound_targets(&self) -> impl Iterator<Item = &'static str> + '_ {
+        RandomGroup::ALL.into_iter()
+            .filter(|g| *g != RandomGroup::Sequencer && !self.group_locked(*g))
+            .flat_map(RandomGroup::params)
+            .filter(|id| !self.param_locked(id))
+    }
+}
+
+/// Moves a normalized value towards the random target `r` and applies the
+/// parameter's safety range.
+pub fn mutate_normalized(param_id: &str, value: f32, amount: f32, r: f32) -> f32 {
+    let mutated = value + (r - value) * amount.clamp(0.0, 1.0);
+    match SAFE_RANGES.iter().find(|(id, _, _)| *id == param_id) {
+        Some(&(_, min, max)) => mutated.clamp(min, max),
+        None => mutated.clamp(0.0, 1.0),
+    }
+}
+
+fn mutate_unit(value: f32, amount: f32, r: f32) -> f32 {
+    (value + (r - value) * amount).clamp(0.0, 1.0)
+}
+
+/// Mutates beat probabilities, strength cells and note chances in place,
+/// keeping at least one likely beat and one likely note.
+pub fn mutate_sequencer<R: Rng>(data: &mut PresetData, amount: f32, rng: &mut R) {
+    let amount = amount.clamp(0.0, 1.0);
+    let beats: [&mut [f32]; 15] = [
+        &mut data.straight_1_1, &mut data.straight_1_2, &mut data.straight_1_4,
+        &mut data.straight_1_8, &mut data.straight_1_16, &mut data.straight_1_32,
+        &mut data.triplet_1_2t, &mut data.triplet_1_4t, &mut data.triplet_1_8t, &mut data.triplet_1_16t,
+        &mut data.dotted_1_2d, &mut data.dotted_1_4d, &mut data.dotted_1_8d,
+        &mut data.dotted_1_16d, &mut data.dotted_1_32d,
+    ];
+    let mut busiest = 0.0f32;
+    for value in beats.into_iter().flatten() {
+        *value = mutate_unit(*value, amount, rng.gen());
+        busiest = busiest.max(*value);
+    }
+    if busiest < MIN_BEAT_PROBABILITY {
+        data.straight_1_4[0] = 1.0;
+    }
+
+    for cell in &mut data.strength_values {
+        *cell = (mutate_unit(*cell as f32 / 100.0, amount, rng.gen()) * 100.0).round() as u8;
+    }
+
+    for note in &mut data.notes {
+        note.chance = (mutate_unit(note.chance as f32 / 127.0, amount, rng.gen()) * 127.0).round() as u8;
+    }
+    if let Some(likeliest) = data.notes.iter_mut().max_by_key(|n| n.chance) {
+        likeliest.chance = likeliest.chance.max(MIN_NOTE_CHANCE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn groups_cover_sound_params_and_skip_enables() {
+        let pll: Vec<_> = RandomGroup::Pll.params().collect();
+        assert!(pll.contains(&"synth_pll_damping"));
+        assert!(pll.contains(&"synth_tube_drive"));
+        assert!(!pll.contains(&"synth_pll_enable"));
+        assert_eq!(RandomGroup::of("macro_1"), None);
+        assert_eq!(RandomGroup::of("synth_osc_d"), Some(RandomGroup::Vps));
+    }
+
+    #[test]
+    fn locks_and_safety_limits() {
+        let mut settings = RandomizerSettings::default();
+        settings.toggle_group(RandomGroup::Filter);
+        settings.toggle_param("synth_osc_d");
+        let targets: Vec<_> = settings.sound_targets().collect();
+        assert!(!targets.iter().any(|id| id.starts_with("synth_filter_")));
+        assert!(!targets.contains(&"synth_osc_d"));
+        assert!(targets.contains(&"synth_osc_v"));
+
+        assert_eq!(mutate_normalized("synth_osc_volume", 0.5, 1.0, 0.0), 0.3);
+        assert_eq!(mutate_normalized("synth_filter_resonance", 0.5, 1.0, 1.0), 0.85);
+        assert!((mutate_normalized("synth_osc_v", 0.2, 0.5, 0.6) - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sequencer_mutation_stays_playable() {
+        // The init preset has no beats at all
+        let mut data = PresetData::default();
+        let mut rng = StdRng::seed_from_u64(7);
+        mutate_sequencer(&mut data, 0.0, &mut rng);
+        assert_eq!(data.straight_1_4[0], 1.0);
+
+        mutate_sequencer(&mut data, 1.0, &mut rng);
+        assert!(data.strength_values.iter().all(|&v| v <= 100));
+        assert!(data.notes.is_empty() || data.notes.iter().any(|n| n.chance >= MIN_NOTE_CHANCE));
+    }
+}
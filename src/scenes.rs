@@ -0,0 +1,234 @@
+//! Performance scenes: snapshots of the whole patch stored in the preset and
+//! recalled from the editor, Program Change or notes. The audio thread times
+//! each recall (waiting for the next beat or bar, then running the morph) and
+//! applies it as macro router offsets, so recalls land without the editor.
+
+use nih_plug::prelude::{NoteEvent, ParamPtr};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
+use std::sync::Mutex;
+
+pub const SCENE_COUNT: usize = 16;
+/// Lowest note that recalls a scene on the scene note channel (C1).
+pub const SCENE_NOTE_BASE: u8 = 36;
+
+pub const MORPH_TIME_LABELS: [&str; 8] = ["Instant", "1/16", "1/8", "1/4", "1/2", "1 Bar", "2 Bars", "4 Bars"];
+const MORPH_TIME_BEATS: [f64; 8] = [0.0, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+pub const QUANTIZE_LABELS: [&str; 3] = ["Off", "Beat", "Bar"];
+const QUANTIZE_BEATS: [f64; 3] = [0.0, 1.0, 4.0];
+
+pub fn morph_time_beats(index: i32) -> f64 {
+    MORPH_TIME_BEATS[index.clamp(0, MORPH_TIME_BEATS.len() as i32 - 1) as usize]
+}
+
+pub fn quantize_beats(index: i32) -> f64 {
+    QUANTIZE_BEATS[index.clamp(0, QUANTIZE_BEATS.len() as i32 - 1) as usize]
+}
+
+/// What an incoming MIDI event means for scene recall.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SceneMidi {
+    Recall(usize),
+    /// A note on the scene channel outside the scene range; dropped.
+    Consumed,
+    Pass,
+}
+
+/// `note_channel` is 1-16, or 0 when note recall is off. The whole scene
+/// channel is kept away from the sequencer and synth.
+pub fn route_midi(event: &NoteEvent<()>, program_change: bool, note_channel: i32) -> SceneMidi {
+    let on_scene_channel = |channel: u8| note_channel > 0 && channel as i32 == note_channel - 1;
+    match *event {
+        NoteEvent::MidiProgramChange { program, .. } if program_change && (program as usize) < SCENE_COUNT => {
+            SceneMidi::Recall(program as usize)
+        }
+        NoteEvent::NoteOn { channel, note, .. } if on_scene_channel(channel) => {
+            match note.checked_sub(SCENE_NOTE_BASE) {
+                Some(slot) if (slot as usize) < SCENE_COUNT => SceneMidi::Recall(slot as usize),
+                _ => SceneMidi::Consumed,
+            }
+        }
+        NoteEvent::NoteOff { channel, .. } if on_scene_channel(channel) => SceneMidi::Consumed,
+        _ => SceneMidi::Pass,
+    }
+}
+
+struct Transition {
+    scene: usize,
+    wait_beats: f64,
+    length_beats: f64,
+    elapsed_beats: f64,
+}
+
+impl Transition {
+    /// Negative while waiting for the quantize boundary.
+    fn progress(&self) -> f32 {
+        let t = self.elapsed_beats - self.wait_beats;
+        if t < 0.0 {
+            -1.0
+        } else if self.length_beats <= 0.0 {
+            1.0
+        } else {
+            (t / self.length_beats).min(1.0) as f32
+        }
+    }
+}
+
+/// Audio-thread side of scene recall.
+#[derive(Default)]
+pub struct SceneClock {
+    transition: Option<Transition>,
+    serial: u32,
+}
+
+impl SceneClock {
+    /// Starts a recall. `position_beats` is the song position when the
+    /// transport is running; while stopped the morph starts at once.
+    pub fn trigger(&mut self, scene: usize, position_beats: Option<f64>, quantize_beats: f64, length_beats: f64) {
+        let wait_beats = match position_beats {
+            Some(pos) if quantize_beats > 0.0 => {
+                let into = pos.max(0.0) % quantize_beats;
+                if into < 1e-6 { 0.0 } else { quantize_beats - into }
+            }
+            _ => 0.0,
+        };
+        self.serial = self.serial.wrapping_add(1);
+        self.transition = Some(Transition { scene, wait_beats, length_beats, elapsed_beats: 0.0 });
+    }
+
+    pub fn advance(&mut self, beats: f64) {
+        if let Some(transition) = &mut self.transition {
+            transition.elapsed_beats += beats;
+        }
+    }
+
+    pub fn apply_controls(&mut self, controls: &SceneControls, position_beats: Option<f64>, quantize_beats: f64, length_beats: f64) {
+        let request = controls.request.swap(-1, Ordering::Relaxed);
+        if request >= 0 {
+            self.trigger(request as usize, position_beats, quantize_beats, length_beats);
+        }
+    }
+
+    /// Scene and progress of the latest recall.
+    pub fn state(&self) -> Option<(usize, f32)> {
+        self.transition.as_ref().map(|t| (t.scene, t.progress()))
+    }
+
+    /// Bumped by every recall.
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    pub fn publish(&self, controls: &SceneControls) {
+        let (scene, progress) = self.state().map_or((-1, 0.0), |(scene, progress)| (scene as i32, progress));
+        controls.progress.store(progress.to_bits(), Ordering::Relaxed);
+        controls.active.store(scene, Ordering::Relaxed);
+        controls.serial.store(self.serial, Ordering::Release);
+    }
+}
+
+/// Editor-side handle on scene recall, shared with the audio thread.
+pub struct SceneControls {
+    pub request: AtomicI32, // scene index, -1 = none
+    pub active: AtomicI32,  // scene of the latest recall, -1 = none yet
+    pub progress: AtomicU32, // f32 bits; negative while waiting for the boundary
+    pub serial: AtomicU32,
+    /// Set when a preset is written to the knobs; the audio thread drops the
+    /// scene offsets.
+    pub reset: AtomicBool,
+    /// Offsets of the last recall that landed, so saving and storing can
+    /// capture the scene being heard rather than the bare knobs.
+    pub offsets: Mutex<Vec<(ParamPtr, f32)>>,
+}
+
+impl SceneControls {
+    pub fn new() -> Self {
+        Self {
+            request: AtomicI32::new(-1),
+            active: AtomicI32::new(-1),
+            progress: AtomicU32::new(0),
+            serial: AtomicU32::new(0),
+            reset: AtomicBool::new(false),
+            offsets: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn request_recall(&self, scene: usize) {
+        self.request.store(scene as i32, Ordering::Relaxed);
+    }
+
+    pub fn active_scene(&self) -> Option<usize> {
+        usize::try_from(self.active.load(Ordering::Relaxed)).ok()
+    }
+
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.progress.load(Ordering::Relaxed))
+    }
+
+    pub fn request_reset(&self) {
+        self.reset.store(true, Ordering::Release);
+    }
+
+    pub fn take_reset(&self) -> bool {
+        self.reset.swap(false, Ordering::AcqRel)
+    }
+
+    /// Offsets of the landed recall by parameter.
+    pub fn landed_offsets(&self) -> HashMap<ParamPtr, f32> {
+        self.offsets.lock().map(|offsets| offsets.iter().copied().collect()).unwrap_or_default()
+    }
+}
+
+impl Default for SceneControls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recall_waits_for_the_next_bar_then_morphs() {
+        let mut clock = SceneClock::default();
+        clock.trigger(3, Some(5.0), 4.0, 2.0);
+        let controls = SceneControls::new();
+        clock.publish(&controls);
+        assert_eq!(controls.active_scene(), Some(3));
+        assert!(controls.progress() < 0.0);
+
+        clock.advance(3.0);
+        clock.publish(&controls);
+        assert!(controls.progress().abs() < 1e-6);
+        clock.advance(1.0);
+        clock.publish(&controls);
+        assert!((controls.progress() - 0.5).abs() < 1e-6);
+        clock.advance(5.0);
+        clock.publish(&controls);
+        assert_eq!(controls.progress(), 1.0);
+    }
+
+    #[test]
+    fn stopped_transport_and_instant_morph_land_at_once() {
+        let mut clock = SceneClock::default();
+        clock.trigger(0, None, 4.0, 0.0);
+        let controls = SceneControls::new();
+        clock.publish(&controls);
+        assert_eq!(controls.progress(), 1.0);
+        assert_eq!(controls.serial.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn midi_routing() {
+        let pc = NoteEvent::MidiProgramChange { timing: 0, channel: 0, program: 2 };
+        assert_eq!(route_midi(&pc, true, 0), SceneMidi::Recall(2));
+        assert_eq!(route_midi(&pc, false, 0), SceneMidi::Pass);
+
+        let note = |channel, note| NoteEvent::NoteOn { timing: 0, voice_id: None, channel, note, velocity: 1.0 };
+        assert_eq!(route_midi(&note(15, 37), false, 16), SceneMidi::Recall(1));
+        assert_eq!(route_midi(&note(15, 80), false, 16), SceneMidi::Consumed);
+        assert_eq!(route_midi(&note(0, 37), false, 16), SceneMidi::Pass);
+        assert_eq!(route_midi(&note(15, 37), false, 0), SceneMidi::Pass);
+    }
+}
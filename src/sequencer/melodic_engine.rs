@@ -1,5 +1,6 @@
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use super::ml_suggest::SLOT_COUNT;
 
 #[derive(Clone, Debug)]
 pub struct MelodicNote {
@@ -119,48 +120,88 @@ impl MelodySuggester {
 
         let root_offset = (target_root % 12) as i8 - fragment.root_pitch_class as i8;
 
-        let mut result = Vec::with_capacity(fragment.notes.len());
+        vary_notes(&fragment.notes, root_offset, config, rng)
+    }
+}
 
-        for (i, note) in fragment.notes.iter().enumerate() {
-            if config.note_drop_chance > 0.0
-                && rng.gen::<f32>() < config.note_drop_chance
-                && i > 0
-                && note.start_time > 0.05
-            {
-                continue;
-            }
+/// Applies the `MelodicConfig` variation controls (drop, pitch, octave and
+/// rhythm jitter) to a note list, transposing it by `root_offset` semitones.
+pub fn vary_notes(
+    notes: &[MelodicNote],
+    root_offset: i8,
+    config: &MelodicConfig,
+    rng: &mut impl Rng,
+) -> Vec<MelodicNote> {
+    let mut result = Vec::with_capacity(notes.len());
+
+    for (i, note) in notes.iter().enumerate() {
+        if config.note_drop_chance > 0.0
+            && rng.gen::<f32>() < config.note_drop_chance
+            && i > 0
+            && note.start_time > 0.05
+        {
+            continue;
+        }
 
-            let mut pitch = note.relative_pitch as i16 + root_offset as i16;
+        let mut pitch = note.relative_pitch as i16 + root_offset as i16;
 
-            if config.pitch_variation > 0.0 && rng.gen::<f32>() < config.pitch_variation {
-                let shift = if rng.gen::<bool>() { 1 } else { -1 };
-                pitch += shift;
-            }
+        if config.pitch_variation > 0.0 && rng.gen::<f32>() < config.pitch_variation {
+            let shift = if rng.gen::<bool>() { 1 } else { -1 };
+            pitch += shift;
+        }
 
-            if config.octave_variation > 0.0 && rng.gen::<f32>() < config.octave_variation {
-                let shift = if rng.gen::<bool>() { 12 } else { -12 };
-                pitch += shift;
-            }
+        if config.octave_variation > 0.0 && rng.gen::<f32>() < config.octave_variation {
+            let shift = if rng.gen::<bool>() { 12 } else { -12 };
+            pitch += shift;
+        }
 
-            pitch = pitch.clamp(-36, 36);
+        pitch = pitch.clamp(-36, 36);
 
-            let mut start = note.start_time;
-            if config.rhythm_variation > 0.0 {
-                let max_shift = config.rhythm_variation * 0.05;
-                let shift = rng.gen_range(-max_shift..max_shift);
-                start = (start + shift).clamp(0.0, 1.0);
-            }
+        let mut start = note.start_time;
+        if config.rhythm_variation > 0.0 {
+            let max_shift = config.rhythm_variation * 0.05;
+            let shift = rng.gen_range(-max_shift..max_shift);
+            start = (start + shift).clamp(0.0, 1.0);
+        }
 
-            result.push(MelodicNote {
-                relative_pitch: pitch as i8,
-                start_time: start,
-                duration: note.duration,
-                velocity: note.velocity,
-            });
+        result.push(MelodicNote {
+            relative_pitch: pitch as i8,
+            start_time: start,
+            duration: note.duration,
+            velocity: note.velocity,
+        });
+    }
+
+    result.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap());
+    result
+}
+
+/// One bar of a transcribed phrase: literal beat probabilities plus the
+/// melody relative to the phrase root.
+#[derive(Clone, Debug)]
+pub struct PhraseBar {
+    pub beats: [f32; SLOT_COUNT],
+    pub melody: Vec<MelodicNote>,
+}
+
+/// Longest phrase call-and-response records, in bars.
+pub const MAX_PHRASE_BARS: usize = 8;
+/// Most notes a phrase can hold.
+pub const MAX_PHRASE_NOTES: usize = 256;
+
+impl PhraseBar {
+    /// An empty bar with room for a whole phrase's melody, so filling it
+    /// on the audio thread never allocates.
+    pub fn with_capacity() -> Self {
+        Self {
+            beats: [0.0; SLOT_COUNT],
+            melody: Vec::with_capacity(MAX_PHRASE_NOTES),
         }
+    }
 
-        result.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap());
-        result
+    pub fn clear(&mut self) {
+        self.beats = [0.0; SLOT_COUNT];
+        self.melody.clear();
     }
 }
 
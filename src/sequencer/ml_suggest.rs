@@ -62,37 +62,51 @@ pub fn reverse_flat_index(flat: usize) -> (BeatMode, usize, usize) {
     (BeatMode::Straight, 1, 0)
 }
 
+#[derive(Clone, Copy)]
 struct BeatSlotSpan {
     flat_index: usize,
     start: f32,
     end: f32,
 }
 
-fn build_slot_spans() -> Vec<BeatSlotSpan> {
-    let mut spans = Vec::with_capacity(SLOT_COUNT);
+fn build_slot_spans() -> [BeatSlotSpan; SLOT_COUNT] {
+    let mut spans = [BeatSlotSpan { flat_index: 0, start: 0.0, end: 0.0 }; SLOT_COUNT];
     let mut flat = 0;
     for &(mode, count) in &DIVISIONS {
         for index in 0..count {
             let (start, end) = DeviceParams::get_beat_time_span(mode, count, index);
-            spans.push(BeatSlotSpan { flat_index: flat, start, end });
+            spans[flat] = BeatSlotSpan { flat_index: flat, start, end };
             flat += 1;
         }
     }
     spans
 }
 
+/// Scales down overlapping slots so no point in the bar sums above 127.
+/// Works on fixed-size buffers only, so call-and-response can run it on the
+/// audio thread.
 pub fn normalize_beat_constraints(result: &mut [f32; SLOT_COUNT]) {
     let spans = build_slot_spans();
 
-    let mut time_points: Vec<f32> = Vec::with_capacity(SLOT_COUNT * 2);
+    let mut points = [0.0f32; SLOT_COUNT * 2];
+    let mut count = 0;
     for span in &spans {
         if result[span.flat_index] > 0.0 {
-            time_points.push(span.start);
-            time_points.push(span.end);
+            points[count] = span.start;
+            points[count + 1] = span.end;
+            count += 2;
         }
     }
-    time_points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-    time_points.dedup_by(|a, b| (*a - *b).abs() < 0.00001);
+    let points = &mut points[..count];
+    points.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mut unique = 0;
+    for i in 0..points.len() {
+        if unique == 0 || (points[i] - points[unique - 1]).abs() >= 0.00001 {
+            points[unique] = points[i];
+            unique += 1;
+        }
+    }
+    let time_points = &points[..unique];
 
     if time_points.len() < 2 {
         return;
@@ -100,23 +114,15 @@ pub fn normalize_beat_constraints(result: &mut [f32; SLOT_COUNT]) {
 
     let mut scale_factors = [1.0f32; SLOT_COUNT];
 
-    for i in 0..time_points.len() - 1 {
-        let mid = (time_points[i] + time_points[i + 1]) / 2.0;
-
-        let mut active_indices = Vec::new();
-        let mut total = 0.0f32;
-
-        for span in &spans {
-            if result[span.flat_index] > 0.0 && mid >= span.start && mid < span.end {
-                active_indices.push(span.flat_index);
-                total += result[span.flat_index];
-            }
-        }
+    for pair in time_points.windows(2) {
+        let mid = (pair[0] + pair[1]) / 2.0;
+        let active = |span: &&BeatSlotSpan| result[span.flat_index] > 0.0 && mid >= span.start && mid < span.end;
 
+        let total: f32 = spans.iter().filter(active).map(|span| result[span.flat_index]).sum();
         if total > 127.0 {
             let factor = 127.0 / total;
-            for &idx in &active_indices {
-                scale_factors[idx] = scale_factors[idx].min(factor);
+            for span in spans.iter().filter(active) {
+                scale_factors[span.flat_index] = scale_factors[span.flat_index].min(factor);
             }
         }
     }
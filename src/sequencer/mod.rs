@@ -6,20 +6,22 @@ pub mod ml_suggest;
 pub mod multi_bar;
 pub mod scales;
 pub mod styles;
+pub mod transpose;
 
 use std::sync::Arc;
+use crate::macro_controls::MacroRouter;
 use crate::params::{BeatMode, DeviceParams};
 use crate::sequencer::ml_suggest::flat_index;
 use rand::Rng;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
-use nih_plug::prelude::Param;
 pub use note_utils::{NotePool, midi_to_frequency};
 #[allow(unused_imports)]
 pub use scales::{Scale, StabilityPattern, OctaveRandomization, OctaveDirection};
 pub use styles::{StylePattern, StyleConfig, build_pitch_sequence};
 pub use multi_bar::MultiBarConfig;
-pub use melodic_engine::MelodicConfig;
+pub use melodic_engine::{MelodicConfig, PhraseBar, MAX_PHRASE_BARS, MAX_PHRASE_NOTES};
+pub use transpose::{TransposeQuantize, Transposer};
 
 #[derive(Clone, Debug)]
 pub struct BeatLinks {
@@ -128,6 +130,12 @@ pub struct Sequencer {
     beat_overrides: Option<[f32; 152]>,
     swing_override: Option<f32>,
     pub beat_links: BeatLinks,
+    phrase_bars: Vec<PhraseBar>,
+    /// Bars of `phrase_bars` in use; 0 without a phrase.
+    phrase_len: usize,
+    phrase_bar_idx: usize,
+    phrase_beats: Option<[f32; 152]>,
+    pub transposer: Transposer,
     rng: StdRng,
 }
 
@@ -167,6 +175,11 @@ impl Sequencer {
             beat_overrides: None,
             swing_override: None,
             beat_links: BeatLinks::new(),
+            phrase_bars: (0..MAX_PHRASE_BARS).map(|_| PhraseBar::with_capacity()).collect(),
+            phrase_len: 0,
+            phrase_bar_idx: 0,
+            phrase_beats: None,
+            transposer: Transposer::new(),
             rng: StdRng::from_entropy(),
         }
     }
@@ -196,6 +209,18 @@ impl Sequencer {
         }
     }
 
+    /// Position within the current bar in beats (0..4).
+    pub fn bar_position_beats(&self) -> f64 {
+        if self.bar_length_samples == 0 {
+            return 0.0;
+        }
+        self.bar_position_samples as f64 / self.bar_length_samples as f64 * 4.0
+    }
+
+    pub fn bar_length_samples(&self) -> usize {
+        self.bar_length_samples
+    }
+
     pub fn release_current_note(&mut self) {
         self.current_note = None;
     }
@@ -205,6 +230,14 @@ impl Sequencer {
         self.current_note.is_some()
     }
 
+    /// Moves playback back to the start of the current bar without
+    /// regenerating it.
+    pub fn rewind(&mut self) {
+        self.current_note = None;
+        self.bar_position_samples = 0;
+        self.next_event_idx = 0;
+    }
+
     pub fn reset(&mut self) {
         self.current_note = None;
         self.bar_position_samples = 0;
@@ -217,9 +250,44 @@ impl Sequencer {
         self.next_event_idx = 0;
         self.beat_overrides = None;
         self.swing_override = None;
+        self.phrase_bar_idx = 0;
+        self.phrase_beats = None;
+    }
+
+    /// Installs a transcribed phrase; its first bar plays from the next bar boundary.
+    /// The bars are copied into buffers allocated up front.
+    pub fn set_phrase(&mut self, bars: &[PhraseBar]) {
+        for (dst, src) in self.phrase_bars.iter_mut().zip(bars) {
+            dst.beats = src.beats;
+            dst.melody.clear();
+            dst.melody.extend_from_slice(&src.melody);
+        }
+        self.phrase_len = bars.len().min(MAX_PHRASE_BARS);
+        self.phrase_bar_idx = self.phrase_len.saturating_sub(1);
+        self.phrase_beats = None;
+        self.next_bar_ready = false;
+    }
+
+    pub fn clear_phrase(&mut self) {
+        if self.phrase_len > 0 {
+            self.phrase_len = 0;
+            self.phrase_bar_idx = 0;
+            self.phrase_beats = None;
+            self.next_bar_ready = false;
+        }
+    }
+
+    fn beat_probability(&self, params: &DeviceParams, macros: &MacroRouter, mode: BeatMode, count: usize, index: usize) -> f32 {
+        if let Some(ref overrides) = self.beat_overrides {
+            overrides[flat_index(mode, count, index)]
+        } else if let Some(ref phrase) = self.phrase_beats {
+            phrase[flat_index(mode, count, index)]
+        } else {
+            macros.plain(params.get_division_param(mode, count, index))
+        }
     }
 
-    fn hash_params(params: &DeviceParams) -> u64 {
+    fn hash_params(params: &DeviceParams, macros: &MacroRouter) -> u64 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
@@ -228,8 +296,7 @@ impl Sequencer {
         for mode in [BeatMode::Straight, BeatMode::Triplet, BeatMode::Dotted] {
             for (count, _) in DeviceParams::get_divisions_for_mode(mode).iter() {
                 for index in 0..*count {
-                    let param = params.get_division_param(mode, *count, index);
-                    let value = param.modulated_plain_value();
+                    let value = macros.plain(params.get_division_param(mode, *count, index));
                     let value_bits = value.to_bits();
                     value_bits.hash(&mut hasher);
                 }
@@ -237,7 +304,7 @@ impl Sequencer {
         }
 
         // Include swing in the hash so bars regenerate when swing changes
-        params.swing_amount.modulated_plain_value().to_bits().hash(&mut hasher);
+        macros.plain(&params.swing_amount).to_bits().hash(&mut hasher);
 
         hasher.finish()
     }
@@ -268,19 +335,14 @@ impl Sequencer {
     }
 
     /// Compute min/max normalized beat length from enabled beat divisions
-    fn get_enabled_length_range(&self, params: &DeviceParams) -> (f32, f32) {
+    fn get_enabled_length_range(&self, params: &DeviceParams, macros: &MacroRouter) -> (f32, f32) {
         let mut min_duration = f32::MAX;
         let mut max_duration = f32::MIN;
 
         for mode in [BeatMode::Straight, BeatMode::Triplet, BeatMode::Dotted] {
             for (count, _) in DeviceParams::get_divisions_for_mode(mode).iter() {
                 for index in 0..*count {
-                    let probability = if let Some(ref overrides) = self.beat_overrides {
-                        overrides[flat_index(mode, *count, index)]
-                    } else {
-                        let param = params.get_division_param(mode, *count, index);
-                        param.modulated_plain_value()
-                    };
+                    let probability = self.beat_probability(params, macros, mode, *count, index);
 
                     if probability > 0.0 {
                         let (start, end) = DeviceParams::get_beat_time_span(mode, *count, index);
@@ -333,6 +395,27 @@ impl Sequencer {
         }
     }
 
+    fn prepare_phrase_bar(&mut self, bar_idx: usize, rng: &mut impl Rng) {
+        let Some(bar) = self.phrase_bars[..self.phrase_len].get(bar_idx) else {
+            self.phrase_beats = None;
+            return;
+        };
+        let keep = 1.0 - self.melodic_config.note_drop_chance.clamp(0.0, 1.0);
+        let mut beats = bar.beats;
+        for (i, beat) in beats.iter_mut().enumerate() {
+            if i != 0 {
+                *beat *= keep;
+            }
+        }
+        self.phrase_beats = Some(beats);
+        self.current_melodic_notes = melodic_engine::vary_notes(&bar.melody, 0, &self.melodic_config, rng);
+    }
+
+    fn melodic_notes_active(&self) -> bool {
+        (self.melodic_config.enabled || self.phrase_len > 0)
+            && self.melodic_config.blend < 1.0
+    }
+
     fn nearest_melodic_midi_note(&self, start_time: f32, root: u8) -> Option<u8> {
         if self.current_melodic_notes.is_empty() {
             return None;
@@ -353,6 +436,7 @@ impl Sequencer {
         count: usize,
         index: usize,
         params: &DeviceParams,
+        macros: &MacroRouter,
         strength_range: (f32, f32),
         length_range: (f32, f32),
         rng: &mut impl Rng,
@@ -364,7 +448,7 @@ impl Sequencer {
 
         let strength = self.get_strength_at_position(start_time);
 
-        let note_length_percent = params.note_length_percent.modulated_plain_value();
+        let note_length_percent = macros.plain(&params.note_length_percent);
         let base_multiplier = note_length_percent / 100.0;
 
         let length_mod_multiplier = params.calculate_length_multiplier(strength, rng);
@@ -377,14 +461,13 @@ impl Sequencer {
         let shifted_time = (start_time + position_shift).clamp(0.0, 1.0);
 
         let swing_amount = self.swing_override
-            .unwrap_or_else(|| params.swing_amount.modulated_plain_value());
+            .unwrap_or_else(|| macros.plain(&params.swing_amount));
         let swung_start_time = DeviceParams::apply_swing(shifted_time, swing_amount);
         let sample_position = (swung_start_time * total_samples as f32) as usize;
 
         let length_value = (capped_multiplier / 2.0).clamp(0.0, 1.0);
 
-        let midi_note = if self.melodic_config.enabled
-            && self.melodic_config.blend < 1.0
+        let midi_note = if self.melodic_notes_active()
             && rng.gen::<f32>() > self.melodic_config.blend
         {
             let root = self.note_pool.root_note.unwrap_or(48);
@@ -428,14 +511,14 @@ impl Sequencer {
         }
     }
 
-    fn generate_bar_into(&mut self, params: &DeviceParams) {
+    fn generate_bar_into(&mut self, params: &DeviceParams, macros: &MacroRouter) {
         self.scratch_events.clear();
         self.scratch_start_times.clear();
         self.scratch_lost_beats.clear();
         let mut rng = StdRng::seed_from_u64(self.rng.gen());
 
         let strength_range = self.get_strength_range();
-        let length_range = self.get_enabled_length_range(params);
+        let length_range = self.get_enabled_length_range(params, macros);
 
         for mode in [BeatMode::Straight, BeatMode::Triplet, BeatMode::Dotted] {
             for (count, _) in DeviceParams::get_divisions_for_mode(mode).iter() {
@@ -469,12 +552,7 @@ impl Sequencer {
                         let (start, _end) = DeviceParams::get_beat_time_span(mode, *count, index);
 
                         if (start - start_time).abs() < 0.0001 {
-                            let probability = if let Some(ref overrides) = self.beat_overrides {
-                                overrides[flat_index(mode, *count, index)]
-                            } else {
-                                let param = params.get_division_param(mode, *count, index);
-                                param.modulated_plain_value()
-                            };
+                            let probability = self.beat_probability(params, macros, mode, *count, index);
 
                             if probability > 0.0 {
                                 self.scratch_candidates.push((mode, *count, index, probability));
@@ -509,7 +587,7 @@ impl Sequencer {
                         cumulative += probability;
                         if random_value < cumulative {
                             let event = self.create_note_event(
-                                mode, count, index, params,
+                                mode, count, index, params, macros,
                                 strength_range, length_range, &mut rng,
                             );
                             self.scratch_events.push(event);
@@ -547,7 +625,7 @@ impl Sequencer {
                 }
                 let (mode, count, index) = ml_suggest::reverse_flat_index(forced_fi as usize);
                 let event = self.create_note_event(
-                    mode, count, index, params,
+                    mode, count, index, params, macros,
                     strength_range, length_range, &mut rng,
                 );
                 self.scratch_events.push(event);
@@ -653,7 +731,9 @@ impl Sequencer {
         }
     }
 
-    pub fn prepare(&mut self, block_size: usize, params: &DeviceParams) {
+    /// Pattern parameters are read through `macros` so macro offsets shape
+    /// the generated bars.
+    pub fn prepare(&mut self, block_size: usize, params: &DeviceParams, macros: &MacroRouter) {
         if self.current_bar.is_empty() {
             if let Some(ref config) = self.multi_bar {
                 if config.enabled && config.bar_count > 1 {
@@ -662,14 +742,15 @@ impl Sequencer {
             }
             let mut rng = StdRng::seed_from_u64(self.rng.gen());
             self.prepare_melodic_notes(&mut rng);
-            self.generate_bar_into(params);
+            self.prepare_phrase_bar(self.phrase_bar_idx, &mut rng);
+            self.generate_bar_into(params, macros);
             std::mem::swap(&mut self.current_bar, &mut self.scratch_events);
             self.next_event_idx = 0;
-            self.params_hash = Self::hash_params(params);
+            self.params_hash = Self::hash_params(params, macros);
             return;
         }
 
-        let new_hash = Self::hash_params(params);
+        let new_hash = Self::hash_params(params, macros);
         if new_hash != self.params_hash {
             self.params_hash = new_hash;
             self.next_bar_ready = false;
@@ -688,7 +769,11 @@ impl Sequencer {
             }
             let mut rng = StdRng::seed_from_u64(self.rng.gen());
             self.prepare_melodic_notes(&mut rng);
-            self.generate_bar_into(params);
+            if self.phrase_len > 0 {
+                let next_phrase_bar = (self.phrase_bar_idx + 1) % self.phrase_len;
+                self.prepare_phrase_bar(next_phrase_bar, &mut rng);
+            }
+            self.generate_bar_into(params, macros);
             std::mem::swap(&mut self.next_bar, &mut self.scratch_events);
             self.next_bar_ready = true;
         }
@@ -711,28 +796,46 @@ impl Sequencer {
         let mut velocity = 100_u8;
         let mut midi_note = 48_u8;
 
+        let beat_length = (self.bar_length_samples / 4).max(1);
+        if self.bar_position_samples == 0 {
+            self.transposer.on_boundary(TransposeQuantize::Bar);
+        } else if self.bar_position_samples % beat_length == 0 {
+            self.transposer.on_boundary(TransposeQuantize::Beat);
+        }
+        let gate_open = self.transposer.gate_open();
+
         while self.next_event_idx < self.current_bar.len() {
             let event = &self.current_bar[self.next_event_idx];
             if event.sample_position > self.bar_position_samples {
                 break;
             }
             if event.sample_position == self.bar_position_samples {
+                self.next_event_idx += 1;
+                if !gate_open {
+                    break;
+                }
                 should_trigger = true;
-                frequency = event.frequency;
                 velocity = event.velocity;
                 midi_note = event.midi_note;
+                frequency = event.frequency;
+                if self.transposer.is_active() {
+                    let transposed = self.transposer.transpose(midi_note, &self.note_pool);
+                    if transposed != midi_note {
+                        midi_note = transposed;
+                        frequency = midi_to_frequency(transposed) as f64;
+                    }
+                }
                 self.current_note = Some((
                     event.sample_position,
                     event.sample_position + event.duration_samples,
                 ));
-                self.next_event_idx += 1;
                 break;
             }
             self.next_event_idx += 1;
         }
 
         if let Some((_start_pos, end_pos)) = self.current_note {
-            if self.bar_position_samples >= end_pos {
+            if self.bar_position_samples >= end_pos || !gate_open {
                 should_release = true;
                 self.current_note = None;
             }
@@ -750,6 +853,10 @@ impl Sequencer {
             self.next_bar_ready = false;
             self.next_event_idx = 0;
 
+            if self.phrase_len > 0 {
+                self.phrase_bar_idx = (self.phrase_bar_idx + 1) % self.phrase_len;
+            }
+
             if let Some(ref config) = self.multi_bar {
                 if config.enabled && config.bar_count > 1 {
                     self.bar_counter += 1;
@@ -0,0 +1,269 @@
+use super::note_utils::NotePool;
+use super::scales::Scale;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum TransposeQuantize {
+    Immediate,
+    #[default]
+    Beat,
+    Bar,
+}
+
+impl TransposeQuantize {
+    pub fn all() -> [TransposeQuantize; 3] {
+        [
+            TransposeQuantize::Immediate,
+            TransposeQuantize::Beat,
+            TransposeQuantize::Bar,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransposeQuantize::Immediate => "Immediate",
+            TransposeQuantize::Beat => "Beat",
+            TransposeQuantize::Bar => "Bar",
+        }
+    }
+
+    pub fn from_index(i: u8) -> Self {
+        match i {
+            0 => TransposeQuantize::Immediate,
+            2 => TransposeQuantize::Bar,
+            _ => TransposeQuantize::Beat,
+        }
+    }
+
+    pub fn to_index(self) -> u8 {
+        match self {
+            TransposeQuantize::Immediate => 0,
+            TransposeQuantize::Beat => 1,
+            TransposeQuantize::Bar => 2,
+        }
+    }
+}
+
+/// Live transposition of sequenced notes by a played key. The key replaces the
+/// note pool root; in scale-aware mode the shift is counted in scale degrees so
+/// the pattern stays in key.
+pub struct Transposer {
+    pub scale_aware: bool,
+    pub quantize: TransposeQuantize,
+    pub gate: bool,
+    pub scale: Scale,
+    pending_key: Option<u8>,
+    active_key: Option<u8>,
+    key_held: bool,
+}
+
+impl Transposer {
+    pub fn new() -> Self {
+        Self {
+            scale_aware: false,
+            quantize: TransposeQuantize::Beat,
+            gate: false,
+            scale: Scale::default(),
+            pending_key: None,
+            active_key: None,
+            key_held: false,
+        }
+    }
+
+    /// Sets the last held key. `None` means all keys were released: the last
+    /// key stays latched, but the gate closes.
+    pub fn set_key(&mut self, key: Option<u8>) {
+        self.key_held = key.is_some();
+        if key.is_some() {
+            self.pending_key = key;
+            if self.active_key.is_none() || self.quantize == TransposeQuantize::Immediate {
+                self.active_key = key;
+            }
+        }
+    }
+
+    /// Applies the pending key if `boundary` is at least as coarse as the
+    /// quantize setting.
+    pub fn on_boundary(&mut self, boundary: TransposeQuantize) {
+        if boundary >= self.quantize {
+            self.active_key = self.pending_key;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pending_key = None;
+        self.active_key = None;
+        self.key_held = false;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_key.is_some() || self.gate
+    }
+
+    pub fn gate_open(&self) -> bool {
+        !self.gate || self.key_held
+    }
+
+    pub fn transpose(&self, note: u8, pool: &NotePool) -> u8 {
+        let Some(key) = self.active_key else {
+            return note;
+        };
+        let root = pool.root_note.unwrap_or(48);
+        let shifted = if self.scale_aware {
+            let (intervals, len) = self.degree_intervals(pool, root);
+            let steps = degree_steps(key as i16 - root as i16, &intervals[..len]);
+            shift_by_degrees(note as i16 - root as i16, steps, &intervals[..len]) + root as i16
+        } else {
+            note as i16 + key as i16 - root as i16
+        };
+        shifted.clamp(0, 127) as u8
+    }
+
+    /// Absolute pitch classes of the current scale as a bitmask (bit 0 = C).
+    pub fn pitch_class_mask(&self, pool: &NotePool) -> u16 {
+        let root = pool.root_note.unwrap_or(48);
+        let (intervals, len) = self.degree_intervals(pool, root);
+        intervals[..len]
+            .iter()
+            .fold(0u16, |mask, &i| mask | 1 << ((root + i) % 12))
+    }
+
+    /// Scale intervals from the root; the Custom scale falls back to the pitch
+    /// classes present in the pool.
+    fn degree_intervals(&self, pool: &NotePool, root: u8) -> ([u8; 12], usize) {
+        let mut present = [false; 12];
+        present[0] = true;
+        let intervals = self.scale.intervals();
+        if intervals.is_empty() {
+            for n in &pool.notes {
+                if n.chance > 0.0 {
+                    present[(n.effective_midi_note() as i16 - root as i16).rem_euclid(12) as usize] = true;
+                }
+            }
+        } else {
+            for &i in intervals {
+                present[i as usize % 12] = true;
+            }
+        }
+
+        let mut out = [0u8; 12];
+        let mut len = 0;
+        for (pc, &on) in present.iter().enumerate() {
+            if on {
+                out[len] = pc as u8;
+                len += 1;
+            }
+        }
+        (out, len)
+    }
+}
+
+/// Number of scale degrees from the root to the degree nearest `offset`.
+fn degree_steps(offset: i16, intervals: &[u8]) -> i16 {
+    let n = intervals.len() as i16;
+    let octave = offset.div_euclid(12);
+    let pc = offset.rem_euclid(12);
+
+    let mut best = 0i16;
+    let mut best_dist = i16::MAX;
+    for (idx, &i) in intervals.iter().enumerate() {
+        let dist = (pc - i as i16).abs();
+        if dist < best_dist {
+            best_dist = dist;
+            best = idx as i16;
+        }
+    }
+    // The next octave's root may be closer than the top degree.
+    if 12 - pc < best_dist {
+        best = n;
+    }
+    octave * n + best
+}
+
+/// Moves `offset` (semitones from the root) by `steps` scale degrees; notes
+/// outside the scale keep their distance from the degree below them.
+fn shift_by_degrees(offset: i16, steps: i16, intervals: &[u8]) -> i16 {
+    let n = intervals.len() as i16;
+    let octave = offset.div_euclid(12);
+    let pc = offset.rem_euclid(12);
+
+    let idx = intervals.iter().rposition(|&i| i as i16 <= pc).unwrap_or(0) as i16;
+    let remainder = pc - intervals[idx as usize] as i16;
+
+    let target = idx + steps;
+    let target_octave = octave + target.div_euclid(n);
+    let target_idx = target.rem_euclid(n) as usize;
+    target_octave * 12 + intervals[target_idx] as i16 + remainder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_at(root: u8) -> NotePool {
+        let mut pool = NotePool::new();
+        pool.set_root_note(root);
+        pool
+    }
+
+    #[test]
+    fn chromatic_shift_follows_key() {
+        let mut t = Transposer::new();
+        t.quantize = TransposeQuantize::Immediate;
+        let pool = pool_at(48);
+        assert_eq!(t.transpose(52, &pool), 52);
+        t.set_key(Some(53));
+        assert_eq!(t.transpose(48, &pool), 53);
+        assert_eq!(t.transpose(55, &pool), 60);
+    }
+
+    #[test]
+    fn scale_aware_shift_stays_in_key() {
+        let mut t = Transposer::new();
+        t.quantize = TransposeQuantize::Immediate;
+        t.scale_aware = true;
+        t.scale = Scale::Major;
+        let pool = pool_at(48);
+        // D is one degree up: C-E-G becomes D-F-A.
+        t.set_key(Some(50));
+        assert_eq!(t.transpose(48, &pool), 50);
+        assert_eq!(t.transpose(52, &pool), 53);
+        assert_eq!(t.transpose(55, &pool), 57);
+        // B up one degree wraps to the next octave's C.
+        assert_eq!(t.transpose(59, &pool), 60);
+    }
+
+    #[test]
+    fn quantized_key_waits_for_boundary() {
+        let mut t = Transposer::new();
+        t.quantize = TransposeQuantize::Bar;
+        let pool = pool_at(48);
+        t.set_key(Some(50));
+        t.on_boundary(TransposeQuantize::Bar);
+        t.set_key(Some(55));
+        assert_eq!(t.transpose(48, &pool), 50);
+        t.on_boundary(TransposeQuantize::Beat);
+        assert_eq!(t.transpose(48, &pool), 50);
+        t.on_boundary(TransposeQuantize::Bar);
+        assert_eq!(t.transpose(48, &pool), 55);
+    }
+
+    #[test]
+    fn pitch_class_mask_is_rooted_at_pool_root() {
+        let mut t = Transposer::new();
+        t.scale = Scale::Major;
+        // D major: D E F# G A B C#
+        let expected = [2, 4, 6, 7, 9, 11, 1].iter().fold(0u16, |m, &pc| m | 1 << pc);
+        assert_eq!(t.pitch_class_mask(&pool_at(50)), expected);
+    }
+
+    #[test]
+    fn gate_follows_held_key() {
+        let mut t = Transposer::new();
+        t.gate = true;
+        assert!(!t.gate_open());
+        t.set_key(Some(60));
+        assert!(t.gate_open());
+        t.set_key(None);
+        assert!(!t.gate_open());
+    }
+}
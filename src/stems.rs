@@ -0,0 +1,183 @@
+//! Stem outputs for the "Stereo + Stems" layout: one stereo aux port per
+//! oscillator, the sub, and the reverb and looper returns, so a DAW can mix
+//! them on separate channels. Oscillators are tapped before the filter, the
+//! sub after its master HPF, and each return is the wet signal its effect
+//! added to the bus. Stems are delayed by the compressor and limiter
+//! lookahead so they stay aligned with the main output. The master volume
+//! option scales them like the main output; the master FX chain (FX
+//! modules, master HPF, compressor and limiter) is never applied, so they add
+//! up to the main output only while that chain is bypassed.
+
+pub const STEM_COUNT: usize = 6;
+/// Aux port names, in `Stem` order.
+pub const STEM_NAMES: [&str; STEM_COUNT] = ["VPS", "PLL", "SAW", "Sub", "Reverb", "Looper"];
+
+/// Longest master-chain latency the stems can follow.
+const MAX_LATENCY_SECONDS: f32 = 0.02;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stem {
+    Vps,
+    Pll,
+    Saw,
+    Sub,
+    Reverb,
+    Looper,
+}
+
+impl Stem {
+    pub const ALL: [Stem; STEM_COUNT] = [Stem::Vps, Stem::Pll, Stem::Saw, Stem::Sub, Stem::Reverb, Stem::Looper];
+}
+
+struct DelayLine {
+    buf: Vec<f32>,
+    pos: usize,
+}
+
+impl DelayLine {
+    fn process(&mut self, x: f32, delay: usize) -> f32 {
+        let len = self.buf.len();
+        self.buf[self.pos] = x;
+        let out = self.buf[(self.pos + len - delay.min(len - 1)) % len];
+        self.pos = (self.pos + 1) % len;
+        out
+    }
+}
+
+#[derive(Default)]
+pub struct StemTaps {
+    l: [Vec<f32>; STEM_COUNT],
+    r: [Vec<f32>; STEM_COUNT],
+    /// Per-sample master volume of the current block, for post-master stems.
+    pub gain: Vec<f32>,
+    delays: Vec<DelayLine>,
+}
+
+impl StemTaps {
+    pub fn resize(&mut self, max_block: usize, sample_rate: f32) {
+        for buf in self.l.iter_mut().chain(self.r.iter_mut()) {
+            buf.resize(max_block, 0.0);
+        }
+        self.gain.resize(max_block, 1.0);
+        let len = (sample_rate * MAX_LATENCY_SECONDS) as usize + 1;
+        if self.delays.first().map(|d| d.buf.len()) != Some(len) {
+            self.delays = (0..STEM_COUNT * 2).map(|_| DelayLine { buf: vec![0.0; len], pos: 0 }).collect();
+        }
+    }
+
+    pub fn capture(&mut self, stem: Stem, l: &[f32], r: &[f32], num_samples: usize) {
+        self.l[stem as usize][..num_samples].copy_from_slice(&l[..num_samples]);
+        self.r[stem as usize][..num_samples].copy_from_slice(&r[..num_samples]);
+    }
+
+    /// Slews `volume` toward `target` across the block, scales the main
+    /// output by it and keeps the per-sample gain for the stems.
+    pub fn apply_master_volume(&mut self, l: &mut [f32], r: &mut [f32], volume: &mut f32, target: f32, slew_coeff: f32) {
+        for ((l, r), gain) in l.iter_mut().zip(r.iter_mut()).zip(self.gain.iter_mut()) {
+            *volume += (target - *volume) * slew_coeff;
+            *gain = *volume;
+            *l *= *volume;
+            *r *= *volume;
+        }
+    }
+
+    /// Writes one stem to its aux port, scaled by the master volume when
+    /// `master_volume` is set and delayed by `latency` samples. The master FX
+    /// chain is never applied.
+    pub fn write(&mut self, stem: Stem, channels: &mut [&mut [f32]], num_samples: usize, master_volume: bool, latency: usize) {
+        let s = stem as usize;
+        for (ch, out) in channels.iter_mut().enumerate().take(2) {
+            let src = if ch == 0 { &self.l[s] } else { &self.r[s] };
+            let line = &mut self.delays[s * 2 + ch];
+            for i in 0..num_samples.min(out.len()) {
+                let x = if master_volume { src[i] * self.gain[i] } else { src[i] };
+                out[i] = line.process(x, latency);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn taps() -> StemTaps {
+        let mut taps = StemTaps::default();
+        taps.resize(8, 1000.0);
+        taps
+    }
+
+    #[test]
+    fn stems_follow_master_latency() {
+        let mut taps = taps();
+        let impulse = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        taps.capture(Stem::Pll, &impulse, &impulse, 8);
+
+        let (mut l, mut r) = ([0.0; 8], [0.0; 8]);
+        taps.write(Stem::Pll, &mut [&mut l[..], &mut r[..]], 8, false, 3);
+        assert_eq!(l, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(l, r);
+    }
+
+    #[test]
+    fn master_volume_scales_stems() {
+        let mut taps = taps();
+        taps.capture(Stem::Sub, &[0.5; 8], &[0.5; 8], 8);
+        taps.gain[..8].copy_from_slice(&[0.0, 0.25, 0.5, 0.75, 1.0, 1.0, 1.0, 1.0]);
+
+        let (mut l, mut r) = ([0.0; 8], [0.0; 8]);
+        taps.write(Stem::Sub, &mut [&mut l[..], &mut r[..]], 8, true, 0);
+        assert_eq!(l[..4], [0.0, 0.125, 0.25, 0.375]);
+        taps.write(Stem::Sub, &mut [&mut l[..], &mut r[..]], 8, false, 0);
+        assert_eq!(r, [0.5; 8]);
+    }
+
+    #[test]
+    fn stems_sum_to_the_main_output_with_the_chain_bypassed() {
+        let mut taps = taps();
+        let sources: [(Stem, [f32; 8]); 3] = [
+            (Stem::Vps, [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8]),
+            (Stem::Saw, [-0.3; 8]),
+            (Stem::Sub, [0.25, -0.25, 0.25, -0.25, 0.25, -0.25, 0.25, -0.25]),
+        ];
+
+        // The main output as `process` builds it: the sources summed, then
+        // the slewing master volume. With the compressor and limiter off
+        // there is no lookahead to follow.
+        let (mut main_l, mut main_r) = ([0.0; 8], [0.0; 8]);
+        for (stem, x) in &sources {
+            taps.capture(*stem, x, x, 8);
+            for i in 0..8 {
+                main_l[i] += x[i];
+                main_r[i] += x[i];
+            }
+        }
+        let mut volume = 1.0;
+        taps.apply_master_volume(&mut main_l, &mut main_r, &mut volume, 0.2, 0.3);
+
+        let mut sum = [0.0; 8];
+        for (stem, _) in &sources {
+            let (mut l, mut r) = ([0.0; 8], [0.0; 8]);
+            taps.write(*stem, &mut [&mut l[..], &mut r[..]], 8, true, 0);
+            for (sum, l) in sum.iter_mut().zip(l) {
+                *sum += l;
+            }
+        }
+        assert!(volume < 0.3);
+        for (sum, main) in sum.iter().zip(main_l) {
+            assert!((sum - main).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn latency_is_capped() {
+        let mut taps = taps();
+        taps.capture(Stem::Saw, &[1.0; 8], &[1.0; 8], 8);
+        // 20 ms at 1 kHz: anything longer reads the oldest sample kept
+        let (mut l, mut r) = ([0.0; 8], [0.0; 8]);
+        for _ in 0..4 {
+            taps.write(Stem::Saw, &mut [&mut l[..], &mut r[..]], 8, false, 1000);
+        }
+        assert_eq!(l[7], 1.0);
+    }
+}
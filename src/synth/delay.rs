@@ -0,0 +1,404 @@
+use std::f64::consts::PI;
+
+const MAX_DELAY_SECONDS: f64 = 4.0;
+const MIN_DELAY_MS: f64 = 1.0;
+/// Longest left tap, leaving room in the line for a 1.5× right tap and the
+/// wow/flutter swing.
+pub const MAX_DELAY_MS: f64 = MAX_DELAY_SECONDS * 1000.0 / 1.5 - 20.0;
+const TIME_SMOOTH_SECONDS: f64 = 0.12;
+const TAPE_TIME_SMOOTH_SECONDS: f64 = 0.6;
+const DUCK_ATTACK_MS: f64 = 5.0;
+const DUCK_RELEASE_MS: f64 = 250.0;
+const WOW_RATE_HZ: f64 = 0.6;
+const FLUTTER_RATE_HZ: f64 = 7.3;
+const DENORMAL_GUARD: f64 = 1e-18;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DelayMode {
+    Stereo,
+    PingPong,
+    Tape,
+}
+
+impl DelayMode {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::PingPong,
+            2 => Self::Tape,
+            _ => Self::Stereo,
+        }
+    }
+}
+
+struct DelayBuffer {
+    buffer: Vec<f64>,
+    write_pos: usize,
+}
+
+impl DelayBuffer {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(4)],
+            write_pos: 0,
+        }
+    }
+
+    fn resize(&mut self, length: usize) {
+        self.buffer.clear();
+        self.buffer.resize(length.max(4), 0.0);
+        self.write_pos = 0;
+    }
+
+    #[inline]
+    fn write(&mut self, sample: f64) {
+        self.buffer[self.write_pos] = sample;
+        self.write_pos += 1;
+        if self.write_pos >= self.buffer.len() {
+            self.write_pos = 0;
+        }
+    }
+
+    #[inline]
+    fn read(&self, delay: usize) -> f64 {
+        let len = self.buffer.len();
+        let d = delay.min(len - 1);
+        self.buffer[(self.write_pos + len - d - 1) % len]
+    }
+
+    /// Cubic (Hermite) read `delay` samples behind the write head.
+    #[inline]
+    fn read_interpolated(&self, delay: f64) -> f64 {
+        let d = delay.clamp(1.0, (self.buffer.len() - 3) as f64);
+        let d_int = d as usize;
+        let frac = d - d_int as f64;
+
+        let ym1 = self.read(d_int - 1);
+        let y0 = self.read(d_int);
+        let y1 = self.read(d_int + 1);
+        let y2 = self.read(d_int + 2);
+
+        let c1 = 0.5 * (y1 - ym1);
+        let c2 = ym1 - 2.5 * y0 + 2.0 * y1 - 0.5 * y2;
+        let c3 = 0.5 * (y2 - ym1) + 1.5 * (y0 - y1);
+        ((c3 * frac + c2) * frac + c1) * frac + y0
+    }
+
+    fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.write_pos = 0;
+    }
+}
+
+#[derive(Clone, Copy)]
+struct OnePole {
+    state: f64,
+    coeff: f64,
+}
+
+impl OnePole {
+    fn new() -> Self {
+        Self { state: 0.0, coeff: 0.0 }
+    }
+
+    fn set_freq(&mut self, freq: f64, sample_rate: f64) {
+        let w = (2.0 * PI * freq / sample_rate).min(PI * 0.99);
+        self.coeff = (-w).exp();
+    }
+
+    #[inline]
+    fn tick_lpf(&mut self, input: f64) -> f64 {
+        self.state = input + self.coeff * (self.state - input);
+        self.state
+    }
+
+    #[inline]
+    fn tick_hpf(&mut self, input: f64) -> f64 {
+        input - self.tick_lpf(input)
+    }
+
+    fn reset(&mut self) {
+        self.state = 0.0;
+    }
+}
+
+/// Soft saturation in the feedback path; unity gain for small signals so the
+/// repeats only thicken as they build up.
+#[inline]
+fn saturate(x: f64, amount: f64) -> f64 {
+    if amount < 0.001 {
+        return x;
+    }
+    let drive = 1.0 + amount * 4.0;
+    let shaped = (x * drive).tanh() / drive;
+    x + amount * (shaped - x)
+}
+
+/// `time_ms` is the left (or only) tap; `spread` stretches the right tap
+/// by up to 50% in Stereo and Tape modes.
+pub struct DelayParams {
+    pub mode: DelayMode,
+    pub mix: f64,
+    pub time_ms: f64,
+    pub spread: f64,
+    pub feedback: f64,
+    pub lpf_hz: f64,
+    pub hpf_hz: f64,
+    pub saturation: f64,
+    pub mod_rate: f64,
+    pub mod_depth: f64,
+    pub ducking: f64,
+}
+
+/// Tempo-syncable stereo delay fed from its own send bus. The wet signal is
+/// added on top of the main signal, like the reverb.
+pub struct StereoDelay {
+    sample_rate: f64,
+
+    mode: DelayMode,
+    mix: f64,
+    time_samples: f64,
+    spread: f64,
+    feedback: f64,
+    saturation: f64,
+    mod_rate: f64,
+    mod_depth: f64,
+    ducking: f64,
+
+    line_l: DelayBuffer,
+    line_r: DelayBuffer,
+    time_smooth_l: f64,
+    time_smooth_r: f64,
+
+    fb_lpf_l: OnePole,
+    fb_lpf_r: OnePole,
+    fb_hpf_l: OnePole,
+    fb_hpf_r: OnePole,
+    fb_l: f64,
+    fb_r: f64,
+
+    mod_phase: f64,
+    wow_phase: f64,
+    flutter_phase: f64,
+
+    duck_envelope: f64,
+    mix_smooth: f64,
+    smooth_coeff: f64,
+}
+
+impl StereoDelay {
+    pub fn new(sample_rate: f32) -> Self {
+        let sr = sample_rate as f64;
+        let max_len = (MAX_DELAY_SECONDS * sr) as usize + 8;
+        Self {
+            sample_rate: sr,
+            mode: DelayMode::Stereo,
+            mix: 0.0,
+            time_samples: 0.375 * sr,
+            spread: 0.0,
+            feedback: 0.4,
+            saturation: 0.0,
+            mod_rate: 0.5,
+            mod_depth: 0.0,
+            ducking: 0.0,
+            line_l: DelayBuffer::new(max_len),
+            line_r: DelayBuffer::new(max_len),
+            time_smooth_l: 0.375 * sr,
+            time_smooth_r: 0.375 * sr,
+            fb_lpf_l: OnePole::new(),
+            fb_lpf_r: OnePole::new(),
+            fb_hpf_l: OnePole::new(),
+            fb_hpf_r: OnePole::new(),
+            fb_l: 0.0,
+            fb_r: 0.0,
+            mod_phase: 0.0,
+            wow_phase: 0.0,
+            flutter_phase: 0.0,
+            duck_envelope: 0.0,
+            mix_smooth: 0.0,
+            smooth_coeff: 1.0 - (-1.0 / (0.02 * sr)).exp(),
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        let sr = sample_rate as f64;
+        let max_len = (MAX_DELAY_SECONDS * sr) as usize + 8;
+        self.time_samples *= sr / self.sample_rate;
+        self.sample_rate = sr;
+        self.line_l.resize(max_len);
+        self.line_r.resize(max_len);
+        self.smooth_coeff = 1.0 - (-1.0 / (0.02 * sr)).exp();
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.line_l.clear();
+        self.line_r.clear();
+        self.fb_lpf_l.reset();
+        self.fb_lpf_r.reset();
+        self.fb_hpf_l.reset();
+        self.fb_hpf_r.reset();
+        self.fb_l = 0.0;
+        self.fb_r = 0.0;
+        self.time_smooth_l = self.time_samples;
+        self.time_smooth_r = self.time_samples;
+        self.duck_envelope = 0.0;
+        self.mix_smooth = 0.0;
+    }
+
+    pub fn set_params(&mut self, p: &DelayParams) {
+        self.mode = p.mode;
+        self.mix = p.mix.clamp(0.0, 1.0);
+        self.time_samples = p.time_ms.clamp(MIN_DELAY_MS, MAX_DELAY_MS) * 0.001 * self.sample_rate;
+        self.spread = p.spread.clamp(0.0, 1.0);
+        self.feedback = p.feedback.clamp(0.0, 1.0);
+        self.saturation = p.saturation.clamp(0.0, 1.0);
+        self.mod_rate = p.mod_rate.clamp(0.0, 1.0);
+        self.mod_depth = p.mod_depth.clamp(0.0, 1.0);
+        self.ducking = p.ducking.clamp(0.0, 1.0);
+
+        // Tape darkens each repeat a little more than the plain filters.
+        let lpf = if p.mode == DelayMode::Tape { p.lpf_hz * 0.6 } else { p.lpf_hz };
+        self.fb_lpf_l.set_freq(lpf, self.sample_rate);
+        self.fb_lpf_r.set_freq(lpf, self.sample_rate);
+        self.fb_hpf_l.set_freq(p.hpf_hz, self.sample_rate);
+        self.fb_hpf_r.set_freq(p.hpf_hz, self.sample_rate);
+    }
+
+    pub fn process_block(
+        &mut self,
+        main_l: &mut [f32],
+        main_r: &mut [f32],
+        send_l: &[f32],
+        send_r: &[f32],
+    ) {
+        if self.mix < 0.0001 && self.mix_smooth < 0.0001 {
+            return;
+        }
+
+        let israte = 1.0 / self.sample_rate;
+        let tape = self.mode == DelayMode::Tape;
+        let ping_pong = self.mode == DelayMode::PingPong;
+
+        let time_tau = if tape { TAPE_TIME_SMOOTH_SECONDS } else { TIME_SMOOTH_SECONDS };
+        let time_coeff = 1.0 - (-1.0 / (time_tau * self.sample_rate)).exp();
+        let target_l = self.time_samples;
+        let target_r = if ping_pong {
+            self.time_samples
+        } else {
+            self.time_samples * (1.0 + self.spread * 0.5)
+        };
+
+        let mod_hz = 0.05 + self.mod_rate * self.mod_rate * 5.95;
+        let mod_samples = self.mod_depth * self.mod_depth * 0.004 * self.sample_rate;
+        let wow_samples = if tape { 0.0015 * self.sample_rate } else { 0.0 };
+        let flutter_samples = if tape { 0.0002 * self.sample_rate } else { 0.0 };
+        let sat = if tape { self.saturation.max(0.3) } else { self.saturation };
+
+        let duck_attack = (-1.0 / (DUCK_ATTACK_MS * 0.001 * self.sample_rate)).exp();
+        let duck_release = (-1.0 / (DUCK_RELEASE_MS * 0.001 * self.sample_rate)).exp();
+        let sc = self.smooth_coeff;
+
+        for i in 0..main_l.len().min(send_l.len()) {
+            self.mix_smooth += (self.mix - self.mix_smooth) * sc;
+            self.time_smooth_l += (target_l - self.time_smooth_l) * time_coeff;
+            self.time_smooth_r += (target_r - self.time_smooth_r) * time_coeff;
+
+            self.mod_phase += mod_hz * israte;
+            if self.mod_phase >= 1.0 { self.mod_phase -= 1.0; }
+            self.wow_phase += WOW_RATE_HZ * israte;
+            if self.wow_phase >= 1.0 { self.wow_phase -= 1.0; }
+            self.flutter_phase += FLUTTER_RATE_HZ * israte;
+            if self.flutter_phase >= 1.0 { self.flutter_phase -= 1.0; }
+
+            // Quadrature LFO so the two heads drift apart rather than together.
+            let mod_l = (self.mod_phase * 2.0 * PI).sin() * mod_samples;
+            let mod_r = (self.mod_phase * 2.0 * PI).cos() * mod_samples;
+            let tape_mod = (self.wow_phase * 2.0 * PI).sin() * wow_samples
+                + (self.flutter_phase * 2.0 * PI).sin() * flutter_samples;
+
+            let wet_l = self.line_l.read_interpolated(self.time_smooth_l + mod_l + tape_mod + mod_samples);
+            let wet_r = self.line_r.read_interpolated(self.time_smooth_r + mod_r + tape_mod + mod_samples);
+
+            let shaped_l = saturate(self.fb_hpf_l.tick_hpf(self.fb_lpf_l.tick_lpf(wet_l)), sat);
+            let shaped_r = saturate(self.fb_hpf_r.tick_hpf(self.fb_lpf_r.tick_lpf(wet_r)), sat);
+            self.fb_l = shaped_l * self.feedback;
+            self.fb_r = shaped_r * self.feedback;
+
+            let in_l = send_l[i] as f64;
+            let in_r = send_r[i] as f64;
+            if ping_pong {
+                self.line_l.write((in_l + in_r) * 0.5 + self.fb_r + DENORMAL_GUARD);
+                self.line_r.write(self.fb_l - DENORMAL_GUARD);
+            } else {
+                self.line_l.write(in_l + self.fb_l + DENORMAL_GUARD);
+                self.line_r.write(in_r + self.fb_r - DENORMAL_GUARD);
+            }
+
+            let dry_l = main_l[i] as f64;
+            let dry_r = main_r[i] as f64;
+            let dry_power = dry_l * dry_l + dry_r * dry_r;
+            let duck_coeff = if dry_power > self.duck_envelope { duck_attack } else { duck_release };
+            self.duck_envelope = dry_power + duck_coeff * (self.duck_envelope - dry_power);
+            let duck_gain = 1.0 - self.ducking * (self.duck_envelope * 16.0).min(1.0);
+
+            let mix = self.mix_smooth * duck_gain;
+            main_l[i] = (dry_l + mix * shaped_l) as f32;
+            main_r[i] = (dry_r + mix * shaped_r) as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn impulse_response(mode: DelayMode, feedback: f64, len: usize) -> (Vec<f32>, Vec<f32>) {
+        let mut delay = StereoDelay::new(48000.0);
+        delay.set_params(&DelayParams {
+            mode,
+            mix: 1.0,
+            time_ms: 10.0,
+            spread: 0.0,
+            feedback,
+            lpf_hz: 20000.0,
+            hpf_hz: 20.0,
+            saturation: 0.0,
+            mod_rate: 0.0,
+            mod_depth: 0.0,
+            ducking: 0.0,
+        });
+        delay.reset();
+        let mut main_l = vec![0.0f32; len];
+        let mut main_r = vec![0.0f32; len];
+        let mut send = vec![0.0f32; len];
+        send[0] = 1.0;
+        // Let the mix smoother settle before the impulse lands.
+        delay.mix_smooth = 1.0;
+        delay.process_block(&mut main_l, &mut main_r, &send, &send);
+        (main_l, main_r)
+    }
+
+    fn peak_index(buf: &[f32]) -> usize {
+        buf.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap())
+            .map(|(i, _)| i)
+            .unwrap()
+    }
+
+    #[test]
+    fn first_repeat_lands_on_delay_time() {
+        let (l, r) = impulse_response(DelayMode::Stereo, 0.0, 1000);
+        assert!((peak_index(&l) as i64 - 480).abs() <= 2);
+        assert!((peak_index(&r) as i64 - 480).abs() <= 2);
+    }
+
+    #[test]
+    fn ping_pong_alternates_sides() {
+        let (l, r) = impulse_response(DelayMode::PingPong, 0.8, 1200);
+        // First repeat on the left only, second on the right.
+        assert!(l[478..484].iter().any(|s| s.abs() > 0.1));
+        assert!(r[478..484].iter().all(|s| s.abs() < 0.01));
+        assert!(r[958..964].iter().any(|s| s.abs() > 0.1));
+    }
+}
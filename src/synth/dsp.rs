@@ -252,11 +252,20 @@ impl VPSOscillator {
 
     #[inline]
     pub fn next(&mut self, freq: f64, israte: f64, d: f64, v: f64) -> f64 {
-        let s = -(TAU * Self::phi_vps(self.phase, v, d)).cos();
+        let s = Self::value_at(self.phase, d, v);
         self.phase += freq * israte;
         self.phase = self.phase.fract();
         s
     }
+
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    #[inline]
+    pub fn value_at(phase: f64, d: f64, v: f64) -> f64 {
+        -(TAU * Self::phi_vps(phase, v, d)).cos()
+    }
 }
 
 #[inline]
@@ -272,6 +281,24 @@ pub(crate) fn poly_blep_f64(t: f64, dt: f64) -> f64 {
     }
 }
 
+/// PolyBLEP residual for a step of height `jump` that lands `frac` of a
+/// sample before the next output sample. Returns the corrections for the
+/// sample ahead of the step and for the one after it.
+#[inline]
+pub(crate) fn blep_step(jump: f64, frac: f64) -> (f64, f64) {
+    let frac = frac.clamp(0.0, 1.0);
+    let after = 1.0 - frac;
+    (0.5 * jump * frac * frac, -0.5 * jump * after * after)
+}
+
+/// Moves phase `from` toward `to` by `amount` along the shorter way round
+/// the cycle. Both phases are in [0, 1).
+#[inline]
+pub(crate) fn blend_phase(from: f64, to: f64, amount: f64) -> f64 {
+    let diff = (to - from + 0.5).rem_euclid(1.0) - 0.5;
+    (from + diff * amount).rem_euclid(1.0)
+}
+
 #[derive(Debug, Clone)]
 pub struct PolyBlepOscillator {
     phase: f64,
@@ -332,17 +359,19 @@ impl PolyBlepOscillator {
         s * 4.0
     }
 
+    /// Negative frequencies run the phase backwards (through-zero FM).
     #[inline]
     pub fn next_pulse(&mut self, freq: f64, israte: f64, pw: f64) -> f64 {
         let phase_inc = freq * israte;
+        let blep_inc = phase_inc.abs();
         let pw = 0.1 * pw + (1.0 - pw) * 0.5;
         let dc_compensation = (0.5 - pw) * 2.0;
         let mut s = if self.phase < pw { 1.0 } else { -1.0 };
-        s += poly_blep_f64(self.phase, phase_inc);
-        s -= poly_blep_f64((self.phase + (1.0 - pw)).fract(), phase_inc);
+        s += poly_blep_f64(self.phase, blep_inc);
+        s -= poly_blep_f64((self.phase + (1.0 - pw)).fract(), blep_inc);
         s += dc_compensation;
         self.phase += phase_inc;
-        self.phase = self.phase.fract();
+        self.phase = self.phase.rem_euclid(1.0);
         s
     }
 
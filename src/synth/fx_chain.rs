@@ -0,0 +1,170 @@
+/// Movable modules of the master effect chain. The limiter is not listed: it
+/// is pinned after every slot. Indices are stored in params and presets, so
+/// they must stay stable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FxModule {
+    MasterHpf,
+    BoxCut,
+    Brilliance,
+    Looper,
+    ModFx,
+    Delay,
+    Reverb,
+    Compressor,
+    StereoControl,
+}
+
+pub const FX_SLOT_COUNT: usize = 9;
+
+/// Compressor ahead of the looper and effect returns matches the old
+/// "master only" compressor routing.
+pub const DEFAULT_FX_ORDER: [FxModule; FX_SLOT_COUNT] = [
+    FxModule::MasterHpf,
+    FxModule::BoxCut,
+    FxModule::Brilliance,
+    FxModule::Compressor,
+    FxModule::Looper,
+    FxModule::ModFx,
+    FxModule::Delay,
+    FxModule::Reverb,
+    FxModule::StereoControl,
+];
+
+impl FxModule {
+    pub fn from_index(i: i32) -> Option<Self> {
+        match i {
+            0 => Some(Self::MasterHpf),
+            1 => Some(Self::BoxCut),
+            2 => Some(Self::Brilliance),
+            3 => Some(Self::Looper),
+            4 => Some(Self::ModFx),
+            5 => Some(Self::Delay),
+            6 => Some(Self::Reverb),
+            7 => Some(Self::Compressor),
+            8 => Some(Self::StereoControl),
+            _ => None,
+        }
+    }
+
+    pub fn to_index(self) -> i32 {
+        match self {
+            Self::MasterHpf => 0,
+            Self::BoxCut => 1,
+            Self::Brilliance => 2,
+            Self::Looper => 3,
+            Self::ModFx => 4,
+            Self::Delay => 5,
+            Self::Reverb => 6,
+            Self::Compressor => 7,
+            Self::StereoControl => 8,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MasterHpf => "MASTER HPF",
+            Self::BoxCut => "BOX CUT",
+            Self::Brilliance => "BRILLIANCE",
+            Self::Looper => "LOOPER",
+            Self::ModFx => "MOD FX",
+            Self::Delay => "DELAY",
+            Self::Reverb => "REVERB",
+            Self::Compressor => "COMPRESSOR",
+            Self::StereoControl => "STEREO",
+        }
+    }
+
+    /// Modules that add a wet signal on top of the bus can run as a send:
+    /// they tap the bus at their slot and their return skips the later slots.
+    pub fn supports_send(self) -> bool {
+        matches!(self, Self::Looper | Self::Delay | Self::Reverb)
+    }
+}
+
+/// Builds a valid order from stored slot indices: unknown and duplicate
+/// entries are dropped and missing modules are appended in default order.
+pub fn sanitize_order(raw: &[i32]) -> [FxModule; FX_SLOT_COUNT] {
+    let mut order = DEFAULT_FX_ORDER;
+    let mut seen = [false; FX_SLOT_COUNT];
+    let mut len = 0;
+
+    for module in raw.iter().filter_map(|&i| FxModule::from_index(i)) {
+        let idx = module.to_index() as usize;
+        if !seen[idx] && len < FX_SLOT_COUNT {
+            seen[idx] = true;
+            order[len] = module;
+            len += 1;
+        }
+    }
+    for module in DEFAULT_FX_ORDER {
+        if !seen[module.to_index() as usize] {
+            order[len] = module;
+            len += 1;
+        }
+    }
+    order
+}
+
+/// Moves Mod FX next to the module picked by `mod_fx_position`
+/// (0=Pre-Looper, 1=Post-Looper, 2=Post-Reverb, 3=Post-Comp), keeping the
+/// rest of the order.
+pub fn place_mod_fx(order: &[FxModule; FX_SLOT_COUNT], position: i32) -> [FxModule; FX_SLOT_COUNT] {
+    let mut raw: Vec<i32> = order.iter()
+        .filter(|&&m| m != FxModule::ModFx)
+        .map(|m| m.to_index())
+        .collect();
+    let (anchor, after) = match position {
+        0 => (FxModule::Looper, false),
+        2 => (FxModule::Reverb, true),
+        3 => (FxModule::Compressor, true),
+        _ => (FxModule::Looper, true),
+    };
+    let idx = raw.iter().position(|&i| i == anchor.to_index()).unwrap_or(raw.len());
+    raw.insert((idx + after as usize).min(raw.len()), FxModule::ModFx.to_index());
+    sanitize_order(&raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_order_round_trips() {
+        let raw: Vec<i32> = DEFAULT_FX_ORDER.iter().map(|m| m.to_index()).collect();
+        assert_eq!(sanitize_order(&raw), DEFAULT_FX_ORDER);
+    }
+
+    #[test]
+    fn duplicates_and_gaps_are_repaired() {
+        // Reverb twice, compressor and stereo missing, one out-of-range entry
+        let raw = [6, 0, 6, 1, 2, 3, 4, 5, 42];
+        let order = sanitize_order(&raw);
+        assert_eq!(order[0], FxModule::Reverb);
+        assert_eq!(order[1], FxModule::MasterHpf);
+        assert_eq!(order[6], FxModule::Delay);
+        assert_eq!(order[7], FxModule::Compressor);
+        assert_eq!(order[8], FxModule::StereoControl);
+    }
+
+    #[test]
+    fn empty_input_gives_default_order() {
+        assert_eq!(sanitize_order(&[]), DEFAULT_FX_ORDER);
+    }
+
+    #[test]
+    fn mod_fx_position_moves_only_mod_fx() {
+        // Post-Looper is where the default order already has it
+        assert_eq!(place_mod_fx(&DEFAULT_FX_ORDER, 1), DEFAULT_FX_ORDER);
+
+        let order = place_mod_fx(&DEFAULT_FX_ORDER, 2);
+        let reverb = order.iter().position(|&m| m == FxModule::Reverb).unwrap();
+        assert_eq!(order[reverb + 1], FxModule::ModFx);
+        let rest: Vec<_> = order.iter().filter(|&&m| m != FxModule::ModFx).collect();
+        let default_rest: Vec<_> = DEFAULT_FX_ORDER.iter().filter(|&&m| m != FxModule::ModFx).collect();
+        assert_eq!(rest, default_rest);
+
+        let order = place_mod_fx(&order, 0);
+        let looper = order.iter().position(|&m| m == FxModule::Looper).unwrap();
+        assert_eq!(order[looper - 1], FxModule::ModFx);
+    }
+}
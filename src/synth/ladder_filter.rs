@@ -34,6 +34,34 @@ fn fast_tanh(x: f64) -> f64 {
     x * (27.0 + x2) / (27.0 + 9.0 * x2)
 }
 
+/// Applies key tracking, then sweeps the cutoff toward the top (positive
+/// `env_mod`) or bottom (negative) of the range in semitones.
+#[inline]
+pub(crate) fn tracked_cutoff(base_hz: f64, key_track_hz: f64, key_track_amount: f64, env_mod: f64) -> f64 {
+    const MIN_ST: f64 = -54.0;
+    const MAX_ST: f64 = 65.513;
+
+    let key_offset = if key_track_amount > 0.0 {
+        let ratio = key_track_hz / 440.0;
+        let semitones = 12.0 * ratio.log2();
+        semitones * key_track_amount
+    } else {
+        0.0
+    };
+
+    let base_st = (base_hz / 440.0).log2() * 12.0 + key_offset;
+
+    let modulated_st = if env_mod > 0.0 {
+        base_st + env_mod * (MAX_ST - base_st)
+    } else if env_mod < 0.0 {
+        base_st + env_mod * (base_st - MIN_ST)
+    } else {
+        base_st
+    };
+
+    440.0 * (modulated_st / 12.0).exp2()
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum FilterMode {
     Lp24,
@@ -180,21 +208,21 @@ const POLYPHASE_COEFFS: [[f64; UPSAMPLER_TAPS]; 4] = [
     ],
 ];
 
-struct PolyphaseUpsampler {
+pub(crate) struct PolyphaseUpsampler {
     delay: [f64; UPSAMPLER_TAPS],
 }
 
 impl PolyphaseUpsampler {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self { delay: [0.0; UPSAMPLER_TAPS] }
     }
 
-    fn reset(&mut self) {
+    pub(crate) fn reset(&mut self) {
         self.delay = [0.0; UPSAMPLER_TAPS];
     }
 
     #[inline]
-    fn process(&mut self, input: f64) -> [f64; 4] {
+    pub(crate) fn process(&mut self, input: f64) -> [f64; 4] {
         // Shift delay line and insert new sample at the front
         let d = &mut self.delay;
         d.copy_within(0..UPSAMPLER_TAPS - 1, 1);
@@ -479,28 +507,7 @@ impl LadderFilter {
 
     #[inline]
     fn compute_cutoff(&self, base_hz: f64) -> f64 {
-        const MIN_ST: f64 = -54.0;
-        const MAX_ST: f64 = 65.513;
-
-        let key_offset = if self.key_track_amount > 0.0 {
-            let ratio = self.key_track_hz / 440.0;
-            let semitones = 12.0 * ratio.log2();
-            semitones * self.key_track_amount
-        } else {
-            0.0
-        };
-
-        let base_st = (base_hz / 440.0).log2() * 12.0 + key_offset;
-
-        let modulated_st = if self.env_mod > 0.0 {
-            base_st + self.env_mod * (MAX_ST - base_st)
-        } else if self.env_mod < 0.0 {
-            base_st + self.env_mod * (base_st - MIN_ST)
-        } else {
-            base_st
-        };
-
-        440.0 * (modulated_st / 12.0).exp2()
+        tracked_cutoff(base_hz, self.key_track_hz, self.key_track_amount, self.env_mod)
     }
 
     #[inline]
@@ -371,6 +371,34 @@ pub enum ModDestination {
     EnvVelDecay,
     EnvVelSustain,
     FiltEnvHold,
+    // Master modulation effect
+    ModFxRate,
+    ModFxDepth,
+    ModFxFeedback,
+    // Lo-fi
+    LofiBits,
+    LofiRate,
+    LofiDrive,
+    LofiMix,
+    // Wavetable
+    WtPosition,
+    WtVolume,
+    NoiseLevel,
+    SyncSoft,
+    // Ring mod / cross-FM
+    RingPll,
+    RingVpsSaw,
+    XfmVpsSaw,
+    XfmSawRef,
+    // Filter 2
+    Filter2Cutoff,
+    Filter2Resonance,
+    Filter2Morph,
+    // Pitch (±12 semitones at full depth)
+    VpsPitch,
+    SawPitch,
+    PllRefPitch,
+    GlobalPitch,
 }
 
 impl ModDestination {
@@ -436,6 +464,28 @@ impl ModDestination {
             57 => ModDestination::EnvVelDecay,
             58 => ModDestination::EnvVelSustain,
             59 => ModDestination::FiltEnvHold,
+            60 => ModDestination::ModFxRate,
+            61 => ModDestination::ModFxDepth,
+            62 => ModDestination::ModFxFeedback,
+            63 => ModDestination::LofiBits,
+            64 => ModDestination::LofiRate,
+            65 => ModDestination::LofiDrive,
+            66 => ModDestination::LofiMix,
+            67 => ModDestination::WtPosition,
+            68 => ModDestination::WtVolume,
+            69 => ModDestination::NoiseLevel,
+            70 => ModDestination::SyncSoft,
+            71 => ModDestination::RingPll,
+            72 => ModDestination::RingVpsSaw,
+            73 => ModDestination::XfmVpsSaw,
+            74 => ModDestination::XfmSawRef,
+            75 => ModDestination::Filter2Cutoff,
+            76 => ModDestination::Filter2Resonance,
+            77 => ModDestination::Filter2Morph,
+            78 => ModDestination::VpsPitch,
+            79 => ModDestination::SawPitch,
+            80 => ModDestination::PllRefPitch,
+            81 => ModDestination::GlobalPitch,
             _ => ModDestination::None,
         }
     }
@@ -496,6 +546,28 @@ impl ModDestination {
             ModDestination::EnvVelDecay => "Vel>Dec",
             ModDestination::EnvVelSustain => "Vel>Sus",
             ModDestination::FiltEnvHold => "FE Hold",
+            ModDestination::ModFxRate => "MFX Rate",
+            ModDestination::ModFxDepth => "MFX Dpth",
+            ModDestination::ModFxFeedback => "MFX FB",
+            ModDestination::LofiBits => "LoFi Bit",
+            ModDestination::LofiRate => "LoFi SR",
+            ModDestination::LofiDrive => "LoFi Drv",
+            ModDestination::LofiMix => "LoFi Mix",
+            ModDestination::WtPosition => "WT Pos",
+            ModDestination::WtVolume => "WT Vol",
+            ModDestination::NoiseLevel => "Noise Lvl",
+            ModDestination::SyncSoft => "Sync Soft",
+            ModDestination::RingPll => "Ring PLL",
+            ModDestination::RingVpsSaw => "Ring V×S",
+            ModDestination::XfmVpsSaw => "XFM V→S",
+            ModDestination::XfmSawRef => "XFM S→R",
+            ModDestination::Filter2Cutoff => "F2 Cut",
+            ModDestination::Filter2Resonance => "F2 Res",
+            ModDestination::Filter2Morph => "F2 Mrph",
+            ModDestination::VpsPitch => "VPS Pitch",
+            ModDestination::SawPitch => "Saw Pitch",
+            ModDestination::PllRefPitch => "Ref Pitch",
+            ModDestination::GlobalPitch => "Pitch",
         }
     }
 }
@@ -556,6 +628,28 @@ pub struct ModulationValues {
     pub env_vel_decay: f64,
     pub env_vel_sustain: f64,
     pub filt_env_hold: f64,
+    pub mod_fx_rate: f64,
+    pub mod_fx_depth: f64,
+    pub mod_fx_feedback: f64,
+    pub lofi_bits: f64,
+    pub lofi_rate: f64,
+    pub lofi_drive: f64,
+    pub lofi_mix: f64,
+    pub wt_position: f64,
+    pub wt_volume: f64,
+    pub noise_level: f64,
+    pub sync_soft: f64,
+    pub ring_pll: f64,
+    pub ring_vps_saw: f64,
+    pub xfm_vps_saw: f64,
+    pub xfm_saw_ref: f64,
+    pub filter2_cutoff: f64,
+    pub filter2_resonance: f64,
+    pub filter2_morph: f64,
+    pub vps_pitch: f64,
+    pub saw_pitch: f64,
+    pub pll_ref_pitch: f64,
+    pub global_pitch: f64,
 }
 
 impl ModulationValues {
@@ -613,6 +707,28 @@ impl ModulationValues {
         self.env_vel_decay += other.env_vel_decay;
         self.env_vel_sustain += other.env_vel_sustain;
         self.filt_env_hold += other.filt_env_hold;
+        self.mod_fx_rate += other.mod_fx_rate;
+        self.mod_fx_depth += other.mod_fx_depth;
+        self.mod_fx_feedback += other.mod_fx_feedback;
+        self.lofi_bits += other.lofi_bits;
+        self.lofi_rate += other.lofi_rate;
+        self.lofi_drive += other.lofi_drive;
+        self.lofi_mix += other.lofi_mix;
+        self.wt_position += other.wt_position;
+        self.wt_volume += other.wt_volume;
+        self.noise_level += other.noise_level;
+        self.sync_soft += other.sync_soft;
+        self.ring_pll += other.ring_pll;
+        self.ring_vps_saw += other.ring_vps_saw;
+        self.xfm_vps_saw += other.xfm_vps_saw;
+        self.xfm_saw_ref += other.xfm_saw_ref;
+        self.filter2_cutoff += other.filter2_cutoff;
+        self.filter2_resonance += other.filter2_resonance;
+        self.filter2_morph += other.filter2_morph;
+        self.vps_pitch += other.vps_pitch;
+        self.saw_pitch += other.saw_pitch;
+        self.pll_ref_pitch += other.pll_ref_pitch;
+        self.global_pitch += other.global_pitch;
     }
 
     pub fn add_modulation(&mut self, dest: ModDestination, amount: f64, lfo_value: f64) {
@@ -672,6 +788,28 @@ impl ModulationValues {
             ModDestination::EnvVelDecay => self.env_vel_decay += mod_value,
             ModDestination::EnvVelSustain => self.env_vel_sustain += mod_value,
             ModDestination::FiltEnvHold => self.filt_env_hold += mod_value,
+            ModDestination::ModFxRate => self.mod_fx_rate += mod_value,
+            ModDestination::ModFxDepth => self.mod_fx_depth += mod_value,
+            ModDestination::ModFxFeedback => self.mod_fx_feedback += mod_value,
+            ModDestination::LofiBits => self.lofi_bits += mod_value,
+            ModDestination::LofiRate => self.lofi_rate += mod_value,
+            ModDestination::LofiDrive => self.lofi_drive += mod_value,
+            ModDestination::LofiMix => self.lofi_mix += mod_value,
+            ModDestination::WtPosition => self.wt_position += mod_value,
+            ModDestination::WtVolume => self.wt_volume += mod_value,
+            ModDestination::NoiseLevel => self.noise_level += mod_value,
+            ModDestination::SyncSoft => self.sync_soft += mod_value,
+            ModDestination::RingPll => self.ring_pll += mod_value,
+            ModDestination::RingVpsSaw => self.ring_vps_saw += mod_value,
+            ModDestination::XfmVpsSaw => self.xfm_vps_saw += mod_value,
+            ModDestination::XfmSawRef => self.xfm_saw_ref += mod_value,
+            ModDestination::Filter2Cutoff => self.filter2_cutoff += mod_value,
+            ModDestination::Filter2Resonance => self.filter2_resonance += mod_value,
+            ModDestination::Filter2Morph => self.filter2_morph += mod_value,
+            ModDestination::VpsPitch => self.vps_pitch += mod_value,
+            ModDestination::SawPitch => self.saw_pitch += mod_value,
+            ModDestination::PllRefPitch => self.pll_ref_pitch += mod_value,
+            ModDestination::GlobalPitch => self.global_pitch += mod_value,
         }
     }
 }
@@ -787,6 +925,12 @@ impl LfoBank {
         mod_values
     }
 
+    pub fn reset_phases(&mut self) {
+        for lfo in &mut self.lfos {
+            lfo.reset_phase();
+        }
+    }
+
     pub fn get_lfo_output(&self, idx: usize) -> f64 {
         if idx < 3 { self.lfo_outputs[idx] } else { 0.0 }
     }
@@ -0,0 +1,272 @@
+use std::f64::consts::PI;
+
+use super::dsp::{apply_distortion, mono_wavefold, mono_wavefold_pi, rand_01};
+
+const MAX_BITS: f64 = 16.0;
+const MIN_RATE_HZ: f64 = 100.0;
+/// The top of the rate range means "no reduction" at any host sample rate.
+const RATE_BYPASS_HZ: f64 = 48000.0;
+const AA_CUTOFF_RATIO: f64 = 0.45;
+const RATE_MOD_OCTAVES: f64 = 5.0;
+const DENORMAL_GUARD: f64 = 1e-18;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LofiShaper {
+    Off,
+    Soft,
+    Asym,
+    Fold,
+    SineFold,
+    PiFold,
+    HardClip,
+    Tube,
+}
+
+impl LofiShaper {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Soft,
+            2 => Self::Asym,
+            3 => Self::Fold,
+            4 => Self::SineFold,
+            5 => Self::PiFold,
+            6 => Self::HardClip,
+            7 => Self::Tube,
+            _ => Self::Off,
+        }
+    }
+
+    /// Drive 0..1 maps onto each shaper's own amount range.
+    #[inline]
+    fn apply(self, s: f64, drive: f64) -> f64 {
+        match self {
+            Self::Off => s,
+            Self::Soft => apply_distortion(s, drive, 1),
+            Self::Asym => apply_distortion(s + drive * 0.2, drive, 2) - apply_distortion(drive * 0.2, drive, 2),
+            Self::Fold => apply_distortion(s, drive, 3),
+            Self::SineFold => mono_wavefold(s, drive),
+            Self::PiFold => mono_wavefold_pi(s, drive),
+            Self::HardClip => (s * (1.0 + drive * 7.0)).clamp(-1.0, 1.0),
+            Self::Tube => {
+                let x = (s * (1.0 + drive * 4.0)).clamp(-3.0, 3.0);
+                x * (27.0 + x * x) / (27.0 + 9.0 * x * x)
+            }
+        }
+    }
+}
+
+/// Two-pole Butterworth lowpass used ahead of the sample-and-hold.
+#[derive(Clone, Copy, Default)]
+struct AntiAlias {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl AntiAlias {
+    fn set_cutoff(&mut self, cutoff_hz: f64, sample_rate: f64) {
+        let w0 = 2.0 * PI * (cutoff_hz / sample_rate).clamp(0.0001, 0.49);
+        let (sin_w0, cos_w0) = w0.sin_cos();
+        let alpha = sin_w0 / (2.0 * std::f64::consts::FRAC_1_SQRT_2);
+        let a0 = 1.0 + alpha;
+        self.b1 = (1.0 - cos_w0) / a0;
+        self.b0 = self.b1 * 0.5;
+        self.b2 = self.b0;
+        self.a1 = -2.0 * cos_w0 / a0;
+        self.a2 = (1.0 - alpha) / a0;
+    }
+
+    fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y + DENORMAL_GUARD;
+        y
+    }
+}
+
+/// `rate_mod` shifts the reduced rate in ±5 octaves per unit; the other
+/// modulation inputs are added to their base values.
+pub struct LofiParams {
+    pub shaper: LofiShaper,
+    pub bits: f64,
+    pub rate_hz: f64,
+    pub anti_alias: bool,
+    pub jitter: f64,
+    pub drive: f64,
+    pub mix: f64,
+    pub bits_mod: f64,
+    pub rate_mod: f64,
+}
+
+/// Stereo lo-fi stage: waveshaper → sample-rate reducer → bit quantiser.
+pub struct Lofi {
+    sample_rate: f64,
+
+    shaper: LofiShaper,
+    bits: f64,
+    rate_hz: f64,
+    anti_alias: bool,
+    jitter: f64,
+    drive: f64,
+    mix: f64,
+
+    aa_l: AntiAlias,
+    aa_r: AntiAlias,
+    aa_cutoff: f64,
+    hold_phase: f64,
+    hold_step: f64,
+    held_l: f64,
+    held_r: f64,
+}
+
+impl Lofi {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut lofi = Self {
+            sample_rate: sample_rate as f64,
+            shaper: LofiShaper::Off,
+            bits: MAX_BITS,
+            rate_hz: sample_rate as f64,
+            anti_alias: true,
+            jitter: 0.0,
+            drive: 0.0,
+            mix: 1.0,
+            aa_l: AntiAlias::default(),
+            aa_r: AntiAlias::default(),
+            aa_cutoff: 0.0,
+            hold_phase: 0.0,
+            hold_step: 1.0,
+            held_l: 0.0,
+            held_r: 0.0,
+        };
+        lofi.update_anti_alias();
+        lofi
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate as f64;
+        self.aa_cutoff = 0.0;
+        self.update_anti_alias();
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.aa_l.reset();
+        self.aa_r.reset();
+        self.hold_phase = 0.0;
+        self.held_l = 0.0;
+        self.held_r = 0.0;
+    }
+
+    pub fn set_params(&mut self, p: &LofiParams) {
+        self.shaper = p.shaper;
+        self.bits = (p.bits + p.bits_mod * (MAX_BITS - 1.0)).clamp(1.0, MAX_BITS);
+        let base_rate = if p.rate_hz >= RATE_BYPASS_HZ { self.sample_rate.max(p.rate_hz) } else { p.rate_hz };
+        self.rate_hz = (base_rate * (p.rate_mod * RATE_MOD_OCTAVES).exp2()).clamp(MIN_RATE_HZ, self.sample_rate);
+        self.anti_alias = p.anti_alias;
+        self.jitter = p.jitter.clamp(0.0, 1.0);
+        self.drive = p.drive.clamp(0.0, 1.0);
+        self.mix = p.mix.clamp(0.0, 1.0);
+        self.update_anti_alias();
+    }
+
+    fn update_anti_alias(&mut self) {
+        let cutoff = self.rate_hz * AA_CUTOFF_RATIO;
+        // Coefficients only change when the rate moves by more than ~1%
+        if (cutoff - self.aa_cutoff).abs() > self.aa_cutoff * 0.01 {
+            self.aa_cutoff = cutoff;
+            self.aa_l.set_cutoff(cutoff, self.sample_rate);
+            self.aa_r.set_cutoff(cutoff, self.sample_rate);
+        }
+        self.hold_step = self.rate_hz / self.sample_rate;
+    }
+
+    #[inline]
+    pub fn process(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let shaped_l = self.shaper.apply(in_l, self.drive);
+        let shaped_r = self.shaper.apply(in_r, self.drive);
+
+        let reducing = self.hold_step < 0.999;
+        let (mut out_l, mut out_r) = if reducing {
+            let (aa_l, aa_r) = if self.anti_alias {
+                (self.aa_l.process(shaped_l), self.aa_r.process(shaped_r))
+            } else {
+                (shaped_l, shaped_r)
+            };
+            self.hold_phase += self.hold_step;
+            if self.hold_phase >= 1.0 {
+                self.hold_phase -= 1.0;
+                // Jitter pulls the next sampling instant early or late by up to half a period
+                if self.jitter > 0.0 {
+                    self.hold_phase = (self.hold_phase + (rand_01() - 0.5) * self.jitter).clamp(0.0, 0.999);
+                }
+                self.held_l = aa_l;
+                self.held_r = aa_r;
+            }
+            (self.held_l, self.held_r)
+        } else {
+            (shaped_l, shaped_r)
+        };
+
+        if self.bits < MAX_BITS - 0.001 {
+            let levels = (self.bits - 1.0).exp2();
+            out_l = (out_l * levels).round() / levels;
+            out_r = (out_r * levels).round() / levels;
+        }
+
+        (
+            in_l + (out_l - in_l) * self.mix,
+            in_r + (out_r - in_r) * self.mix,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(bits: f64, rate_hz: f64) -> LofiParams {
+        LofiParams {
+            shaper: LofiShaper::Off,
+            bits,
+            rate_hz,
+            anti_alias: false,
+            jitter: 0.0,
+            drive: 0.0,
+            mix: 1.0,
+            bits_mod: 0.0,
+            rate_mod: 0.0,
+        }
+    }
+
+    #[test]
+    fn quantises_to_bit_depth() {
+        let mut lofi = Lofi::new(48000.0);
+        lofi.set_params(&clean(3.0, 48000.0));
+        // 3 bits → steps of 1/4
+        let (l, r) = lofi.process(0.3, -0.6);
+        assert!((l - 0.25).abs() < 1e-12);
+        assert!((r + 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rate_reduction_holds_samples() {
+        let mut lofi = Lofi::new(48000.0);
+        lofi.set_params(&clean(16.0, 12000.0));
+        let outputs: Vec<f64> = (0..16).map(|i| lofi.process(i as f64 * 0.01, 0.0).0).collect();
+        // A quarter of the host rate: each held value repeats for four samples
+        for chunk in outputs[3..15].chunks(4) {
+            assert!(chunk.iter().all(|&v| v == chunk[0]));
+        }
+        assert!(outputs[7] > outputs[3]);
+    }
+}
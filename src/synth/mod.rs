@@ -5,17 +5,26 @@ mod dsp;
 mod oscillator;
 mod envelope;
 mod voice;
+mod lofi;
+mod noise;
 pub mod lfo;
 mod limiter;
 pub mod mod_sequencer;
+pub mod mseg;
 pub mod master_hpf;
 pub mod box_cut;
 pub mod brilliance;
 pub mod stereo_control;
 pub mod ladder_filter;
+mod multi_filter;
+mod resonator;
 pub mod looper;
 pub mod reverb;
+pub mod delay;
+pub mod mod_fx;
 pub mod compressor;
+pub mod fx_chain;
+pub mod wavetable;
 
 pub use voice::Voice;
 pub use lfo::LfoBank;
@@ -26,11 +35,15 @@ pub use brilliance::BrillianceFilter;
 pub use stereo_control::StereoControl;
 pub use looper::PitchedLooper;
 pub use reverb::LushReverb;
+pub use delay::StereoDelay;
+pub use mod_fx::ModFx;
 pub use compressor::Compressor;
 use crate::sequencer::Sequencer;
+use crate::macro_controls::MacroRouter;
 use crate::params::DeviceParams;
 use crate::midi::ExternalNoteEvent;
 use mod_sequencer::ModSequencer;
+use mseg::{MsegBank, MsegShape};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum NotePriority {
@@ -51,6 +64,7 @@ pub struct SynthEngine {
     pll_feedback: f64,
     pub lfo_bank: LfoBank,
     pub mod_sequencer: ModSequencer,
+    pub mseg_bank: MsegBank,
     note_stack: Vec<NoteEntry>,
     note_priority: NotePriority,
     active_seq_note: Option<u8>,
@@ -60,6 +74,10 @@ pub struct SynthEngine {
     pll_buf_l: Vec<f32>,
     pll_buf_r: Vec<f32>,
     saw_buf: Vec<f32>,
+    wt_buf: Vec<f32>,
+    mod_fx_rate_buf: Vec<f32>,
+    mod_fx_depth_buf: Vec<f32>,
+    mod_fx_feedback_buf: Vec<f32>,
 }
 
 impl SynthEngine {
@@ -74,6 +92,7 @@ impl SynthEngine {
             pll_feedback: 0.0,
             lfo_bank: LfoBank::new(sample_rate_f64),
             mod_sequencer: ModSequencer::new(sample_rate_f64),
+            mseg_bank: MsegBank::new(sample_rate_f64),
             note_stack: Vec::with_capacity(16),
             note_priority: NotePriority::Last,
             active_seq_note: None,
@@ -83,6 +102,10 @@ impl SynthEngine {
             pll_buf_l: Vec::with_capacity(2048),
             pll_buf_r: Vec::with_capacity(2048),
             saw_buf: Vec::with_capacity(2048),
+            wt_buf: Vec::with_capacity(2048),
+            mod_fx_rate_buf: Vec::with_capacity(2048),
+            mod_fx_depth_buf: Vec::with_capacity(2048),
+            mod_fx_feedback_buf: Vec::with_capacity(2048),
         }
     }
 
@@ -92,6 +115,7 @@ impl SynthEngine {
         self.sequencer.set_sample_rate(sample_rate as f64);
         self.lfo_bank.set_sample_rate(sample_rate as f64);
         self.mod_sequencer.set_sample_rate(sample_rate as f64);
+        self.mseg_bank.set_sample_rate(sample_rate as f64);
     }
 
     pub fn current_frequency(&self) -> f64 {
@@ -113,6 +137,15 @@ impl SynthEngine {
         self.active_seq_note = None;
     }
 
+    /// Realigns the sequencer, LFOs, mod sequencer and MSEGs to a transport start.
+    pub fn restart_transport(&mut self) {
+        self.stop();
+        self.sequencer.rewind();
+        self.lfo_bank.reset_phases();
+        self.mod_sequencer.reset_phase();
+        self.mseg_bank.reset_phases();
+    }
+
     #[allow(dead_code)]
     pub fn get_current_sequencer_note(&self) -> bool {
         self.sequencer.has_active_note()
@@ -159,6 +192,22 @@ impl SynthEngine {
         self.voice.set_pll_params(track as f64, damp as f64, mult as f64, influence as f64, colored, edge_mode);
     }
 
+    pub fn set_pll_topology(&mut self, topology: i32, track2: f32, damp2: f32, mult2: f32, coupling: f32) {
+        self.voice.set_pll_topology(topology, track2 as f64, damp2 as f64, mult2 as f64, coupling as f64);
+    }
+
+    pub fn set_pll_ref_source(&mut self, source: i32, mix: f32) {
+        self.voice.set_pll_ref_source(source, mix as f64);
+    }
+
+    pub fn set_ring_mod(&mut self, pll_on: bool, pll: f32, vps_saw_on: bool, vps_saw: f32) {
+        self.voice.set_ring_mod(pll_on, pll as f64, vps_saw_on, vps_saw as f64);
+    }
+
+    pub fn set_cross_fm(&mut self, vps_saw_on: bool, vps_saw: f32, saw_ref_on: bool, saw_ref: f32) {
+        self.voice.set_cross_fm(vps_saw_on, vps_saw as f64, saw_ref_on, saw_ref as f64);
+    }
+
     pub fn set_pll_mult_slew_time(&mut self, time: f32) {
         self.voice.set_pll_mult_slew_time(time as f64);
     }
@@ -271,8 +320,9 @@ impl SynthEngine {
         vps: bool,
         reverb: bool,
         saw: bool,
+        wt: bool,
     ) {
-        self.voice.set_bypass_switches(pll, vps, reverb, saw);
+        self.voice.set_bypass_switches(pll, vps, reverb, saw, wt);
     }
 
     pub fn set_vps_formant(&mut self, enabled: bool) {
@@ -379,10 +429,56 @@ impl SynthEngine {
     pub fn set_filter_cutoff_slew(&mut self, s: f32) { self.voice.set_filter_cutoff_slew(s as f64); }
     pub fn set_filter_poles(&mut self, p: i32) { self.voice.set_filter_poles(p); }
 
+    pub fn set_filter2_enabled(&mut self, enabled: bool) {
+        self.voice.set_filter2_enabled(enabled);
+    }
+
+    pub fn set_filter2_params(&mut self, filter_type: i32, cutoff: f32, resonance: f32, morph: f32) {
+        self.voice.set_filter2_params(filter_type as u8, cutoff as f64, resonance as f64, morph as f64);
+    }
+
+    pub fn set_filter2_tracking(&mut self, key_track: f32, env_amount: f32) {
+        self.voice.set_filter2_tracking(key_track as f64, env_amount as f64);
+    }
+
+    pub fn set_filter_routing(&mut self, routing: i32) {
+        self.voice.set_filter_routing(routing);
+    }
+
+    pub fn set_resonator_enabled(&mut self, enabled: bool) {
+        self.voice.set_resonator_enabled(enabled);
+    }
+
+    pub fn set_resonator_params(&mut self, model: i32, excite: i32, decay: f32, brightness: f32, position: f32, mix: f32) {
+        self.voice.set_resonator_params(model, excite, decay as f64, brightness as f64, position as f64, mix as f64);
+    }
+
     pub fn set_saw_fold_range(&mut self, range: i32) {
         self.voice.set_saw_fold_range(range);
     }
 
+    /// Returns the previous table; the caller must not drop it on the audio
+    /// thread.
+    pub fn set_wavetable(&mut self, table: std::sync::Arc<wavetable::Wavetable>) -> std::sync::Arc<wavetable::Wavetable> {
+        self.voice.set_wavetable(table)
+    }
+
+    pub fn set_wt_volume(&mut self, volume: f32) {
+        self.voice.set_wt_volume(volume as f64);
+    }
+
+    pub fn set_wt_octave(&mut self, octave: i32) {
+        self.voice.set_wt_octave(octave);
+    }
+
+    pub fn set_wt_tune(&mut self, tune: i32, fine: f32) {
+        self.voice.set_wt_tune(tune, fine as f64);
+    }
+
+    pub fn set_wt_position(&mut self, position: f32) {
+        self.voice.set_wt_position(position as f64);
+    }
+
     pub fn set_volume(&mut self, volume: f32) {
         self.voice.set_volume(volume as f64);
     }
@@ -444,6 +540,59 @@ impl SynthEngine {
         self.sequencer.beat_links = links;
     }
 
+    pub fn update_phrase(&mut self, note_pool: &crate::sequencer::NotePool, bars: &[crate::sequencer::PhraseBar]) {
+        // Reuses the pool's buffer rather than dropping it on the audio thread
+        self.sequencer.note_pool.notes.clone_from(&note_pool.notes);
+        self.sequencer.note_pool.root_note = note_pool.root_note;
+        self.sequencer.set_phrase(bars);
+    }
+
+    pub fn clear_phrase(&mut self) {
+        self.sequencer.clear_phrase();
+    }
+
+    pub fn sequencer_bar_position(&self) -> crate::midi::BarPosition {
+        crate::midi::BarPosition {
+            beats: self.sequencer.bar_position_beats(),
+            bar_length_samples: self.sequencer.bar_length_samples(),
+        }
+    }
+
+    pub fn set_transpose_key(&mut self, key: Option<u8>) {
+        self.sequencer.transposer.set_key(key);
+    }
+
+    pub fn set_transpose_options(
+        &mut self,
+        scale_aware: bool,
+        quantize: crate::sequencer::TransposeQuantize,
+        gate: bool,
+    ) {
+        let transposer = &mut self.sequencer.transposer;
+        transposer.scale_aware = scale_aware;
+        transposer.quantize = quantize;
+        transposer.gate = gate;
+    }
+
+    pub fn update_scale(&mut self, scale: crate::sequencer::Scale) {
+        self.sequencer.transposer.scale = scale;
+    }
+
+    /// Snaps pitch modulation to the sequencer scale, rooted at the note pool root.
+    pub fn set_pitch_mod_quantize(&mut self, enabled: bool) {
+        let mask = if enabled {
+            self.sequencer.transposer.pitch_class_mask(&self.sequencer.note_pool)
+        } else {
+            0
+        };
+        self.voice.set_pitch_mod_scale(mask);
+    }
+
+    pub fn clear_transpose(&mut self) {
+        self.sequencer.transposer.clear();
+        self.sequencer.transposer.gate = false;
+    }
+
     pub fn set_lfo_params(
         &mut self,
         lfo_idx: usize,
@@ -506,6 +655,22 @@ impl SynthEngine {
         self.mod_sequencer.current_step()
     }
 
+    pub fn set_mseg_shape(&mut self, idx: usize, shape: &MsegShape) {
+        self.mseg_bank.set_shape(idx, shape);
+    }
+
+    pub fn set_mseg_params(&mut self, idx: usize, tempo_sync: bool, retrigger: i32) {
+        self.mseg_bank.set_params(idx, tempo_sync, retrigger);
+    }
+
+    pub fn set_mseg_modulation(&mut self, idx: usize, slot: usize, destination: i32, amount: f32) {
+        self.mseg_bank.set_modulation(idx, slot, destination, amount as f64);
+    }
+
+    pub fn mseg_position(&self, idx: usize) -> f64 {
+        self.mseg_bank.position(idx)
+    }
+
     #[allow(dead_code)]
     pub fn get_lfo_output(&self, idx: usize) -> f32 {
         self.lfo_bank.get_lfo_output(idx) as f32
@@ -523,8 +688,44 @@ impl SynthEngine {
         Some((entry.note, entry.frequency, entry.velocity))
     }
 
-    pub fn set_reverb_sends(&mut self, vps: f64, pll: f64, saw: f64, sub: f64, filter: f64) {
-        self.voice.set_reverb_sends(vps, pll, saw, sub, filter);
+    pub fn set_reverb_sends(&mut self, vps: f64, pll: f64, saw: f64, wt: f64, sub: f64, filter: f64) {
+        self.voice.set_reverb_sends(vps, pll, saw, wt, sub, filter);
+    }
+
+    pub fn set_delay_sends(&mut self, vps: f64, pll: f64, saw: f64, wt: f64, sub: f64, filter: f64) {
+        self.voice.set_delay_sends(vps, pll, saw, wt, sub, filter);
+    }
+
+    pub fn set_lofi_routing(&mut self, enabled: bool, post_filter: bool, vps: bool, pll: bool, saw: bool) {
+        self.voice.set_lofi_routing(enabled, post_filter, vps, pll, saw);
+    }
+
+    pub fn set_lofi_params(&mut self, shaper: i32, bits: f32, rate_hz: f32, anti_alias: bool, jitter: f32, drive: f32, mix: f32) {
+        self.voice.set_lofi_params(
+            shaper,
+            bits as f64,
+            rate_hz as f64,
+            anti_alias,
+            jitter as f64,
+            drive as f64,
+            mix as f64,
+        );
+    }
+
+    pub fn set_noise_params(&mut self, kind: i32, rate_hz: f32, lpf_hz: f32, hpf_hz: f32, filter_env: bool) {
+        self.voice.set_noise_params(kind, rate_hz as f64, lpf_hz as f64, hpf_hz as f64, filter_env);
+    }
+
+    pub fn set_noise_level(&mut self, level: f32) {
+        self.voice.set_noise_level(level as f64);
+    }
+
+    pub fn set_noise_injection(&mut self, point: i32, amount: f32) {
+        self.voice.set_noise_injection(point, amount as f64);
+    }
+
+    pub fn set_sync(&mut self, source_vco: bool, vps: i32, saw: i32, pll: i32, soft: f32) {
+        self.voice.set_sync(source_vco, vps, saw, pll, soft as f64);
     }
 
     pub fn process_block(
@@ -534,7 +735,10 @@ impl SynthEngine {
         sub_output: &mut [f32],
         reverb_send_l: &mut [f32],
         reverb_send_r: &mut [f32],
+        delay_send_l: &mut [f32],
+        delay_send_r: &mut [f32],
         params: &DeviceParams,
+        macros: &MacroRouter,
         feedback_amount: f32,
         _base_freq: f32,
         midi_events: &mut Vec<(bool, bool, u8, u8, usize)>,
@@ -551,9 +755,13 @@ impl SynthEngine {
         self.pll_buf_l.resize(num_samples, 0.0);
         self.pll_buf_r.resize(num_samples, 0.0);
         self.saw_buf.resize(num_samples, 0.0);
+        self.wt_buf.resize(num_samples, 0.0);
+        self.mod_fx_rate_buf.resize(num_samples, 0.0);
+        self.mod_fx_depth_buf.resize(num_samples, 0.0);
+        self.mod_fx_feedback_buf.resize(num_samples, 0.0);
 
         if seq_playing {
-            self.sequencer.prepare(num_samples, params);
+            self.sequencer.prepare(num_samples, params, macros);
         }
 
         let mut ext_idx = 0;
@@ -582,6 +790,7 @@ impl SynthEngine {
                             if self.mod_sequencer.should_retrigger() {
                                 self.mod_sequencer.reset_phase();
                             }
+                            self.mseg_bank.note_on();
                             midi_events.push((true, false, note, vel, sample_idx));
                         }
                     } else {
@@ -637,6 +846,7 @@ impl SynthEngine {
                         if self.mod_sequencer.should_retrigger() {
                             self.mod_sequencer.reset_phase();
                         }
+                        self.mseg_bank.note_on();
                     }
                     self.active_seq_note = Some(midi_note);
                     midi_events.push((true, false, midi_note, velocity, sample_idx));
@@ -646,24 +856,38 @@ impl SynthEngine {
             let mut mod_values = self.lfo_bank.process(bpm);
             let seq_mod = self.mod_sequencer.process(bpm);
             mod_values.accumulate(&seq_mod);
+            let mseg_mod = self.mseg_bank.process(bpm);
+            mod_values.accumulate(&mseg_mod);
             self.voice.apply_modulation(&mod_values);
+            self.mod_fx_rate_buf[sample_idx] = mod_values.mod_fx_rate as f32;
+            self.mod_fx_depth_buf[sample_idx] = mod_values.mod_fx_depth as f32;
+            self.mod_fx_feedback_buf[sample_idx] = mod_values.mod_fx_feedback as f32;
 
-            let (left_sample, right_sample, sub_sample, rev_l, rev_r) = self.voice.process(self.pll_feedback);
+            let (left_sample, right_sample, sub_sample, rev_l, rev_r, dly_l, dly_r) = self.voice.process(self.pll_feedback);
 
             *l = left_sample as f32;
             *r = right_sample as f32;
             sub_output[sample_idx] = sub_sample as f32;
             reverb_send_l[sample_idx] = rev_l as f32;
             reverb_send_r[sample_idx] = rev_r as f32;
+            delay_send_l[sample_idx] = dly_l as f32;
+            delay_send_r[sample_idx] = dly_r as f32;
             self.vps_buf_l[sample_idx] = self.voice.vps_l() as f32;
             self.vps_buf_r[sample_idx] = self.voice.vps_r() as f32;
             self.pll_buf_l[sample_idx] = self.voice.pll_l() as f32;
             self.pll_buf_r[sample_idx] = self.voice.pll_r() as f32;
             self.saw_buf[sample_idx] = self.voice.saw_val() as f32;
+            self.wt_buf[sample_idx] = self.voice.wt_val() as f32;
         }
     }
 
-    pub fn source_buffers(&self) -> (&[f32], &[f32], &[f32], &[f32], &[f32]) {
-        (&self.vps_buf_l, &self.vps_buf_r, &self.pll_buf_l, &self.pll_buf_r, &self.saw_buf)
+    pub fn source_buffers(&self) -> (&[f32], &[f32], &[f32], &[f32], &[f32], &[f32]) {
+        (&self.vps_buf_l, &self.vps_buf_r, &self.pll_buf_l, &self.pll_buf_r, &self.saw_buf, &self.wt_buf)
+    }
+
+    /// Per-sample LFO / step-mod offsets for the master modulation effect,
+    /// which runs outside the voice.
+    pub fn mod_fx_modulation(&self) -> (&[f32], &[f32], &[f32]) {
+        (&self.mod_fx_rate_buf, &self.mod_fx_depth_buf, &self.mod_fx_feedback_buf)
     }
 }
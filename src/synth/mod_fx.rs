@@ -0,0 +1,441 @@
+use std::f64::consts::PI;
+
+const MAX_DELAY_SECONDS: f64 = 0.05;
+const CHORUS_BASE_MS: f64 = 12.0;
+const CHORUS_DEPTH_MS: f64 = 6.0;
+const CHORUS_BBD_LPF_HZ: f64 = 7500.0;
+const FLANGER_CENTER_MS: f64 = 3.0;
+const PHASER_CENTER_HZ: f64 = 700.0;
+const PHASER_OCTAVES: f64 = 4.0;
+pub const MAX_CHORUS_VOICES: usize = 4;
+pub const MAX_PHASER_STAGES: usize = 12;
+const RATE_MOD_OCTAVES: f64 = 3.0;
+const DENORMAL_GUARD: f64 = 1e-18;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ModFxMode {
+    Chorus,
+    Flanger,
+    Phaser,
+}
+
+impl ModFxMode {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Flanger,
+            2 => Self::Phaser,
+            _ => Self::Chorus,
+        }
+    }
+}
+
+struct DelayBuffer {
+    buffer: Vec<f64>,
+    write_pos: usize,
+}
+
+impl DelayBuffer {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(8)],
+            write_pos: 0,
+        }
+    }
+
+    fn resize(&mut self, length: usize) {
+        self.buffer.clear();
+        self.buffer.resize(length.max(8), 0.0);
+        self.write_pos = 0;
+    }
+
+    #[inline]
+    fn write(&mut self, sample: f64) {
+        self.buffer[self.write_pos] = sample;
+        self.write_pos += 1;
+        if self.write_pos >= self.buffer.len() {
+            self.write_pos = 0;
+        }
+    }
+
+    #[inline]
+    fn read(&self, delay: usize) -> f64 {
+        let len = self.buffer.len();
+        let d = delay.min(len - 1);
+        self.buffer[(self.write_pos + len - d - 1) % len]
+    }
+
+    #[inline]
+    fn read_interpolated(&self, delay: f64) -> f64 {
+        let d = delay.clamp(1.0, (self.buffer.len() - 3) as f64);
+        let d_int = d as usize;
+        let frac = d - d_int as f64;
+
+        let ym1 = self.read(d_int - 1);
+        let y0 = self.read(d_int);
+        let y1 = self.read(d_int + 1);
+        let y2 = self.read(d_int + 2);
+
+        let c1 = 0.5 * (y1 - ym1);
+        let c2 = ym1 - 2.5 * y0 + 2.0 * y1 - 0.5 * y2;
+        let c3 = 0.5 * (y2 - ym1) + 1.5 * (y0 - y1);
+        ((c3 * frac + c2) * frac + c1) * frac + y0
+    }
+
+    fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.write_pos = 0;
+    }
+}
+
+#[derive(Clone, Copy)]
+struct OnePole {
+    state: f64,
+    coeff: f64,
+}
+
+impl OnePole {
+    fn new() -> Self {
+        Self { state: 0.0, coeff: 0.0 }
+    }
+
+    fn set_freq(&mut self, freq: f64, sample_rate: f64) {
+        let w = (2.0 * PI * freq / sample_rate).min(PI * 0.99);
+        self.coeff = (-w).exp();
+    }
+
+    #[inline]
+    fn tick_lpf(&mut self, input: f64) -> f64 {
+        self.state = input + self.coeff * (self.state - input);
+        self.state
+    }
+
+    fn reset(&mut self) {
+        self.state = 0.0;
+    }
+}
+
+/// First-order allpass stage for the phaser.
+#[derive(Clone, Copy, Default)]
+struct AllpassStage {
+    x1: f64,
+    y1: f64,
+}
+
+impl AllpassStage {
+    #[inline]
+    fn tick(&mut self, input: f64, a: f64) -> f64 {
+        let y = a * input + self.x1 - a * self.y1;
+        self.x1 = input;
+        self.y1 = y;
+        y
+    }
+}
+
+#[inline]
+fn triangle(phase: f64) -> f64 {
+    let p = phase - phase.floor();
+    if p < 0.5 { 4.0 * p - 1.0 } else { 3.0 - 4.0 * p }
+}
+
+/// `rate_hz` is the already tempo-resolved LFO rate. `spread` offsets the
+/// right channel's LFO by up to half a cycle.
+pub struct ModFxParams {
+    pub mode: ModFxMode,
+    pub rate_hz: f64,
+    pub depth: f64,
+    pub feedback: f64,
+    pub mix: f64,
+    pub voices: i32,
+    pub stages: i32,
+    pub spread: f64,
+}
+
+/// Stereo chorus / through-zero flanger / multi-stage phaser, used as an
+/// insert on the master bus. `mix` crossfades dry and wet; 0.5 gives the
+/// deepest notches for the flanger and phaser.
+pub struct ModFx {
+    sample_rate: f64,
+
+    mode: ModFxMode,
+    rate_hz: f64,
+    depth: f64,
+    feedback: f64,
+    mix: f64,
+    voices: usize,
+    stages: usize,
+    spread: f64,
+
+    line_l: DelayBuffer,
+    line_r: DelayBuffer,
+    bbd_lpf_l: OnePole,
+    bbd_lpf_r: OnePole,
+    allpass_l: [AllpassStage; MAX_PHASER_STAGES],
+    allpass_r: [AllpassStage; MAX_PHASER_STAGES],
+    fb_l: f64,
+    fb_r: f64,
+
+    phase: f64,
+    mix_smooth: f64,
+    depth_smooth: f64,
+    feedback_smooth: f64,
+    smooth_coeff: f64,
+}
+
+impl ModFx {
+    pub fn new(sample_rate: f32) -> Self {
+        let sr = sample_rate as f64;
+        let max_len = (MAX_DELAY_SECONDS * sr) as usize + 8;
+        let mut fx = Self {
+            sample_rate: sr,
+            mode: ModFxMode::Chorus,
+            rate_hz: 0.5,
+            depth: 0.5,
+            feedback: 0.0,
+            mix: 0.0,
+            voices: 3,
+            stages: 6,
+            spread: 0.5,
+            line_l: DelayBuffer::new(max_len),
+            line_r: DelayBuffer::new(max_len),
+            bbd_lpf_l: OnePole::new(),
+            bbd_lpf_r: OnePole::new(),
+            allpass_l: [AllpassStage::default(); MAX_PHASER_STAGES],
+            allpass_r: [AllpassStage::default(); MAX_PHASER_STAGES],
+            fb_l: 0.0,
+            fb_r: 0.0,
+            phase: 0.0,
+            mix_smooth: 0.0,
+            depth_smooth: 0.5,
+            feedback_smooth: 0.0,
+            smooth_coeff: 1.0 - (-1.0 / (0.02 * sr)).exp(),
+        };
+        fx.bbd_lpf_l.set_freq(CHORUS_BBD_LPF_HZ, sr);
+        fx.bbd_lpf_r.set_freq(CHORUS_BBD_LPF_HZ, sr);
+        fx
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        let sr = sample_rate as f64;
+        let max_len = (MAX_DELAY_SECONDS * sr) as usize + 8;
+        self.sample_rate = sr;
+        self.line_l.resize(max_len);
+        self.line_r.resize(max_len);
+        self.bbd_lpf_l.set_freq(CHORUS_BBD_LPF_HZ, sr);
+        self.bbd_lpf_r.set_freq(CHORUS_BBD_LPF_HZ, sr);
+        self.smooth_coeff = 1.0 - (-1.0 / (0.02 * sr)).exp();
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.line_l.clear();
+        self.line_r.clear();
+        self.bbd_lpf_l.reset();
+        self.bbd_lpf_r.reset();
+        self.allpass_l = [AllpassStage::default(); MAX_PHASER_STAGES];
+        self.allpass_r = [AllpassStage::default(); MAX_PHASER_STAGES];
+        self.fb_l = 0.0;
+        self.fb_r = 0.0;
+        self.mix_smooth = 0.0;
+        self.depth_smooth = self.depth;
+        self.feedback_smooth = self.feedback;
+    }
+
+    pub fn set_params(&mut self, p: &ModFxParams) {
+        if p.mode != self.mode {
+            // Different topologies share the delay lines; start clean.
+            self.fb_l = 0.0;
+            self.fb_r = 0.0;
+            self.allpass_l = [AllpassStage::default(); MAX_PHASER_STAGES];
+            self.allpass_r = [AllpassStage::default(); MAX_PHASER_STAGES];
+        }
+        self.mode = p.mode;
+        self.rate_hz = p.rate_hz.clamp(0.001, 20.0);
+        self.depth = p.depth.clamp(0.0, 1.0);
+        self.feedback = p.feedback.clamp(-1.0, 1.0);
+        self.mix = p.mix.clamp(0.0, 1.0);
+        self.voices = (p.voices.max(1) as usize).min(MAX_CHORUS_VOICES);
+        self.stages = (p.stages.max(1) as usize).min(MAX_PHASER_STAGES);
+        self.spread = p.spread.clamp(0.0, 1.0);
+    }
+
+    /// Processes in place. The modulation slices carry per-sample offsets from
+    /// the LFO bank / step modulator (rate in ±octaves/3, depth and feedback
+    /// additive); they may be empty.
+    pub fn process_block(
+        &mut self,
+        main_l: &mut [f32],
+        main_r: &mut [f32],
+        rate_mod: &[f32],
+        depth_mod: &[f32],
+        feedback_mod: &[f32],
+    ) {
+        if self.mix < 0.0001 && self.mix_smooth < 0.0001 {
+            return;
+        }
+
+        let israte = 1.0 / self.sample_rate;
+        let sc = self.smooth_coeff;
+        let r_offset = self.spread * 0.5;
+        let ms = self.sample_rate * 0.001;
+
+        for i in 0..main_l.len().min(main_r.len()) {
+            let rate_m = rate_mod.get(i).copied().unwrap_or(0.0) as f64;
+            let depth_m = depth_mod.get(i).copied().unwrap_or(0.0) as f64;
+            let fb_m = feedback_mod.get(i).copied().unwrap_or(0.0) as f64;
+
+            self.mix_smooth += (self.mix - self.mix_smooth) * sc;
+            self.depth_smooth += ((self.depth + depth_m).clamp(0.0, 1.0) - self.depth_smooth) * sc;
+            self.feedback_smooth += ((self.feedback + fb_m).clamp(-1.0, 1.0) - self.feedback_smooth) * sc;
+
+            let rate = if rate_m != 0.0 {
+                self.rate_hz * (rate_m * RATE_MOD_OCTAVES).exp2()
+            } else {
+                self.rate_hz
+            };
+            self.phase += rate * israte;
+            if self.phase >= 1.0 { self.phase -= self.phase.floor(); }
+
+            let in_l = main_l[i] as f64;
+            let in_r = main_r[i] as f64;
+            let depth = self.depth_smooth;
+
+            let (dry_l, dry_r, wet_l, wet_r) = match self.mode {
+                ModFxMode::Chorus => {
+                    let fb = self.feedback_smooth * 0.5;
+                    self.line_l.write(in_l + self.fb_l * fb + DENORMAL_GUARD);
+                    self.line_r.write(in_r + self.fb_r * fb - DENORMAL_GUARD);
+
+                    // Voices are evenly spaced in LFO phase; alternate voices
+                    // lean left/right so a single voice still spreads.
+                    let n = self.voices;
+                    let mut sum_l = 0.0;
+                    let mut sum_r = 0.0;
+                    for v in 0..n {
+                        let voice_phase = self.phase + v as f64 / n as f64;
+                        let base = CHORUS_BASE_MS + v as f64 * 1.7;
+                        let d_l = (base + triangle(voice_phase) * CHORUS_DEPTH_MS * depth) * ms;
+                        let d_r = (base + triangle(voice_phase + r_offset) * CHORUS_DEPTH_MS * depth) * ms;
+                        sum_l += self.line_l.read_interpolated(d_l);
+                        sum_r += self.line_r.read_interpolated(d_r);
+                    }
+                    let norm = 1.0 / (n as f64).sqrt();
+                    // BBD character: band-limited, lightly saturated wet path
+                    let wet_l = (self.bbd_lpf_l.tick_lpf(sum_l * norm) * 1.2).tanh() / 1.2;
+                    let wet_r = (self.bbd_lpf_r.tick_lpf(sum_r * norm) * 1.2).tanh() / 1.2;
+                    self.fb_l = wet_l;
+                    self.fb_r = wet_r;
+                    (in_l, in_r, wet_l, wet_r)
+                }
+                ModFxMode::Flanger => {
+                    let fb = self.feedback_smooth * 0.95;
+                    self.line_l.write(in_l + self.fb_l * fb + DENORMAL_GUARD);
+                    self.line_r.write(in_r + self.fb_r * fb - DENORMAL_GUARD);
+
+                    // Through-zero: the dry path is a fixed tap at the centre
+                    // delay, the wet tap sweeps either side of it.
+                    let center = FLANGER_CENTER_MS * ms;
+                    let sweep_l = (self.phase * 2.0 * PI).sin() * depth;
+                    let sweep_r = ((self.phase + r_offset) * 2.0 * PI).sin() * depth;
+                    let dry_l = self.line_l.read_interpolated(center);
+                    let dry_r = self.line_r.read_interpolated(center);
+                    let wet_l = self.line_l.read_interpolated(center * (1.0 + sweep_l));
+                    let wet_r = self.line_r.read_interpolated(center * (1.0 + sweep_r));
+                    self.fb_l = wet_l;
+                    self.fb_r = wet_r;
+                    (dry_l, dry_r, wet_l, wet_r)
+                }
+                ModFxMode::Phaser => {
+                    let fb = self.feedback_smooth * 0.9;
+                    let lfo_l = (self.phase * 2.0 * PI).sin();
+                    let lfo_r = ((self.phase + r_offset) * 2.0 * PI).sin();
+                    let nyq = self.sample_rate * 0.45;
+                    let f_l = (PHASER_CENTER_HZ * (lfo_l * depth * PHASER_OCTAVES * 0.5).exp2()).clamp(20.0, nyq);
+                    let f_r = (PHASER_CENTER_HZ * (lfo_r * depth * PHASER_OCTAVES * 0.5).exp2()).clamp(20.0, nyq);
+                    let t_l = (PI * f_l * israte).tan();
+                    let t_r = (PI * f_r * israte).tan();
+                    let a_l = (t_l - 1.0) / (t_l + 1.0);
+                    let a_r = (t_r - 1.0) / (t_r + 1.0);
+
+                    let mut x_l = in_l + self.fb_l * fb + DENORMAL_GUARD;
+                    let mut x_r = in_r + self.fb_r * fb - DENORMAL_GUARD;
+                    for s in 0..self.stages {
+                        x_l = self.allpass_l[s].tick(x_l, a_l);
+                        x_r = self.allpass_r[s].tick(x_r, a_r);
+                    }
+                    self.fb_l = x_l;
+                    self.fb_r = x_r;
+                    (in_l, in_r, x_l, x_r)
+                }
+            };
+
+            let mix = self.mix_smooth;
+            main_l[i] = (dry_l * (1.0 - mix) + wet_l * mix) as f32;
+            main_r[i] = (dry_r * (1.0 - mix) + wet_r * mix) as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(len: usize, freq: f64) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * PI * freq * i as f64 / 48000.0).sin() as f32)
+            .collect()
+    }
+
+    fn rms(buf: &[f32]) -> f64 {
+        (buf.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / buf.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn flanger_without_depth_is_a_pure_delay() {
+        let mut fx = ModFx::new(48000.0);
+        fx.set_params(&ModFxParams {
+            mode: ModFxMode::Flanger,
+            rate_hz: 0.5,
+            depth: 0.0,
+            feedback: 0.0,
+            mix: 1.0,
+            voices: 1,
+            stages: 4,
+            spread: 0.0,
+        });
+        fx.reset();
+        fx.mix_smooth = 1.0;
+        let input = sine(4800, 440.0);
+        let mut l = input.clone();
+        let mut r = input.clone();
+        fx.process_block(&mut l, &mut r, &[], &[], &[]);
+
+        let center = (FLANGER_CENTER_MS * 48.0) as usize;
+        for i in 1000..1100 {
+            assert!((l[i] - input[i - center]).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn phaser_preserves_level_when_fully_wet() {
+        let mut fx = ModFx::new(48000.0);
+        fx.set_params(&ModFxParams {
+            mode: ModFxMode::Phaser,
+            rate_hz: 0.2,
+            depth: 1.0,
+            feedback: 0.0,
+            mix: 1.0,
+            voices: 1,
+            stages: 8,
+            spread: 0.5,
+        });
+        fx.reset();
+        fx.mix_smooth = 1.0;
+        let input = sine(48000, 1000.0);
+        let mut l = input.clone();
+        let mut r = input.clone();
+        fx.process_block(&mut l, &mut r, &[], &[], &[]);
+
+        let ratio = rms(&l[4800..]) / rms(&input[4800..]);
+        assert!((ratio - 1.0).abs() < 0.05, "allpass chain changed level: {ratio}");
+    }
+}
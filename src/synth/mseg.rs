@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+
+use super::dsp::SlewValue;
+use super::lfo::{LfoSyncDivision, ModDestination, ModulationValues};
+
+pub const MSEG_COUNT: usize = 2;
+pub const MSEG_MAX_POINTS: usize = 16;
+
+const MIN_SEGMENT_MS: f64 = 0.5;
+pub const MAX_SEGMENT_MS: f32 = 10000.0;
+/// Curve ±1 bends a segment to an e^±6 exponential.
+const CURVE_RANGE: f64 = 6.0;
+/// The bar clock for bar retriggers assumes 4/4.
+const BEATS_PER_BAR: f64 = 4.0;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MsegPoint {
+    /// -1..1
+    pub level: f32,
+    /// Time from the previous point when free-running.
+    pub time_ms: f32,
+    /// `LfoSyncDivision` index for the time from the previous point when synced.
+    pub division: i32,
+    /// Bend of the segment arriving at this point: < 0 rushes, > 0 eases in.
+    pub curve: f32,
+}
+
+impl MsegPoint {
+    pub const fn new(level: f32, time_ms: f32, division: i32, curve: f32) -> Self {
+        Self { level, time_ms, division, curve }
+    }
+}
+
+/// Breakpoints of one envelope. The first point is the start level; its time is ignored.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MsegShape {
+    pub points: [MsegPoint; MSEG_MAX_POINTS],
+    pub count: usize,
+    pub looping: bool,
+    pub loop_start: usize,
+    pub loop_end: usize,
+}
+
+impl Default for MsegShape {
+    /// A plucked rise and fall: 0 → 1 over a 1/16, back to 0 over a 1/4.
+    fn default() -> Self {
+        let mut points = [MsegPoint::new(0.0, 100.0, 4, 0.0); MSEG_MAX_POINTS];
+        points[1] = MsegPoint::new(1.0, 50.0, 4, -0.3);
+        points[2] = MsegPoint::new(0.0, 400.0, 2, 0.5);
+        Self { points, count: 3, looping: false, loop_start: 0, loop_end: 2 }
+    }
+}
+
+impl MsegShape {
+    pub fn points(&self) -> &[MsegPoint] {
+        &self.points[..self.count]
+    }
+
+    /// Clamps the point count, levels and loop points back into range.
+    pub fn sanitize(&mut self) {
+        self.count = self.count.clamp(2, MSEG_MAX_POINTS);
+        for p in &mut self.points {
+            p.level = p.level.clamp(-1.0, 1.0);
+            p.time_ms = p.time_ms.clamp(MIN_SEGMENT_MS as f32, MAX_SEGMENT_MS);
+            p.curve = p.curve.clamp(-1.0, 1.0);
+        }
+        self.loop_end = self.loop_end.clamp(1, self.count - 1);
+        self.loop_start = self.loop_start.min(self.loop_end - 1);
+    }
+
+    /// Inserts a point before `index`, keeping loop points on the same breakpoints.
+    pub fn insert(&mut self, index: usize, point: MsegPoint) -> bool {
+        if self.count >= MSEG_MAX_POINTS || index == 0 || index > self.count {
+            return false;
+        }
+        self.points.copy_within(index..self.count, index + 1);
+        self.points[index] = point;
+        self.count += 1;
+        if self.loop_start >= index {
+            self.loop_start += 1;
+        }
+        if self.loop_end >= index {
+            self.loop_end += 1;
+        }
+        self.sanitize();
+        true
+    }
+
+    /// Removes a point; the start point and the last two points stay.
+    pub fn remove(&mut self, index: usize) -> bool {
+        if self.count <= 2 || index == 0 || index >= self.count {
+            return false;
+        }
+        self.points.copy_within(index + 1..self.count, index);
+        self.count -= 1;
+        if self.loop_start >= index {
+            self.loop_start = self.loop_start.saturating_sub(1);
+        }
+        if self.loop_end >= index {
+            self.loop_end -= 1;
+        }
+        self.sanitize();
+        true
+    }
+
+    /// Length of the segment arriving at point `index`, in seconds.
+    pub fn segment_seconds(&self, index: usize, tempo_sync: bool, bpm: f64) -> f64 {
+        let p = &self.points[index];
+        let seconds = if tempo_sync {
+            LfoSyncDivision::from_index(p.division).beats() * 60.0 / bpm.max(1.0)
+        } else {
+            p.time_ms as f64 * 0.001
+        };
+        seconds.max(MIN_SEGMENT_MS * 0.001)
+    }
+
+    /// Level along the segment arriving at point `index`, `t` in 0..1.
+    pub fn segment_value(&self, index: usize, t: f64) -> f64 {
+        let from = self.points[index - 1].level as f64;
+        let to = &self.points[index];
+        from + (to.level as f64 - from) * curve_shape(t, to.curve as f64)
+    }
+}
+
+#[inline]
+fn curve_shape(t: f64, curve: f64) -> f64 {
+    if curve.abs() < 1e-3 {
+        return t;
+    }
+    let k = curve * CURVE_RANGE;
+    (k * t).exp_m1() / k.exp_m1()
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MsegRetrigger {
+    Free,
+    Note,
+    Bar,
+}
+
+impl MsegRetrigger {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Note,
+            2 => Self::Bar,
+            _ => Self::Free,
+        }
+    }
+}
+
+pub struct Mseg {
+    shape: MsegShape,
+    tempo_sync: bool,
+    retrigger: MsegRetrigger,
+    /// Point the current segment is heading to (1..count)
+    segment: usize,
+    segment_pos: f64,
+    holding: bool,
+    output: f64,
+}
+
+impl Mseg {
+    pub fn new() -> Self {
+        Self {
+            shape: MsegShape::default(),
+            tempo_sync: true,
+            retrigger: MsegRetrigger::Note,
+            segment: 1,
+            segment_pos: 0.0,
+            holding: false,
+            output: 0.0,
+        }
+    }
+
+    pub fn set_shape(&mut self, shape: &MsegShape) {
+        self.shape = *shape;
+        self.shape.sanitize();
+        if self.segment >= self.shape.count {
+            self.segment = self.shape.count - 1;
+            self.segment_pos = 1.0;
+        }
+    }
+
+    pub fn trigger(&mut self) {
+        self.segment = 1;
+        self.segment_pos = 0.0;
+        self.holding = false;
+    }
+
+    /// Playhead in points: 1.5 is halfway between the second and third point.
+    pub fn position(&self) -> f64 {
+        (self.segment - 1) as f64 + self.segment_pos.min(1.0)
+    }
+
+    pub fn output(&self) -> f64 {
+        self.output
+    }
+
+    fn process(&mut self, bpm: f64, sample_rate: f64) -> f64 {
+        if self.holding {
+            return self.output;
+        }
+        let seconds = self.shape.segment_seconds(self.segment, self.tempo_sync, bpm);
+        self.segment_pos += 1.0 / (seconds * sample_rate);
+
+        while self.segment_pos >= 1.0 {
+            let last = self.shape.count - 1;
+            let loop_end = self.shape.looping && self.segment == self.shape.loop_end;
+            if loop_end || (self.segment == last && self.retrigger == MsegRetrigger::Free) {
+                // Free-running without a loop cycles the whole shape like an LFO
+                let start = if loop_end { self.shape.loop_start } else { 0 };
+                self.segment = start + 1;
+                self.segment_pos -= 1.0;
+            } else if self.segment < last {
+                self.segment += 1;
+                self.segment_pos -= 1.0;
+            } else {
+                self.segment_pos = 1.0;
+                self.holding = true;
+                break;
+            }
+        }
+
+        self.output = self.shape.segment_value(self.segment, self.segment_pos.min(1.0));
+        self.output
+    }
+}
+
+impl Default for Mseg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Two multi-segment envelopes with two modulation slots each.
+pub struct MsegBank {
+    msegs: [Mseg; MSEG_COUNT],
+    destinations: [[ModDestination; 2]; MSEG_COUNT],
+    amounts: [[f64; 2]; MSEG_COUNT],
+    amount_slews: [[SlewValue; 2]; MSEG_COUNT],
+    /// Beats into the current bar, for bar retriggers
+    bar_beats: f64,
+    sample_rate: f64,
+}
+
+impl MsegBank {
+    pub fn new(sample_rate: f64) -> Self {
+        let make_slew = || {
+            let mut s = SlewValue::new();
+            s.set_sample_rate(sample_rate);
+            s
+        };
+
+        Self {
+            msegs: [Mseg::new(), Mseg::new()],
+            destinations: [[ModDestination::None; 2]; MSEG_COUNT],
+            amounts: [[0.0; 2]; MSEG_COUNT],
+            amount_slews: [[make_slew(), make_slew()], [make_slew(), make_slew()]],
+            bar_beats: 0.0,
+            sample_rate,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        for slots in &mut self.amount_slews {
+            for slew in slots {
+                slew.set_sample_rate(sample_rate);
+            }
+        }
+    }
+
+    pub fn set_shape(&mut self, idx: usize, shape: &MsegShape) {
+        if idx < MSEG_COUNT {
+            self.msegs[idx].set_shape(shape);
+        }
+    }
+
+    pub fn set_params(&mut self, idx: usize, tempo_sync: bool, retrigger: i32) {
+        if idx < MSEG_COUNT {
+            self.msegs[idx].tempo_sync = tempo_sync;
+            self.msegs[idx].retrigger = MsegRetrigger::from_index(retrigger);
+        }
+    }
+
+    pub fn set_modulation(&mut self, idx: usize, slot: usize, destination: i32, amount: f64) {
+        if idx < MSEG_COUNT && slot < 2 {
+            self.destinations[idx][slot] = ModDestination::from_index(destination);
+            self.amounts[idx][slot] = amount;
+        }
+    }
+
+    /// Restarts every note-retriggered envelope.
+    pub fn note_on(&mut self) {
+        for mseg in &mut self.msegs {
+            if mseg.retrigger == MsegRetrigger::Note {
+                mseg.trigger();
+            }
+        }
+    }
+
+    /// Realigns all envelopes and the bar clock to a transport start.
+    pub fn reset_phases(&mut self) {
+        self.bar_beats = 0.0;
+        for mseg in &mut self.msegs {
+            mseg.trigger();
+        }
+    }
+
+    pub fn position(&self, idx: usize) -> f64 {
+        if idx < MSEG_COUNT { self.msegs[idx].position() } else { 0.0 }
+    }
+
+    pub fn get_output(&self, idx: usize) -> f64 {
+        if idx < MSEG_COUNT { self.msegs[idx].output() } else { 0.0 }
+    }
+
+    pub fn process(&mut self, bpm: f64) -> ModulationValues {
+        let mut mod_values = ModulationValues::default();
+
+        self.bar_beats += bpm / 60.0 / self.sample_rate;
+        if self.bar_beats >= BEATS_PER_BAR {
+            self.bar_beats -= BEATS_PER_BAR;
+            for mseg in &mut self.msegs {
+                if mseg.retrigger == MsegRetrigger::Bar {
+                    mseg.trigger();
+                }
+            }
+        }
+
+        for idx in 0..MSEG_COUNT {
+            let value = self.msegs[idx].process(bpm, self.sample_rate);
+            for slot in 0..2 {
+                let dest = self.destinations[idx][slot];
+                let amount = self.amount_slews[idx][slot].next(self.amounts[idx][slot], 30.0);
+                mod_values.add_modulation(dest, amount, value);
+            }
+        }
+
+        mod_values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn free_shape() -> MsegShape {
+        let mut shape = MsegShape::default();
+        shape.points[1] = MsegPoint::new(1.0, 10.0, 0, 0.0);
+        shape.points[2] = MsegPoint::new(-1.0, 10.0, 0, 0.0);
+        shape
+    }
+
+    fn run(mseg: &mut Mseg, ms: f64) -> Vec<f64> {
+        let n = (ms * 48.0) as usize;
+        (0..n).map(|_| mseg.process(120.0, 48000.0)).collect()
+    }
+
+    #[test]
+    fn one_shot_holds_last_level() {
+        let mut mseg = Mseg::new();
+        mseg.tempo_sync = false;
+        mseg.set_shape(&free_shape());
+        let out = run(&mut mseg, 30.0);
+        // Linear segments: peak after 10 ms, bottom after 20 ms, then hold
+        assert!((out[479] - 1.0).abs() < 0.01);
+        assert!(out[1000..].iter().all(|&v| v == -1.0));
+    }
+
+    #[test]
+    fn loop_region_repeats_and_free_mode_cycles() {
+        let mut shape = free_shape();
+        shape.looping = true;
+        shape.loop_start = 1;
+        shape.loop_end = 2;
+        let mut mseg = Mseg::new();
+        mseg.tempo_sync = false;
+        mseg.set_shape(&shape);
+        let out = run(&mut mseg, 45.0);
+        // After the attack, the 1 → -1 segment restarts every 10 ms
+        assert!((out[1439] - out[959]).abs() < 0.01);
+        assert!(out[1439] < -0.9);
+
+        let mut mseg = Mseg::new();
+        mseg.tempo_sync = false;
+        mseg.retrigger = MsegRetrigger::Free;
+        mseg.set_shape(&free_shape());
+        let out = run(&mut mseg, 45.0);
+        // Without a loop, free mode wraps back to the start point
+        assert!((out[959] + 1.0).abs() < 0.01);
+        assert!((out[1200] - 0.5).abs() < 0.01);
+        assert!((out[1439] - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn synced_segments_follow_tempo() {
+        let mut shape = free_shape();
+        // 1/4 at 120 BPM is 500 ms
+        shape.points[1].division = 2;
+        let mut mseg = Mseg::new();
+        mseg.set_shape(&shape);
+        let out = run(&mut mseg, 250.0);
+        assert!((out.last().unwrap() - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn curve_bends_segment_and_edits_keep_loop_points() {
+        assert!(curve_shape(0.5, 1.0) < 0.1);
+        assert!(curve_shape(0.5, -1.0) > 0.9);
+        assert_eq!(curve_shape(1.0, 0.7), 1.0);
+
+        let mut shape = free_shape();
+        shape.looping = true;
+        shape.loop_start = 1;
+        shape.loop_end = 2;
+        assert!(shape.insert(1, MsegPoint::new(0.5, 5.0, 4, 0.0)));
+        assert_eq!((shape.count, shape.loop_start, shape.loop_end), (4, 2, 3));
+        assert!(shape.remove(1));
+        assert_eq!((shape.count, shape.loop_start, shape.loop_end), (3, 1, 2));
+        assert!(shape.remove(1));
+        assert!(!shape.remove(1));
+        assert_eq!((shape.count, shape.loop_start, shape.loop_end), (2, 0, 1));
+    }
+}
@@ -0,0 +1,429 @@
+use std::f64::consts::PI;
+
+use super::dsp::Oversampling;
+use super::ladder_filter::{tracked_cutoff, PolyphaseUpsampler};
+
+const DENORMAL_GUARD: f64 = 1e-18;
+const MAX_SVF_STAGES: usize = 4;
+/// Lowest comb pitch; sizes the delay line.
+const COMB_MIN_HZ: f64 = 20.0;
+/// Cutoff at which the vowel formants sit at their reference frequencies.
+const VOWEL_REFERENCE_HZ: f64 = 1000.0;
+/// Total bell boost, split across however many stages are active.
+const PEAK_GAIN: f64 = 4.0;
+
+/// How the ladder (F1) and this filter (F2) share the oscillator mix.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FilterRouting {
+    /// Mix → F1 → F2.
+    Serial,
+    /// Mix → F1 and mix → F2, averaged.
+    Parallel,
+    /// PLL/VPS/sub → F1, SAW/WT/noise → F2, summed.
+    Split,
+}
+
+impl FilterRouting {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Parallel,
+            2 => Self::Split,
+            _ => Self::Serial,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MultiFilterType {
+    Notch,
+    Peak,
+    Allpass,
+    Comb,
+    Vowel,
+}
+
+impl MultiFilterType {
+    pub fn from_index(idx: u8) -> Self {
+        match idx {
+            1 => Self::Peak,
+            2 => Self::Allpass,
+            3 => Self::Comb,
+            4 => Self::Vowel,
+            _ => Self::Notch,
+        }
+    }
+}
+
+pub struct MultiFilterParams {
+    pub cutoff: f64,
+    pub resonance: f64,
+    pub filter_type: u8,
+    pub morph: f64,
+    pub key_track_hz: f64,
+    pub key_track_amount: f64,
+    pub env_mod: f64,
+}
+
+// Tenor formants (Hz) and levels (dB) for A, E, I, O, U
+const VOWEL_FREQS: [[f64; 3]; 5] = [
+    [650.0, 1080.0, 2650.0],
+    [400.0, 1700.0, 2600.0],
+    [290.0, 1870.0, 2800.0],
+    [400.0, 800.0, 2600.0],
+    [350.0, 600.0, 2700.0],
+];
+const VOWEL_LEVELS_DB: [[f64; 3]; 5] = [
+    [0.0, -6.0, -7.0],
+    [0.0, -14.0, -12.0],
+    [0.0, -15.0, -18.0],
+    [0.0, -10.0, -12.0],
+    [0.0, -20.0, -17.0],
+];
+
+/// TPT state-variable stage; coefficients are shared by the caller.
+#[derive(Clone, Copy, Default)]
+struct SvfStage {
+    ic1eq: f64,
+    ic2eq: f64,
+}
+
+#[derive(Clone, Copy)]
+struct SvfCoeffs {
+    k: f64,
+    a1: f64,
+    a2: f64,
+    a3: f64,
+}
+
+impl SvfCoeffs {
+    fn new(cutoff_hz: f64, k: f64, sample_rate: f64) -> Self {
+        let g = (PI * (cutoff_hz / sample_rate).clamp(0.00001, 0.49)).tan();
+        let a1 = 1.0 / (1.0 + g * (g + k));
+        let a2 = g * a1;
+        Self { k, a1, a2, a3: g * a2 }
+    }
+}
+
+impl SvfStage {
+    /// Returns (lowpass, bandpass, highpass).
+    #[inline]
+    fn process(&mut self, x: f64, c: &SvfCoeffs) -> (f64, f64, f64) {
+        let v3 = x - self.ic2eq;
+        let v1 = c.a1 * self.ic1eq + c.a2 * v3;
+        let v2 = self.ic2eq + c.a2 * self.ic1eq + c.a3 * v3;
+        self.ic1eq = 2.0 * v1 - self.ic1eq + DENORMAL_GUARD;
+        self.ic2eq = 2.0 * v2 - self.ic2eq + DENORMAL_GUARD;
+        (v2, v1, x - c.k * v1 - v2)
+    }
+
+    fn reset(&mut self) {
+        self.ic1eq = 0.0;
+        self.ic2eq = 0.0;
+    }
+}
+
+/// Per-sample settings resolved once and shared by both channels.
+struct Frame {
+    kind: MultiFilterType,
+    svf: SvfCoeffs,
+    stages: usize,
+    stage_frac: f64,
+    peak_stage_gain: f64,
+    comb_delay: f64,
+    comb_feedback: f64,
+    comb_damp: f64,
+    formants: [SvfCoeffs; 3],
+    formant_gains: [f64; 3],
+}
+
+struct ChannelState {
+    svf: [SvfStage; MAX_SVF_STAGES],
+    formant: [SvfStage; 3],
+    comb: Vec<f64>,
+    comb_pos: usize,
+    comb_lp: f64,
+}
+
+impl ChannelState {
+    fn new(comb_len: usize) -> Self {
+        Self {
+            svf: [SvfStage::default(); MAX_SVF_STAGES],
+            formant: [SvfStage::default(); 3],
+            comb: vec![0.0; comb_len],
+            comb_pos: 0,
+            comb_lp: 0.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.svf.iter_mut().for_each(SvfStage::reset);
+        self.formant.iter_mut().for_each(SvfStage::reset);
+        self.comb.fill(0.0);
+        self.comb_pos = 0;
+        self.comb_lp = 0.0;
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64, f: &Frame) -> f64 {
+        match f.kind {
+            MultiFilterType::Notch | MultiFilterType::Peak | MultiFilterType::Allpass => {
+                // Chain stages; Morph crossfades between the last two
+                let mut prev = x;
+                let mut s = x;
+                for stage in self.svf.iter_mut().take(f.stages) {
+                    prev = s;
+                    let (_, bp, _) = stage.process(s, &f.svf);
+                    let kbp = f.svf.k * bp;
+                    s = match f.kind {
+                        MultiFilterType::Notch => s - kbp,
+                        MultiFilterType::Peak => s + f.peak_stage_gain * kbp,
+                        _ => s - 2.0 * kbp,
+                    };
+                }
+                if f.stages > 1 {
+                    prev + (s - prev) * f.stage_frac
+                } else {
+                    s
+                }
+            }
+            MultiFilterType::Comb => {
+                let len = self.comb.len();
+                let read = self.comb_pos as f64 + len as f64 - f.comb_delay;
+                let i0 = read.floor() as usize % len;
+                let i1 = (i0 + 1) % len;
+                let frac = read - read.floor();
+                let delayed = self.comb[i0] + (self.comb[i1] - self.comb[i0]) * frac;
+                self.comb_lp += (delayed - self.comb_lp) * (1.0 - f.comb_damp);
+                let y = x + self.comb_lp * f.comb_feedback;
+                self.comb[self.comb_pos] = y + DENORMAL_GUARD;
+                self.comb_pos = (self.comb_pos + 1) % len;
+                // Unity at the comb peaks
+                y * (1.0 - f.comb_feedback.abs())
+            }
+            MultiFilterType::Vowel => {
+                let mut out = 0.0;
+                for ((stage, c), gain) in self.formant.iter_mut().zip(&f.formants).zip(&f.formant_gains) {
+                    let (_, bp, _) = stage.process(x, c);
+                    out += c.k * bp * gain;
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Second filter slot: SVF notch/peak/allpass, comb and vowel, run at 4×
+/// with the ladder's polyphase upsampler.
+pub struct MultiFilter {
+    sample_rate: f64,
+    cutoff_hz: f64,
+    resonance: f64,
+    kind: MultiFilterType,
+    morph: f64,
+    key_track_hz: f64,
+    key_track_amount: f64,
+    env_mod: f64,
+    left: ChannelState,
+    right: ChannelState,
+    up_left: PolyphaseUpsampler,
+    up_right: PolyphaseUpsampler,
+    os_left: Oversampling<4>,
+    os_right: Oversampling<4>,
+}
+
+impl MultiFilter {
+    pub fn new(sample_rate: f32) -> Self {
+        let sr = sample_rate as f64;
+        let mut os_left = Oversampling::<4>::new();
+        let mut os_right = Oversampling::<4>::new();
+        os_left.set_sample_rate(sr);
+        os_right.set_sample_rate(sr);
+        let comb_len = Self::comb_len(sr);
+
+        Self {
+            sample_rate: sr,
+            cutoff_hz: VOWEL_REFERENCE_HZ,
+            resonance: 0.0,
+            kind: MultiFilterType::Notch,
+            morph: 0.0,
+            key_track_hz: 440.0,
+            key_track_amount: 0.0,
+            env_mod: 0.0,
+            left: ChannelState::new(comb_len),
+            right: ChannelState::new(comb_len),
+            up_left: PolyphaseUpsampler::new(),
+            up_right: PolyphaseUpsampler::new(),
+            os_left,
+            os_right,
+        }
+    }
+
+    fn comb_len(sample_rate: f64) -> usize {
+        (sample_rate * 4.0 / COMB_MIN_HZ).ceil() as usize + 4
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate as f64;
+        let comb_len = Self::comb_len(self.sample_rate);
+        self.left = ChannelState::new(comb_len);
+        self.right = ChannelState::new(comb_len);
+        self.up_left.reset();
+        self.up_right.reset();
+        self.os_left.set_sample_rate(self.sample_rate);
+        self.os_right.set_sample_rate(self.sample_rate);
+    }
+
+    pub fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+        self.up_left.reset();
+        self.up_right.reset();
+        self.os_left.reset();
+        self.os_right.reset();
+    }
+
+    pub fn set_params(&mut self, p: &MultiFilterParams) {
+        let kind = MultiFilterType::from_index(p.filter_type);
+        if kind != self.kind {
+            // Stale state from another topology would ring through
+            self.left.reset();
+            self.right.reset();
+            self.kind = kind;
+        }
+        self.cutoff_hz = p.cutoff;
+        self.resonance = p.resonance.clamp(0.0, 1.0);
+        self.morph = p.morph.clamp(0.0, 1.0);
+        self.key_track_hz = p.key_track_hz;
+        self.key_track_amount = p.key_track_amount.clamp(0.0, 1.0);
+        self.env_mod = p.env_mod.clamp(-1.0, 1.0);
+    }
+
+    fn frame(&self) -> Frame {
+        let os_rate = self.sample_rate * 4.0;
+        let cutoff = tracked_cutoff(self.cutoff_hz, self.key_track_hz, self.key_track_amount, self.env_mod)
+            .clamp(20.0, self.sample_rate * 0.45);
+
+        let stages_f = 1.0 + self.morph * (MAX_SVF_STAGES - 1) as f64;
+        let stages = (stages_f.ceil() as usize).clamp(1, MAX_SVF_STAGES);
+        let stage_frac = stages_f - (stages - 1) as f64;
+        let k = 2.0 - 1.96 * self.resonance;
+
+        let mut formants = [SvfCoeffs::new(VOWEL_REFERENCE_HZ, 1.0, os_rate); 3];
+        let mut formant_gains = [0.0; 3];
+        if self.kind == MultiFilterType::Vowel {
+            let shift = (cutoff / VOWEL_REFERENCE_HZ).clamp(0.25, 4.0);
+            let pos = self.morph * (VOWEL_FREQS.len() - 1) as f64;
+            let a = (pos.floor() as usize).min(VOWEL_FREQS.len() - 2);
+            let t = pos - a as f64;
+            let formant_k = 0.3 - 0.25 * self.resonance;
+            for i in 0..3 {
+                // Interpolate formants in log frequency, levels in dB
+                let hz = VOWEL_FREQS[a][i] * (VOWEL_FREQS[a + 1][i] / VOWEL_FREQS[a][i]).powf(t) * shift;
+                let db = VOWEL_LEVELS_DB[a][i] + (VOWEL_LEVELS_DB[a + 1][i] - VOWEL_LEVELS_DB[a][i]) * t;
+                formants[i] = SvfCoeffs::new(hz.min(self.sample_rate * 0.45), formant_k, os_rate);
+                formant_gains[i] = 10.0_f64.powf(db / 20.0);
+            }
+        }
+
+        Frame {
+            kind: self.kind,
+            svf: SvfCoeffs::new(cutoff, k, os_rate),
+            stages,
+            stage_frac,
+            peak_stage_gain: PEAK_GAIN.powf(1.0 / stages_f) - 1.0,
+            comb_delay: (os_rate / cutoff).clamp(1.0, self.left.comb.len() as f64 - 2.0),
+            comb_feedback: self.resonance * 0.98,
+            comb_damp: self.morph * 0.95,
+            formants,
+            formant_gains,
+        }
+    }
+
+    #[inline]
+    pub fn process(&mut self, left: f64, right: f64) -> (f64, f64) {
+        let frame = self.frame();
+
+        let mut buf_l = self.up_left.process(left);
+        let mut buf_r = self.up_right.process(right);
+        for s in &mut buf_l {
+            *s = self.left.process(*s, &frame);
+        }
+        for s in &mut buf_r {
+            *s = self.right.process(*s, &frame);
+        }
+
+        self.os_left.resample_buffer().copy_from_slice(&buf_l);
+        let mut out_l = self.os_left.downsample();
+        self.os_right.resample_buffer().copy_from_slice(&buf_r);
+        let mut out_r = self.os_right.downsample();
+
+        if !out_l.is_finite() {
+            out_l = 0.0;
+            self.left.reset();
+        }
+        if !out_r.is_finite() {
+            out_r = 0.0;
+            self.right.reset();
+        }
+        (out_l, out_r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone_gain(kind: u8, cutoff: f64, resonance: f64, morph: f64, tone_hz: f64) -> f64 {
+        let sr = 48000.0;
+        let mut filter = MultiFilter::new(sr as f32);
+        filter.set_params(&MultiFilterParams {
+            cutoff,
+            resonance,
+            filter_type: kind,
+            morph,
+            key_track_hz: 440.0,
+            key_track_amount: 0.0,
+            env_mod: 0.0,
+        });
+        let n = 24000;
+        let mut sum_in = 0.0;
+        let mut sum_out = 0.0;
+        for i in 0..n {
+            let x = (std::f64::consts::TAU * tone_hz * i as f64 / sr).sin();
+            let (y, _) = filter.process(x, x);
+            // Skip the settling half
+            if i >= n / 2 {
+                sum_in += x * x;
+                sum_out += y * y;
+            }
+        }
+        (sum_out / sum_in).sqrt()
+    }
+
+    #[test]
+    fn notch_removes_cutoff_and_passes_elsewhere() {
+        assert!(tone_gain(0, 1000.0, 0.0, 0.0, 1000.0) < 0.05);
+        assert!(tone_gain(0, 1000.0, 0.0, 0.0, 8000.0) > 0.8);
+    }
+
+    #[test]
+    fn peak_boosts_cutoff() {
+        let boost = tone_gain(1, 1000.0, 0.5, 0.0, 1000.0);
+        assert!((boost - PEAK_GAIN).abs() < 0.3, "boost {}", boost);
+    }
+
+    #[test]
+    fn allpass_keeps_level() {
+        for hz in [200.0, 1000.0, 5000.0] {
+            let g = tone_gain(2, 1000.0, 0.5, 1.0, hz);
+            assert!((g - 1.0).abs() < 0.05, "{} Hz gain {}", hz, g);
+        }
+    }
+
+    #[test]
+    fn comb_peaks_at_harmonics() {
+        let on = tone_gain(3, 200.0, 0.9, 0.0, 400.0);
+        let off = tone_gain(3, 200.0, 0.9, 0.0, 500.0);
+        assert!(on > off * 4.0, "harmonic {} between {}", on, off);
+    }
+}
@@ -0,0 +1,279 @@
+use std::f64::consts::PI;
+
+use super::dsp::rand_01;
+
+const MIN_RATE_HZ: f64 = 1.0;
+/// Filter cutoffs at the ends of their ranges leave the signal untouched.
+const LPF_OPEN_HZ: f64 = 20000.0;
+const HPF_OPEN_HZ: f64 = 20.0;
+/// Crackle impulses ring down over ~0.3 ms.
+const CRACKLE_DECAY_S: f64 = 0.0003;
+const DENORMAL_GUARD: f64 = 1e-18;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NoiseType {
+    White,
+    Pink,
+    Brown,
+    Crackle,
+    Velvet,
+    SampleHold,
+}
+
+impl NoiseType {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Pink,
+            2 => Self::Brown,
+            3 => Self::Crackle,
+            4 => Self::Velvet,
+            5 => Self::SampleHold,
+            _ => Self::White,
+        }
+    }
+}
+
+/// Two-pole TPT state-variable filter (Q = 0.707) with lowpass and highpass taps.
+#[derive(Clone, Copy, Default)]
+struct Svf {
+    a1: f64,
+    a2: f64,
+    a3: f64,
+    ic1eq: f64,
+    ic2eq: f64,
+    cutoff: f64,
+}
+
+impl Svf {
+    const K: f64 = std::f64::consts::SQRT_2;
+
+    fn set_cutoff(&mut self, cutoff_hz: f64, sample_rate: f64) {
+        // Coefficients only change when the cutoff moves by more than ~1%
+        if (cutoff_hz - self.cutoff).abs() <= self.cutoff * 0.01 {
+            return;
+        }
+        self.cutoff = cutoff_hz;
+        let g = (PI * (cutoff_hz / sample_rate).clamp(0.0001, 0.49)).tan();
+        self.a1 = 1.0 / (1.0 + g * (g + Self::K));
+        self.a2 = g * self.a1;
+        self.a3 = g * self.a2;
+    }
+
+    fn reset(&mut self) {
+        self.ic1eq = 0.0;
+        self.ic2eq = 0.0;
+    }
+
+    /// Returns (lowpass, highpass).
+    #[inline]
+    fn process(&mut self, x: f64) -> (f64, f64) {
+        let v3 = x - self.ic2eq;
+        let v1 = self.a1 * self.ic1eq + self.a2 * v3;
+        let v2 = self.ic2eq + self.a2 * self.ic1eq + self.a3 * v3;
+        self.ic1eq = 2.0 * v1 - self.ic1eq + DENORMAL_GUARD;
+        self.ic2eq = 2.0 * v2 - self.ic2eq + DENORMAL_GUARD;
+        (v2, x - Self::K * v1 - v2)
+    }
+}
+
+/// Mono noise source: colour/impulse generator → highpass → lowpass.
+pub struct NoiseGenerator {
+    sample_rate: f64,
+
+    kind: NoiseType,
+    rate_hz: f64,
+    lpf_hz: f64,
+    hpf_hz: f64,
+
+    // Paul Kellet's pink filter
+    pink: [f64; 7],
+    brown: f64,
+    crackle: f64,
+    crackle_decay: f64,
+    // Shared by velvet (impulse slot) and S&H (hold period)
+    period_phase: f64,
+    velvet_at: f64,
+    velvet_fired: bool,
+    held: f64,
+
+    lpf: Svf,
+    hpf: Svf,
+}
+
+impl NoiseGenerator {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut noise = Self {
+            sample_rate: sample_rate as f64,
+            kind: NoiseType::White,
+            rate_hz: 1000.0,
+            lpf_hz: LPF_OPEN_HZ,
+            hpf_hz: HPF_OPEN_HZ,
+            pink: [0.0; 7],
+            brown: 0.0,
+            crackle: 0.0,
+            crackle_decay: 0.0,
+            period_phase: 0.0,
+            velvet_at: 0.0,
+            velvet_fired: true,
+            held: 0.0,
+            lpf: Svf::default(),
+            hpf: Svf::default(),
+        };
+        noise.update_coefficients();
+        noise
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate as f64;
+        self.lpf.cutoff = 0.0;
+        self.hpf.cutoff = 0.0;
+        self.update_coefficients();
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.pink = [0.0; 7];
+        self.brown = 0.0;
+        self.crackle = 0.0;
+        self.period_phase = 0.0;
+        self.velvet_fired = true;
+        self.held = 0.0;
+        self.lpf.reset();
+        self.hpf.reset();
+    }
+
+    /// `rate_hz` is the S&H clock and the crackle/velvet impulse density.
+    pub fn set_params(&mut self, kind: NoiseType, rate_hz: f64, lpf_hz: f64, hpf_hz: f64) {
+        self.kind = kind;
+        self.rate_hz = rate_hz.clamp(MIN_RATE_HZ, self.sample_rate * 0.5);
+        self.lpf_hz = lpf_hz;
+        self.hpf_hz = hpf_hz;
+        self.update_coefficients();
+    }
+
+    fn update_coefficients(&mut self) {
+        self.lpf.set_cutoff(self.lpf_hz, self.sample_rate);
+        self.hpf.set_cutoff(self.hpf_hz, self.sample_rate);
+        self.crackle_decay = (-1.0 / (CRACKLE_DECAY_S * self.sample_rate)).exp();
+    }
+
+    #[inline]
+    fn white() -> f64 {
+        rand_01() * 2.0 - 1.0
+    }
+
+    #[inline]
+    fn random_sign() -> f64 {
+        if rand_01() < 0.5 { -1.0 } else { 1.0 }
+    }
+
+    #[inline]
+    fn raw(&mut self) -> f64 {
+        match self.kind {
+            NoiseType::White => Self::white(),
+            NoiseType::Pink => {
+                let w = Self::white();
+                let b = &mut self.pink;
+                b[0] = 0.99886 * b[0] + w * 0.0555179;
+                b[1] = 0.99332 * b[1] + w * 0.0750759;
+                b[2] = 0.96900 * b[2] + w * 0.1538520;
+                b[3] = 0.86650 * b[3] + w * 0.3104856;
+                b[4] = 0.55000 * b[4] + w * 0.5329522;
+                b[5] = -0.7616 * b[5] - w * 0.0168980;
+                let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + w * 0.5362;
+                b[6] = w * 0.115926;
+                pink * 0.2
+            }
+            NoiseType::Brown => {
+                self.brown = (self.brown + Self::white() * 0.02) / 1.02;
+                self.brown * 3.5
+            }
+            NoiseType::Crackle => {
+                self.crackle *= self.crackle_decay;
+                if rand_01() < self.rate_hz / self.sample_rate {
+                    // Mostly faint ticks with the occasional loud pop
+                    let amp = rand_01();
+                    self.crackle = amp * amp * amp * Self::random_sign();
+                }
+                self.crackle
+            }
+            NoiseType::Velvet => {
+                // One ±1 impulse at a random slot in each period
+                let step = self.rate_hz / self.sample_rate;
+                self.period_phase += step;
+                if self.period_phase >= 1.0 {
+                    self.period_phase -= 1.0;
+                    // The last sample of a period lands at or after 1 - step
+                    self.velvet_at = rand_01() * (1.0 - step);
+                    self.velvet_fired = false;
+                }
+                if !self.velvet_fired && self.period_phase >= self.velvet_at {
+                    self.velvet_fired = true;
+                    Self::random_sign()
+                } else {
+                    0.0
+                }
+            }
+            NoiseType::SampleHold => {
+                self.period_phase += self.rate_hz / self.sample_rate;
+                if self.period_phase >= 1.0 {
+                    self.period_phase -= 1.0;
+                    self.held = Self::white();
+                }
+                self.held
+            }
+        }
+    }
+
+    #[inline]
+    pub fn next(&mut self) -> f64 {
+        let mut s = self.raw();
+        if self.hpf_hz > HPF_OPEN_HZ {
+            s = self.hpf.process(s).1;
+        }
+        if self.lpf_hz < LPF_OPEN_HZ {
+            s = self.lpf.process(s).0;
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_hold_steps_at_rate() {
+        let mut noise = NoiseGenerator::new(48000.0);
+        noise.set_params(NoiseType::SampleHold, 12000.0, LPF_OPEN_HZ, HPF_OPEN_HZ);
+        let out: Vec<f64> = (0..20).map(|_| noise.next()).collect();
+        // A quarter of the host rate: each held value repeats for four samples
+        for chunk in out[3..19].chunks(4) {
+            assert!(chunk.iter().all(|&v| v == chunk[0]));
+        }
+    }
+
+    #[test]
+    fn velvet_fires_once_per_period() {
+        let mut noise = NoiseGenerator::new(48000.0);
+        noise.set_params(NoiseType::Velvet, 1000.0, LPF_OPEN_HZ, HPF_OPEN_HZ);
+        let out: Vec<f64> = (0..48000).map(|_| noise.next()).collect();
+        let impulses = out.iter().filter(|&&v| v != 0.0).count();
+        assert!((999..=1000).contains(&impulses), "{} impulses", impulses);
+        assert!(out.iter().all(|&v| v == 0.0 || v.abs() == 1.0));
+    }
+
+    #[test]
+    fn brown_is_darker_than_white() {
+        // Mean absolute first difference tracks high-frequency energy
+        let roughness = |kind| {
+            let mut noise = NoiseGenerator::new(48000.0);
+            noise.set_params(kind, 1000.0, LPF_OPEN_HZ, HPF_OPEN_HZ);
+            let out: Vec<f64> = (0..48000).map(|_| noise.next()).collect();
+            let rms = (out.iter().map(|v| v * v).sum::<f64>() / out.len() as f64).sqrt();
+            out.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>() / out.len() as f64 / rms
+        };
+        assert!(roughness(NoiseType::Brown) < roughness(NoiseType::Pink));
+        assert!(roughness(NoiseType::Pink) < roughness(NoiseType::White));
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use super::dsp::{VPSOscillator, PolyBlepOscillator, rand_01, poly_blep_f64};
+use super::dsp::{VPSOscillator, PolyBlepOscillator, rand_01, poly_blep_f64, blep_step};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum PllMode {
@@ -8,10 +8,74 @@ pub enum PllMode {
     EdgePFD,
 }
 
+/// Where the noise source perturbs the PLL.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NoiseInjection {
+    Off,
+    /// Jitters the reference phase / edge the detector sees.
+    Reference,
+    /// Adds to the phase error ahead of the loop filter.
+    LoopFilter,
+    /// Kicks the VCO phase directly, outside the loop's correction.
+    VcoPhase,
+}
+
+impl NoiseInjection {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Reference,
+            2 => Self::LoopFilter,
+            3 => Self::VcoPhase,
+            _ => Self::Off,
+        }
+    }
+}
+
+/// How the two PLL loops are wired.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PllTopology {
+    /// Left/right pair locked to the same reference.
+    Stereo,
+    /// Loop 1's VCO is loop 2's reference.
+    Cascade,
+    /// Each loop's reference is pulled toward the other loop's VCO.
+    Dual,
+}
+
+impl PllTopology {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Cascade,
+            2 => Self::Dual,
+            _ => Self::Stereo,
+        }
+    }
+}
+
+/// Oscillator mixed into the PLL reference.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PllRefSource {
+    Reference,
+    Vps,
+    Saw,
+}
+
+impl PllRefSource {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Vps,
+            2 => Self::Saw,
+            _ => Self::Reference,
+        }
+    }
+}
+
 pub struct Oscillator {
     osc: VPSOscillator,
     freq: f64,
     israte: f64,
+    // Second half of the last sync reset's BLEP
+    blep_pending: f64,
 }
 
 impl Oscillator {
@@ -20,6 +84,7 @@ impl Oscillator {
             osc: VPSOscillator::new(rand_01() * 0.25),
             freq: 220.0,
             israte: 1.0 / sample_rate,
+            blep_pending: 0.0,
         }
     }
 
@@ -43,9 +108,36 @@ impl Oscillator {
         self.osc.set_phase(0.0);
     }
 
+    pub fn phase(&self) -> f64 {
+        self.osc.phase()
+    }
+
     pub fn next(&mut self, d: f64, v: f64) -> f64 {
         let v_limited = VPSOscillator::limit_v(d, v);
-        self.osc.next(self.freq, self.israte, d, v_limited)
+        let pending = std::mem::take(&mut self.blep_pending);
+        self.osc.next(self.freq, self.israte, d, v_limited) + pending
+    }
+
+    /// `reset` is how far before the next sample the master wrapped, in
+    /// samples (negative when it didn't). `amount` scales the phase toward
+    /// zero: 1 is hard sync, below that soft sync.
+    pub fn next_synced(&mut self, d: f64, v: f64, reset: f64, amount: f64) -> f64 {
+        if reset < 0.0 {
+            return self.next(d, v);
+        }
+        let v_limited = VPSOscillator::limit_v(d, v);
+        let dt = self.freq * self.israte;
+        let phase = self.osc.phase();
+        let mut out = VPSOscillator::value_at(phase, d, v_limited) + std::mem::take(&mut self.blep_pending);
+
+        let before = (phase + dt * (1.0 - reset)).fract();
+        let after = before * (1.0 - amount);
+        let jump = VPSOscillator::value_at(after, d, v_limited) - VPSOscillator::value_at(before, d, v_limited);
+        let (pre, post) = blep_step(jump, reset);
+        out += pre;
+        self.blep_pending = post;
+        self.osc.set_phase((after + dt * reset).fract());
+        out
     }
 }
 
@@ -96,6 +188,9 @@ pub struct SawOscillator {
     dc_block_x1: f64,
     dc_block_y1: f64,
     dc_block_r: f64,
+    blep_pending: f64,
+    // Linear FM as a fraction of `freq`; below -1 the phase runs backwards
+    fm: f64,
 }
 
 impl SawOscillator {
@@ -111,6 +206,8 @@ impl SawOscillator {
             dc_block_x1: 0.0,
             dc_block_y1: 0.0,
             dc_block_r: Self::compute_dc_block_r(sample_rate),
+            blep_pending: 0.0,
+            fm: 0.0,
         }
     }
 
@@ -127,15 +224,59 @@ impl SawOscillator {
         self.phase = 0.0;
     }
 
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    /// Through-zero FM for the next step: the instantaneous frequency is
+    /// `freq * (1 + fm)`.
+    #[inline]
+    pub fn set_fm(&mut self, fm: f64) {
+        self.fm = fm;
+    }
+
+    #[inline]
+    fn increment(&self) -> f64 {
+        self.freq * (1.0 + self.fm) / self.sample_rate
+    }
+
     pub fn next(&mut self) -> f64 {
-        let dt = self.freq / self.sample_rate;
-        let raw = 2.0 * self.phase - 1.0 - poly_blep_f64(self.phase, dt);
+        let dt = self.increment();
+        let raw = 2.0 * self.phase - 1.0 - poly_blep_f64(self.phase, dt.abs())
+            + std::mem::take(&mut self.blep_pending);
 
         self.phase += dt;
         if self.phase >= 1.0 {
             self.phase -= 1.0;
+        } else if self.phase < 0.0 {
+            self.phase += 1.0;
+        }
+
+        self.dc_block(raw)
+    }
+
+    /// Same reset convention as [`Oscillator::next_synced`].
+    pub fn next_synced(&mut self, reset: f64, amount: f64) -> f64 {
+        if reset < 0.0 {
+            return self.next();
         }
+        let dt = self.increment();
+        let mut raw = 2.0 * self.phase - 1.0 - poly_blep_f64(self.phase, dt.abs())
+            + std::mem::take(&mut self.blep_pending);
+
+        let before = (self.phase + dt * (1.0 - reset)).rem_euclid(1.0);
+        let after = before * (1.0 - amount);
+        let (pre, post) = blep_step(2.0 * (after - before), reset);
+        raw += pre;
+        self.blep_pending = post;
+
+        self.phase = (after + dt * reset).rem_euclid(1.0);
+
+        self.dc_block(raw)
+    }
 
+    #[inline]
+    fn dc_block(&mut self, raw: f64) -> f64 {
         let y = raw - self.dc_block_x1 + self.dc_block_r * self.dc_block_y1;
         self.dc_block_x1 = raw;
         self.dc_block_y1 = y;
@@ -238,6 +379,14 @@ pub struct PLLOscillator {
     injection_amount: f64,
     injection_mult: f64,
 
+    // Noise injection (sample set by the voice, already scaled by amount)
+    noise_injection: NoiseInjection,
+    noise_in: f64,
+
+    // VCO sync to the reference cycle (0 = off, 1 = hard)
+    vco_sync: f64,
+    sync_ref_phase: f64,
+
     // Cached computed values
     cached_israte: f64,
     cached_nyquist: f64,
@@ -317,6 +466,12 @@ impl PLLOscillator {
             injection_amount: 0.0,
             injection_mult: 2.0,
 
+            noise_injection: NoiseInjection::Off,
+            noise_in: 0.0,
+
+            vco_sync: 0.0,
+            sync_ref_phase: 0.0,
+
             cached_israte: israte,
             cached_nyquist: 0.48 * sample_rate,
             cached_integrator_decay: 0.0,
@@ -391,6 +546,23 @@ impl PLLOscillator {
         self.injection_mult = if x4 { 4.0 } else { 2.0 };
     }
 
+    pub fn set_noise_injection(&mut self, point: NoiseInjection) {
+        self.noise_injection = point;
+    }
+
+    #[inline]
+    pub fn set_noise_input(&mut self, noise: f64) {
+        self.noise_in = noise;
+    }
+
+    pub fn set_vco_sync(&mut self, amount: f64) {
+        self.vco_sync = amount.clamp(0.0, 1.0);
+    }
+
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
     #[allow(dead_code)]
     pub fn get_phase_delta(&self) -> f64 {
         self.phase_delta
@@ -556,6 +728,16 @@ impl PLLOscillator {
         use std::f64::consts::TAU;
 
         self.base_freq = input_freq;
+        // A drop of more than half a cycle is a forward wrap; smaller drops
+        // are a reference running backwards under through-zero FM
+        let ref_wrapped = self.sync_ref_phase - input_phase > 0.5;
+        self.sync_ref_phase = input_phase;
+
+        let (input_phase, ref_pulse) = if self.noise_injection == NoiseInjection::Reference {
+            (input_phase + self.noise_in * 0.25, ref_pulse + self.noise_in)
+        } else {
+            (input_phase, ref_pulse)
+        };
 
         let phase_error = match self.mode {
             PllMode::AnalogLikePD => {
@@ -566,6 +748,11 @@ impl PLLOscillator {
                 self.next_pfd(ref_pulse)
             }
         };
+        let phase_error = if self.noise_injection == NoiseInjection::LoopFilter {
+            (phase_error + self.noise_in).clamp(-1.0, 1.0)
+        } else {
+            phase_error
+        };
 
         self.phase_delta = phase_error;
 
@@ -596,6 +783,18 @@ impl PLLOscillator {
         }
 
         self.phase += corrected_freq.clamp(20.0, self.cached_nyquist) * self.cached_israte;
+        if self.noise_injection == NoiseInjection::VcoPhase {
+            self.phase += self.noise_in * 0.05;
+        }
+
+        if self.vco_sync > 0.0 && ref_wrapped {
+            // Pull the phase the VCO had when the reference wrapped toward
+            // zero, then replay the time since the wrap
+            let inc = corrected_freq.clamp(20.0, self.cached_nyquist) * self.cached_israte;
+            let since = (self.sync_ref_phase / (input_freq * self.cached_israte).max(1e-9)).clamp(0.0, 1.0);
+            let at_reset = (self.phase - inc * since).rem_euclid(1.0);
+            self.phase = at_reset * (1.0 - self.vco_sync) + inc * since;
+        }
 
         if self.phase >= 1.0 {
             self.phase -= 1.0;
@@ -668,4 +867,38 @@ mod tests {
         assert!(max_error < 0.0001, "max error {:.8} exceeds 0.01% threshold", max_error);
         assert!(rms_error < 0.00005, "RMS error {:.8} exceeds 0.005% threshold", rms_error);
     }
+    #[test]
+    fn saw_hard_sync_restarts_at_reset_point() {
+        let mut saw = SawOscillator::new(48000.0);
+        saw.set_frequency(480.0);
+        saw.phase = 0.6;
+        saw.next_synced(0.25, 1.0);
+        // The master wrapped a quarter sample before now: that much phase since the reset
+        assert!((saw.phase - 0.01 * 0.25).abs() < 1e-12, "phase {}", saw.phase);
+    }
+
+    #[test]
+    fn saw_through_zero_fm_runs_backwards() {
+        let mut saw = SawOscillator::new(48000.0);
+        saw.set_frequency(480.0);
+        saw.phase = 0.005;
+        saw.set_fm(-2.0);
+        assert!(saw.next().is_finite());
+        // -480 Hz instantaneous: one step back across the wrap
+        assert!((saw.phase - 0.995).abs() < 1e-12, "phase {}", saw.phase);
+    }
+
+    #[test]
+    fn zero_sync_amount_matches_free_running() {
+        let mut free = SawOscillator::new(48000.0);
+        let mut synced = SawOscillator::new(48000.0);
+        for osc in [&mut free, &mut synced] {
+            osc.set_frequency(330.0);
+            osc.phase = 0.3;
+        }
+        for i in 0..256 {
+            let reset = if i % 17 == 0 { 0.5 } else { -1.0 };
+            assert!((free.next() - synced.next_synced(reset, 0.0)).abs() < 1e-9);
+        }
+    }
 }
@@ -0,0 +1,415 @@
+use std::f64::consts::{PI, TAU};
+
+use super::dsp::rand_01;
+
+const DENORMAL_GUARD: f64 = 1e-18;
+/// Lowest tracked pitch; sizes the delay lines.
+const MIN_FREQ_HZ: f64 = 20.0;
+const MODES: usize = 8;
+/// Longest noise burst fired on trigger.
+const BURST_S: f64 = 0.005;
+/// Decay control maps to a T60 of 50 ms .. 10 s.
+const T60_MIN_S: f64 = 0.05;
+const T60_RANGE: f64 = 200.0;
+/// Brings a burst through the modes to roughly the string's level.
+const MODAL_BURST_GAIN: f64 = 0.25;
+
+// Free-free bar partials
+const BAR_RATIOS: [f64; MODES] = [1.0, 2.756, 5.404, 8.933, 13.344, 18.638, 24.815, 31.877];
+// Church bell partials relative to the prime: hum, prime, tierce, quint, nominal and above
+const BELL_RATIOS: [f64; MODES] = [0.5, 1.0, 1.183, 1.506, 2.0, 2.514, 2.662, 3.011];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ResonatorModel {
+    /// Karplus-Strong string.
+    String,
+    Bar,
+    Bell,
+    /// Closed-open tube: odd harmonics only.
+    Tube,
+}
+
+impl ResonatorModel {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Bar,
+            2 => Self::Bell,
+            3 => Self::Tube,
+            _ => Self::String,
+        }
+    }
+
+    fn is_modal(self) -> bool {
+        matches!(self, Self::Bar | Self::Bell)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ResonatorExcite {
+    /// The voice's oscillator mix drives the resonator continuously.
+    Mix,
+    /// A noise burst on each trigger.
+    Noise,
+    Both,
+}
+
+impl ResonatorExcite {
+    pub fn from_index(i: i32) -> Self {
+        match i {
+            1 => Self::Noise,
+            2 => Self::Both,
+            _ => Self::Mix,
+        }
+    }
+}
+
+pub struct ResonatorParams {
+    pub model: ResonatorModel,
+    pub excite: ResonatorExcite,
+    pub frequency: f64,
+    pub decay: f64,
+    pub brightness: f64,
+    pub position: f64,
+    pub mix: f64,
+}
+
+/// Delay-line loop shared by the string and tube models.
+struct Waveguide {
+    line: Vec<f64>,
+    pos: usize,
+    damp_prev: f64,
+    // Feedforward comb on the excitation: the pick/blow position
+    pick_line: Vec<f64>,
+    pick_pos: usize,
+}
+
+impl Waveguide {
+    fn new(len: usize) -> Self {
+        Self {
+            line: vec![0.0; len],
+            pos: 0,
+            damp_prev: 0.0,
+            pick_line: vec![0.0; len],
+            pick_pos: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.line.fill(0.0);
+        self.pos = 0;
+        self.damp_prev = 0.0;
+        self.pick_line.fill(0.0);
+        self.pick_pos = 0;
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64, delay: f64, pick: usize, gain: f64, damp: f64, invert: bool) -> f64 {
+        let len = self.line.len();
+
+        self.pick_line[self.pick_pos] = x;
+        let picked = x - self.pick_line[(self.pick_pos + len - pick) % len];
+        self.pick_pos = (self.pick_pos + 1) % len;
+
+        let read = self.pos as f64 + len as f64 - delay;
+        let i0 = read.floor() as usize % len;
+        let i1 = (i0 + 1) % len;
+        let frac = read - read.floor();
+        let delayed = self.line[i0] + (self.line[i1] - self.line[i0]) * frac;
+
+        // Two-tap loop lowpass; its phase delay (damp samples) is taken off the line length
+        let lp = (1.0 - damp) * delayed + damp * self.damp_prev;
+        self.damp_prev = delayed;
+        let fb = if invert { -lp } else { lp } * gain;
+
+        let y = picked + fb;
+        self.line[self.pos] = y + DENORMAL_GUARD;
+        self.pos = (self.pos + 1) % len;
+        y
+    }
+}
+
+/// Two-pole resonator for one partial of the modal models.
+#[derive(Clone, Copy, Default)]
+struct Mode {
+    a1: f64,
+    a2: f64,
+    // Unity peak gain for continuous input
+    b_mix: f64,
+    // Impulse input; unit ring before MODAL_BURST_GAIN
+    b_burst: f64,
+    amp: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Mode {
+    #[inline]
+    fn process(&mut self, mix: f64, burst: f64) -> f64 {
+        let y = self.b_mix * (mix - self.x2) + self.b_burst * burst
+            + self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = mix;
+        self.y2 = self.y1;
+        self.y1 = y + DENORMAL_GUARD;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.x1 = 0.0;
+        self.x2 = 0.0;
+        self.y1 = 0.0;
+        self.y2 = 0.0;
+    }
+}
+
+/// Tuned resonator bank: Karplus-Strong string, modal bar/bell and tube.
+/// Mono core fed by the summed input; the dry signal keeps its stereo image.
+pub struct Resonator {
+    sample_rate: f64,
+    model: ResonatorModel,
+    excite: ResonatorExcite,
+    frequency: f64,
+    decay: f64,
+    brightness: f64,
+    position: f64,
+    mix: f64,
+    waveguide: Waveguide,
+    modes: [Mode; MODES],
+    mode_norm: f64,
+    // Inputs the mode coefficients were last computed for
+    modes_key: [f64; 4],
+    burst_remaining: usize,
+    burst_len: usize,
+}
+
+impl Resonator {
+    pub fn new(sample_rate: f32) -> Self {
+        let sr = sample_rate as f64;
+        Self {
+            sample_rate: sr,
+            model: ResonatorModel::String,
+            excite: ResonatorExcite::Mix,
+            frequency: 220.0,
+            decay: 0.5,
+            brightness: 0.5,
+            position: 0.3,
+            mix: 0.5,
+            waveguide: Waveguide::new(Self::line_len(sr)),
+            modes: [Mode::default(); MODES],
+            mode_norm: 1.0,
+            modes_key: [0.0; 4],
+            burst_remaining: 0,
+            burst_len: 1,
+        }
+    }
+
+    fn line_len(sample_rate: f64) -> usize {
+        (sample_rate / MIN_FREQ_HZ).ceil() as usize + 4
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate as f64;
+        self.waveguide = Waveguide::new(Self::line_len(self.sample_rate));
+        self.modes_key = [0.0; 4];
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.waveguide.reset();
+        self.modes.iter_mut().for_each(Mode::reset);
+        self.burst_remaining = 0;
+    }
+
+    pub fn set_params(&mut self, p: &ResonatorParams) {
+        if p.model != self.model {
+            // A string tail would otherwise carry over into the bar
+            self.reset();
+            self.model = p.model;
+            self.modes_key = [0.0; 4];
+        }
+        self.excite = p.excite;
+        self.frequency = p.frequency.clamp(MIN_FREQ_HZ, self.sample_rate * 0.45);
+        self.decay = p.decay.clamp(0.0, 1.0);
+        self.brightness = p.brightness.clamp(0.0, 1.0);
+        self.position = p.position.clamp(0.0, 1.0);
+        self.mix = p.mix.clamp(0.0, 1.0);
+    }
+
+    /// Fires the noise burst; called on note trigger.
+    pub fn excite(&mut self) {
+        if self.excite == ResonatorExcite::Mix {
+            return;
+        }
+        let max_len = (BURST_S * self.sample_rate) as usize;
+        // One period at most, or the copies pile up in the loop
+        let len = if self.model.is_modal() {
+            max_len
+        } else {
+            (self.sample_rate / self.frequency).min(max_len as f64) as usize
+        };
+        self.burst_len = len.max(1);
+        self.burst_remaining = self.burst_len;
+    }
+
+    fn t60(&self) -> f64 {
+        T60_MIN_S * T60_RANGE.powf(self.decay)
+    }
+
+    fn update_modes(&mut self) {
+        let key = [self.frequency, self.decay, self.brightness, self.position];
+        if key == self.modes_key {
+            return;
+        }
+        self.modes_key = key;
+
+        let ratios = if self.model == ResonatorModel::Bell { &BELL_RATIOS } else { &BAR_RATIOS };
+        let t60 = self.t60();
+        let dark = 1.0 - self.brightness;
+        // Strike point from near the end (0) to the middle (1)
+        let strike = 0.1 + 0.4 * self.position;
+        let nyquist = self.sample_rate * 0.45;
+        let mut norm = 0.0;
+        for (k, (mode, ratio)) in self.modes.iter_mut().zip(ratios).enumerate() {
+            let hz = self.frequency * ratio;
+            if hz >= nyquist {
+                mode.amp = 0.0;
+                mode.reset();
+                continue;
+            }
+            // Upper partials die faster and start quieter as brightness drops
+            let mode_t60 = t60 / (1.0 + dark * (ratio - 1.0).max(0.0));
+            let r = 0.001_f64.powf(1.0 / (mode_t60 * self.sample_rate));
+            let w = TAU * hz / self.sample_rate;
+            mode.a1 = 2.0 * r * w.cos();
+            mode.a2 = r * r;
+            mode.b_mix = (1.0 - r * r) * 0.5;
+            mode.b_burst = w.sin() * MODAL_BURST_GAIN;
+            mode.amp = (PI * (k + 1) as f64 * strike).sin().abs() * ratio.powf(-dark);
+            norm += mode.amp;
+        }
+        self.mode_norm = if norm > 0.0 { 1.0 / norm } else { 0.0 };
+    }
+
+    #[inline]
+    fn next_burst(&mut self) -> f64 {
+        if self.burst_remaining == 0 {
+            return 0.0;
+        }
+        let env = self.burst_remaining as f64 / self.burst_len as f64;
+        self.burst_remaining -= 1;
+        (rand_01() * 2.0 - 1.0) * env
+    }
+
+    #[inline]
+    pub fn process(&mut self, left: f64, right: f64) -> (f64, f64) {
+        let mix_in = if self.excite == ResonatorExcite::Noise { 0.0 } else { (left + right) * 0.5 };
+        let burst = self.next_burst();
+
+        let wet = match self.model {
+            ResonatorModel::String | ResonatorModel::Tube => {
+                let tube = self.model == ResonatorModel::Tube;
+                let period = self.sample_rate / self.frequency;
+                // A closed tube inverts at the reflection, so half the period spans the loop
+                let loop_len = if tube { period * 0.5 } else { period };
+                let gain = 0.001_f64.powf(loop_len / (self.t60() * self.sample_rate));
+                let damp = 0.5 * (1.0 - self.brightness);
+                let delay = (loop_len - damp).clamp(2.0, self.waveguide.line.len() as f64 - 2.0);
+                let pick = 1 + (self.position * 0.5 * loop_len) as usize;
+                // Continuous input is scaled so the harmonic peaks sit near unity
+                let x = mix_in * (1.0 - gain) + burst;
+                self.waveguide.process(x, delay, pick, gain, damp, tube)
+            }
+            ResonatorModel::Bar | ResonatorModel::Bell => {
+                self.update_modes();
+                let mut sum = 0.0;
+                for mode in self.modes.iter_mut().filter(|m| m.amp > 0.0) {
+                    sum += mode.process(mix_in, burst) * mode.amp;
+                }
+                sum * self.mode_norm
+            }
+        };
+
+        let wet = if wet.is_finite() {
+            wet
+        } else {
+            self.reset();
+            0.0
+        };
+        let dry = 1.0 - self.mix;
+        (left * dry + wet * self.mix, right * dry + wet * self.mix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plucked(model: ResonatorModel, frequency: f64, decay: f64) -> Vec<f64> {
+        let mut res = Resonator::new(48000.0);
+        res.set_params(&ResonatorParams {
+            model,
+            excite: ResonatorExcite::Noise,
+            frequency,
+            decay,
+            brightness: 0.5,
+            position: 0.3,
+            mix: 1.0,
+        });
+        res.excite();
+        (0..48000).map(|_| res.process(0.0, 0.0).0).collect()
+    }
+
+    fn best_lag(signal: &[f64], min: usize, max: usize) -> usize {
+        let corr = |lag: usize| signal.iter().zip(&signal[lag..]).map(|(a, b)| a * b).sum::<f64>();
+        (min..=max).max_by(|&a, &b| corr(a).total_cmp(&corr(b))).unwrap()
+    }
+
+    fn rms(signal: &[f64]) -> f64 {
+        (signal.iter().map(|v| v * v).sum::<f64>() / signal.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn string_and_tube_ring_at_pitch() {
+        // 220 Hz at 48 kHz: a period of ~218.2 samples
+        for model in [ResonatorModel::String, ResonatorModel::Tube] {
+            let out = plucked(model, 220.0, 0.7);
+            let lag = best_lag(&out[4800..14400], 180, 260);
+            assert!((217..=219).contains(&lag), "{:?} lag {}", model, lag);
+        }
+    }
+
+    #[test]
+    fn decay_control_lengthens_tail() {
+        for model in [ResonatorModel::String, ResonatorModel::Bar, ResonatorModel::Bell] {
+            let short = plucked(model, 330.0, 0.2);
+            let long = plucked(model, 330.0, 0.8);
+            let tail = 24000..48000;
+            assert!(rms(&long[tail.clone()]) > rms(&short[tail]) * 10.0, "{:?}", model);
+        }
+    }
+
+    #[test]
+    fn mix_excitation_stays_bounded() {
+        let mut res = Resonator::new(48000.0);
+        for model in [ResonatorModel::String, ResonatorModel::Bar, ResonatorModel::Bell, ResonatorModel::Tube] {
+            res.set_params(&ResonatorParams {
+                model,
+                excite: ResonatorExcite::Mix,
+                frequency: 110.0,
+                decay: 1.0,
+                brightness: 1.0,
+                position: 0.5,
+                mix: 1.0,
+            });
+            let peak = (0..48000)
+                .map(|i| {
+                    let x = (TAU * 110.0 * i as f64 / 48000.0).sin();
+                    res.process(x, x).0.abs()
+                })
+                .fold(0.0, f64::max);
+            assert!(peak < 4.0, "{:?} peak {}", model, peak);
+        }
+    }
+}
@@ -1,13 +1,20 @@
 #![allow(clippy::too_many_arguments)]
 
-use super::dsp::{Oversampling, SlewValue, apply_distortion, mono_wavefold, mono_wavefold_pi};
-use super::oscillator::{Oscillator, PolyBlepWrapper, PLLOscillator, SawOscillator};
+use super::dsp::{Oversampling, SlewValue, apply_distortion, blend_phase, mono_wavefold, mono_wavefold_pi};
+use super::oscillator::{NoiseInjection, Oscillator, PllRefSource, PllTopology, PolyBlepWrapper, PLLOscillator, SawOscillator};
 use super::envelope::{Envelope, EnvelopeLoopMode, TailEnvelope};
 use super::lfo::ModulationValues;
 use super::simd::{stereo, stereo_left, stereo_right, stereo_wavefold, stereo_wavefold_pi, OnePoleSlewValue, StereoDCBlocker};
 use super::ladder_filter::{LadderFilter, FilterParams};
+use super::multi_filter::{FilterRouting, MultiFilter, MultiFilterParams};
+use super::lofi::{Lofi, LofiParams, LofiShaper};
+use super::noise::{NoiseGenerator, NoiseType};
+use super::resonator::{Resonator, ResonatorExcite, ResonatorModel, ResonatorParams};
+use super::wavetable::{Wavetable, WavetableOscillator};
 
 const PLL_FEEDBACK_CUTOFF_HZ: f64 = 1479.0;
+// Peak FM index for the cross-FM paths; past 1 the carrier runs backwards
+const XFM_MAX_INDEX: f64 = 4.0;
 
 struct SawTightFilter {
     ic1eq: f64,
@@ -115,6 +122,7 @@ pub struct Voice {
     pll_reference_oscillator_right: PolyBlepWrapper,
     fm_oscillator: SineOscillator,
     saw_oscillator: SawOscillator,
+    wt_oscillator: WavetableOscillator,
 
     // ===== Processing =====
     volume_envelope: Envelope,
@@ -158,6 +166,15 @@ pub struct Voice {
     saw_os_64x: Oversampling<64>,
     saw_os_128x: Oversampling<128>,
 
+    // Wavetable oversampling (mono)
+    wt_os_2x: Oversampling<2>,
+    wt_os_4x: Oversampling<4>,
+    wt_os_8x: Oversampling<8>,
+    wt_os_16x: Oversampling<16>,
+    wt_os_32x: Oversampling<32>,
+    wt_os_64x: Oversampling<64>,
+    wt_os_128x: Oversampling<128>,
+
     // VPS DC blocker
     vps_dc_blocker: StereoDCBlocker,
 
@@ -214,6 +231,22 @@ pub struct Voice {
     saw_tight_slew: OnePoleSlewValue,
     saw_tight_filter: SawTightFilter,
 
+    // ===== Wavetable Oscillator =====
+    wt_enabled: bool,
+    wt_octave: i32,
+    wt_tune: i32,
+    wt_fine: f64,
+    wt_volume: f64,
+    target_wt_volume: f64,
+    wt_volume_slew: SlewValue,
+    wt_position: f64,
+    target_wt_position: f64,
+    wt_position_slew: OnePoleSlewValue,
+    mod_wt_position: f64,
+    mod_wt_volume: f64,
+    mod_slew_wt_position: SlewValue,
+    mod_slew_wt_volume: SlewValue,
+
     // ===== Ladder Filter =====
     ladder_filter: LadderFilter,
     filter_enabled: bool,
@@ -302,6 +335,51 @@ pub struct Voice {
     filt_env_loop_mode: EnvelopeLoopMode,
     filter_drive_boost: i32,
 
+    // ===== Filter 2 =====
+    multi_filter: MultiFilter,
+    filter2_enabled: bool,
+    filter2_type: u8,
+    filter_routing: FilterRouting,
+    filter2_cutoff: f64,
+    target_filter2_cutoff: f64,
+    filter2_cutoff_slew: SlewValue,
+    filter2_resonance: f64,
+    target_filter2_resonance: f64,
+    filter2_resonance_slew: SlewValue,
+    filter2_morph: f64,
+    target_filter2_morph: f64,
+    filter2_morph_slew: SlewValue,
+    filter2_key_track: f64,
+    target_filter2_key_track: f64,
+    filter2_key_track_slew: SlewValue,
+    filter2_env_amount: f64,
+    target_filter2_env_amount: f64,
+    filter2_env_amount_slew: SlewValue,
+    mod_filter2_cutoff: f64,
+    mod_filter2_resonance: f64,
+    mod_filter2_morph: f64,
+    mod_slew_filter2_cutoff: SlewValue,
+    mod_slew_filter2_resonance: SlewValue,
+    mod_slew_filter2_morph: SlewValue,
+
+    // ===== Resonator =====
+    resonator: Resonator,
+    resonator_enabled: bool,
+    resonator_model: ResonatorModel,
+    resonator_excite: ResonatorExcite,
+    resonator_decay: f64,
+    target_resonator_decay: f64,
+    resonator_decay_slew: SlewValue,
+    resonator_brightness: f64,
+    target_resonator_brightness: f64,
+    resonator_brightness_slew: SlewValue,
+    resonator_position: f64,
+    target_resonator_position: f64,
+    resonator_position_slew: SlewValue,
+    resonator_mix: f64,
+    target_resonator_mix: f64,
+    resonator_mix_slew: SlewValue,
+
     // ===== PLL =====
     pll_volume: f64,
     pll_track_speed: f64,
@@ -563,18 +641,149 @@ pub struct Voice {
     target_pll_multiplier: f64,
     pll_mult_slew_state: SlewValue,
 
+    // ===== Lo-fi =====
+    lofi: Lofi,
+    lofi_enabled: bool,
+    lofi_post_filter: bool,
+    lofi_vps: bool,
+    lofi_pll: bool,
+    lofi_saw: bool,
+    lofi_shaper: LofiShaper,
+    lofi_bits: f64,
+    lofi_rate: f64,
+    lofi_anti_alias: bool,
+    lofi_jitter: f64,
+    lofi_drive: f64,
+    target_lofi_drive: f64,
+    lofi_drive_slew: SlewValue,
+    lofi_mix: f64,
+    target_lofi_mix: f64,
+    lofi_mix_slew: SlewValue,
+    mod_lofi_bits: f64,
+    mod_lofi_rate: f64,
+    mod_lofi_drive: f64,
+    mod_lofi_mix: f64,
+    mod_slew_lofi_bits: SlewValue,
+    mod_slew_lofi_rate: SlewValue,
+    mod_slew_lofi_drive: SlewValue,
+    mod_slew_lofi_mix: SlewValue,
+
+    // ===== Pitch modulation =====
+    // Scale pitch classes (bit 0 = C) to snap to; 0 leaves pitch mod unquantized
+    pitch_mod_scale: u16,
+    mod_global_pitch: f64,
+    vps_pitch_mult: f64,
+    saw_pitch_mult: f64,
+    pll_ref_pitch_mult: f64,
+    mod_slew_global_pitch: SlewValue,
+    mod_slew_vps_pitch: SlewValue,
+    mod_slew_saw_pitch: SlewValue,
+    mod_slew_pll_ref_pitch: SlewValue,
+
+    // ===== Noise =====
+    noise: NoiseGenerator,
+    noise_type: NoiseType,
+    noise_rate: f64,
+    noise_lpf: f64,
+    noise_hpf: f64,
+    noise_filter_env: bool,
+    noise_level: f64,
+    target_noise_level: f64,
+    noise_level_slew: SlewValue,
+    mod_noise_level: f64,
+    mod_slew_noise_level: SlewValue,
+    noise_injection: NoiseInjection,
+    noise_inject_amount: f64,
+    target_noise_inject_amount: f64,
+    noise_inject_amount_slew: SlewValue,
+
+    // ===== Oscillator Sync =====
+    sync_source_vco: bool,
+    vps_sync: i32,
+    saw_sync: i32,
+    pll_sync: i32,
+    sync_soft: f64,
+    target_sync_soft: f64,
+    sync_soft_slew: SlewValue,
+    mod_sync_soft: f64,
+    mod_slew_sync_soft: SlewValue,
+    // Per oversampled step: how far before the next step the master wrapped, or -1
+    sync_events: [f64; 128],
+    sync_master_phase: f64,
+
+    // ===== PLL Topology =====
+    pll_topology: PllTopology,
+    pll2_track: f64,
+    target_pll2_track: f64,
+    pll2_track_slew: SlewValue,
+    pll2_damping: f64,
+    target_pll2_damping: f64,
+    pll2_damping_slew: SlewValue,
+    pll2_multiplier: f64,
+    pll_coupling: f64,
+    target_pll_coupling: f64,
+    pll_coupling_slew: SlewValue,
+    pll_ref_source: PllRefSource,
+    pll_ref_mix: f64,
+    target_pll_ref_mix: f64,
+    pll_ref_mix_slew: SlewValue,
+    // Routed oscillator from the previous host sample, per oversampled step
+    pll_ext_phase: [f64; 128],
+    pll_ext_signal: [f64; 128],
+    pll_ext_freq: f64,
+
+    // ===== Ring Mod / Cross-FM =====
+    ring_pll_enabled: bool,
+    ring_pll: f64,
+    target_ring_pll: f64,
+    ring_pll_slew: SlewValue,
+    mod_ring_pll: f64,
+    mod_slew_ring_pll: SlewValue,
+    ring_vps_saw_enabled: bool,
+    ring_vps_saw: f64,
+    target_ring_vps_saw: f64,
+    ring_vps_saw_slew: SlewValue,
+    mod_ring_vps_saw: f64,
+    mod_slew_ring_vps_saw: SlewValue,
+    xfm_vps_saw_enabled: bool,
+    xfm_vps_saw: f64,
+    target_xfm_vps_saw: f64,
+    xfm_vps_saw_slew: SlewValue,
+    mod_xfm_vps_saw: f64,
+    mod_slew_xfm_vps_saw: SlewValue,
+    xfm_saw_ref_enabled: bool,
+    xfm_saw_ref: f64,
+    target_xfm_saw_ref: f64,
+    xfm_saw_ref_slew: SlewValue,
+    mod_xfm_saw_ref: f64,
+    mod_slew_xfm_saw_ref: SlewValue,
+    // This host sample's VPS output per oversampled step, read by the SAW loop
+    vps_steps: [f64; 128],
+    // Previous host sample's SAW output per oversampled step, read by the PLL loop
+    saw_steps: [f64; 128],
+
     // ===== Reverb Send Levels =====
     reverb_send_vps: f64,
     reverb_send_pll: f64,
     reverb_send_saw: f64,
+    reverb_send_wt: f64,
     reverb_send_sub: f64,
     reverb_send_filter: f64,
 
+    // ===== Delay Send Levels =====
+    delay_send_vps: f64,
+    delay_send_pll: f64,
+    delay_send_saw: f64,
+    delay_send_wt: f64,
+    delay_send_sub: f64,
+    delay_send_filter: f64,
+
     last_vps_l: f64,
     last_vps_r: f64,
     last_pll_l: f64,
     last_pll_r: f64,
     last_saw: f64,
+    last_wt: f64,
 }
 
 impl Voice {
@@ -639,6 +848,14 @@ impl Voice {
         let mut saw_os_64x = Oversampling::<64>::new(); saw_os_64x.set_sample_rate(sample_rate_f64);
         let mut saw_os_128x = Oversampling::<128>::new(); saw_os_128x.set_sample_rate(sample_rate_f64);
 
+        let mut wt_os_2x = Oversampling::<2>::new(); wt_os_2x.set_sample_rate(sample_rate_f64);
+        let mut wt_os_4x = Oversampling::<4>::new(); wt_os_4x.set_sample_rate(sample_rate_f64);
+        let mut wt_os_8x = Oversampling::<8>::new(); wt_os_8x.set_sample_rate(sample_rate_f64);
+        let mut wt_os_16x = Oversampling::<16>::new(); wt_os_16x.set_sample_rate(sample_rate_f64);
+        let mut wt_os_32x = Oversampling::<32>::new(); wt_os_32x.set_sample_rate(sample_rate_f64);
+        let mut wt_os_64x = Oversampling::<64>::new(); wt_os_64x.set_sample_rate(sample_rate_f64);
+        let mut wt_os_128x = Oversampling::<128>::new(); wt_os_128x.set_sample_rate(sample_rate_f64);
+
         // Default to 2x oversampling
         let processing_rate = sample_rate_f64 * 2.0;
 
@@ -665,6 +882,7 @@ impl Voice {
             pll_reference_oscillator_right: PolyBlepWrapper::new(processing_rate),
             fm_oscillator: SineOscillator::new(processing_rate),
             saw_oscillator: SawOscillator::new(sample_rate_f64),
+            wt_oscillator: WavetableOscillator::new(sample_rate_f64),
 
             volume_envelope: Envelope::new(sample_rate_f64),
             oversampling_2x_left,
@@ -693,6 +911,9 @@ impl Voice {
             saw_os_2x, saw_os_4x, saw_os_8x, saw_os_16x,
             saw_os_32x, saw_os_64x, saw_os_128x,
 
+            wt_os_2x, wt_os_4x, wt_os_8x, wt_os_16x,
+            wt_os_32x, wt_os_64x, wt_os_128x,
+
             vps_dc_blocker: StereoDCBlocker::new(),
 
             base_frequency: 220.0,
@@ -741,6 +962,21 @@ impl Voice {
             saw_tight_slew: make_one_pole_slew(),
             saw_tight_filter: SawTightFilter::new(sample_rate_f64),
 
+            wt_enabled: false,
+            wt_octave: 0,
+            wt_tune: 0,
+            wt_fine: 0.0,
+            wt_volume: 0.0,
+            target_wt_volume: 0.0,
+            wt_volume_slew: make_slew(),
+            wt_position: 0.0,
+            target_wt_position: 0.0,
+            wt_position_slew: make_one_pole_slew(),
+            mod_wt_position: 0.0,
+            mod_wt_volume: 0.0,
+            mod_slew_wt_position: make_slew(),
+            mod_slew_wt_volume: make_slew(),
+
             ladder_filter: LadderFilter::new(sample_rate),
             filter_enabled: false,
             filter_cutoff: 20000.0,
@@ -828,6 +1064,49 @@ impl Voice {
             filt_env_loop_mode: EnvelopeLoopMode::OneShot,
             filter_drive_boost: 0,
 
+            multi_filter: MultiFilter::new(sample_rate),
+            filter2_enabled: false,
+            filter2_type: 0,
+            filter_routing: FilterRouting::Serial,
+            filter2_cutoff: 1000.0,
+            target_filter2_cutoff: 1000.0,
+            filter2_cutoff_slew: make_slew(),
+            filter2_resonance: 0.0,
+            target_filter2_resonance: 0.0,
+            filter2_resonance_slew: make_slew(),
+            filter2_morph: 0.0,
+            target_filter2_morph: 0.0,
+            filter2_morph_slew: make_slew(),
+            filter2_key_track: 0.0,
+            target_filter2_key_track: 0.0,
+            filter2_key_track_slew: make_slew(),
+            filter2_env_amount: 0.0,
+            target_filter2_env_amount: 0.0,
+            filter2_env_amount_slew: make_slew(),
+            mod_filter2_cutoff: 0.0,
+            mod_filter2_resonance: 0.0,
+            mod_filter2_morph: 0.0,
+            mod_slew_filter2_cutoff: make_slew(),
+            mod_slew_filter2_resonance: make_slew(),
+            mod_slew_filter2_morph: make_slew(),
+
+            resonator: Resonator::new(sample_rate),
+            resonator_enabled: false,
+            resonator_model: ResonatorModel::String,
+            resonator_excite: ResonatorExcite::Mix,
+            resonator_decay: 0.5,
+            target_resonator_decay: 0.5,
+            resonator_decay_slew: make_slew(),
+            resonator_brightness: 0.5,
+            target_resonator_brightness: 0.5,
+            resonator_brightness_slew: make_slew(),
+            resonator_position: 0.3,
+            target_resonator_position: 0.3,
+            resonator_position_slew: make_slew(),
+            resonator_mix: 0.5,
+            target_resonator_mix: 0.5,
+            resonator_mix_slew: make_slew(),
+
             pll_volume: 0.0,
             pll_track_speed: 0.5,
             pll_damping: 0.3,
@@ -1067,16 +1346,128 @@ impl Voice {
             bpm: 120.0,
             target_pll_multiplier: 1.0,
             pll_mult_slew_state: make_slew(),
+            lofi: Lofi::new(sample_rate),
+            lofi_enabled: false,
+            lofi_post_filter: false,
+            lofi_vps: true,
+            lofi_pll: true,
+            lofi_saw: true,
+            lofi_shaper: LofiShaper::Off,
+            lofi_bits: 16.0,
+            lofi_rate: 48000.0,
+            lofi_anti_alias: true,
+            lofi_jitter: 0.0,
+            lofi_drive: 0.0,
+            target_lofi_drive: 0.0,
+            lofi_drive_slew: make_slew(),
+            lofi_mix: 1.0,
+            target_lofi_mix: 1.0,
+            lofi_mix_slew: make_slew(),
+            mod_lofi_bits: 0.0,
+            mod_lofi_rate: 0.0,
+            mod_lofi_drive: 0.0,
+            mod_lofi_mix: 0.0,
+            mod_slew_lofi_bits: make_slew(),
+            mod_slew_lofi_rate: make_slew(),
+            mod_slew_lofi_drive: make_slew(),
+            mod_slew_lofi_mix: make_slew(),
+            pitch_mod_scale: 0,
+            mod_global_pitch: 0.0,
+            vps_pitch_mult: 1.0,
+            saw_pitch_mult: 1.0,
+            pll_ref_pitch_mult: 1.0,
+            mod_slew_global_pitch: make_slew(),
+            mod_slew_vps_pitch: make_slew(),
+            mod_slew_saw_pitch: make_slew(),
+            mod_slew_pll_ref_pitch: make_slew(),
+            noise: NoiseGenerator::new(sample_rate),
+            noise_type: NoiseType::White,
+            noise_rate: 1000.0,
+            noise_lpf: 20000.0,
+            noise_hpf: 20.0,
+            noise_filter_env: false,
+            noise_level: 0.0,
+            target_noise_level: 0.0,
+            noise_level_slew: make_slew(),
+            mod_noise_level: 0.0,
+            mod_slew_noise_level: make_slew(),
+            noise_injection: NoiseInjection::Off,
+            noise_inject_amount: 0.0,
+            target_noise_inject_amount: 0.0,
+            noise_inject_amount_slew: make_slew(),
+            sync_source_vco: false,
+            vps_sync: 0,
+            saw_sync: 0,
+            pll_sync: 0,
+            sync_soft: 0.5,
+            target_sync_soft: 0.5,
+            sync_soft_slew: make_slew(),
+            mod_sync_soft: 0.0,
+            mod_slew_sync_soft: make_slew(),
+            sync_events: [-1.0; 128],
+            sync_master_phase: 0.0,
+            pll_topology: PllTopology::Stereo,
+            pll2_track: 0.5,
+            target_pll2_track: 0.5,
+            pll2_track_slew: make_slew(),
+            pll2_damping: 0.3,
+            target_pll2_damping: 0.3,
+            pll2_damping_slew: make_slew(),
+            pll2_multiplier: 1.0,
+            pll_coupling: 0.5,
+            target_pll_coupling: 0.5,
+            pll_coupling_slew: make_slew(),
+            pll_ref_source: PllRefSource::Reference,
+            pll_ref_mix: 1.0,
+            target_pll_ref_mix: 1.0,
+            pll_ref_mix_slew: make_slew(),
+            pll_ext_phase: [0.0; 128],
+            pll_ext_signal: [0.0; 128],
+            pll_ext_freq: 0.0,
+            ring_pll_enabled: false,
+            ring_pll: 0.0,
+            target_ring_pll: 0.0,
+            ring_pll_slew: make_slew(),
+            mod_ring_pll: 0.0,
+            mod_slew_ring_pll: make_slew(),
+            ring_vps_saw_enabled: false,
+            ring_vps_saw: 0.0,
+            target_ring_vps_saw: 0.0,
+            ring_vps_saw_slew: make_slew(),
+            mod_ring_vps_saw: 0.0,
+            mod_slew_ring_vps_saw: make_slew(),
+            xfm_vps_saw_enabled: false,
+            xfm_vps_saw: 0.0,
+            target_xfm_vps_saw: 0.0,
+            xfm_vps_saw_slew: make_slew(),
+            mod_xfm_vps_saw: 0.0,
+            mod_slew_xfm_vps_saw: make_slew(),
+            xfm_saw_ref_enabled: false,
+            xfm_saw_ref: 0.0,
+            target_xfm_saw_ref: 0.0,
+            xfm_saw_ref_slew: make_slew(),
+            mod_xfm_saw_ref: 0.0,
+            mod_slew_xfm_saw_ref: make_slew(),
+            vps_steps: [0.0; 128],
+            saw_steps: [0.0; 128],
             reverb_send_vps: 0.0,
             reverb_send_pll: 0.0,
             reverb_send_saw: 0.0,
+            reverb_send_wt: 0.0,
             reverb_send_sub: 0.0,
             reverb_send_filter: 0.0,
+            delay_send_vps: 0.0,
+            delay_send_pll: 0.0,
+            delay_send_saw: 0.0,
+            delay_send_wt: 0.0,
+            delay_send_sub: 0.0,
+            delay_send_filter: 0.0,
             last_vps_l: 0.0,
             last_vps_r: 0.0,
             last_pll_l: 0.0,
             last_pll_r: 0.0,
             last_saw: 0.0,
+            last_wt: 0.0,
         }
     }
 
@@ -1087,24 +1478,100 @@ impl Voice {
         vps: bool,
         _reverb: bool,
         saw: bool,
+        wt: bool,
     ) {
         self.pll_enabled = pll;
         self.vps_enabled = vps;
         self.saw_enabled = saw;
+        self.wt_enabled = wt;
     }
 
     pub fn set_vps_formant(&mut self, enabled: bool) {
         self.vps_formant = enabled;
     }
 
-    pub fn set_reverb_sends(&mut self, vps: f64, pll: f64, saw: f64, sub: f64, filter: f64) {
+    pub fn set_reverb_sends(&mut self, vps: f64, pll: f64, saw: f64, wt: f64, sub: f64, filter: f64) {
         self.reverb_send_vps = vps;
         self.reverb_send_pll = pll;
         self.reverb_send_saw = saw;
+        self.reverb_send_wt = wt;
         self.reverb_send_sub = sub;
         self.reverb_send_filter = filter;
     }
 
+    pub fn set_delay_sends(&mut self, vps: f64, pll: f64, saw: f64, wt: f64, sub: f64, filter: f64) {
+        self.delay_send_vps = vps;
+        self.delay_send_pll = pll;
+        self.delay_send_saw = saw;
+        self.delay_send_wt = wt;
+        self.delay_send_sub = sub;
+        self.delay_send_filter = filter;
+    }
+
+    pub fn set_lofi_routing(&mut self, enabled: bool, post_filter: bool, vps: bool, pll: bool, saw: bool) {
+        if enabled && !self.lofi_enabled {
+            self.lofi.reset();
+        }
+        self.lofi_enabled = enabled;
+        self.lofi_post_filter = post_filter;
+        self.lofi_vps = vps;
+        self.lofi_pll = pll;
+        self.lofi_saw = saw;
+    }
+
+    pub fn set_lofi_params(
+        &mut self,
+        shaper: i32,
+        bits: f64,
+        rate_hz: f64,
+        anti_alias: bool,
+        jitter: f64,
+        drive: f64,
+        mix: f64,
+    ) {
+        self.lofi_shaper = LofiShaper::from_index(shaper);
+        self.lofi_bits = bits;
+        self.lofi_rate = rate_hz;
+        self.lofi_anti_alias = anti_alias;
+        self.lofi_jitter = jitter;
+        self.target_lofi_drive = drive;
+        self.target_lofi_mix = mix;
+    }
+
+    pub fn set_noise_params(&mut self, kind: i32, rate_hz: f64, lpf_hz: f64, hpf_hz: f64, filter_env: bool) {
+        self.noise_type = NoiseType::from_index(kind);
+        self.noise_rate = rate_hz;
+        self.noise_lpf = lpf_hz;
+        self.noise_hpf = hpf_hz;
+        self.noise_filter_env = filter_env;
+    }
+
+    pub fn set_noise_level(&mut self, level: f64) {
+        self.target_noise_level = level;
+    }
+
+    pub fn set_noise_injection(&mut self, point: i32, amount: f64) {
+        self.noise_injection = NoiseInjection::from_index(point);
+        self.target_noise_inject_amount = amount;
+    }
+
+    /// Sync modes: 0 = off, 1 = hard, 2 = soft (scaled by `soft`).
+    pub fn set_sync(&mut self, source_vco: bool, vps: i32, saw: i32, pll: i32, soft: f64) {
+        self.sync_source_vco = source_vco;
+        self.vps_sync = vps;
+        self.saw_sync = saw;
+        self.pll_sync = pll;
+        self.target_sync_soft = soft;
+    }
+
+    fn sync_amount(&self, mode: i32) -> f64 {
+        match mode {
+            1 => 1.0,
+            2 => self.sync_soft,
+            _ => 0.0,
+        }
+    }
+
     pub fn set_oversampling(&mut self, factor: i32) {
         if factor != self.current_os_factor {
             self.current_os_factor = factor;
@@ -1147,6 +1614,13 @@ impl Voice {
             self.saw_os_32x.set_sample_rate(new_rate);
             self.saw_os_64x.set_sample_rate(new_rate);
             self.saw_os_128x.set_sample_rate(new_rate);
+            self.wt_os_2x.set_sample_rate(new_rate);
+            self.wt_os_4x.set_sample_rate(new_rate);
+            self.wt_os_8x.set_sample_rate(new_rate);
+            self.wt_os_16x.set_sample_rate(new_rate);
+            self.wt_os_32x.set_sample_rate(new_rate);
+            self.wt_os_64x.set_sample_rate(new_rate);
+            self.wt_os_128x.set_sample_rate(new_rate);
             self.vps_os_2x_left.set_sample_rate(new_rate);
             self.vps_os_2x_right.set_sample_rate(new_rate);
             self.vps_os_4x_left.set_sample_rate(new_rate);
@@ -1228,6 +1702,10 @@ impl Voice {
             update_slew(&mut self.mod_slew_saw_volume);
             self.saw_tight_slew.set_sample_rate(new_rate);
             self.saw_tight_filter.set_sample_rate(new_rate);
+            update_slew(&mut self.wt_volume_slew);
+            self.wt_position_slew.set_sample_rate(new_rate);
+            update_slew(&mut self.mod_slew_wt_position);
+            update_slew(&mut self.mod_slew_wt_volume);
             self.ladder_filter.set_sample_rate(new_rate as f32);
             update_slew(&mut self.filter_cutoff_slew);
             update_slew(&mut self.filter_resonance_slew);
@@ -1239,6 +1717,49 @@ impl Voice {
             update_slew(&mut self.mod_slew_filter_resonance);
             update_slew(&mut self.mod_slew_filter_drive);
             update_slew(&mut self.mod_slew_filter_env_amount);
+            self.multi_filter.set_sample_rate(new_rate as f32);
+            update_slew(&mut self.filter2_cutoff_slew);
+            update_slew(&mut self.filter2_resonance_slew);
+            update_slew(&mut self.filter2_morph_slew);
+            update_slew(&mut self.filter2_key_track_slew);
+            update_slew(&mut self.filter2_env_amount_slew);
+            update_slew(&mut self.mod_slew_filter2_cutoff);
+            update_slew(&mut self.mod_slew_filter2_resonance);
+            update_slew(&mut self.mod_slew_filter2_morph);
+            self.resonator.set_sample_rate(sample_rate);
+            update_slew(&mut self.resonator_decay_slew);
+            update_slew(&mut self.resonator_brightness_slew);
+            update_slew(&mut self.resonator_position_slew);
+            update_slew(&mut self.resonator_mix_slew);
+            self.lofi.set_sample_rate(sample_rate);
+            update_slew(&mut self.lofi_drive_slew);
+            update_slew(&mut self.lofi_mix_slew);
+            update_slew(&mut self.mod_slew_lofi_bits);
+            update_slew(&mut self.mod_slew_lofi_rate);
+            update_slew(&mut self.mod_slew_lofi_drive);
+            update_slew(&mut self.mod_slew_lofi_mix);
+            update_slew(&mut self.mod_slew_global_pitch);
+            update_slew(&mut self.mod_slew_vps_pitch);
+            update_slew(&mut self.mod_slew_saw_pitch);
+            update_slew(&mut self.mod_slew_pll_ref_pitch);
+            self.noise.set_sample_rate(sample_rate);
+            update_slew(&mut self.noise_level_slew);
+            update_slew(&mut self.mod_slew_noise_level);
+            update_slew(&mut self.noise_inject_amount_slew);
+            update_slew(&mut self.sync_soft_slew);
+            update_slew(&mut self.mod_slew_sync_soft);
+            update_slew(&mut self.pll2_track_slew);
+            update_slew(&mut self.pll2_damping_slew);
+            update_slew(&mut self.pll_coupling_slew);
+            update_slew(&mut self.pll_ref_mix_slew);
+            update_slew(&mut self.ring_pll_slew);
+            update_slew(&mut self.mod_slew_ring_pll);
+            update_slew(&mut self.ring_vps_saw_slew);
+            update_slew(&mut self.mod_slew_ring_vps_saw);
+            update_slew(&mut self.xfm_vps_saw_slew);
+            update_slew(&mut self.mod_slew_xfm_vps_saw);
+            update_slew(&mut self.xfm_saw_ref_slew);
+            update_slew(&mut self.mod_slew_xfm_saw_ref);
             self.pll_mult_slew_state.set_sample_rate(new_rate);
 
             // Now update processing sample rate (oscillators, filters, etc.)
@@ -1288,6 +1809,7 @@ impl Voice {
         self.pll_feedback_filter_coeff = alpha;
         self.pll_feedback_filter_gain = 0.5 * (1.0 - alpha);
         self.saw_oscillator.set_sample_rate(rate);
+        self.wt_oscillator.set_sample_rate(rate);
         self.vps_oscillator_left.set_sample_rate(rate);
         self.vps_oscillator_right.set_sample_rate(rate);
 
@@ -1323,6 +1845,7 @@ impl Voice {
     pub fn pll_l(&self) -> f64 { self.last_pll_l }
     pub fn pll_r(&self) -> f64 { self.last_pll_r }
     pub fn saw_val(&self) -> f64 { self.last_saw }
+    pub fn wt_val(&self) -> f64 { self.last_wt }
 
     pub fn set_frequency(&mut self, freq: f64, _pll_feedback: f64, feedback_amount: f64) {
         self.target_frequency = freq;
@@ -1406,6 +1929,27 @@ impl Voice {
         self.saw_fold_range = range;
     }
 
+    pub fn set_wavetable(&mut self, table: std::sync::Arc<Wavetable>) -> std::sync::Arc<Wavetable> {
+        self.wt_oscillator.set_table(table)
+    }
+
+    pub fn set_wt_volume(&mut self, volume: f64) {
+        self.target_wt_volume = volume;
+    }
+
+    pub fn set_wt_octave(&mut self, octave: i32) {
+        self.wt_octave = octave;
+    }
+
+    pub fn set_wt_tune(&mut self, tune: i32, fine: f64) {
+        self.wt_tune = tune;
+        self.wt_fine = fine;
+    }
+
+    pub fn set_wt_position(&mut self, position: f64) {
+        self.target_wt_position = position;
+    }
+
     pub fn set_filter_enabled(&mut self, enabled: bool) {
         self.filter_enabled = enabled;
     }
@@ -1468,6 +2012,45 @@ impl Voice {
     pub fn set_filter_cutoff_slew(&mut self, s: f64) { self.target_filter_cutoff_slew_amt = s; }
     pub fn set_filter_poles(&mut self, p: i32) { self.filter_poles = p as usize; }
 
+    pub fn set_filter2_enabled(&mut self, enabled: bool) {
+        if enabled && !self.filter2_enabled {
+            self.multi_filter.reset();
+        }
+        self.filter2_enabled = enabled;
+    }
+
+    pub fn set_filter2_params(&mut self, filter_type: u8, cutoff: f64, resonance: f64, morph: f64) {
+        self.filter2_type = filter_type;
+        self.target_filter2_cutoff = cutoff;
+        self.target_filter2_resonance = resonance;
+        self.target_filter2_morph = morph;
+    }
+
+    pub fn set_filter2_tracking(&mut self, key_track: f64, env_amount: f64) {
+        self.target_filter2_key_track = key_track;
+        self.target_filter2_env_amount = env_amount;
+    }
+
+    pub fn set_filter_routing(&mut self, routing: i32) {
+        self.filter_routing = FilterRouting::from_index(routing);
+    }
+
+    pub fn set_resonator_enabled(&mut self, enabled: bool) {
+        if enabled && !self.resonator_enabled {
+            self.resonator.reset();
+        }
+        self.resonator_enabled = enabled;
+    }
+
+    pub fn set_resonator_params(&mut self, model: i32, excite: i32, decay: f64, brightness: f64, position: f64, mix: f64) {
+        self.resonator_model = ResonatorModel::from_index(model);
+        self.resonator_excite = ResonatorExcite::from_index(excite);
+        self.target_resonator_decay = decay;
+        self.target_resonator_brightness = brightness;
+        self.target_resonator_position = position;
+        self.target_resonator_mix = mix;
+    }
+
     pub fn set_bpm(&mut self, bpm: f64) {
         self.bpm = bpm.max(1.0);
     }
@@ -1491,6 +2074,35 @@ impl Voice {
         self.pll_colored = colored;
     }
 
+    /// Second-loop settings apply to the Cascade and Dual topologies;
+    /// `coupling` only to Dual.
+    pub fn set_pll_topology(&mut self, topology: i32, track2: f64, damp2: f64, mult2: f64, coupling: f64) {
+        self.pll_topology = PllTopology::from_index(topology);
+        self.target_pll2_track = track2;
+        self.target_pll2_damping = damp2;
+        self.pll2_multiplier = mult2;
+        self.target_pll_coupling = coupling;
+    }
+
+    pub fn set_pll_ref_source(&mut self, source: i32, mix: f64) {
+        self.pll_ref_source = PllRefSource::from_index(source);
+        self.target_pll_ref_mix = mix;
+    }
+
+    pub fn set_ring_mod(&mut self, pll_on: bool, pll: f64, vps_saw_on: bool, vps_saw: f64) {
+        self.ring_pll_enabled = pll_on;
+        self.target_ring_pll = pll;
+        self.ring_vps_saw_enabled = vps_saw_on;
+        self.target_ring_vps_saw = vps_saw;
+    }
+
+    pub fn set_cross_fm(&mut self, vps_saw_on: bool, vps_saw: f64, saw_ref_on: bool, saw_ref: f64) {
+        self.xfm_vps_saw_enabled = vps_saw_on;
+        self.target_xfm_vps_saw = vps_saw;
+        self.xfm_saw_ref_enabled = saw_ref_on;
+        self.target_xfm_saw_ref = saw_ref;
+    }
+
     pub fn set_pll_mult_slew_time(&mut self, time: f64) {
         self.pll_mult_slew_time = time;
     }
@@ -1592,6 +2204,14 @@ impl Voice {
         self.mod_saw_fold = self.mod_slew_saw_fold.next(mod_values.saw_fold, MOD_SLEW_MS);
         self.mod_saw_shape_amount = self.mod_slew_saw_shape_amount.next(mod_values.saw_shape_amount, MOD_SLEW_MS);
         self.mod_saw_volume = self.mod_slew_saw_volume.next(mod_values.saw_volume, MOD_SLEW_MS);
+        self.mod_wt_position = self.mod_slew_wt_position.next(mod_values.wt_position, MOD_SLEW_MS);
+        self.mod_wt_volume = self.mod_slew_wt_volume.next(mod_values.wt_volume, MOD_SLEW_MS);
+        self.mod_noise_level = self.mod_slew_noise_level.next(mod_values.noise_level, MOD_SLEW_MS);
+        self.mod_sync_soft = self.mod_slew_sync_soft.next(mod_values.sync_soft, MOD_SLEW_MS);
+        self.mod_ring_pll = self.mod_slew_ring_pll.next(mod_values.ring_pll, MOD_SLEW_MS);
+        self.mod_ring_vps_saw = self.mod_slew_ring_vps_saw.next(mod_values.ring_vps_saw, MOD_SLEW_MS);
+        self.mod_xfm_vps_saw = self.mod_slew_xfm_vps_saw.next(mod_values.xfm_vps_saw, MOD_SLEW_MS);
+        self.mod_xfm_saw_ref = self.mod_slew_xfm_saw_ref.next(mod_values.xfm_saw_ref, MOD_SLEW_MS);
         self.mod_env_attack = self.mod_slew_env_attack.next(mod_values.env_attack, MOD_SLEW_MS);
         self.mod_env_attack_shape = self.mod_slew_env_attack_shape.next(mod_values.env_attack_shape, MOD_SLEW_MS);
         self.mod_env_decay = self.mod_slew_env_decay.next(mod_values.env_decay, MOD_SLEW_MS);
@@ -1621,6 +2241,51 @@ impl Voice {
         self.mod_filter_pole_spread = self.mod_slew_filter_pole_spread.next(mod_values.filter_pole_spread, MOD_SLEW_MS);
         self.mod_filter_res_character = self.mod_slew_filter_res_character.next(mod_values.filter_res_character, MOD_SLEW_MS);
         self.mod_filter_res_tilt = self.mod_slew_filter_res_tilt.next(mod_values.filter_res_tilt, MOD_SLEW_MS);
+        self.mod_filter2_cutoff = self.mod_slew_filter2_cutoff.next(mod_values.filter2_cutoff, MOD_SLEW_MS);
+        self.mod_filter2_resonance = self.mod_slew_filter2_resonance.next(mod_values.filter2_resonance, MOD_SLEW_MS);
+        self.mod_filter2_morph = self.mod_slew_filter2_morph.next(mod_values.filter2_morph, MOD_SLEW_MS);
+        self.mod_lofi_bits = self.mod_slew_lofi_bits.next(mod_values.lofi_bits, MOD_SLEW_MS);
+        self.mod_lofi_rate = self.mod_slew_lofi_rate.next(mod_values.lofi_rate, MOD_SLEW_MS);
+        self.mod_lofi_drive = self.mod_slew_lofi_drive.next(mod_values.lofi_drive, MOD_SLEW_MS);
+        self.mod_lofi_mix = self.mod_slew_lofi_mix.next(mod_values.lofi_mix, MOD_SLEW_MS);
+
+        // Pitch destinations span ±12 semitones; per-oscillator offsets stack on
+        // the global one and are quantized as a sum so both land on the scale
+        let global = self.mod_slew_global_pitch.next(mod_values.global_pitch, MOD_SLEW_MS) * 12.0;
+        let vps = self.mod_slew_vps_pitch.next(mod_values.vps_pitch, MOD_SLEW_MS) * 12.0;
+        let saw = self.mod_slew_saw_pitch.next(mod_values.saw_pitch, MOD_SLEW_MS) * 12.0;
+        let pll_ref = self.mod_slew_pll_ref_pitch.next(mod_values.pll_ref_pitch, MOD_SLEW_MS) * 12.0;
+        self.mod_global_pitch = self.quantize_pitch_mod(global);
+        self.vps_pitch_mult = ((self.quantize_pitch_mod(global + vps) - self.mod_global_pitch) / 12.0).exp2();
+        self.saw_pitch_mult = ((self.quantize_pitch_mod(global + saw) - self.mod_global_pitch) / 12.0).exp2();
+        self.pll_ref_pitch_mult = ((self.quantize_pitch_mod(global + pll_ref) - self.mod_global_pitch) / 12.0).exp2();
+    }
+
+    /// Snaps a semitone offset so the played note plus the offset lands on the
+    /// nearest scale note. The played note itself always counts as in scale, so
+    /// zero modulation never detunes an out-of-scale note.
+    fn quantize_pitch_mod(&self, semitones: f64) -> f64 {
+        if self.pitch_mod_scale == 0 {
+            return semitones;
+        }
+        let note = self.current_midi_note as i32;
+        let target = self.current_midi_note + semitones;
+        let center = target.round() as i32;
+        let mut best = semitones.round();
+        let mut best_dist = f64::MAX;
+        for n in (center - 6)..=(center + 6) {
+            let in_scale = self.pitch_mod_scale & (1 << n.rem_euclid(12)) != 0;
+            let dist = (n as f64 - target).abs();
+            if (in_scale || n == note) && dist < best_dist {
+                best_dist = dist;
+                best = (n - note) as f64;
+            }
+        }
+        best
+    }
+
+    pub fn set_pitch_mod_scale(&mut self, mask: u16) {
+        self.pitch_mod_scale = mask & 0x0fff;
     }
 
     pub fn set_volume(&mut self, volume: f64) {
@@ -1729,6 +2394,9 @@ impl Voice {
             self.filt_env_decay_s_curve,
             self.filt_env_release_s_curve,
         );
+        if self.resonator_enabled {
+            self.resonator.excite();
+        }
 
         if was_idle {
             self.reset_oscillator_phases();
@@ -1764,6 +2432,9 @@ impl Voice {
             self.filt_env_decay_s_curve,
             self.filt_env_release_s_curve,
         );
+        if self.resonator_enabled {
+            self.resonator.excite();
+        }
     }
 
     fn reset_oscillator_phases(&mut self) {
@@ -1774,6 +2445,7 @@ impl Voice {
         self.pll_reference_oscillator.reset_phase();
         self.pll_reference_oscillator_right.reset_phase();
         self.saw_oscillator.trigger();
+        self.wt_oscillator.trigger();
 
         self.pll_prev_out_l = 0.0;
         self.pll_prev_out_r = 0.0;
@@ -1815,6 +2487,10 @@ impl Voice {
 
         self.drift_phase_l = 0.0;
         self.drift_phase_r = 0.33;
+        self.lofi.reset();
+        self.noise.reset();
+        self.multi_filter.reset();
+        self.resonator.reset();
     }
 
     fn saw_downsample(&mut self) -> f64 {
@@ -1829,6 +2505,18 @@ impl Voice {
         }
     }
 
+    fn wt_downsample(&mut self) -> f64 {
+        match self.effective_ratio {
+            2 => self.wt_os_2x.downsample(),
+            4 => self.wt_os_4x.downsample(),
+            8 => self.wt_os_8x.downsample(),
+            16 => self.wt_os_16x.downsample(),
+            32 => self.wt_os_32x.downsample(),
+            64 => self.wt_os_64x.downsample(),
+            _ => self.wt_os_128x.downsample(),
+        }
+    }
+
     fn vps_downsample_left(&mut self) -> f64 {
         match self.effective_ratio {
             2 => self.vps_os_2x_left.downsample(),
@@ -1853,7 +2541,7 @@ impl Voice {
         }
     }
 
-    pub fn process(&mut self, _pll_feedback: f64) -> (f64, f64, f64, f64, f64) {
+    pub fn process(&mut self, _pll_feedback: f64) -> (f64, f64, f64, f64, f64, f64, f64) {
         let volume_env = if self.vca_mode {
             1.0
         } else {
@@ -1902,7 +2590,7 @@ impl Voice {
         let glide_ms = if self.glide_time_ms > 0.5 { self.glide_time_ms } else { 0.5 };
         let target_log2 = self.target_frequency.max(1.0).log2();
         let slewed_log2 = self.freq_slew.next(target_log2, glide_ms);
-        self.base_frequency = (2.0_f64).powf(slewed_log2);
+        self.base_frequency = (2.0_f64).powf(slewed_log2 + self.mod_global_pitch / 12.0);
 
         // PLL slews + modulation
         self.pll_volume = (self.pll_volume_slew.next(self.target_pll_volume, 20.0) + self.mod_pll_volume).clamp(0.0, 1.0);
@@ -1942,6 +2630,52 @@ impl Voice {
         self.saw_shape_amount = (self.saw_shape_amount_slew.next(self.target_saw_shape_amount, 50.0) + self.mod_saw_shape_amount).clamp(0.0, 1.0);
         self.saw_tight = self.saw_tight_slew.next(self.target_saw_tight, 50.0).clamp(0.0, 1.0);
 
+        // Wavetable slews + modulation
+        self.wt_volume = (self.wt_volume_slew.next(self.target_wt_volume, 20.0) + self.mod_wt_volume).clamp(0.0, 1.0);
+        self.wt_position = (self.wt_position_slew.next(self.target_wt_position, 20.0) + self.mod_wt_position).clamp(0.0, 1.0);
+
+        // Lo-fi slews + modulation
+        self.lofi_drive = (self.lofi_drive_slew.next(self.target_lofi_drive, 20.0) + self.mod_lofi_drive).clamp(0.0, 1.0);
+        self.lofi_mix = (self.lofi_mix_slew.next(self.target_lofi_mix, 20.0) + self.mod_lofi_mix).clamp(0.0, 1.0);
+        if self.lofi_enabled {
+            self.lofi.set_params(&LofiParams {
+                shaper: self.lofi_shaper,
+                bits: self.lofi_bits,
+                rate_hz: self.lofi_rate,
+                anti_alias: self.lofi_anti_alias,
+                jitter: self.lofi_jitter,
+                drive: self.lofi_drive,
+                mix: self.lofi_mix,
+                bits_mod: self.mod_lofi_bits,
+                rate_mod: self.mod_lofi_rate,
+            });
+        }
+
+        // Noise slews + modulation
+        self.noise_level = (self.noise_level_slew.next(self.target_noise_level, 20.0) + self.mod_noise_level).clamp(0.0, 1.0);
+        self.noise_inject_amount = self.noise_inject_amount_slew.next(self.target_noise_inject_amount, 20.0).clamp(0.0, 1.0);
+        let noise_injecting = self.pll_enabled
+            && self.noise_injection != NoiseInjection::Off
+            && self.noise_inject_amount > 0.001;
+        if self.noise_level > 0.001 || noise_injecting {
+            self.noise.set_params(self.noise_type, self.noise_rate, self.noise_lpf, self.noise_hpf);
+        }
+
+        // Sync slew + modulation
+        self.sync_soft = (self.sync_soft_slew.next(self.target_sync_soft, 20.0) + self.mod_sync_soft).clamp(0.0, 1.0);
+
+        // PLL topology slews
+        self.pll2_track = self.pll2_track_slew.next(self.target_pll2_track, 50.0).clamp(0.0, 1.0);
+        self.pll2_damping = self.pll2_damping_slew.next(self.target_pll2_damping, 50.0).clamp(0.001, 1.0);
+        self.pll_coupling = self.pll_coupling_slew.next(self.target_pll_coupling, 50.0).clamp(0.0, 1.0);
+        self.pll_ref_mix = self.pll_ref_mix_slew.next(self.target_pll_ref_mix, 50.0).clamp(0.0, 1.0);
+
+        // Ring mod / cross-FM slews + modulation
+        self.ring_pll = (self.ring_pll_slew.next(self.target_ring_pll, 20.0) + self.mod_ring_pll).clamp(0.0, 1.0);
+        self.ring_vps_saw = (self.ring_vps_saw_slew.next(self.target_ring_vps_saw, 20.0) + self.mod_ring_vps_saw).clamp(0.0, 1.0);
+        self.xfm_vps_saw = (self.xfm_vps_saw_slew.next(self.target_xfm_vps_saw, 20.0) + self.mod_xfm_vps_saw).clamp(0.0, 1.0);
+        self.xfm_saw_ref = (self.xfm_saw_ref_slew.next(self.target_xfm_saw_ref, 20.0) + self.mod_xfm_saw_ref).clamp(0.0, 1.0);
+
         // Filter slews + modulation (octave-based for perceptually consistent depth)
         let base_cutoff = self.filter_cutoff_slew.next(self.target_filter_cutoff, 0.001);
         let mod_octaves = self.mod_filter_cutoff * 5.0;
@@ -1960,6 +2694,20 @@ impl Voice {
         self.filter_res_tilt = (self.filter_res_tilt_slew.next(self.target_filter_res_tilt, 20.0) + self.mod_filter_res_tilt).clamp(-1.0, 1.0);
         self.filter_cutoff_slew_amt = self.filter_cutoff_slew_amt_slew.next(self.target_filter_cutoff_slew_amt, 20.0).clamp(0.0, 1.0);
 
+        // Filter 2 slews + modulation
+        let base_cutoff2 = self.filter2_cutoff_slew.next(self.target_filter2_cutoff, 0.001);
+        self.filter2_cutoff = (base_cutoff2 * (self.mod_filter2_cutoff * 5.0).exp2()).clamp(20.0, 20000.0);
+        self.filter2_resonance = (self.filter2_resonance_slew.next(self.target_filter2_resonance, 20.0) + self.mod_filter2_resonance).clamp(0.0, 1.0);
+        self.filter2_morph = (self.filter2_morph_slew.next(self.target_filter2_morph, 20.0) + self.mod_filter2_morph).clamp(0.0, 1.0);
+        self.filter2_key_track = self.filter2_key_track_slew.next(self.target_filter2_key_track, 20.0).clamp(0.0, 1.0);
+        self.filter2_env_amount = self.filter2_env_amount_slew.next(self.target_filter2_env_amount, 20.0).clamp(-1.0, 1.0);
+
+        // Resonator slews
+        self.resonator_decay = self.resonator_decay_slew.next(self.target_resonator_decay, 20.0).clamp(0.0, 1.0);
+        self.resonator_brightness = self.resonator_brightness_slew.next(self.target_resonator_brightness, 20.0).clamp(0.0, 1.0);
+        self.resonator_position = self.resonator_position_slew.next(self.target_resonator_position, 20.0).clamp(0.0, 1.0);
+        self.resonator_mix = self.resonator_mix_slew.next(self.target_resonator_mix, 20.0).clamp(0.0, 1.0);
+
         // Sub slew + modulation
         self.sub_volume = (self.sub_volume_slew.next(self.target_sub_volume, 20.0) + self.mod_sub_volume).clamp(0.0, 1.0);
 
@@ -1988,7 +2736,50 @@ impl Voice {
         self.master_volume = self.master_volume_slew.next(self.target_master_volume, 20.0);
 
         if !self.vca_mode && !self.volume_envelope.is_active() && !self.pll_tail_envelope.is_active() {
-            return (0.0, 0.0, 0.0, 0.0, 0.0);
+            return (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        }
+
+        // ===== NOISE AT DAW RATE =====
+        // One sample feeds both the mix and the PLL injection point
+        let noise_sample = if self.noise_level > 0.001 || noise_injecting {
+            self.noise.next()
+        } else {
+            0.0
+        };
+        let noise_inject = if noise_injecting { noise_sample * self.noise_inject_amount } else { 0.0 };
+
+        // ===== CROSS-MOD AMOUNTS =====
+        // Paths need both ends running; VPS feeds SAW within this sample,
+        // SAW feeds the PLL reference one host sample late
+        let ring_pll = if self.ring_pll_enabled { self.ring_pll } else { 0.0 };
+        let ring_vps_saw = if self.ring_vps_saw_enabled && self.vps_enabled { self.ring_vps_saw } else { 0.0 };
+        let xfm_vps_saw = if self.xfm_vps_saw_enabled && self.vps_enabled { self.xfm_vps_saw * XFM_MAX_INDEX } else { 0.0 };
+        let xfm_saw_ref = if self.xfm_saw_ref_enabled && self.saw_enabled { self.xfm_saw_ref * XFM_MAX_INDEX } else { 0.0 };
+        let vps_feeds_saw = ring_vps_saw > 0.0 || xfm_vps_saw > 0.0;
+
+        // ===== SYNC MASTER =====
+        // VPS/SAW read the master's wraps per oversampled step; the PLL loop
+        // records them, or a bare phase stands in for the reference when
+        // the PLL is off
+        let vps_sync_amt = if self.vps_enabled { self.sync_amount(self.vps_sync) } else { 0.0 };
+        let saw_sync_amt = if self.saw_enabled { self.sync_amount(self.saw_sync) } else { 0.0 };
+        let osc_sync = vps_sync_amt > 0.0 || saw_sync_amt > 0.0;
+        let pll_sync_amt = self.sync_amount(self.pll_sync);
+        if osc_sync {
+            let iterations = self.effective_ratio as usize;
+            self.sync_events[..iterations].fill(-1.0);
+            if !self.pll_enabled && !self.sync_source_vco {
+                let tune_mult = 2.0_f64.powf((self.pll_ref_tune as f64 + self.pll_ref_fine) / 12.0);
+                let ref_freq = self.base_frequency * 2.0_f64.powi(self.pll_ref_octave) * tune_mult * self.pll_ref_pitch_mult;
+                let inc = ref_freq / self.processing_sample_rate;
+                for event in self.sync_events.iter_mut().take(iterations) {
+                    self.sync_master_phase += inc;
+                    if self.sync_master_phase >= 1.0 {
+                        self.sync_master_phase -= 1.0;
+                        *event = self.sync_master_phase / inc;
+                    }
+                }
+            }
         }
 
         // ===== PLL OVERSAMPLED BLOCK =====
@@ -2006,7 +2797,20 @@ impl Voice {
                 super::oscillator::PllMode::AnalogLikePD
             };
             let effective_mult = self.pll_multiplier;
-            let use_stereo_pll = self.pll_damping_stereo_offset > 0.0001 || self.pll_stereo_track_offset > 0.0001 || self.pll_stereo_phase > 0.0001;
+            let topology = self.pll_topology;
+            let use_stereo_pll = topology == PllTopology::Stereo
+                && (self.pll_damping_stereo_offset > 0.0001 || self.pll_stereo_track_offset > 0.0001 || self.pll_stereo_phase > 0.0001);
+            // Cascade and Dual drive the right oscillator as the second loop
+            let run_right = use_stereo_pll || topology != PllTopology::Stereo;
+            let stereo_out = use_stereo_pll || topology == PllTopology::Dual;
+            let coupling = self.pll_coupling;
+
+            // The routed oscillator only advances while it's switched on
+            let ref_mix = match self.pll_ref_source {
+                PllRefSource::Vps if self.vps_enabled => self.pll_ref_mix,
+                PllRefSource::Saw if self.saw_enabled => self.pll_ref_mix,
+                _ => 0.0,
+            };
 
             let damp_left = (self.pll_damping - self.pll_damping_stereo_offset).clamp(0.001, 1.0);
             let damp_right = if use_stereo_pll {
@@ -2034,9 +2838,17 @@ impl Voice {
             self.pll_oscillator_left.set_anti_alias(self.pll_anti_alias);
             self.pll_oscillator_left.set_injection_amount(self.pll_injection_amount);
             self.pll_oscillator_left.set_injection_mult(self.pll_injection_x4);
+            self.pll_oscillator_left.set_noise_injection(self.noise_injection);
+            self.pll_oscillator_left.set_noise_input(noise_inject);
+            self.pll_oscillator_left.set_vco_sync(pll_sync_amt);
             self.pll_oscillator_left.set_params(track_left, damp_left, effective_mult, slewed_influence, self.pll_colored, mode);
 
-            if use_stereo_pll {
+            if run_right {
+                let (track_r, damp_r, mult_r) = if topology == PllTopology::Stereo {
+                    (track_right, damp_right, effective_mult)
+                } else {
+                    (self.pll2_track, self.pll2_damping, self.pll2_multiplier)
+                };
                 self.pll_oscillator_right.set_experimental_params(
                     self.pll_retrigger,
                     self.pll_burst_threshold,
@@ -2050,7 +2862,10 @@ impl Voice {
                 self.pll_oscillator_right.set_anti_alias(self.pll_anti_alias);
                 self.pll_oscillator_right.set_injection_amount(self.pll_injection_amount);
                 self.pll_oscillator_right.set_injection_mult(self.pll_injection_x4);
-                self.pll_oscillator_right.set_params(track_right, damp_right, effective_mult, slewed_influence, self.pll_colored, mode);
+                self.pll_oscillator_right.set_noise_injection(self.noise_injection);
+                self.pll_oscillator_right.set_noise_input(noise_inject);
+                self.pll_oscillator_right.set_vco_sync(pll_sync_amt);
+                self.pll_oscillator_right.set_params(track_r, damp_r, mult_r, slewed_influence, self.pll_colored, mode);
             }
 
             let iterations = self.effective_ratio as usize;
@@ -2068,7 +2883,7 @@ impl Voice {
             };
 
             let pll_tune_mult = 2.0_f64.powf((self.pll_ref_tune as f64 + self.pll_ref_fine) / 12.0);
-            let ref_freq = self.base_frequency * 2.0_f64.powi(self.pll_ref_octave) * pll_tune_mult;
+            let ref_freq = self.base_frequency * 2.0_f64.powi(self.pll_ref_octave) * pll_tune_mult * self.pll_ref_pitch_mult;
             let fm_ratio = self.pll_fm_ratio_float;
             let effective_fm_amount = self.pll_fm_amount * 0.4;
             let fm_env_mod = 1.0;
@@ -2100,25 +2915,114 @@ impl Voice {
                 let ref_mod_l = ((ref_freq * (1.0 + drift_mod_l) + fm_mod) * (1.0 + fb_mod)).clamp(20.0, self.processing_sample_rate * 2.0);
                 let ref_mod_r = ((ref_freq * (1.0 + drift_mod_r) + fm_mod) * (1.0 + fb_mod)).clamp(20.0, self.processing_sample_rate * 2.0);
 
-                self.pll_reference_oscillator.set_frequency(ref_mod_l);
+                // Through-zero FM moves the reference only; the loops keep
+                // ref_mod as their centre frequency
+                let (ref_osc_l, ref_osc_r) = if xfm_saw_ref > 0.0 {
+                    let limit = self.processing_sample_rate * 0.5;
+                    let fm = 1.0 + xfm_saw_ref * self.saw_steps[i];
+                    ((ref_mod_l * fm).clamp(-limit, limit), (ref_mod_r * fm).clamp(-limit, limit))
+                } else {
+                    (ref_mod_l, ref_mod_r)
+                };
+
+                self.pll_reference_oscillator.set_frequency(ref_osc_l);
                 let ref_pulse_l = self.pll_reference_oscillator.next(self.pll_ref_pulse_width);
                 let ref_phase_l = self.pll_reference_oscillator.get_phase();
 
-                let pll_raw_l = self.pll_oscillator_left.next(ref_phase_l, ref_mod_l, ref_pulse_l);
-                let pll_raw_r = if use_stereo_pll {
-                    self.pll_reference_oscillator_right.set_frequency(ref_mod_r);
-                    let ref_pulse_r = self.pll_reference_oscillator_right.next(self.pll_ref_pulse_width);
-                    let ref_phase_r = (self.pll_reference_oscillator_right.get_phase() + self.pll_stereo_phase) % 1.0;
-                    self.pll_oscillator_right.next(ref_phase_r, ref_mod_r, ref_pulse_r)
+                // VPS/SAW routed into the reference: phase, edges and frequency
+                let ext_phase = self.pll_ext_phase[i];
+                let ext_signal = self.pll_ext_signal[i];
+                let ext_freq = self.pll_ext_freq;
+                let (ref_phase_l, ref_pulse_l, ref_in_l) = if ref_mix > 0.0 {
+                    (
+                        blend_phase(ref_phase_l, ext_phase, ref_mix),
+                        ref_pulse_l + (ext_signal - ref_pulse_l) * ref_mix,
+                        ref_mod_l + (ext_freq - ref_mod_l) * ref_mix,
+                    )
+                } else {
+                    (ref_phase_l, ref_pulse_l, ref_mod_l)
+                };
+
+                // Dual loops listen to each other's previous step
+                let prev_phase_l = self.pll_oscillator_left.phase();
+                let prev_phase_r = self.pll_oscillator_right.phase();
+                let (in_phase_l, in_pulse_l) = if topology == PllTopology::Dual {
+                    (
+                        blend_phase(ref_phase_l, prev_phase_r, coupling),
+                        ref_pulse_l + (self.pll_prev_out_r - ref_pulse_l) * coupling,
+                    )
                 } else {
-                    pll_raw_l
+                    (ref_phase_l, ref_pulse_l)
+                };
+
+                let pll_raw_l = self.pll_oscillator_left.next(in_phase_l, ref_in_l, in_pulse_l);
+
+                let pll_raw_r = match topology {
+                    PllTopology::Cascade => {
+                        // Loop 1's VCO runs at its multiplied frequency
+                        let stage1_phase = self.pll_oscillator_left.phase();
+                        self.pll_oscillator_right.next(stage1_phase, ref_in_l * effective_mult, pll_raw_l)
+                    }
+                    PllTopology::Dual => {
+                        let in_phase_r = blend_phase(ref_phase_l, prev_phase_l, coupling);
+                        let in_pulse_r = ref_pulse_l + (self.pll_prev_out_l - ref_pulse_l) * coupling;
+                        self.pll_oscillator_right.next(in_phase_r, ref_in_l, in_pulse_r)
+                    }
+                    PllTopology::Stereo if use_stereo_pll => {
+                        self.pll_reference_oscillator_right.set_frequency(ref_osc_r);
+                        let ref_pulse_r = self.pll_reference_oscillator_right.next(self.pll_ref_pulse_width);
+                        let ref_phase_r = self.pll_reference_oscillator_right.get_phase();
+                        let (ref_phase_r, ref_pulse_r, ref_in_r) = if ref_mix > 0.0 {
+                            (
+                                blend_phase(ref_phase_r, ext_phase, ref_mix),
+                                ref_pulse_r + (ext_signal - ref_pulse_r) * ref_mix,
+                                ref_mod_r + (ext_freq - ref_mod_r) * ref_mix,
+                            )
+                        } else {
+                            (ref_phase_r, ref_pulse_r, ref_mod_r)
+                        };
+                        let ref_phase_r = (ref_phase_r + self.pll_stereo_phase) % 1.0;
+                        self.pll_oscillator_right.next(ref_phase_r, ref_in_r, ref_pulse_r)
+                    }
+                    PllTopology::Stereo => pll_raw_l,
                 };
 
+                if osc_sync {
+                    let master = if !self.sync_source_vco {
+                        ref_phase_l
+                    } else if topology == PllTopology::Cascade {
+                        self.pll_oscillator_right.phase()
+                    } else {
+                        self.pll_oscillator_left.phase()
+                    };
+                    if self.sync_master_phase - master > 0.5 {
+                        let inc = if self.sync_source_vco {
+                            master + 1.0 - self.sync_master_phase
+                        } else {
+                            ref_in_l / self.processing_sample_rate
+                        };
+                        self.sync_events[i] = (master / inc.max(1e-9)).clamp(0.0, 1.0);
+                    }
+                    self.sync_master_phase = master;
+                }
+
+                // Cascade only sounds the second loop
+                let pll_raw_l = if topology == PllTopology::Cascade { pll_raw_r } else { pll_raw_l };
+
                 self.pll_prev_out_l = pll_raw_l;
                 self.pll_prev_out_r = pll_raw_r;
 
                 feedback = feedback * fb_coeff + (pll_raw_l + pll_raw_r) * fb_gain;
 
+                let (pll_raw_l, pll_raw_r) = if ring_pll > 0.0 {
+                    (
+                        pll_raw_l + (pll_raw_l * ref_pulse_l - pll_raw_l) * ring_pll,
+                        pll_raw_r + (pll_raw_r * ref_pulse_l - pll_raw_r) * ring_pll,
+                    )
+                } else {
+                    (pll_raw_l, pll_raw_r)
+                };
+
                 let (out_l, out_r) = if self.tube_drive > 0.001 {
                     let drive = 1.0 + self.tube_drive * 4.0;
                     let xl = pll_raw_l * drive;
@@ -2132,7 +3036,7 @@ impl Voice {
                 };
 
                 buf_l[i] = out_l;
-                buf_r[i] = if use_stereo_pll { out_r } else { out_l };
+                buf_r[i] = if stereo_out { out_r } else { out_l };
             }
 
             // Downsample PLL output
@@ -2169,7 +3073,7 @@ impl Voice {
 
         if self.vps_enabled {
             let tune_mult = 2.0_f64.powf((self.vps_tune as f64 + self.vps_fine) / 12.0);
-            let base_freq = self.base_frequency * 2.0_f64.powi(self.vps_octave) * tune_mult;
+            let base_freq = self.base_frequency * 2.0_f64.powi(self.vps_octave) * tune_mult * self.vps_pitch_mult;
 
             let use_stereo_v = self.vps_stereo_v_offset > 0.0001;
             let use_stereo_d = self.vps_stereo_d_offset > 0.0001;
@@ -2193,6 +3097,10 @@ impl Voice {
             let vps_use_oversampling = self.effective_ratio > 1;
             let dist_type = (self.vps_shape_type + 1) as u8;
             let shape_amt = self.vps_shape_amount;
+            let feeds_pll = self.pll_ref_source == PllRefSource::Vps;
+            if feeds_pll {
+                self.pll_ext_freq = base_freq;
+            }
 
             let (vbuf_l, vbuf_r): (&mut [f64], &mut [f64]) = match self.effective_ratio {
                 2 => (self.vps_os_2x_left.resample_buffer(), self.vps_os_2x_right.resample_buffer()),
@@ -2206,8 +3114,14 @@ impl Voice {
             };
 
             for i in 0..vps_iterations {
-                let raw_l = self.vps_oscillator_left.next(d_left, v_left);
-                let raw_r = if use_stereo { self.vps_oscillator_right.next(d_right, v_right) } else { raw_l };
+                let reset = if vps_sync_amt > 0.0 { self.sync_events[i] } else { -1.0 };
+                let step_phase = self.vps_oscillator_left.phase();
+                let raw_l = self.vps_oscillator_left.next_synced(d_left, v_left, reset, vps_sync_amt);
+                let raw_r = if use_stereo {
+                    self.vps_oscillator_right.next_synced(d_right, v_right, reset, vps_sync_amt)
+                } else {
+                    raw_l
+                };
 
                 let (shaped_l, shaped_r) = if self.vps_shape_amount > 0.001 {
                     (
@@ -2230,6 +3144,14 @@ impl Voice {
                     (shaped_l, shaped_r)
                 };
 
+                if feeds_pll {
+                    self.pll_ext_phase[i] = step_phase;
+                    self.pll_ext_signal[i] = folded_l;
+                }
+                if vps_feeds_saw {
+                    self.vps_steps[i] = folded_l;
+                }
+
                 vbuf_l[i] = folded_l;
                 vbuf_r[i] = folded_r;
             }
@@ -2247,10 +3169,16 @@ impl Voice {
 
         // ===== SAW OVERSAMPLED =====
         let mut saw_out = 0.0_f64;
-        if self.saw_enabled && self.saw_volume > 0.001 {
+        // Keeps running at zero volume while it drives the PLL reference
+        let saw_is_ref = self.pll_ref_source == PllRefSource::Saw;
+        let saw_feeds_pll = saw_is_ref || xfm_saw_ref > 0.0;
+        if self.saw_enabled && (self.saw_volume > 0.001 || saw_feeds_pll) {
             let tune_mult = 2.0_f64.powf((self.saw_tune as f64 + self.saw_fine) / 12.0);
-            let saw_freq = self.base_frequency * 2.0_f64.powi(self.saw_octave) * tune_mult;
+            let saw_freq = self.base_frequency * 2.0_f64.powi(self.saw_octave) * tune_mult * self.saw_pitch_mult;
             self.saw_oscillator.set_frequency(saw_freq);
+            if saw_is_ref {
+                self.pll_ext_freq = saw_freq;
+            }
 
             let iterations = self.effective_ratio as usize;
             let use_oversampling = self.effective_ratio > 1;
@@ -2268,8 +3196,12 @@ impl Voice {
                 _ => self.saw_os_2x.resample_buffer(),
             };
 
-            for sample in buf.iter_mut().take(iterations) {
-                let raw = self.saw_oscillator.next();
+            for (i, sample) in buf.iter_mut().take(iterations).enumerate() {
+                let reset = if saw_sync_amt > 0.0 { self.sync_events[i] } else { -1.0 };
+                let step_phase = self.saw_oscillator.phase();
+                let fm = if xfm_vps_saw > 0.0 { xfm_vps_saw * self.vps_steps[i] } else { 0.0 };
+                self.saw_oscillator.set_fm(fm);
+                let raw = self.saw_oscillator.next_synced(reset, saw_sync_amt);
 
                 let shaped = if self.saw_shape_amount > 0.001 {
                     apply_distortion(raw, shape_amt, dist_type)
@@ -2287,7 +3219,20 @@ impl Voice {
                     shaped
                 };
 
-                *sample = folded;
+                if saw_is_ref {
+                    self.pll_ext_phase[i] = step_phase;
+                    self.pll_ext_signal[i] = folded;
+                }
+                if xfm_saw_ref > 0.0 {
+                    self.saw_steps[i] = folded;
+                }
+
+                // VPS × SAW comes out on the SAW channel
+                *sample = if ring_vps_saw > 0.0 {
+                    folded + (folded * self.vps_steps[i] - folded) * ring_vps_saw
+                } else {
+                    folded
+                };
             }
 
             let sample = if use_oversampling {
@@ -2300,6 +3245,41 @@ impl Voice {
             saw_out = tightened * self.saw_volume * volume_env;
         }
 
+        // ===== WAVETABLE OVERSAMPLED =====
+        let mut wt_out = 0.0_f64;
+        if self.wt_enabled && self.wt_volume > 0.001 {
+            let tune_mult = 2.0_f64.powf((self.wt_tune as f64 + self.wt_fine) / 12.0);
+            let wt_freq = self.base_frequency * 2.0_f64.powi(self.wt_octave) * tune_mult;
+            self.wt_oscillator.set_frequency(wt_freq);
+
+            let iterations = self.effective_ratio as usize;
+            let use_oversampling = self.effective_ratio > 1;
+            let position = self.wt_position;
+
+            let buf: &mut [f64] = match self.effective_ratio {
+                2 => self.wt_os_2x.resample_buffer(),
+                4 => self.wt_os_4x.resample_buffer(),
+                8 => self.wt_os_8x.resample_buffer(),
+                16 => self.wt_os_16x.resample_buffer(),
+                32 => self.wt_os_32x.resample_buffer(),
+                64 => self.wt_os_64x.resample_buffer(),
+                128 => self.wt_os_128x.resample_buffer(),
+                _ => self.wt_os_2x.resample_buffer(),
+            };
+
+            for sample in buf.iter_mut().take(iterations) {
+                *sample = self.wt_oscillator.next(position);
+            }
+
+            let sample = if use_oversampling {
+                self.wt_downsample()
+            } else {
+                buf[0]
+            };
+
+            wt_out = sample * self.wt_volume * volume_env;
+        }
+
         // ===== MIX AT DAW RATE =====
         let pll_env = if volume_env > 0.0001 {
             volume_env
@@ -2316,26 +3296,77 @@ impl Voice {
             0.0
         };
 
-        let pre_filter_l = vps_out_l + pll_out_final_l + saw_out + sub_pre;
-        let pre_filter_r = vps_out_r + pll_out_final_r + saw_out + sub_pre;
-
-        let (mixed_l, mixed_r) = if self.filter_enabled {
-            let filter_env = if self.filter_envelope.is_active() {
-                self.filter_envelope.update_params(
-                    self.filt_env_attack, self.filt_env_attack_shape,
-                    (self.filt_env_hold + self.mod_filt_env_hold * 5000.0).max(0.0),
-                    self.filt_env_decay, self.filt_env_decay_shape,
-                    self.filt_env_sustain,
-                    self.filt_env_release, self.filt_env_release_shape,
-                    self.filt_env_loop_mode,
-                    self.filt_env_attack_s_curve,
-                    self.filt_env_decay_s_curve,
-                    self.filt_env_release_s_curve,
-                );
-                self.filter_envelope.next()
+        // Runs whenever the filter or the noise source needs it
+        let noise_on_filter_env = self.noise_filter_env && self.noise_level > 0.001;
+        let filter_env = if (self.filter_enabled || self.filter2_enabled || noise_on_filter_env) && self.filter_envelope.is_active() {
+            self.filter_envelope.update_params(
+                self.filt_env_attack, self.filt_env_attack_shape,
+                (self.filt_env_hold + self.mod_filt_env_hold * 5000.0).max(0.0),
+                self.filt_env_decay, self.filt_env_decay_shape,
+                self.filt_env_sustain,
+                self.filt_env_release, self.filt_env_release_shape,
+                self.filt_env_loop_mode,
+                self.filt_env_attack_s_curve,
+                self.filt_env_decay_s_curve,
+                self.filt_env_release_s_curve,
+            );
+            self.filter_envelope.next()
+        } else {
+            0.0
+        };
+
+        let noise_out = if self.noise_level > 0.001 {
+            let noise_env = if self.noise_filter_env { filter_env } else { volume_env };
+            noise_sample * self.noise_level * noise_env
+        } else {
+            0.0
+        };
+
+        // Bus A (VPS/PLL/sub) and bus B (SAW/WT/noise) stay apart for split routing
+        let lofi_pre = self.lofi_enabled && !self.lofi_post_filter;
+        let (bus_a_l, bus_a_r, bus_b_l, bus_b_r) = if lofi_pre {
+            // Only the selected oscillators pass through the crusher; the rest join after it.
+            // The crushed signal rides on bus A.
+            let mut crush_l = 0.0;
+            let mut crush_r = 0.0;
+            let mut a_l = sub_pre;
+            let mut a_r = sub_pre;
+            let mut b = wt_out + noise_out;
+            for (selected, l, r) in [
+                (self.lofi_vps, vps_out_l, vps_out_r),
+                (self.lofi_pll, pll_out_final_l, pll_out_final_r),
+            ] {
+                if selected {
+                    crush_l += l;
+                    crush_r += r;
+                } else {
+                    a_l += l;
+                    a_r += r;
+                }
+            }
+            if self.lofi_saw {
+                crush_l += saw_out;
+                crush_r += saw_out;
             } else {
-                0.0
-            };
+                b += saw_out;
+            }
+            let (crushed_l, crushed_r) = self.lofi.process(crush_l, crush_r);
+            (crushed_l + a_l, crushed_r + a_r, b, b)
+        } else {
+            (vps_out_l + pll_out_final_l + sub_pre,
+             vps_out_r + pll_out_final_r + sub_pre,
+             saw_out + wt_out + noise_out,
+             saw_out + wt_out + noise_out)
+        };
+
+        let split = self.filter2_enabled && self.filter_routing == FilterRouting::Split;
+        let (pre_filter_l, pre_filter_r) = if split {
+            (bus_a_l, bus_a_r)
+        } else {
+            (bus_a_l + bus_b_l, bus_a_r + bus_b_r)
+        };
+
+        let (f1_l, f1_r) = if self.filter_enabled {
             self.ladder_filter.set_params(&FilterParams {
                 cutoff: self.filter_cutoff,
                 resonance: self.filter_resonance,
@@ -2362,6 +3393,53 @@ impl Voice {
             (pre_filter_l, pre_filter_r)
         };
 
+        let (mixed_l, mixed_r) = if self.filter2_enabled {
+            self.multi_filter.set_params(&MultiFilterParams {
+                cutoff: self.filter2_cutoff,
+                resonance: self.filter2_resonance,
+                filter_type: self.filter2_type,
+                morph: self.filter2_morph,
+                key_track_hz: self.base_frequency,
+                key_track_amount: self.filter2_key_track,
+                env_mod: self.filter2_env_amount * filter_env,
+            });
+            match self.filter_routing {
+                FilterRouting::Serial => self.multi_filter.process(f1_l, f1_r),
+                FilterRouting::Parallel => {
+                    let (f2_l, f2_r) = self.multi_filter.process(pre_filter_l, pre_filter_r);
+                    ((f1_l + f2_l) * 0.5, (f1_r + f2_r) * 0.5)
+                }
+                FilterRouting::Split => {
+                    let (f2_l, f2_r) = self.multi_filter.process(bus_b_l, bus_b_r);
+                    (f1_l + f2_l, f1_r + f2_r)
+                }
+            }
+        } else {
+            (f1_l, f1_r)
+        };
+
+        // Tracks the glided note pitch, so microtonal steps land on the resonator too
+        let (mixed_l, mixed_r) = if self.resonator_enabled {
+            self.resonator.set_params(&ResonatorParams {
+                model: self.resonator_model,
+                excite: self.resonator_excite,
+                frequency: self.base_frequency,
+                decay: self.resonator_decay,
+                brightness: self.resonator_brightness,
+                position: self.resonator_position,
+                mix: self.resonator_mix,
+            });
+            self.resonator.process(mixed_l, mixed_r)
+        } else {
+            (mixed_l, mixed_r)
+        };
+
+        let (mixed_l, mixed_r) = if self.lofi_enabled && self.lofi_post_filter {
+            self.lofi.process(mixed_l, mixed_r)
+        } else {
+            (mixed_l, mixed_r)
+        };
+
         // ===== SUB AT DAW RATE (separate output for HPF routing, or 0 if routed through filter) =====
         let sub_sample = if self.sub_filter_route {
             0.0
@@ -2379,6 +3457,7 @@ impl Voice {
         self.last_pll_l = pll_out_final_l * self.master_volume * vel_scale;
         self.last_pll_r = pll_out_final_r * self.master_volume * vel_scale;
         self.last_saw = saw_out * self.master_volume * vel_scale;
+        self.last_wt = wt_out * self.master_volume * vel_scale;
         let final_l = mixed_l * self.master_volume * vel_scale;
         let final_r = mixed_r * self.master_volume * vel_scale;
         let final_sub = sub_sample * self.master_volume * vel_scale;
@@ -2392,13 +3471,124 @@ impl Voice {
             ((vps_out_l * self.reverb_send_vps
                 + pll_out_final_l * self.reverb_send_pll
                 + saw_out * self.reverb_send_saw
+                + wt_out * self.reverb_send_wt
                 + sub_pre * self.reverb_send_sub) * vel_scale,
              (vps_out_r * self.reverb_send_vps
                 + pll_out_final_r * self.reverb_send_pll
                 + saw_out * self.reverb_send_saw
+                + wt_out * self.reverb_send_wt
                 + sub_pre * self.reverb_send_sub) * vel_scale)
         };
 
-        (final_l, final_r, final_sub, rev_send_l, rev_send_r)
+        // ===== DELAY SEND =====
+        let (dly_send_l, dly_send_r) = if self.delay_send_filter > 0.0 {
+            (mixed_l * self.delay_send_filter * vel_scale,
+             mixed_r * self.delay_send_filter * vel_scale)
+        } else {
+            ((vps_out_l * self.delay_send_vps
+                + pll_out_final_l * self.delay_send_pll
+                + saw_out * self.delay_send_saw
+                + wt_out * self.delay_send_wt
+                + sub_pre * self.delay_send_sub) * vel_scale,
+             (vps_out_r * self.delay_send_vps
+                + pll_out_final_r * self.delay_send_pll
+                + saw_out * self.delay_send_saw
+                + wt_out * self.delay_send_wt
+                + sub_pre * self.delay_send_sub) * vel_scale)
+        };
+
+        (final_l, final_r, final_sub, rev_send_l, rev_send_r, dly_send_l, dly_send_r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    fn pll_voice(topology: i32, track2: f64, damp2: f64, mult2: f64, coupling: f64) -> Voice {
+        let mut voice = Voice::new(SAMPLE_RATE);
+        voice.set_bypass_switches(true, false, false, false, false);
+        voice.set_pll_volume(1.0);
+        voice.set_pll_topology(topology, track2, damp2, mult2, coupling);
+        voice.set_frequency(220.0, 0.0, 0.0);
+        voice.set_velocity(127);
+        voice.trigger();
+        voice
+    }
+
+    fn run(voice: &mut Voice, samples: usize) -> Vec<(f64, f64)> {
+        (0..samples)
+            .map(|_| {
+                voice.process(0.0);
+                (voice.pll_l(), voice.pll_r())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stereo_without_offsets_runs_one_loop() {
+        let mut voice = pll_voice(0, 0.9, 0.8, 3.0, 0.5);
+        let right_phase = voice.pll_oscillator_right.phase();
+        let out = run(&mut voice, 4800);
+        assert!(out.iter().any(|&(l, _)| l.abs() > 0.01));
+        assert!(out.iter().all(|&(l, r)| l == r));
+        // The second loop's settings don't apply and it never runs
+        assert_eq!(voice.pll_oscillator_right.phase(), right_phase);
+    }
+
+    #[test]
+    fn cascade_locks_to_both_multipliers() {
+        let second_loop_freq = |mult1: f64, mult2: f64, track: f64| {
+            let mut voice = pll_voice(1, track, 0.3, mult2, 0.5);
+            voice.set_pll_params(track, 0.3, mult1, 0.5, false, false);
+            run(&mut voice, 48000);
+            // Unwrapped phase of loop 2 over one second
+            let mut cycles = 0.0;
+            let mut prev = voice.pll_oscillator_right.phase();
+            for _ in 0..48000 {
+                voice.process(0.0);
+                let phase = voice.pll_oscillator_right.phase();
+                cycles += (phase - prev).rem_euclid(1.0);
+                prev = phase;
+            }
+            cycles
+        };
+        assert!((second_loop_freq(1.0, 1.0, 0.5) - 220.0).abs() < 0.01);
+        // Without tracking pull each loop sits on its multiplied centre,
+        // so loop 2 runs at ref × MULT × MUL2
+        let stacked = second_loop_freq(2.0, 4.0, 0.0);
+        assert!((stacked - 1760.0).abs() < 1760.0 * 0.01, "{stacked}");
+    }
+
+    #[test]
+    fn dual_without_coupling_runs_two_independent_loops() {
+        let mut dual = pll_voice(2, 0.7, 0.5, 2.0, 0.0);
+        let mut first = pll_voice(0, 0.5, 0.3, 1.0, 0.0);
+        let mut second = pll_voice(0, 0.5, 0.3, 1.0, 0.0);
+        second.set_pll_params(0.7, 0.5, 2.0, 0.5, false, false);
+        run(&mut dual, 48000);
+        run(&mut first, 48000);
+        run(&mut second, 48000);
+        let dual = run(&mut dual, 4800);
+        let first = run(&mut first, 4800);
+        let second = run(&mut second, 4800);
+        for ((dual, first), second) in dual.iter().zip(&first).zip(&second) {
+            assert_eq!(dual.0, first.0);
+            assert_eq!(dual.1, second.0);
+        }
+    }
+
+    #[test]
+    fn routed_reference_at_zero_mix_is_the_plain_reference() {
+        let mut plain = pll_voice(0, 0.5, 0.3, 1.0, 0.5);
+        let mut routed = pll_voice(0, 0.5, 0.3, 1.0, 0.5);
+        plain.set_bypass_switches(true, true, false, true, false);
+        routed.set_bypass_switches(true, true, false, true, false);
+        for source in [1, 2] {
+            routed.set_pll_ref_source(source, 0.0);
+            assert_eq!(run(&mut plain, 2400), run(&mut routed, 2400));
+        }
     }
 }
@@ -0,0 +1,565 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::dsp::rand_01;
+
+/// Serum-style frame length; every loaded frame is resampled to this size.
+pub const FRAME_SIZE: usize = 2048;
+pub const BUILTIN_NAME: &str = "Built-in";
+const MAX_FRAMES: usize = 256;
+/// Level k keeps at most `FRAME_SIZE / 2 >> k` harmonics; the last level is a pure sine.
+const MIP_LEVELS: usize = 11;
+const MIN_LEVEL_LEN: usize = 64;
+const BUILTIN_FRAMES: usize = 64;
+
+/// One band-limited copy of every frame. Each frame is stored with a guard
+/// sample (a copy of sample 0) so interpolation never wraps.
+struct MipLevel {
+    len: usize,
+    data: Vec<f32>,
+}
+
+impl MipLevel {
+    #[inline]
+    fn read(&self, frame: usize, phase: f64) -> f64 {
+        let pos = phase * self.len as f64;
+        let i = pos as usize;
+        let frac = pos - i as f64;
+        let base = frame * (self.len + 1) + i.min(self.len - 1);
+        let a = self.data[base] as f64;
+        let b = self.data[base + 1] as f64;
+        a + (b - a) * frac
+    }
+}
+
+pub struct Wavetable {
+    pub name: String,
+    frame_count: usize,
+    levels: Vec<MipLevel>,
+}
+
+/// Cosine/sine amplitudes per harmonic, index 0 = fundamental.
+type Harmonics = Vec<(f64, f64)>;
+
+impl Wavetable {
+    /// Sine → triangle → saw → square, so the oscillator is playable before
+    /// any user tables are installed.
+    pub fn builtin() -> Self {
+        let max_h = FRAME_SIZE / 2 - 1;
+        let sine = |n: usize| if n == 1 { 1.0 } else { 0.0 };
+        let triangle = |n: usize| {
+            if n % 2 == 0 {
+                0.0
+            } else {
+                let sign = if (n / 2) % 2 == 0 { 1.0 } else { -1.0 };
+                sign * 8.0 / (std::f64::consts::PI.powi(2) * (n * n) as f64)
+            }
+        };
+        let saw = |n: usize| {
+            let sign = if n % 2 == 1 { 1.0 } else { -1.0 };
+            sign * 2.0 / (std::f64::consts::PI * n as f64)
+        };
+        let square = |n: usize| if n % 2 == 1 { 4.0 / (std::f64::consts::PI * n as f64) } else { 0.0 };
+        let shapes: [&dyn Fn(usize) -> f64; 4] = [&sine, &triangle, &saw, &square];
+
+        let spectra: Vec<Harmonics> = (0..BUILTIN_FRAMES)
+            .map(|f| {
+                let pos = f as f64 / (BUILTIN_FRAMES - 1) as f64 * (shapes.len() - 1) as f64;
+                let seg = (pos as usize).min(shapes.len() - 2);
+                let t = pos - seg as f64;
+                (1..=max_h)
+                    .map(|n| (0.0, shapes[seg](n) * (1.0 - t) + shapes[seg + 1](n) * t))
+                    .collect()
+            })
+            .collect();
+
+        Self::from_spectra(BUILTIN_NAME, &spectra)
+    }
+
+    /// Mono (first channel) WAV. Frame size comes from a Serum `clm ` chunk
+    /// when present, otherwise 2048; files shorter than one frame are a
+    /// single-cycle waveform.
+    pub fn from_wav_bytes(name: &str, bytes: &[u8]) -> Result<Self, String> {
+        let wav = parse_wav(bytes)?;
+        if wav.samples.len() < 2 {
+            return Err("WAV file contains no audio".to_string());
+        }
+
+        let frame_size = wav.clm_frame_size.unwrap_or(FRAME_SIZE);
+        let frames: Vec<&[f64]> = if wav.samples.len() < frame_size {
+            vec![&wav.samples[..]]
+        } else {
+            wav.samples.chunks_exact(frame_size).take(MAX_FRAMES).collect()
+        };
+
+        let spectra: Vec<Harmonics> = frames.iter().map(|f| analyse_frame(f)).collect();
+        Ok(Self::from_spectra(name, &spectra))
+    }
+
+    fn from_spectra(name: &str, spectra: &[Harmonics]) -> Self {
+        let frame_count = spectra.len().max(1);
+        let mut levels: Vec<MipLevel> = (0..MIP_LEVELS)
+            .map(|k| {
+                let max_h = (FRAME_SIZE / 2) >> k;
+                let len = (max_h * 4).clamp(MIN_LEVEL_LEN, FRAME_SIZE);
+                let max_h = max_h.min(len / 2 - 1);
+                let mut data = Vec::with_capacity(frame_count * (len + 1));
+                let mut re = vec![0.0; len];
+                let mut im = vec![0.0; len];
+                for spectrum in spectra {
+                    re.iter_mut().for_each(|v| *v = 0.0);
+                    im.iter_mut().for_each(|v| *v = 0.0);
+                    for (i, &(a, b)) in spectrum.iter().take(max_h).enumerate() {
+                        let n = i + 1;
+                        re[n] = a * 0.5;
+                        im[n] = -b * 0.5;
+                        re[len - n] = a * 0.5;
+                        im[len - n] = b * 0.5;
+                    }
+                    fft(&mut re, &mut im, true);
+                    data.extend(re.iter().map(|&v| v as f32));
+                    data.push(re[0] as f32);
+                }
+                MipLevel { len, data }
+            })
+            .collect();
+
+        // Normalise the whole table, not each frame, so morphing keeps the
+        // relative loudness of the source
+        let peak = levels[0].data.iter().fold(0.0_f32, |m, v| m.max(v.abs()));
+        if peak > 1e-9 {
+            let gain = 1.0 / peak;
+            for level in &mut levels {
+                level.data.iter_mut().for_each(|v| *v *= gain);
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            frame_count,
+            levels,
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// `phase_inc` is cycles per sample at the rate the oscillator runs at.
+    #[inline]
+    pub fn sample(&self, phase: f64, position: f64, phase_inc: f64) -> f64 {
+        // One level above the alias-free minimum so the crossfade partner
+        // is band-limited too
+        let level_f = (phase_inc.abs() * (FRAME_SIZE * 2) as f64).max(1.0).log2().min((MIP_LEVELS - 1) as f64);
+        let level = level_f as usize;
+        let level_frac = level_f - level as f64;
+
+        let frame_f = position.clamp(0.0, 1.0) * (self.frame_count - 1) as f64;
+        let frame = (frame_f as usize).min(self.frame_count - 1);
+        let frame_next = (frame + 1).min(self.frame_count - 1);
+        let frame_frac = frame_f - frame as f64;
+
+        let read_level = |k: usize| {
+            let lvl = &self.levels[k];
+            let a = lvl.read(frame, phase);
+            let b = lvl.read(frame_next, phase);
+            a + (b - a) * frame_frac
+        };
+
+        let s = read_level(level);
+        if level_frac > 1e-6 && level + 1 < MIP_LEVELS {
+            s + (read_level(level + 1) - s) * level_frac
+        } else {
+            s
+        }
+    }
+}
+
+struct WavData {
+    samples: Vec<f64>,
+    clm_frame_size: Option<usize>,
+}
+
+fn read_u16(b: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([b[at], b[at + 1]])
+}
+
+fn read_u32(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+fn parse_wav(bytes: &[u8]) -> Result<WavData, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a RIFF/WAVE file".to_string());
+    }
+
+    let mut format = None;
+    let mut data = None;
+    let mut clm_frame_size = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = read_u32(bytes, pos + 4) as usize;
+        let body_start = pos + 8;
+        let body_end = (body_start + size).min(bytes.len());
+        let body = &bytes[body_start..body_end];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let mut tag = read_u16(body, 0);
+                // WAVE_FORMAT_EXTENSIBLE carries the real tag in the sub-format GUID
+                if tag == 0xFFFE && body.len() >= 26 {
+                    tag = read_u16(body, 24);
+                }
+                format = Some((tag, read_u16(body, 2) as usize, read_u16(body, 14) as usize));
+            }
+            b"data" => data = Some(body),
+            b"clm " => {
+                // Serum writes e.g. "<!>2048 01000000 wavetable (www.xferrecords.com)"
+                let text = String::from_utf8_lossy(body);
+                clm_frame_size = text
+                    .strip_prefix("<!>")
+                    .and_then(|t| t.split_whitespace().next())
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .filter(|&n| n >= 16);
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        pos = body_start + size + (size & 1);
+    }
+
+    let (tag, channels, bits) = format.ok_or_else(|| "Missing fmt chunk".to_string())?;
+    let data = data.ok_or_else(|| "Missing data chunk".to_string())?;
+    if channels == 0 {
+        return Err("WAV file has no channels".to_string());
+    }
+
+    let bytes_per_sample = bits / 8;
+    let stride = bytes_per_sample * channels;
+    if stride == 0 {
+        return Err(format!("Unsupported bit depth: {}", bits));
+    }
+
+    let decode: fn(&[u8]) -> f64 = match (tag, bits) {
+        (1, 8) => |s| (s[0] as f64 - 128.0) / 128.0,
+        (1, 16) => |s| i16::from_le_bytes([s[0], s[1]]) as f64 / 32768.0,
+        (1, 24) => |s| (i32::from_le_bytes([0, s[0], s[1], s[2]]) >> 8) as f64 / 8_388_608.0,
+        (1, 32) => |s| i32::from_le_bytes([s[0], s[1], s[2], s[3]]) as f64 / 2_147_483_648.0,
+        (3, 32) => |s| f32::from_le_bytes([s[0], s[1], s[2], s[3]]) as f64,
+        (3, 64) => |s| f64::from_le_bytes([s[0], s[1], s[2], s[3], s[4], s[5], s[6], s[7]]),
+        _ => return Err(format!("Unsupported WAV format {} / {} bit", tag, bits)),
+    };
+
+    let samples = data.chunks_exact(stride).map(|frame| decode(&frame[..bytes_per_sample])).collect();
+    Ok(WavData { samples, clm_frame_size })
+}
+
+/// Harmonic amplitudes of one cycle. Non power-of-two lengths are linearly
+/// resampled to `FRAME_SIZE` first.
+fn analyse_frame(frame: &[f64]) -> Harmonics {
+    let mut re: Vec<f64> = if frame.len().is_power_of_two() {
+        frame.to_vec()
+    } else {
+        let scale = frame.len() as f64 / FRAME_SIZE as f64;
+        (0..FRAME_SIZE)
+            .map(|i| {
+                let pos = i as f64 * scale;
+                let idx = pos as usize;
+                let frac = pos - idx as f64;
+                let a = frame[idx % frame.len()];
+                let b = frame[(idx + 1) % frame.len()];
+                a + (b - a) * frac
+            })
+            .collect()
+    };
+    let n = re.len();
+    let mut im = vec![0.0; n];
+    fft(&mut re, &mut im, false);
+
+    let norm = 2.0 / n as f64;
+    (1..n / 2).map(|k| (re[k] * norm, -im[k] * norm)).collect()
+}
+
+/// In-place iterative radix-2 FFT. The inverse is unscaled.
+fn fft(re: &mut [f64], im: &mut [f64], inverse: bool) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * std::f64::consts::TAU / len as f64;
+        let (w_im, w_re) = angle.sin_cos();
+        for start in (0..n).step_by(len) {
+            let mut cur_re = 1.0;
+            let mut cur_im = 0.0;
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+pub fn get_wavetables_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|mut path| {
+        path.push("Device");
+        path.push("wavetables");
+        path
+    })
+}
+
+/// Table names (file stems), built-in first.
+pub fn list_wavetables() -> Vec<String> {
+    let mut names = vec![BUILTIN_NAME.to_string()];
+
+    let Some(dir) = get_wavetables_dir() else {
+        return names;
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return names;
+    };
+
+    let mut external: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let is_wav = path.extension()?.to_str()?.eq_ignore_ascii_case("wav");
+            if !is_wav {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().to_string())
+        })
+        .collect();
+
+    external.sort_by_key(|n| n.to_lowercase());
+    names.extend(external);
+    names
+}
+
+pub fn load_wavetable(name: &str) -> Result<Arc<Wavetable>, String> {
+    if name == BUILTIN_NAME || name.is_empty() {
+        return Ok(Arc::new(Wavetable::builtin()));
+    }
+
+    let dir = get_wavetables_dir()
+        .ok_or_else(|| "Cannot determine data directory".to_string())?;
+    let path = ["wav", "WAV"]
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", name, ext)))
+        .find(|p| p.exists())
+        .ok_or_else(|| format!("Wavetable not found: {}", name))?;
+
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Wavetable::from_wav_bytes(name, &bytes).map(Arc::new)
+}
+
+/// Hands tables from the editor to the audio thread. The table the audio
+/// thread replaces is parked until `free_retired` runs off the audio thread,
+/// so a swap never deallocates in `process`.
+pub struct WavetableExchange {
+    current: Mutex<Arc<Wavetable>>,
+    retired: Mutex<Option<Arc<Wavetable>>>,
+}
+
+impl WavetableExchange {
+    pub fn new(table: Arc<Wavetable>) -> Self {
+        Self {
+            current: Mutex::new(table),
+            retired: Mutex::new(None),
+        }
+    }
+
+    pub fn current(&self) -> Option<Arc<Wavetable>> {
+        self.current.lock().ok().map(|t| Arc::clone(&t))
+    }
+
+    pub fn set(&self, table: Arc<Wavetable>) {
+        self.free_retired();
+        if let Ok(mut current) = self.current.lock() {
+            *current = table;
+        }
+    }
+
+    /// Audio-thread side: hands the current table to `install` and parks the
+    /// one it replaces. Returns false, to retry next block, while a lock is
+    /// held or the previous table is still parked.
+    pub fn swap(&self, install: impl FnOnce(Arc<Wavetable>) -> Arc<Wavetable>) -> bool {
+        match (self.current.try_lock(), self.retired.try_lock()) {
+            (Ok(table), Ok(mut retired)) if retired.is_none() => {
+                *retired = Some(install(Arc::clone(&table)));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn free_retired(&self) {
+        if let Ok(mut retired) = self.retired.lock() {
+            retired.take();
+        }
+    }
+}
+
+pub struct WavetableOscillator {
+    table: Arc<Wavetable>,
+    phase: f64,
+    freq: f64,
+    israte: f64,
+}
+
+impl WavetableOscillator {
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            table: Arc::new(Wavetable::builtin()),
+            phase: rand_01() * 0.25,
+            freq: 220.0,
+            israte: 1.0 / sample_rate,
+        }
+    }
+
+    /// Returns the previous table.
+    pub fn set_table(&mut self, table: Arc<Wavetable>) -> Arc<Wavetable> {
+        std::mem::replace(&mut self.table, table)
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.israte = 1.0 / sample_rate;
+    }
+
+    pub fn set_frequency(&mut self, freq: f64) {
+        self.freq = freq;
+    }
+
+    pub fn trigger(&mut self) {
+        self.phase = 0.0;
+    }
+
+    #[inline]
+    pub fn next(&mut self, position: f64) -> f64 {
+        let dt = self.freq * self.israte;
+        let out = self.table.sample(self.phase, position, dt);
+        self.phase += dt;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav_16bit(samples: &[i16], clm: Option<&str>) -> Vec<u8> {
+        let mut chunks = Vec::new();
+        chunks.extend_from_slice(b"fmt ");
+        chunks.extend_from_slice(&16u32.to_le_bytes());
+        chunks.extend_from_slice(&1u16.to_le_bytes());
+        chunks.extend_from_slice(&1u16.to_le_bytes());
+        chunks.extend_from_slice(&44100u32.to_le_bytes());
+        chunks.extend_from_slice(&88200u32.to_le_bytes());
+        chunks.extend_from_slice(&2u16.to_le_bytes());
+        chunks.extend_from_slice(&16u16.to_le_bytes());
+        if let Some(text) = clm {
+            chunks.extend_from_slice(b"clm ");
+            chunks.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            chunks.extend_from_slice(text.as_bytes());
+            if text.len() % 2 == 1 {
+                chunks.push(0);
+            }
+        }
+        chunks.extend_from_slice(b"data");
+        chunks.extend_from_slice(&((samples.len() * 2) as u32).to_le_bytes());
+        for s in samples {
+            chunks.extend_from_slice(&s.to_le_bytes());
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&((chunks.len() + 4) as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVE");
+        out.extend_from_slice(&chunks);
+        out
+    }
+
+    #[test]
+    fn serum_clm_chunk_sets_frame_size() {
+        let cycle: Vec<i16> = (0..256)
+            .map(|i| ((i as f64 / 256.0 * std::f64::consts::TAU).sin() * 16000.0) as i16)
+            .collect();
+        let samples: Vec<i16> = cycle.iter().chain(cycle.iter()).chain(cycle.iter()).copied().collect();
+        let table = Wavetable::from_wav_bytes("test", &wav_16bit(&samples, Some("<!>256 10000000 wavetable"))).unwrap();
+        assert_eq!(table.frame_count(), 3);
+
+        // A sine survives resampling and normalisation
+        let s = table.sample(0.25, 0.5, 1.0 / 1000.0);
+        assert!((s - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn short_file_is_single_cycle() {
+        let samples: Vec<i16> = (0..600).map(|i| if i < 300 { 12000 } else { -12000 }).collect();
+        let table = Wavetable::from_wav_bytes("test", &wav_16bit(&samples, None)).unwrap();
+        assert_eq!(table.frame_count(), 1);
+    }
+
+    #[test]
+    fn exchange_swaps_again_once_the_retired_table_is_freed() {
+        let exchange = WavetableExchange::new(Arc::new(Wavetable::builtin()));
+        let mut osc = WavetableOscillator::new(48000.0);
+        // Load, then a sample-rate change re-applying the same table: no
+        // editor `set` in between, only the background free
+        for _ in 0..2 {
+            assert!(exchange.swap(|table| osc.set_table(table)));
+            assert!(!exchange.swap(|table| osc.set_table(table)));
+            exchange.free_retired();
+        }
+        assert!(Arc::ptr_eq(&osc.table, &exchange.current().unwrap()));
+        assert!(exchange.retired.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn high_notes_use_band_limited_levels() {
+        // Built-in saw frame at 5 kHz / 48 kHz: harmonics 1–4 fit below
+        // Nyquist, the 5th would alias and must be absent
+        let table = Wavetable::builtin();
+        let dt = 5000.0 / 48000.0;
+        let position = 2.0 / 3.0;
+        let n = 960;
+        let harmonic_amp = |h: f64| {
+            let (mut c, mut s) = (0.0, 0.0);
+            for i in 0..n {
+                let phase = i as f64 / n as f64;
+                let v = table.sample(phase, position, dt);
+                let angle = phase * std::f64::consts::TAU * h;
+                c += v * angle.cos();
+                s += v * angle.sin();
+            }
+            (c * c + s * s).sqrt() * 2.0 / n as f64
+        };
+        assert!(harmonic_amp(4.0) > 0.01);
+        assert!(harmonic_amp(5.0) < 1e-5);
+    }
+}
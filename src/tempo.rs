@@ -1,30 +1,196 @@
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU8, Ordering};
+
+const MIN_BPM: f64 = 20.0;
+const MAX_BPM: f64 = 300.0;
+const TAP_HISTORY: usize = 8;
+const TAP_TIMEOUT_SECONDS: f64 = 2.0;
+/// A tap interval this far off the running average starts a fresh average.
+const TAP_OUTLIER_RATIO: f64 = 0.35;
+/// Tempo offset applied while a nudge button is held.
+const NUDGE_AMOUNT: f64 = 0.04;
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TempoSource {
     Manual,
     Host,
+    MidiClock,
+}
+
+impl TempoSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TempoSource::Manual => "Internal",
+            TempoSource::Host => "Host",
+            TempoSource::MidiClock => "MIDI Clock",
+        }
+    }
+
+    pub fn from_index(i: u8) -> Self {
+        match i {
+            1 => TempoSource::Host,
+            2 => TempoSource::MidiClock,
+            _ => TempoSource::Manual,
+        }
+    }
+
+    pub fn to_index(self) -> u8 {
+        match self {
+            TempoSource::Manual => 0,
+            TempoSource::Host => 1,
+            TempoSource::MidiClock => 2,
+        }
+    }
+}
+
+/// Which source the tempo should follow. Auto prefers a locked MIDI clock,
+/// then a playing host, then the internal tempo.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TempoMode {
+    #[default]
+    Auto,
+    Host,
+    MidiClock,
+    Internal,
+}
+
+impl TempoMode {
+    pub fn all() -> [TempoMode; 4] {
+        [
+            TempoMode::Auto,
+            TempoMode::Host,
+            TempoMode::MidiClock,
+            TempoMode::Internal,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TempoMode::Auto => "Auto",
+            TempoMode::Host => "Host",
+            TempoMode::MidiClock => "MIDI Clock",
+            TempoMode::Internal => "Internal",
+        }
+    }
+
+    pub fn from_index(i: u8) -> Self {
+        match i {
+            1 => TempoMode::Host,
+            2 => TempoMode::MidiClock,
+            3 => TempoMode::Internal,
+            _ => TempoMode::Auto,
+        }
+    }
+
+    pub fn to_index(self) -> u8 {
+        match self {
+            TempoMode::Auto => 0,
+            TempoMode::Host => 1,
+            TempoMode::MidiClock => 2,
+            TempoMode::Internal => 3,
+        }
+    }
+}
+
+/// Averages the intervals between taps; a long pause or a sudden change of
+/// pace starts over.
+struct TapTempo {
+    intervals: [f64; TAP_HISTORY],
+    count: usize,
+    next: usize,
+    last_tap: Option<f64>,
+}
+
+impl TapTempo {
+    fn new() -> Self {
+        Self {
+            intervals: [0.0; TAP_HISTORY],
+            count: 0,
+            next: 0,
+            last_tap: None,
+        }
+    }
+
+    fn tap(&mut self, time_seconds: f64) -> Option<f64> {
+        let last = self.last_tap.replace(time_seconds)?;
+        let interval = time_seconds - last;
+        if interval <= 0.0 || interval > TAP_TIMEOUT_SECONDS {
+            self.reset_intervals();
+            return None;
+        }
+
+        if self.count > 0 {
+            let average = self.average();
+            if (interval - average).abs() > average * TAP_OUTLIER_RATIO {
+                self.reset_intervals();
+            }
+        }
+
+        self.intervals[self.next] = interval;
+        self.next = (self.next + 1) % TAP_HISTORY;
+        self.count = (self.count + 1).min(TAP_HISTORY);
+        Some(60.0 / self.average())
+    }
+
+    fn average(&self) -> f64 {
+        let sum: f64 = self.intervals[..self.count].iter().sum();
+        sum / self.count as f64
+    }
+
+    fn reset_intervals(&mut self) {
+        self.count = 0;
+        self.next = 0;
+    }
+}
+
+/// Linear tempo change spread over a number of beats of internal playback.
+#[derive(Clone, Copy)]
+struct TempoRamp {
+    from_bpm: f64,
+    to_bpm: f64,
+    length_beats: f64,
+    elapsed_beats: f64,
 }
 
+/// Tempo arbitration plus the internal transport used when there is no host
+/// transport to follow (standalone builds) or when Internal mode is forced.
 pub struct TempoTracker {
     manual_bpm: f64,
     host_bpm: Option<f64>,
+    clock_bpm: Option<f64>,
     current_bpm: f64,
     source: TempoSource,
+    mode: TempoMode,
+    host_available: bool,
     host_is_playing: bool,
+    playing: bool,
+    position_beats: f64,
+    nudge: f64,
+    tap: TapTempo,
+    ramp: Option<TempoRamp>,
 }
 
 impl TempoTracker {
-    pub fn new(_sample_rate: f64) -> Self {
+    pub fn new() -> Self {
         Self {
             manual_bpm: 120.0,
             host_bpm: None,
+            clock_bpm: None,
             current_bpm: 120.0,
             source: TempoSource::Manual,
+            mode: TempoMode::Auto,
+            host_available: true,
             host_is_playing: false,
+            playing: false,
+            position_beats: 0.0,
+            nudge: 0.0,
+            tap: TapTempo::new(),
+            ramp: None,
         }
     }
 
     pub fn set_manual_bpm(&mut self, bpm: f64) {
-        self.manual_bpm = bpm.clamp(20.0, 300.0);
+        self.manual_bpm = bpm.clamp(MIN_BPM, MAX_BPM);
+        self.ramp = None;
         self.update_current_bpm();
     }
 
@@ -32,25 +198,52 @@ impl TempoTracker {
         self.manual_bpm
     }
 
+    pub fn set_mode(&mut self, mode: TempoMode) {
+        self.mode = mode;
+        self.update_current_bpm();
+    }
+
+    /// Whether the plugin runs under a host with a real transport. The
+    /// standalone wrapper reports a transport that is always playing, so it
+    /// is ignored there.
+    pub fn set_host_available(&mut self, available: bool) {
+        self.host_available = available;
+        self.update_current_bpm();
+    }
+
     pub fn set_host_tempo(&mut self, bpm: Option<f64>, is_playing: bool) {
         self.host_bpm = bpm;
         self.host_is_playing = is_playing;
         self.update_current_bpm();
     }
 
+    /// Tempo of a locked incoming MIDI clock, or `None` when unlocked.
+    pub fn set_clock_tempo(&mut self, bpm: Option<f64>) {
+        self.clock_bpm = bpm;
+        self.update_current_bpm();
+    }
+
     fn update_current_bpm(&mut self) {
-        if self.host_is_playing {
-            if let Some(host_bpm) = self.host_bpm {
-                if host_bpm > 0.0 {
-                    self.source = TempoSource::Host;
-                    self.current_bpm = host_bpm;
-                    return;
-                }
-            }
-        }
+        let host = if self.host_available && self.host_is_playing {
+            self.host_bpm.filter(|&bpm| bpm > 0.0)
+        } else {
+            None
+        };
+        let clock = self.clock_bpm.filter(|&bpm| bpm > 0.0);
 
-        self.source = TempoSource::Manual;
-        self.current_bpm = self.manual_bpm;
+        let external = match self.mode {
+            TempoMode::Auto => clock
+                .map(|bpm| (TempoSource::MidiClock, bpm))
+                .or(host.map(|bpm| (TempoSource::Host, bpm))),
+            TempoMode::Host => host.map(|bpm| (TempoSource::Host, bpm)),
+            TempoMode::MidiClock => clock.map(|bpm| (TempoSource::MidiClock, bpm)),
+            TempoMode::Internal => None,
+        };
+
+        let (source, bpm) = external
+            .unwrap_or((TempoSource::Manual, self.manual_bpm * (1.0 + self.nudge * NUDGE_AMOUNT)));
+        self.source = source;
+        self.current_bpm = bpm;
     }
 
     pub fn get_bpm(&self) -> f64 {
@@ -65,21 +258,271 @@ impl TempoTracker {
         self.source != TempoSource::Manual
     }
 
-    pub fn increment_bpm(&mut self) {
-        if !self.is_locked() {
-            self.set_manual_bpm(self.manual_bpm + 1.0);
+    /// Registers a tap at `time_seconds`; from the second tap on the manual
+    /// tempo follows the averaged tap interval.
+    pub fn tap(&mut self, time_seconds: f64) {
+        if self.is_locked() {
+            return;
+        }
+        if let Some(bpm) = self.tap.tap(time_seconds) {
+            self.set_manual_bpm(bpm);
+        }
+    }
+
+    /// Temporarily speeds up (`direction > 0`) or slows down the manual tempo
+    /// while held; 0 releases the nudge.
+    pub fn set_nudge(&mut self, direction: i32) {
+        self.nudge = direction.signum() as f64;
+        self.update_current_bpm();
+    }
+
+    /// Ramps the manual tempo to `target_bpm` over `bars` bars of internal
+    /// playback.
+    pub fn start_ramp(&mut self, target_bpm: f64, bars: u32) {
+        let to_bpm = target_bpm.clamp(MIN_BPM, MAX_BPM);
+        if bars == 0 {
+            self.set_manual_bpm(to_bpm);
+            return;
         }
+        self.ramp = Some(TempoRamp {
+            from_bpm: self.manual_bpm,
+            to_bpm,
+            length_beats: bars as f64 * 4.0,
+            elapsed_beats: 0.0,
+        });
     }
 
-    pub fn decrement_bpm(&mut self) {
-        if !self.is_locked() {
-            self.set_manual_bpm(self.manual_bpm - 1.0);
+    pub fn cancel_ramp(&mut self) {
+        self.ramp = None;
+    }
+
+    pub fn is_ramping(&self) -> bool {
+        self.ramp.is_some()
+    }
+
+    /// The internal transport drives playback when there is no host or when
+    /// Internal mode is selected.
+    pub fn uses_internal_transport(&self) -> bool {
+        !self.host_available || self.mode == TempoMode::Internal
+    }
+
+    pub fn play(&mut self) {
+        if !self.playing {
+            self.playing = true;
+            self.position_beats = 0.0;
         }
     }
+
+    pub fn stop(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn position_beats(&self) -> f64 {
+        self.position_beats
+    }
+
+    /// Moves the internal transport and any running ramp on by one block.
+    pub fn advance(&mut self, num_samples: usize, sample_rate: f32) {
+        if !self.playing || sample_rate <= 0.0 {
+            return;
+        }
+        let beats = self.current_bpm / 60.0 * num_samples as f64 / sample_rate as f64;
+        self.position_beats += beats;
+
+        if let Some(ramp) = &mut self.ramp {
+            ramp.elapsed_beats += beats;
+            let t = (ramp.elapsed_beats / ramp.length_beats).min(1.0);
+            self.manual_bpm = ramp.from_bpm + (ramp.to_bpm - ramp.from_bpm) * t;
+            if t >= 1.0 {
+                self.ramp = None;
+            }
+            self.update_current_bpm();
+        }
+    }
+
+    /// Applies pending requests from the editor.
+    pub fn apply_controls(&mut self, controls: &TransportControls, time_seconds: f64) {
+        self.set_mode(TempoMode::from_index(controls.mode.load(Ordering::Relaxed)));
+        self.set_nudge(controls.nudge.load(Ordering::Relaxed));
+
+        let bpm_request = controls.bpm_request.swap(0, Ordering::Relaxed);
+        if bpm_request != 0 && !self.is_locked() {
+            self.set_manual_bpm(bpm_request as f64 / 100.0);
+        }
+        if controls.tap_request.swap(false, Ordering::Relaxed) {
+            self.tap(time_seconds);
+        }
+        let ramp_request = controls.ramp_request.swap(0, Ordering::Relaxed);
+        if ramp_request != 0 {
+            let bars = controls.ramp_bars.load(Ordering::Relaxed) as u32;
+            self.start_ramp(ramp_request as f64 / 100.0, bars);
+        }
+        if controls.ramp_cancel.swap(false, Ordering::Relaxed) {
+            self.cancel_ramp();
+        }
+        match controls.play_request.swap(0, Ordering::Relaxed) {
+            1 => self.play(),
+            2 => self.stop(),
+            _ => {}
+        }
+    }
+
+    /// Reports the transport state back to the editor.
+    pub fn publish(&self, controls: &TransportControls) {
+        controls.manual_bpm.store((self.manual_bpm * 100.0) as u32, Ordering::Relaxed);
+        controls.playing.store(self.playing, Ordering::Relaxed);
+        controls.internal.store(self.uses_internal_transport(), Ordering::Relaxed);
+        controls.source.store(self.source.to_index(), Ordering::Relaxed);
+        controls.ramping.store(self.is_ramping(), Ordering::Relaxed);
+    }
 }
 
 impl Default for TempoTracker {
     fn default() -> Self {
-        Self::new(44100.0)
+        Self::new()
+    }
+}
+
+/// Editor-side handle on the internal transport. Requests are picked up by
+/// the audio thread once per block; status fields are written back from it.
+pub struct TransportControls {
+    pub mode: AtomicU8,
+    pub bpm_request: AtomicU32, // bpm * 100, 0 = none
+    pub tap_request: AtomicBool,
+    pub nudge: AtomicI32,
+    pub ramp_request: AtomicU32, // target bpm * 100, 0 = none
+    pub ramp_bars: AtomicU8,
+    pub ramp_cancel: AtomicBool,
+    pub play_request: AtomicU8, // 0=none, 1=play, 2=stop
+    pub manual_bpm: AtomicU32,
+    pub playing: AtomicBool,
+    pub internal: AtomicBool,
+    pub source: AtomicU8,
+    pub ramping: AtomicBool,
+}
+
+impl TransportControls {
+    pub fn new(mode: TempoMode, manual_bpm: f32) -> Self {
+        let bpm = (manual_bpm.clamp(MIN_BPM as f32, MAX_BPM as f32) * 100.0) as u32;
+        Self {
+            mode: AtomicU8::new(mode.to_index()),
+            bpm_request: AtomicU32::new(bpm),
+            tap_request: AtomicBool::new(false),
+            nudge: AtomicI32::new(0),
+            ramp_request: AtomicU32::new(0),
+            ramp_bars: AtomicU8::new(4),
+            ramp_cancel: AtomicBool::new(false),
+            // Start running, like the free-running sequencer did before there
+            // was an internal transport.
+            play_request: AtomicU8::new(1),
+            manual_bpm: AtomicU32::new(bpm),
+            playing: AtomicBool::new(false),
+            internal: AtomicBool::new(false),
+            source: AtomicU8::new(0),
+            ramping: AtomicBool::new(false),
+        }
+    }
+
+    pub fn request_play(&self) {
+        self.play_request.store(1, Ordering::Relaxed);
+    }
+
+    pub fn request_stop(&self) {
+        self.play_request.store(2, Ordering::Relaxed);
+    }
+
+    pub fn request_tap(&self) {
+        self.tap_request.store(true, Ordering::Relaxed);
+    }
+
+    pub fn request_bpm(&self, bpm: f32) {
+        let bpm = bpm.clamp(MIN_BPM as f32, MAX_BPM as f32);
+        self.bpm_request.store((bpm * 100.0) as u32, Ordering::Relaxed);
+        self.manual_bpm.store((bpm * 100.0) as u32, Ordering::Relaxed);
+    }
+
+    pub fn request_ramp(&self, target_bpm: f32, bars: u8) {
+        self.ramp_bars.store(bars, Ordering::Relaxed);
+        self.ramp_request.store((target_bpm.clamp(MIN_BPM as f32, MAX_BPM as f32) * 100.0) as u32, Ordering::Relaxed);
+    }
+
+    pub fn get_manual_bpm(&self) -> f32 {
+        self.manual_bpm.load(Ordering::Relaxed) as f32 / 100.0
+    }
+
+    pub fn get_source(&self) -> TempoSource {
+        TempoSource::from_index(self.source.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tap_tempo_averages_intervals() {
+        let mut tracker = TempoTracker::new();
+        tracker.set_host_available(false);
+        for (i, jitter) in [0.0, 0.01, -0.01, 0.005, -0.005].iter().enumerate() {
+            tracker.tap(i as f64 * 0.5 + jitter);
+        }
+        assert!((tracker.get_bpm() - 120.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn tap_tempo_restarts_after_pause_or_pace_change() {
+        let mut tracker = TempoTracker::new();
+        tracker.set_host_available(false);
+        for t in [0.0, 0.5, 1.0] {
+            tracker.tap(t);
+        }
+        // Half the interval: the old average is discarded, not blended in.
+        tracker.tap(1.25);
+        assert!((tracker.get_bpm() - 240.0).abs() < 0.01);
+
+        // After a long pause the next tap only starts a new measurement.
+        tracker.tap(5.0);
+        assert!((tracker.get_bpm() - 240.0).abs() < 0.01);
+        tracker.tap(5.6);
+        assert!((tracker.get_bpm() - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn ramp_reaches_target_after_bars() {
+        let mut tracker = TempoTracker::new();
+        tracker.set_host_available(false);
+        tracker.set_manual_bpm(100.0);
+        tracker.start_ramp(140.0, 2);
+        tracker.play();
+        for _ in 0..48000 {
+            tracker.advance(64, 48000.0);
+            if !tracker.is_ramping() {
+                break;
+            }
+        }
+        assert!(!tracker.is_ramping());
+        assert_eq!(tracker.get_bpm(), 140.0);
+        assert!((tracker.position_beats() - 8.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn auto_mode_prefers_clock_then_host() {
+        let mut tracker = TempoTracker::new();
+        tracker.set_manual_bpm(90.0);
+        tracker.set_host_tempo(Some(128.0), true);
+        assert_eq!(tracker.get_source(), TempoSource::Host);
+        tracker.set_clock_tempo(Some(100.0));
+        assert_eq!(tracker.get_source(), TempoSource::MidiClock);
+        assert_eq!(tracker.get_bpm(), 100.0);
+
+        // The standalone transport is never followed.
+        tracker.set_clock_tempo(None);
+        tracker.set_host_available(false);
+        assert_eq!(tracker.get_source(), TempoSource::Manual);
+        assert_eq!(tracker.get_bpm(), 90.0);
     }
 }
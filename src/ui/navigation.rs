@@ -3,7 +3,9 @@ use nih_plug_egui::egui::{self, Color32, Margin};
 use super::page::Page;
 use super::SharedUiState;
 use crate::params::DeviceParams;
+use crate::tempo::TempoSource;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 pub fn render(ui: &mut egui::Ui, current_page: &mut Page, params: &Arc<DeviceParams>, setter: &ParamSetter, ui_state: &Arc<SharedUiState>) {
     egui::Frame::default()
@@ -51,7 +53,14 @@ pub fn render(ui: &mut egui::Ui, current_page: &mut Page, params: &Arc<DevicePar
 
                 ui.add_space(24.0);
 
-                let playing = params.sequencer_enable.value();
+                let transport = &ui_state.transport;
+                let internal = transport.internal.load(Ordering::Relaxed);
+                let seq_enabled = params.sequencer_enable.value();
+                let playing = if internal {
+                    seq_enabled && transport.playing.load(Ordering::Relaxed)
+                } else {
+                    seq_enabled
+                };
                 let play_label = if playing { "\u{23F9}" } else { "\u{25B6}" };
                 let play_color = if playing {
                     Color32::from_rgb(80, 200, 80)
@@ -63,13 +72,41 @@ pub fn render(ui: &mut egui::Ui, current_page: &mut Page, params: &Arc<DevicePar
                 )
                 .min_size(egui::vec2(56.0, 56.0));
                 if ui.add(play_btn).clicked() {
-                    setter.set_parameter(&params.sequencer_enable, !playing);
+                    if !internal {
+                        setter.set_parameter(&params.sequencer_enable, !playing);
+                    } else if playing {
+                        transport.request_stop();
+                    } else {
+                        if !seq_enabled {
+                            setter.set_parameter(&params.sequencer_enable, true);
+                        }
+                        transport.request_play();
+                    }
+                }
+
+                ui.add_space(8.0);
+
+                // Tempo readout doubles as the tap button; it is greyed out
+                // while an external tempo is followed.
+                let tempo = ui_state.current_tempo.load(Ordering::Relaxed) as f32 / 100.0;
+                let locked = transport.get_source() != TempoSource::Manual;
+                let tempo_color = if locked {
+                    Color32::from_rgb(110, 110, 110)
+                } else {
+                    Color32::WHITE
+                };
+                let tap_btn = egui::Button::new(
+                    egui::RichText::new(format!("{:.1}", tempo)).size(20.0).color(tempo_color)
+                )
+                .min_size(egui::vec2(80.0, 56.0));
+                if ui.add_enabled(!locked, tap_btn).on_hover_text("Tap tempo").clicked() {
+                    transport.request_tap();
                 }
 
                 ui.add_space(16.0);
 
                 let mut volume = params.global_volume.modulated_plain_value();
-                ui.style_mut().spacing.slider_width = 220.0;
+                ui.style_mut().spacing.slider_width = 140.0;
                 ui.style_mut().spacing.slider_rail_height = 14.0;
                 let slider = egui::Slider::new(&mut volume, 0.0..=1.0)
                     .show_value(false)
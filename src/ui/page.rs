@@ -10,15 +10,17 @@ pub enum Page {
     Notes,
     Strength,
     Synth,
+    Perform,
     Presets,
     Settings,
 }
 
 impl Page {
-    pub fn all() -> [Page; 7] {
+    pub fn all() -> [Page; 8] {
         [
             Page::Presets,
             Page::Synth,
+            Page::Perform,
             Page::BeatProbability,
             Page::Notes,
             Page::Strength,
@@ -34,6 +36,7 @@ impl Page {
             Page::Notes => "Notes",
             Page::Strength => "Strength",
             Page::Synth => "Sound",
+            Page::Perform => "Perform",
             Page::Presets => "Presets",
             Page::Settings => "Settings",
         }
@@ -62,6 +65,9 @@ impl Page {
             Page::Synth => {
                 super::pages::synth::render(tui, params, setter, ui_state)
             }
+            Page::Perform => {
+                super::pages::perform::render(tui, params, setter, ui_state)
+            }
             Page::Presets => {
                 super::pages::presets::render(tui, params, setter, ui_state)
             }
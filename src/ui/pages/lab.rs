@@ -0,0 +1,409 @@
+use crate::params::DeviceParams;
+use crate::preset::manager::{FactoryBank, UserBank};
+use crate::preset::{breed, mutate_normalized, mutate_sequencer, Preset, PresetData, RandomGroup, RandomizerSettings};
+use crate::ui::SharedUiState;
+use super::presets::{apply_preset_data, save_params_to_preset_data};
+use nih_plug::prelude::ParamSetter;
+use nih_plug_egui::egui::{self, Color32};
+use rand::Rng;
+use std::sync::Arc;
+
+const HEADER_FONT: f32 = 18.0;
+const UI_FONT: f32 = 16.0;
+const HINT_FONT: f32 = 13.0;
+const LABEL_COLOR: Color32 = Color32::from_gray(140);
+const LAB_ACCENT: Color32 = Color32::from_rgb(90, 70, 110);
+const LOCK_COLOR: Color32 = Color32::from_rgb(150, 60, 60);
+const HISTORY_LIMIT: usize = 32;
+const POPULATION: usize = 8;
+const COL_GAP: f32 = 32.0;
+
+/// Patches replaced by the randomizer, newest last.
+#[derive(Clone, Default)]
+struct History {
+    undo: Vec<Arc<PresetData>>,
+    redo: Vec<Arc<PresetData>>,
+}
+
+impl History {
+    fn push(&mut self, data: PresetData) {
+        self.undo.push(Arc::new(data));
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+}
+
+#[derive(Clone)]
+struct Child {
+    data: Arc<PresetData>,
+    starred: bool,
+}
+
+#[derive(Clone)]
+struct Breeding {
+    parents: Vec<(String, Arc<PresetData>)>,
+    children: Vec<Child>,
+    generation: u32,
+    amount: f32,
+    auditioned: Option<usize>,
+    save_bank: UserBank,
+    message: Option<String>,
+}
+
+impl Default for Breeding {
+    fn default() -> Self {
+        Self {
+            parents: Vec::new(),
+            children: Vec::new(),
+            generation: 0,
+            amount: 0.3,
+            auditioned: None,
+            save_bank: UserBank::U1,
+            message: None,
+        }
+    }
+}
+
+impl Breeding {
+    fn breed(&mut self, params: &DeviceParams) {
+        let parents: Vec<&PresetData> = self.parents.iter().map(|(_, p)| p.as_ref()).collect();
+        let mut rng = rand::thread_rng();
+        self.children = (0..POPULATION)
+            .map(|_| Child { data: Arc::new(breed(&parents, self.amount, params, &mut rng)), starred: false })
+            .collect();
+        self.generation += 1;
+        self.auditioned = None;
+    }
+
+    fn starred(&self) -> impl Iterator<Item = (usize, &Child)> {
+        self.children.iter().enumerate().filter(|(_, c)| c.starred)
+    }
+
+    fn child_name(&self, index: usize) -> String {
+        format!("Gen {} #{}", self.generation, index + 1)
+    }
+}
+
+pub(super) fn render(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let history_id = egui::Id::new("lab_history");
+    let mut history = ui.ctx().data_mut(|d| d.get_temp::<History>(history_id)).unwrap_or_default();
+
+    let full = ui.available_rect_before_wrap();
+    let col_w = (full.width() - COL_GAP) / 2.0;
+    let left_rect = egui::Rect::from_min_size(full.min, egui::vec2(col_w, full.height()));
+    let right_rect = egui::Rect::from_min_max(egui::pos2(full.min.x + col_w + COL_GAP, full.min.y), full.max);
+    let sep_x = full.min.x + col_w + COL_GAP / 2.0;
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, full.top() + 4.0), egui::pos2(sep_x, full.bottom() - 4.0)],
+        egui::Stroke::new(1.0, Color32::from_gray(50)),
+    );
+
+    ui.allocate_new_ui(egui::UiBuilder::new().max_rect(left_rect), |ui| {
+        render_randomizer(ui, params, setter, ui_state, &mut history);
+    });
+    ui.allocate_new_ui(egui::UiBuilder::new().max_rect(right_rect), |ui| {
+        render_breeding(ui, params, setter, ui_state, &mut history);
+    });
+
+    ui.ctx().data_mut(|d| d.insert_temp(history_id, history));
+}
+
+fn render_randomizer(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+    history: &mut History,
+) {
+    let original = params.randomizer.read().map(|s| s.clone()).unwrap_or_default();
+    let mut settings = original.clone();
+
+    ui.label(egui::RichText::new("RANDOMIZE").size(HEADER_FONT).strong());
+    ui.add_space(9.0);
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("NUDGE").size(HINT_FONT).color(LABEL_COLOR));
+        ui.style_mut().spacing.slider_width = 140.0;
+        ui.add(egui::Slider::new(&mut settings.amount, 0.0..=1.0).fixed_decimals(2));
+        ui.label(egui::RichText::new("WILD").size(HINT_FONT).color(LABEL_COLOR));
+        ui.add_space(16.0);
+
+        let go = egui::Button::new(egui::RichText::new("RANDOMIZE").size(UI_FONT).strong().color(Color32::WHITE))
+            .min_size(egui::vec2(120.0, 40.0))
+            .fill(LAB_ACCENT);
+        if ui.add(go).clicked() {
+            randomize(params, setter, ui_state, &settings, history);
+        }
+        ui.add_space(8.0);
+
+        let undo = egui::Button::new(egui::RichText::new(format!("UNDO ({})", history.undo.len())).size(UI_FONT))
+            .min_size(egui::vec2(100.0, 40.0));
+        if ui.add_enabled(!history.undo.is_empty(), undo).clicked() {
+            if let Some(previous) = history.undo.pop() {
+                history.redo.push(Arc::new(save_params_to_preset_data(params, ui_state)));
+                apply_preset_data(&previous, params, setter, ui_state);
+            }
+        }
+        let redo = egui::Button::new(egui::RichText::new("REDO").size(UI_FONT))
+            .min_size(egui::vec2(80.0, 40.0));
+        if ui.add_enabled(!history.redo.is_empty(), redo).clicked() {
+            if let Some(next) = history.redo.pop() {
+                history.undo.push(Arc::new(save_params_to_preset_data(params, ui_state)));
+                apply_preset_data(&next, params, setter, ui_state);
+            }
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("LOCK").size(HINT_FONT).color(LABEL_COLOR));
+        for group in RandomGroup::ALL {
+            let locked = settings.group_locked(group);
+            let button = egui::Button::new(egui::RichText::new(group.label()).size(UI_FONT).color(Color32::WHITE))
+                .min_size(egui::vec2(70.0, 32.0))
+                .fill(if locked { LOCK_COLOR } else { Color32::from_rgb(50, 50, 50) });
+            if ui.add(button).clicked() {
+                settings.toggle_group(group);
+            }
+        }
+    });
+
+    ui.add_space(12.0);
+    egui::ScrollArea::vertical().id_salt("lab_param_locks").max_height(240.0).show(ui, |ui| {
+        for group in RandomGroup::ALL.into_iter().filter(|g| *g != RandomGroup::Sequencer) {
+            let group_locked = settings.group_locked(group);
+            ui.horizontal_wrapped(|ui| {
+                ui.label(egui::RichText::new(group.label()).size(HINT_FONT).color(LABEL_COLOR));
+                for id in group.params() {
+                    let locked = group_locked || settings.param_locked(id);
+                    let label = id.strip_prefix("synth_").unwrap_or(id);
+                    let chip = egui::Button::new(egui::RichText::new(label).size(HINT_FONT))
+                        .fill(if locked { LOCK_COLOR } else { Color32::from_rgb(40, 40, 48) });
+                    if ui.add_enabled(!group_locked, chip).clicked() {
+                        settings.toggle_param(id);
+                    }
+                }
+            });
+            ui.add_space(4.0);
+        }
+    });
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Red is locked. Oscillator and filter switches are never changed, and levels, cutoff, resonance and drive stay in safe ranges.")
+            .size(HINT_FONT)
+            .color(LABEL_COLOR),
+    );
+
+    if settings != original {
+        if let Ok(mut stored) = params.randomizer.write() {
+            *stored = settings;
+        }
+    }
+}
+
+fn render_breeding(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+    history: &mut History,
+) {
+    let state_id = egui::Id::new("lab_breeding");
+    let mut breeding = ui.ctx().data_mut(|d| d.get_temp::<Breeding>(state_id)).unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("BREED").size(HEADER_FONT).strong());
+        if breeding.generation > 0 {
+            ui.label(egui::RichText::new(format!("GEN {}", breeding.generation)).size(HINT_FONT).color(LABEL_COLOR));
+        }
+    });
+    ui.add_space(9.0);
+
+    // Parents
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new("PARENTS").size(HINT_FONT).color(LABEL_COLOR));
+        let mut remove = None;
+        for (i, (name, _)) in breeding.parents.iter().enumerate() {
+            if ui.button(egui::RichText::new(format!("{}  ✕", name)).size(HINT_FONT)).clicked() {
+                remove = Some(i);
+            }
+        }
+        if let Some(i) = remove {
+            breeding.parents.remove(i);
+        }
+        render_parent_picker(ui, ui_state, &mut breeding);
+        if ui.button(egui::RichText::new("+ CURRENT").size(HINT_FONT)).clicked() {
+            breeding.parents.push(("Current".to_string(), Arc::new(save_params_to_preset_data(params, ui_state))));
+        }
+    });
+
+    ui.add_space(10.0);
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("MUTATION").size(HINT_FONT).color(LABEL_COLOR));
+        ui.style_mut().spacing.slider_width = 140.0;
+        ui.add(egui::Slider::new(&mut breeding.amount, 0.0..=1.0).fixed_decimals(2));
+        ui.add_space(12.0);
+
+        let can_breed = breeding.parents.len() >= 2;
+        let go = egui::Button::new(egui::RichText::new("BREED").size(UI_FONT).strong().color(Color32::WHITE))
+            .min_size(egui::vec2(100.0, 40.0))
+            .fill(LAB_ACCENT);
+        if ui.add_enabled(can_breed, go).on_disabled_hover_text("Pick at least two parents").clicked() {
+            breeding.breed(params);
+        }
+
+        let starred = breeding.starred().count();
+        let next = egui::Button::new(egui::RichText::new("NEXT GEN").size(UI_FONT))
+            .min_size(egui::vec2(100.0, 40.0));
+        if ui.add_enabled(starred >= 2, next).on_hover_text("Breed the starred children").clicked() {
+            breeding.parents = breeding.starred()
+                .map(|(i, c)| (breeding.child_name(i), c.data.clone()))
+                .collect();
+            breeding.breed(params);
+        }
+    });
+
+    // Population
+    ui.add_space(12.0);
+    let mut audition = None;
+    for row in 0..breeding.children.len().div_ceil(4) {
+        ui.horizontal(|ui| {
+            for i in row * 4..(row * 4 + 4).min(breeding.children.len()) {
+                let child = &mut breeding.children[i];
+                let selected = breeding.auditioned == Some(i);
+                let tile = egui::Button::new(egui::RichText::new(format!("#{}", i + 1)).size(UI_FONT))
+                    .min_size(egui::vec2(70.0, 48.0))
+                    .fill(if selected { LAB_ACCENT } else { Color32::from_rgb(50, 50, 50) });
+                if ui.add(tile).on_hover_text("Audition").clicked() {
+                    audition = Some(i);
+                }
+                let star = egui::Button::new(egui::RichText::new(if child.starred { "★" } else { "☆" }).size(UI_FONT))
+                    .min_size(egui::vec2(40.0, 48.0))
+                    .fill(if child.starred { Color32::from_rgb(180, 140, 40) } else { Color32::from_rgb(60, 60, 60) });
+                if ui.add(star).clicked() {
+                    child.starred = !child.starred;
+                }
+                ui.add_space(8.0);
+            }
+        });
+        ui.add_space(6.0);
+    }
+    if let Some(i) = audition {
+        history.push(save_params_to_preset_data(params, ui_state));
+        apply_preset_data(&breeding.children[i].data, params, setter, ui_state);
+        breeding.auditioned = Some(i);
+    }
+
+    // Save winners
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("SAVE ★ TO").size(HINT_FONT).color(LABEL_COLOR));
+        egui::ComboBox::from_id_salt("breed_save_bank")
+            .selected_text(egui::RichText::new(format!("User {}", breeding.save_bank.label())).size(HINT_FONT))
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                for bank in UserBank::all() {
+                    ui.selectable_value(&mut breeding.save_bank, bank, format!("User {}", bank.label()));
+                }
+            });
+        let save = egui::Button::new(egui::RichText::new("SAVE").size(UI_FONT).color(Color32::WHITE))
+            .min_size(egui::vec2(80.0, 36.0))
+            .fill(Color32::from_rgb(80, 100, 60));
+        if ui.add_enabled(breeding.starred().next().is_some(), save).clicked() {
+            breeding.message = Some(save_starred(ui_state, &breeding));
+        }
+        if let Some(message) = &breeding.message {
+            ui.label(egui::RichText::new(message).size(HINT_FONT).color(LABEL_COLOR));
+        }
+    });
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Each field comes from a random parent; mutation moves sound parameters like the randomizer, within its safety ranges. Auditioning can be undone.")
+            .size(HINT_FONT)
+            .color(LABEL_COLOR),
+    );
+
+    ui.ctx().data_mut(|d| d.insert_temp(state_id, breeding));
+}
+
+fn render_parent_picker(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>, breeding: &mut Breeding) {
+    let filter_id = egui::Id::new("breed_parent_filter");
+    egui::ComboBox::from_id_salt("breed_parent_picker")
+        .selected_text(egui::RichText::new("+ PRESET").size(HINT_FONT))
+        .width(110.0)
+        .height(360.0)
+        .show_ui(ui, |ui| {
+            let mut filter = ui.ctx().data_mut(|d| d.get_temp::<String>(filter_id)).unwrap_or_default();
+            ui.add(egui::TextEdit::singleline(&mut filter).hint_text("filter"));
+            let needle = filter.to_lowercase();
+            if let Ok(mgr) = ui_state.preset_manager.lock() {
+                let banks = FactoryBank::all().into_iter()
+                    .map(|b| (format!("F{}", b.label()), mgr.get_factory_bank(b)))
+                    .chain(UserBank::all().into_iter().map(|b| (format!("U{}", b.label()), mgr.get_user_bank(b))));
+                for (bank_label, bank) in banks {
+                    for (i, preset) in bank.presets.iter().enumerate() {
+                        if !preset.name.to_lowercase().contains(&needle) {
+                            continue;
+                        }
+                        if ui.selectable_label(false, format!("{}{:02} {}", bank_label, i + 1, preset.name)).clicked() {
+                            breeding.parents.push((preset.name.clone(), Arc::new((*preset.data).clone())));
+                        }
+                    }
+                }
+            }
+            ui.ctx().data_mut(|d| d.insert_temp(filter_id, filter));
+        });
+}
+
+/// Writes starred children into the empty slots of the chosen user bank.
+fn save_starred(ui_state: &Arc<SharedUiState>, breeding: &Breeding) -> String {
+    let Ok(mut mgr) = ui_state.preset_manager.lock() else { return "Preset manager busy".to_string() };
+    let bank = breeding.save_bank;
+    let mut slots = (0..32).filter(|&i| mgr.is_user_preset_empty(bank, i)).collect::<Vec<_>>().into_iter();
+    let mut saved = 0;
+    for (i, child) in breeding.starred() {
+        let Some(slot) = slots.next() else { break };
+        let preset = Preset::with_author_and_description(&breeding.child_name(i), "Lab", "", (*child.data).clone());
+        mgr.save_to_user_slot(bank, slot, preset);
+        saved += 1;
+    }
+    if saved == 0 {
+        return format!("User {} is full", bank.label());
+    }
+    match mgr.save_user_presets() {
+        Ok(_) => format!("Saved {} to User {}", saved, bank.label()),
+        Err(e) => format!("Save error: {}", e),
+    }
+}
+
+fn randomize(
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+    settings: &RandomizerSettings,
+    history: &mut History,
+) {
+    let before = save_params_to_preset_data(params, ui_state);
+    let mut rng = rand::thread_rng();
+
+    // Sequencer first: applying preset data rewrites every param, so the
+    // sound mutation below has to come after it
+    if !settings.group_locked(RandomGroup::Sequencer) {
+        let mut data = before.clone();
+        mutate_sequencer(&mut data, settings.amount, &mut rng);
+        apply_preset_data(&data, params, setter, ui_state);
+    }
+    for id in settings.sound_targets() {
+        if let Some(value) = params.read_normalized_value(id) {
+            params.apply_normalized_cc(setter, id, mutate_normalized(id, value, settings.amount, rng.gen()));
+        }
+    }
+    history.push(before);
+}
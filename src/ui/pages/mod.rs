@@ -1,7 +1,9 @@
 pub mod beat_probability;
+pub mod lab;
 pub mod length;
 pub mod modulation;
 pub mod notes;
+pub mod perform;
 pub mod presets;
 pub mod settings;
 pub mod strength;
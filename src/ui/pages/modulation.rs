@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use nih_plug_egui::egui::{self, Color32};
 use crate::params::DeviceParams;
+use crate::synth::mseg::{MsegPoint, MsegShape, MAX_SEGMENT_MS, MSEG_COUNT, MSEG_MAX_POINTS};
 use crate::ui::grid_picker::{self, GridPickerGroup};
 use crate::ui::shared_state::SharedUiState;
 use nih_plug::prelude::*;
@@ -60,6 +61,36 @@ const DEST_GROUPS: &[GridPickerGroup] = &[
         tint: Color32::from_rgba_premultiplied(0, 8, 0, 6),
         entries: &[("Saw Fold", 28), ("Saw SHP", 29), ("Saw Vol", 30)],
     },
+    GridPickerGroup {
+        name: "WAVETABLE",
+        tint: Color32::from_rgba_premultiplied(0, 6, 8, 6),
+        entries: &[("WT Pos", 67), ("WT Vol", 68)],
+    },
+    GridPickerGroup {
+        name: "NOISE",
+        tint: Color32::from_rgba_premultiplied(6, 6, 6, 6),
+        entries: &[("Noise Lvl", 69)],
+    },
+    GridPickerGroup {
+        name: "SYNC",
+        tint: Color32::from_rgba_premultiplied(8, 4, 8, 6),
+        entries: &[("Sync Soft", 70)],
+    },
+    GridPickerGroup {
+        name: "XMOD",
+        tint: Color32::from_rgba_premultiplied(8, 6, 0, 6),
+        entries: &[("Ring PLL", 71), ("Ring V×S", 72), ("XFM V→S", 73), ("XFM S→R", 74)],
+    },
+    GridPickerGroup {
+        name: "FILTER 2",
+        tint: Color32::from_rgba_premultiplied(6, 2, 8, 6),
+        entries: &[("F2 Cut", 75), ("F2 Res", 76), ("F2 Mrph", 77)],
+    },
+    GridPickerGroup {
+        name: "PITCH",
+        tint: Color32::from_rgba_premultiplied(8, 2, 4, 6),
+        entries: &[("Pitch", 81), ("VPS Pitch", 78), ("Saw Pitch", 79), ("Ref Pitch", 80)],
+    },
     GridPickerGroup {
         name: "ENVELOPE",
         tint: Color32::from_rgba_premultiplied(6, 4, 0, 6),
@@ -83,6 +114,16 @@ const DEST_GROUPS: &[GridPickerGroup] = &[
         tint: Color32::from_rgba_premultiplied(0, 6, 6, 6),
         entries: &[("Drift", 13), ("Tube", 14)],
     },
+    GridPickerGroup {
+        name: "LO-FI",
+        tint: Color32::from_rgba_premultiplied(6, 6, 0, 6),
+        entries: &[("LoFi Bit", 63), ("LoFi SR", 64), ("LoFi Drv", 65), ("LoFi Mix", 66)],
+    },
+    GridPickerGroup {
+        name: "MOD FX",
+        tint: Color32::from_rgba_premultiplied(6, 0, 4, 6),
+        entries: &[("MFX Rate", 60), ("MFX Dpth", 61), ("MFX FB", 62)],
+    },
 ];
 
 const FONT: f32 = 19.0;
@@ -115,6 +156,15 @@ const STEP_COLOR_UNI: Color32 = Color32::from_rgb(100, 170, 120);
 const PLAYHEAD_COLOR: Color32 = Color32::from_rgb(255, 200, 80);
 const TOOL_BTN_COLOR: Color32 = Color32::from_rgb(60, 80, 100);
 
+const MSEG_EDITOR_H: f32 = 220.0;
+const MSEG_POINT_RADIUS: f32 = 6.0;
+const MSEG_HIT_RADIUS: f32 = 12.0;
+const MSEG_CURVE_STEPS: usize = 24;
+const MSEG_LINE_COLOR: Color32 = Color32::from_rgb(120, 190, 170);
+const MSEG_POINT_COLOR: Color32 = Color32::from_gray(200);
+const MSEG_LOOP_COLOR: Color32 = Color32::from_rgba_premultiplied(30, 40, 20, 40);
+const MSEG_ACCENT: Color32 = Color32::from_rgb(60, 130, 110);
+
 pub fn render_ui(
     ui: &mut egui::Ui,
     params: &Arc<DeviceParams>,
@@ -170,6 +220,17 @@ pub fn render_step_mod_ui(
     render_step_seq_panel(ui, params, setter, ui_state);
 }
 
+pub fn render_mseg_ui(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    grid_picker::set_content_rect(ui, ui.available_rect_before_wrap());
+    ui.add_space(6.0);
+    render_mseg_panel(ui, params, setter, ui_state);
+}
+
 fn render_lfo_column(
     ui: &mut egui::Ui,
     setter: &ParamSetter,
@@ -648,6 +709,14 @@ fn render_step_seq_panel(
                 setter.set_parameter(&params.mseq_retrigger, true);
             }
 
+            ui.add_space(12.0);
+
+            // Shared by every source routed to a pitch destination
+            let quantize = params.mod_pitch_quantize.value();
+            if render_option_button(ui, "SCALE", quantize, Color32::from_rgb(160, 110, 60)) {
+                setter.set_parameter(&params.mod_pitch_quantize, !quantize);
+            }
+
             ui.add_space(16.0);
             ui.label(egui::RichText::new("RATE").size(FONT).color(LABEL_COLOR));
             ui.add_space(4.0);
@@ -757,6 +826,279 @@ fn render_step_seq_panel(
     });
 }
 
+fn render_mseg_panel(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let content_rect = ui.max_rect();
+    let margin_l = 32.0;
+    let margin_r = 4.0;
+    let margin_t = 14.0;
+    let usable_w = content_rect.width() - margin_l - margin_r;
+
+    let sel_id = egui::Id::new("mseg_sel");
+    let idx = ui.ctx().data_mut(|d| *d.get_temp_mut_or(sel_id, 0usize)).min(MSEG_COUNT - 1);
+    let (tempo_sync, retrigger, dest1, amount1, dest2, amount2) = match idx {
+        0 => (&params.mseg1_tempo_sync, &params.mseg1_retrigger,
+              &params.mseg1_dest1, &params.mseg1_amount1, &params.mseg1_dest2, &params.mseg1_amount2),
+        _ => (&params.mseg2_tempo_sync, &params.mseg2_retrigger,
+              &params.mseg2_dest1, &params.mseg2_amount1, &params.mseg2_dest2, &params.mseg2_amount2),
+    };
+    let synced = tempo_sync.value();
+
+    // Breakpoints live outside the param system: edit a copy, write back on change
+    let original = params.mseg_shapes.read().map(|s| s[idx]).unwrap_or_default();
+    let mut shape = original;
+    let point_id = egui::Id::new("mseg_point").with(idx);
+    let mut selected = ui.ctx().data_mut(|d| *d.get_temp_mut_or(point_id, 1usize)).min(shape.count - 1);
+
+    let inner_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin_l, content_rect.top() + margin_t),
+        egui::pos2(content_rect.right() - margin_r, content_rect.bottom()),
+    );
+    let mut inner_ui = ui.new_child(egui::UiBuilder::new().max_rect(inner_rect));
+    inner_ui.vertical(|ui| {
+        ui.set_width(usable_w);
+
+        ui.label(egui::RichText::new("MSEG").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+
+            for i in 0..MSEG_COUNT {
+                if render_option_button(ui, &format!("MSEG {}", i + 1), idx == i, Color32::from_rgb(140, 80, 160)) {
+                    ui.ctx().data_mut(|d| d.insert_temp(sel_id, i));
+                }
+            }
+
+            ui.add_space(12.0);
+
+            let mode = retrigger.value();
+            for (label, value) in [("FREE", 0), ("NOTE", 1), ("BAR", 2)] {
+                if render_option_button(ui, label, mode == value, Color32::from_rgb(80, 160, 80)) {
+                    setter.set_parameter(retrigger, value);
+                }
+            }
+
+            ui.add_space(16.0);
+            let mut sync = synced;
+            render_toggle(ui, &mut sync, "SYNC");
+            if sync != synced {
+                setter.set_parameter(tempo_sync, sync);
+            }
+            ui.add_space(50.0);
+            render_toggle(ui, &mut shape.looping, "LOOP");
+        });
+
+        ui.add_space(10.0);
+
+        let playhead = ui_state.mseg_position(idx);
+        render_mseg_editor(ui, &mut shape, synced, &mut selected, playhead, usable_w);
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+
+            ui.label(egui::RichText::new(format!("POINT {}", selected + 1)).size(FONT).color(LABEL_COLOR));
+            ui.add_space(8.0);
+
+            let point = &mut shape.points[selected];
+            ui.label(egui::RichText::new("TIME").size(FONT).color(LABEL_COLOR));
+            ui.add_space(4.0);
+            ui.add_enabled_ui(selected > 0, |ui| {
+                if synced {
+                    let mut division = None;
+                    render_division_combo(ui, "mseg_div", 100.0, point.division as usize,
+                        |i| division = Some(i as i32));
+                    if let Some(d) = division {
+                        point.division = d;
+                    }
+                } else {
+                    set_slider_color(ui, RATE_COLOR);
+                    ui.style_mut().spacing.slider_width = 120.0;
+                    ui.style_mut().spacing.slider_rail_height = SLIDER_RAIL;
+                    ui.add(egui::Slider::new(&mut point.time_ms, 1.0..=MAX_SEGMENT_MS)
+                        .logarithmic(true)
+                        .suffix(" ms")
+                        .clamping(egui::SliderClamping::Always));
+                }
+            });
+
+            ui.add_space(12.0);
+            ui.label(egui::RichText::new("CURVE").size(FONT).color(LABEL_COLOR));
+            ui.add_space(4.0);
+            set_slider_color(ui, PHASE_MOD_COLOR);
+            ui.style_mut().spacing.slider_width = 100.0;
+            ui.add_enabled(selected > 0, egui::Slider::new(&mut point.curve, -1.0..=1.0)
+                .clamping(egui::SliderClamping::Always)
+                .show_value(false));
+
+            ui.add_space(12.0);
+            ui.label(egui::RichText::new("LEVEL").size(FONT).color(LABEL_COLOR));
+            ui.add_space(4.0);
+            set_slider_color(ui, SLEW_COLOR);
+            ui.add(egui::Slider::new(&mut point.level, -1.0..=1.0)
+                .clamping(egui::SliderClamping::Always)
+                .show_value(false));
+        });
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            if render_styled_button(ui, "LOOP IN", TOOL_BTN_COLOR) && selected < shape.loop_end {
+                shape.loop_start = selected;
+                shape.looping = true;
+            }
+            if render_styled_button(ui, "LOOP OUT", TOOL_BTN_COLOR) && selected > shape.loop_start {
+                shape.loop_end = selected;
+                shape.looping = true;
+            }
+            if render_styled_button(ui, "DELETE", TOOL_BTN_COLOR) && shape.remove(selected) {
+                selected = selected.min(shape.count - 1);
+            }
+            if render_styled_button(ui, "RESET", TOOL_BTN_COLOR) {
+                shape = MsegShape::default();
+                selected = 1;
+            }
+        });
+
+        ui.add_space(10.0);
+
+        let prefix = format!("mseg{}", idx + 1);
+        ui.horizontal(|ui| {
+            render_route_slot_horizontal(ui, setter, &prefix, 1, dest1, amount1,
+                COL_DEST_COMBO, AMOUNT_INLINE_WIDTH);
+            ui.add_space(20.0);
+            render_route_slot_horizontal(ui, setter, &prefix, 2, dest2, amount2,
+                COL_DEST_COMBO, AMOUNT_INLINE_WIDTH);
+        });
+    });
+
+    ui.ctx().data_mut(|d| d.insert_temp(point_id, selected));
+    if shape != original {
+        shape.sanitize();
+        if let Ok(mut shapes) = params.mseg_shapes.write() {
+            shapes[idx] = shape;
+        }
+        ui_state.mark_mseg_dirty();
+    }
+}
+
+/// Breakpoint canvas: drag points to move them (sideways sets time when free-running),
+/// double-click empty space to add a point, double-click a point to remove it.
+fn render_mseg_editor(
+    ui: &mut egui::Ui,
+    shape: &mut MsegShape,
+    synced: bool,
+    selected: &mut usize,
+    playhead: f32,
+    width: f32,
+) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, MSEG_EDITOR_H), egui::Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, Color32::from_gray(30));
+
+    // Synced segments scale together with tempo, so any fixed BPM lays them out
+    let mut times = [0.0f32; MSEG_MAX_POINTS];
+    for i in 1..shape.count {
+        times[i] = times[i - 1] + shape.segment_seconds(i, synced, 120.0) as f32;
+    }
+    let total = times[shape.count - 1].max(1e-6);
+    let pad = MSEG_POINT_RADIUS + 2.0;
+    let span_w = rect.width() - 2.0 * pad;
+    let span_h = rect.height() / 2.0 - pad;
+    let x_at = |i: usize| rect.left() + pad + times[i] / total * span_w;
+    let y_at = |level: f32| rect.center().y - level * span_h;
+    let level_at = |y: f32| ((rect.center().y - y) / span_h).clamp(-1.0, 1.0);
+
+    painter.line_segment(
+        [egui::pos2(rect.left(), rect.center().y), egui::pos2(rect.right(), rect.center().y)],
+        egui::Stroke::new(1.0, Color32::from_gray(55)),
+    );
+    if shape.looping {
+        let loop_rect = egui::Rect::from_x_y_ranges(x_at(shape.loop_start)..=x_at(shape.loop_end), rect.y_range());
+        painter.rect_filled(loop_rect, 0.0, MSEG_LOOP_COLOR);
+    }
+
+    let mut line = Vec::with_capacity(shape.count * MSEG_CURVE_STEPS + 1);
+    line.push(egui::pos2(x_at(0), y_at(shape.points[0].level)));
+    for i in 1..shape.count {
+        let (x0, x1) = (x_at(i - 1), x_at(i));
+        for step in 1..=MSEG_CURVE_STEPS {
+            let t = step as f32 / MSEG_CURVE_STEPS as f32;
+            let level = shape.segment_value(i, t as f64) as f32;
+            line.push(egui::pos2(x0 + (x1 - x0) * t, y_at(level)));
+        }
+    }
+    painter.add(egui::Shape::line(line, egui::Stroke::new(2.0, MSEG_LINE_COLOR)));
+
+    let seg = (playhead.floor() as usize + 1).min(shape.count - 1);
+    let x = x_at(seg - 1) + (x_at(seg) - x_at(seg - 1)) * (playhead - (seg - 1) as f32).clamp(0.0, 1.0);
+    painter.line_segment(
+        [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+        egui::Stroke::new(1.0, PLAYHEAD_COLOR),
+    );
+
+    for i in 0..shape.count {
+        let center = egui::pos2(x_at(i), y_at(shape.points[i].level));
+        let color = if i == *selected { PLAYHEAD_COLOR } else { MSEG_POINT_COLOR };
+        painter.circle_filled(center, MSEG_POINT_RADIUS, color);
+    }
+
+    let nearest = |pos: egui::Pos2| {
+        (0..shape.count)
+            .map(|i| (i, egui::pos2(x_at(i), y_at(shape.points[i].level)).distance(pos)))
+            .filter(|&(_, d)| d <= MSEG_HIT_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    };
+    let Some(pos) = response.interact_pointer_pos() else { return };
+    // Only a drag that starts on a point moves it
+    let grab_id = response.id.with("grab");
+
+    if response.double_clicked() {
+        match nearest(pos) {
+            Some(i) => {
+                if shape.remove(i) {
+                    *selected = (*selected).min(shape.count - 1);
+                }
+            }
+            None => {
+                let seg = (1..shape.count).find(|&i| pos.x <= x_at(i)).unwrap_or(shape.count - 1);
+                let (x0, x1) = (x_at(seg - 1), x_at(seg));
+                let frac = ((pos.x - x0) / (x1 - x0).max(1.0)).clamp(0.05, 0.95);
+                let next = shape.points[seg];
+                let point = MsegPoint::new(level_at(pos.y), next.time_ms * frac, next.division, 0.0);
+                if shape.insert(seg, point) {
+                    shape.points[seg + 1].time_ms = next.time_ms * (1.0 - frac);
+                    *selected = seg;
+                }
+            }
+        }
+    } else if response.drag_started() || response.clicked() {
+        let hit = nearest(pos);
+        if let Some(i) = hit {
+            *selected = i;
+        }
+        ui.ctx().data_mut(|d| d.insert_temp(grab_id, hit));
+    } else if response.dragged() {
+        let grabbed = ui.ctx().data(|d| d.get_temp::<Option<usize>>(grab_id)).flatten();
+        if let Some(i) = grabbed.filter(|&i| i < shape.count) {
+            let point = &mut shape.points[i];
+            point.level = level_at(pos.y);
+            if !synced && i > 0 {
+                let dt_ms = response.drag_delta().x / span_w * total * 1000.0;
+                point.time_ms = (point.time_ms + dt_ms).clamp(1.0, MAX_SEGMENT_MS);
+            }
+        }
+    }
+}
+
 fn render_styled_button(
     ui: &mut egui::Ui,
     label: &str,
@@ -129,11 +129,13 @@ pub fn render(
         let midi_mode = MidiInputMode::from_index(
             ui_state.midi_mode.load(std::sync::atomic::Ordering::Relaxed),
         );
-        if midi_mode == MidiInputMode::ChordFollow || midi_mode == MidiInputMode::Accompaniment {
+        if midi_mode != MidiInputMode::Passthrough {
             ui.horizontal(|ui| {
                 let label = match midi_mode {
                     MidiInputMode::ChordFollow => "MIDI Chord Follow active — notes controlled by incoming MIDI",
                     MidiInputMode::Accompaniment => "MIDI Accompaniment active — notes adapted from harmonic analysis",
+                    MidiInputMode::CallResponse => "MIDI Call & Response active — pattern answers the last played phrase",
+                    MidiInputMode::Transpose => "MIDI Transpose active — pattern follows the last played key",
                     _ => "",
                 };
                 ui.label(
@@ -0,0 +1,397 @@
+use crate::macro_controls::{is_macro_target, MacroTarget, MACRO_COUNT, MACRO_MAX_TARGETS};
+use crate::params::DeviceParams;
+use crate::preset::Preset;
+use crate::ui::SharedUiState;
+use crate::scenes::{MORPH_TIME_LABELS, QUANTIZE_LABELS, SCENE_COUNT};
+use super::presets::{capture_scene, update_morph_snapshots};
+use super::synth::{render_int_choice_buttons, render_vertical_slider, SliderScale};
+use egui_taffy::TuiBuilderLogic;
+use nih_plug::prelude::{ParamSetter, Params};
+use nih_plug_egui::egui;
+use nih_plug_egui::egui::Color32;
+use std::sync::Arc;
+
+const HEADER_FONT: f32 = 18.0;
+const UI_FONT: f32 = 16.0;
+const HINT_FONT: f32 = 13.0;
+const COL_GAP: f32 = 32.0;
+const MARGIN: f32 = 20.0;
+const MACRO_ACCENT: Color32 = Color32::from_rgb(200, 120, 50);
+const MORPH_ACCENT: Color32 = Color32::from_rgb(130, 90, 170);
+const SCENE_ACCENT: Color32 = Color32::from_rgb(60, 140, 150);
+const LABEL_COLOR: Color32 = Color32::from_gray(140);
+
+const MACRO_LABELS: [&str; MACRO_COUNT] = ["M1", "M2", "M3", "M4", "M5", "M6", "M7", "M8"];
+const MACRO_IDS: [&str; MACRO_COUNT] = [
+    "macro_1", "macro_2", "macro_3", "macro_4",
+    "macro_5", "macro_6", "macro_7", "macro_8",
+];
+
+pub fn render(
+    tui: &mut egui_taffy::Tui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    use egui_taffy::taffy::{prelude::*, style::AlignItems};
+
+    tui.style(Style {
+        flex_grow: 1.0,
+        align_items: Some(AlignItems::Stretch),
+        ..Default::default()
+    })
+    .ui(|ui| {
+        let screen_rect = ui.ctx().screen_rect();
+        let top_y = ui.cursor().min.y;
+
+        let content_rect = egui::Rect::from_min_max(
+            egui::pos2(screen_rect.left() + MARGIN, top_y + MARGIN),
+            egui::pos2(screen_rect.right() - MARGIN, screen_rect.bottom() - MARGIN),
+        );
+
+        let left_w = MACRO_COUNT as f32 * 63.0;
+        let left_rect = egui::Rect::from_min_size(
+            content_rect.min,
+            egui::vec2(left_w, content_rect.height()),
+        );
+        let right_rect = egui::Rect::from_min_max(
+            egui::pos2(content_rect.min.x + left_w + COL_GAP, content_rect.min.y),
+            content_rect.max,
+        );
+
+        let sep_x = content_rect.min.x + left_w + COL_GAP / 2.0;
+        ui.painter().line_segment(
+            [
+                egui::pos2(sep_x, content_rect.top() + 4.0),
+                egui::pos2(sep_x, content_rect.bottom() - 4.0),
+            ],
+            egui::Stroke::new(1.0, Color32::from_gray(50)),
+        );
+
+        let sel_id = egui::Id::new("macro_sel");
+        let selected = ui.ctx().data_mut(|d| *d.get_temp_mut_or(sel_id, 0usize)).min(MACRO_COUNT - 1);
+
+        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(left_rect), |ui| {
+            render_macro_knobs(ui, params, setter, ui_state, selected, sel_id);
+            ui.add_space(28.0);
+            render_morph(ui, params, setter, ui_state);
+        });
+
+        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(right_rect), |ui| {
+            render_scenes(ui, params, setter, ui_state);
+            ui.add_space(20.0);
+            egui::ScrollArea::vertical().id_salt("macro_targets_scroll").show(ui, |ui| {
+                render_macro_targets(ui, params, ui_state, selected);
+            });
+        });
+    });
+}
+
+fn render_macro_knobs(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+    selected: usize,
+    sel_id: egui::Id,
+) {
+    let ml = &*ui_state.midi_learn;
+    let counts: [usize; MACRO_COUNT] = params.macro_targets.read()
+        .map(|t| std::array::from_fn(|i| t[i].len()))
+        .unwrap_or_default();
+
+    ui.label(egui::RichText::new("MACROS").size(HEADER_FONT).strong());
+    ui.add_space(9.0);
+
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 5.0;
+        for (i, param) in params.macros().into_iter().enumerate() {
+            let color = if i == selected { MACRO_ACCENT } else { Color32::from_rgb(110, 80, 50) };
+            render_vertical_slider(
+                ui, params, setter, param, MACRO_LABELS[i],
+                0.0, 1.0, SliderScale::Linear, Some(color),
+                Some((ml, MACRO_IDS[i])),
+            );
+        }
+    });
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 5.0;
+        for (i, count) in counts.iter().enumerate() {
+            let text = egui::RichText::new(count.to_string()).size(UI_FONT);
+            let button = egui::Button::new(text)
+                .min_size(egui::vec2(58.0, 28.0))
+                .selected(i == selected);
+            if ui.add(button).on_hover_text("Edit targets").clicked() {
+                ui.ctx().data_mut(|d| d.insert_temp(sel_id, i));
+            }
+        }
+    });
+    ui.add_space(4.0);
+    ui.label(egui::RichText::new("Targets per macro; click to edit").size(HINT_FONT).color(LABEL_COLOR));
+}
+
+fn render_morph(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let name = |p: &Option<Preset>| p.as_ref().map_or_else(|| "—".to_string(), |p| p.name.clone());
+    let (name_a, name_b) = params.morph_pair.read()
+        .map(|pair| (name(&pair.a), name(&pair.b)))
+        .unwrap_or_default();
+
+    ui.label(egui::RichText::new("MORPH").size(HEADER_FONT).strong());
+    ui.add_space(9.0);
+
+    ui.horizontal(|ui| {
+        render_vertical_slider(
+            ui, params, setter, &params.morph_position, "A/B",
+            0.0, 1.0, SliderScale::Linear, Some(MORPH_ACCENT),
+            Some((&*ui_state.midi_learn, "morph_position")),
+        );
+        ui.add_space(12.0);
+        ui.vertical(|ui| {
+            ui.label(egui::RichText::new(format!("B  {}", name_b)).size(UI_FONT));
+            ui.add_space(60.0);
+            ui.label(egui::RichText::new(format!("A  {}", name_a)).size(UI_FONT));
+            ui.add_space(12.0);
+            ui.label(
+                egui::RichText::new("Pick A and B with →A / →B on the presets page")
+                    .size(HINT_FONT)
+                    .color(LABEL_COLOR),
+            );
+        });
+    });
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SceneMode {
+    Recall,
+    Store,
+    Clear,
+}
+
+fn render_scenes(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let mode_id = egui::Id::new("scene_mode");
+    let mut mode = ui.ctx().data_mut(|d| *d.get_temp_mut_or(mode_id, SceneMode::Recall));
+    let filled: [bool; SCENE_COUNT] = params.scenes.read()
+        .map(|s| std::array::from_fn(|i| s.get(i).is_some_and(Option::is_some)))
+        .unwrap_or_default();
+    let active = ui_state.scenes.active_scene();
+    let progress = ui_state.scenes.progress();
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("SCENES").size(HEADER_FONT).strong());
+        ui.add_space(16.0);
+        for (label, value) in [("STORE", SceneMode::Store), ("CLEAR", SceneMode::Clear)] {
+            let button = egui::Button::new(egui::RichText::new(label).size(UI_FONT))
+                .min_size(egui::vec2(80.0, 28.0))
+                .selected(mode == value);
+            if ui.add(button).clicked() {
+                mode = if mode == value { SceneMode::Recall } else { value };
+            }
+        }
+    });
+    ui.add_space(9.0);
+
+    for row in 0..2 {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            for i in row * 8..row * 8 + 8 {
+                let is_active = active == Some(i);
+                let fill = match (filled[i], is_active) {
+                    (true, true) => SCENE_ACCENT,
+                    (true, false) => Color32::from_rgb(40, 75, 80),
+                    (false, _) => Color32::from_rgb(40, 40, 48),
+                };
+                let button = egui::Button::new(egui::RichText::new((i + 1).to_string()).size(UI_FONT))
+                    .min_size(egui::vec2(52.0, 40.0))
+                    .fill(fill);
+                let response = ui.add(button);
+                // Morph progress along the bottom edge; hollow while waiting for the beat/bar
+                if is_active && progress < 1.0 {
+                    let r = response.rect;
+                    let bar = egui::Rect::from_min_max(egui::pos2(r.left(), r.bottom() - 4.0), r.right_bottom());
+                    if progress < 0.0 {
+                        ui.painter().rect_stroke(bar, 1.0, egui::Stroke::new(1.0, SCENE_ACCENT), egui::epaint::StrokeKind::Inside);
+                    } else {
+                        let w = bar.width() * progress;
+                        ui.painter().rect_filled(egui::Rect::from_min_size(bar.min, egui::vec2(w, bar.height())), 1.0, Color32::WHITE);
+                    }
+                }
+                if response.clicked() {
+                    match mode {
+                        SceneMode::Recall => ui_state.scenes.request_recall(i),
+                        SceneMode::Store | SceneMode::Clear => {
+                            let snapshot = (mode == SceneMode::Store).then(|| capture_scene(params, ui_state));
+                            if let Ok(mut scenes) = params.scenes.write() {
+                                scenes.resize(SCENE_COUNT, None);
+                                scenes[i] = snapshot;
+                            }
+                            update_morph_snapshots(params, ui_state);
+                            mode = SceneMode::Recall;
+                        }
+                    }
+                }
+            }
+        });
+    }
+    ui.ctx().data_mut(|d| d.insert_temp(mode_id, mode));
+
+    ui.add_space(10.0);
+    let morph_options: Vec<(&str, i32)> = MORPH_TIME_LABELS.iter().enumerate().map(|(i, l)| (*l, i as i32)).collect();
+    let quantize_options: Vec<(&str, i32)> = QUANTIZE_LABELS.iter().enumerate().map(|(i, l)| (*l, i as i32)).collect();
+    egui::Grid::new("scene_settings_grid")
+        .num_columns(2)
+        .spacing(egui::vec2(12.0, 6.0))
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new("TIME").size(HINT_FONT).color(LABEL_COLOR));
+            render_int_choice_buttons(ui, setter, &params.scene_morph_time, &morph_options, 54.0, SCENE_ACCENT);
+            ui.end_row();
+
+            ui.label(egui::RichText::new("START").size(HINT_FONT).color(LABEL_COLOR));
+            render_int_choice_buttons(ui, setter, &params.scene_quantize, &quantize_options, 54.0, SCENE_ACCENT);
+            ui.end_row();
+
+            ui.label(egui::RichText::new("MIDI").size(HINT_FONT).color(LABEL_COLOR));
+            ui.horizontal(|ui| {
+                let pc = params.scene_program_change.value();
+                let button = egui::Button::new(egui::RichText::new("PROGRAM CHANGE").size(HINT_FONT))
+                    .min_size(egui::vec2(130.0, 28.0))
+                    .selected(pc);
+                if ui.add(button).clicked() {
+                    setter.set_parameter(&params.scene_program_change, !pc);
+                }
+                ui.add_space(8.0);
+                let channel = params.scene_note_channel.value();
+                let channel_label = |ch: i32| if ch == 0 { "Notes off".to_string() } else { format!("Notes ch {}", ch) };
+                egui::ComboBox::from_id_salt("scene_note_channel")
+                    .selected_text(egui::RichText::new(channel_label(channel)).size(HINT_FONT))
+                    .width(110.0)
+                    .show_ui(ui, |ui| {
+                        for ch in 0..=16 {
+                            if ui.selectable_label(ch == channel, channel_label(ch)).clicked() {
+                                setter.set_parameter(&params.scene_note_channel, ch);
+                            }
+                        }
+                    });
+            });
+            ui.end_row();
+        });
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Notes from C1 recall scenes 1–16 on the note channel")
+            .size(HINT_FONT)
+            .color(LABEL_COLOR),
+    );
+}
+
+fn render_macro_targets(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    ui_state: &Arc<SharedUiState>,
+    selected: usize,
+) {
+    // Target lists live outside the param system: edit a copy, write back on change
+    let original = params.macro_targets.read()
+        .map(|t| t[selected].clone())
+        .unwrap_or_default();
+    let mut targets = original.clone();
+
+    ui.label(egui::RichText::new(format!("MACRO {} TARGETS", selected + 1)).size(HEADER_FONT).strong());
+    ui.add_space(9.0);
+
+    let ids = param_ids(ui, params);
+    let mut remove = None;
+
+    egui::Grid::new("macro_targets_grid")
+        .num_columns(5)
+        .spacing(egui::vec2(12.0, 8.0))
+        .show(ui, |ui| {
+            for label in ["PARAMETER", "MIN", "MAX", "CURVE", ""] {
+                ui.label(egui::RichText::new(label).size(HINT_FONT).color(LABEL_COLOR));
+            }
+            ui.end_row();
+
+            for (row, target) in targets.iter_mut().enumerate() {
+                render_param_picker(ui, egui::Id::new(("macro_target", selected, row)), &ids, &mut target.param_id);
+                ui.add(egui::Slider::new(&mut target.min, -1.0..=1.0).fixed_decimals(2));
+                ui.add(egui::Slider::new(&mut target.max, -1.0..=1.0).fixed_decimals(2));
+                ui.add(egui::Slider::new(&mut target.curve, -1.0..=1.0).fixed_decimals(2));
+                if ui.button(egui::RichText::new("✕").size(UI_FONT)).clicked() {
+                    remove = Some(row);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(row) = remove {
+        targets.remove(row);
+    }
+
+    ui.add_space(8.0);
+    let can_add = targets.len() < MACRO_MAX_TARGETS;
+    let add = egui::Button::new(egui::RichText::new("+ ADD TARGET").size(UI_FONT))
+        .min_size(egui::vec2(140.0, 32.0));
+    if ui.add_enabled(can_add, add).clicked() {
+        if let Some(first) = ids.first() {
+            targets.push(MacroTarget::new(first.clone()));
+        }
+    }
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Min/Max are offsets added to the target's own value at macro 0 and 1")
+            .size(HINT_FONT)
+            .color(LABEL_COLOR),
+    );
+
+    if targets != original {
+        if let Ok(mut all) = params.macro_targets.write() {
+            all[selected] = targets;
+        }
+        ui_state.mark_macro_dirty();
+    }
+}
+
+/// Parameter ids a macro can target, cached for the editor session.
+fn param_ids(ui: &egui::Ui, params: &Arc<DeviceParams>) -> Arc<Vec<String>> {
+    let id = egui::Id::new("macro_param_ids");
+    if let Some(ids) = ui.ctx().data(|d| d.get_temp::<Arc<Vec<String>>>(id)) {
+        return ids;
+    }
+    let ids: Arc<Vec<String>> = Arc::new(
+        params.param_map().into_iter()
+            .map(|(id, _, _)| id)
+            .filter(|id| is_macro_target(id))
+            .collect(),
+    );
+    ui.ctx().data_mut(|d| d.insert_temp(id, ids.clone()));
+    ids
+}
+
+fn render_param_picker(ui: &mut egui::Ui, id: egui::Id, ids: &[String], current: &mut String) {
+    let filter_id = id.with("filter");
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(egui::RichText::new(current.as_str()).size(UI_FONT))
+        .width(260.0)
+        .height(320.0)
+        .show_ui(ui, |ui| {
+            let mut filter = ui.ctx().data_mut(|d| d.get_temp::<String>(filter_id)).unwrap_or_default();
+            ui.add(egui::TextEdit::singleline(&mut filter).hint_text("filter"));
+            let needle = filter.to_lowercase();
+            for param_id in ids.iter().filter(|p| p.contains(&needle)) {
+                if ui.selectable_label(param_id == current, param_id.as_str()).clicked() {
+                    *current = param_id.clone();
+                }
+            }
+            ui.ctx().data_mut(|d| d.insert_temp(filter_id, filter));
+        });
+}
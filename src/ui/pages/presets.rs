@@ -6,8 +6,12 @@ use nih_plug_egui::egui::{self, Color32};
 use crate::params::DeviceParams;
 use crate::ui::SharedUiState;
 use crate::preset::manager::{FactoryBank, UserBank, PresetLocation};
-use crate::preset::Preset;
+use crate::preset::{routed_params, MorphSnapshot, MorphSnapshots, ParamWriter, PatternData, Preset, SnapshotCapture};
+use crate::synth::fx_chain::{place_mod_fx, sanitize_order, DEFAULT_FX_ORDER};
+use crate::macro_controls::MACRO_MAX_TARGETS;
+use crate::scenes::SCENE_COUNT;
 use nih_plug::prelude::*;
+use std::collections::HashMap;
 
 #[derive(Clone, PartialEq)]
 enum PresetSection {
@@ -31,6 +35,8 @@ struct PresetPageState {
     name_buffer: String,
     author_buffer: String,
     status_message: Option<(String, std::time::Instant)>,
+    /// Randomizer and breeding tools replace the preset grid.
+    lab: bool,
 }
 
 impl Default for PresetPageState {
@@ -44,6 +50,7 @@ impl Default for PresetPageState {
             name_buffer: String::new(),
             author_buffer: String::new(),
             status_message: None,
+            lab: false,
         }
     }
 }
@@ -115,17 +122,34 @@ pub fn render(
                         Color32::from_rgb(50, 50, 50)
                     });
 
+                    let lab_btn = egui::Button::new(
+                        egui::RichText::new("LAB").size(18.0).strong().color(Color32::WHITE)
+                    )
+                    .min_size(egui::vec2(90.0, 48.0))
+                    .fill(if state.lab {
+                        Color32::from_rgb(90, 70, 110)
+                    } else {
+                        Color32::from_rgb(50, 50, 50)
+                    });
+
                     if ui.add(factory_btn).clicked() {
                         state.section = PresetSection::Factory;
                         state.selected_preset = 0;
+                        state.lab = false;
                     }
                     ui.add_space(8.0);
                     if ui.add(user_btn).clicked() {
                         state.section = PresetSection::User;
                         state.selected_preset = 0;
+                        state.lab = false;
+                    }
+                    ui.add_space(8.0);
+                    if ui.add(lab_btn).clicked() {
+                        state.lab = !state.lab;
                     }
 
                     match state.mode {
+                        _ if state.lab => {}
                         PageMode::Browse => {
                             ui.add_space(24.0);
                             let title_resp = ui.label(egui::RichText::new(selected_preset_name_for_header.to_uppercase()).size(20.0).strong());
@@ -202,6 +226,35 @@ pub fn render(
                                     state.name_buffer = selected_preset_name_for_header.clone();
                                     state.author_buffer = "User".to_string();
                                 }
+
+                                ui.add_space(16.0);
+
+                                for slot in ["B", "A"] {
+                                    let morph_btn = egui::Button::new(
+                                        egui::RichText::new(format!("→{}", slot)).size(18.0).color(Color32::WHITE)
+                                    ).min_size(egui::vec2(56.0, 48.0))
+                                    .fill(Color32::from_rgb(90, 70, 110));
+
+                                    let hover = if slot == "A" { "Load as morph preset A" } else { "Use as morph preset B" };
+                                    if ui.add(morph_btn).on_hover_text(hover).clicked() {
+                                        let preset = ui_state.preset_manager.lock().ok().map(|mgr| match state.section {
+                                            PresetSection::Factory => mgr.get_factory_bank(state.factory_bank).presets[state.selected_preset].clone(),
+                                            PresetSection::User => mgr.get_user_bank(state.user_bank).presets[state.selected_preset].clone(),
+                                        });
+                                        if let Some(preset) = preset {
+                                            // The morph offsets the knobs by B - A, so A is loaded as the starting sound
+                                            if slot == "A" {
+                                                load_preset_to_params(&preset.data, params, setter, ui_state);
+                                            }
+                                            if let Ok(mut pair) = params.morph_pair.write() {
+                                                if slot == "A" { pair.a = Some(preset); } else { pair.b = Some(preset); }
+                                            }
+                                            update_morph_snapshots(params, ui_state);
+                                            state.status_message = Some((format!("Set morph {}", slot), std::time::Instant::now()));
+                                        }
+                                    }
+                                    ui.add_space(4.0);
+                                }
                             });
                         }
                         PageMode::Save => {
@@ -289,7 +342,9 @@ pub fn render(
                 // Bank buttons (2 cols) + Preset grid (8 cols) side by side
                 let mut clicked_preset: Option<usize> = None;
 
-                if let Ok(manager) = ui_state.preset_manager.lock() {
+                if state.lab {
+                    super::lab::render(ui, params, setter, ui_state);
+                } else if let Ok(manager) = ui_state.preset_manager.lock() {
                     let current_location = Some(manager.current_location());
 
                     let banks: Vec<(usize, &str, bool)> = match state.section {
@@ -523,6 +578,59 @@ fn load_preset_to_params(
     params: &Arc<DeviceParams>,
     setter: &ParamSetter,
     ui_state: &Arc<SharedUiState>,
+) {
+    apply_preset_data(data, params, setter, ui_state);
+    if let Ok(mut scenes) = params.scenes.write() {
+        *scenes = data.scenes.clone();
+        scenes.resize(SCENE_COUNT, None);
+    }
+    update_morph_snapshots(params, ui_state);
+    ui_state.request_dsp_reset();
+}
+
+/// Rebuilds the A/B and scene snapshots the audio thread morphs between.
+/// Runs when the pair or the scenes change and after the plugin state is
+/// restored.
+pub fn update_morph_snapshots(params: &DeviceParams, ui_state: &SharedUiState) {
+    let routed = routed_params(params);
+    let snapshot = |data: &crate::preset::PresetData| {
+        let capture = SnapshotCapture::default();
+        write_preset_params(data, params, &capture);
+        MorphSnapshot { params: capture.finish(&routed), pattern: PatternData::from_preset(data) }
+    };
+    let ab = params.morph_pair.read().ok().and_then(|pair| match (&pair.a, &pair.b) {
+        (Some(a), Some(b)) => Some((snapshot(&a.data), snapshot(&b.data))),
+        _ => None,
+    });
+    let scenes: Vec<Option<MorphSnapshot>> = params.scenes.read()
+        .map(|scenes| scenes.iter().map(|scene| scene.as_ref().map(&snapshot)).collect())
+        .unwrap_or_default();
+    // Room for the audio thread to publish a landed recall without allocating
+    if let Ok(mut offsets) = ui_state.scenes.offsets.lock() {
+        offsets.reserve(routed.len().saturating_sub(offsets.len()));
+    }
+    if let Ok(mut snapshots) = ui_state.morph_snapshots.lock() {
+        *snapshots = MorphSnapshots::new(ab, scenes);
+    }
+    ui_state.mark_morph_dirty();
+}
+
+/// Current patch as a scene snapshot.
+pub(super) fn capture_scene(
+    params: &Arc<DeviceParams>,
+    ui_state: &Arc<SharedUiState>,
+) -> crate::preset::PresetData {
+    let mut data = save_params_to_preset_data(params, ui_state);
+    data.scenes.clear();
+    data
+}
+
+/// Sends every parameter a preset stores to `setter`: the host when loading,
+/// or a `SnapshotCapture` when building morph snapshots.
+fn write_preset_params(
+    data: &crate::preset::PresetData,
+    params: &DeviceParams,
+    setter: &impl ParamWriter,
 ) {
     for &v in data.straight_1_1.iter() {
         setter.set_parameter(&params.div1_beat1, v);
@@ -773,6 +881,44 @@ fn load_preset_to_params(
     setter.set_parameter(&params.synth_saw_shape_type, data.synth_saw_shape_type);
     setter.set_parameter(&params.synth_saw_shape_amount, data.synth_saw_shape_amount);
 
+    setter.set_parameter(&params.synth_wt_volume, data.synth_wt_volume);
+    setter.set_parameter(&params.synth_wt_octave, data.synth_wt_octave);
+    setter.set_parameter(&params.synth_wt_tune, data.synth_wt_tune);
+    setter.set_parameter(&params.synth_wt_fine, data.synth_wt_fine);
+    setter.set_parameter(&params.synth_wt_position, data.synth_wt_position);
+
+    setter.set_parameter(&params.synth_noise_level, data.synth_noise_level);
+    setter.set_parameter(&params.synth_noise_type, data.synth_noise_type);
+    setter.set_parameter(&params.synth_noise_rate, data.synth_noise_rate);
+    setter.set_parameter(&params.synth_noise_lpf, data.synth_noise_lpf);
+    setter.set_parameter(&params.synth_noise_hpf, data.synth_noise_hpf);
+    setter.set_parameter(&params.synth_noise_env, data.synth_noise_env);
+    setter.set_parameter(&params.synth_noise_inject, data.synth_noise_inject);
+    setter.set_parameter(&params.synth_noise_inject_amount, data.synth_noise_inject_amount);
+
+    setter.set_parameter(&params.synth_sync_source, data.synth_sync_source);
+    setter.set_parameter(&params.synth_vps_sync, data.synth_vps_sync);
+    setter.set_parameter(&params.synth_saw_sync, data.synth_saw_sync);
+    setter.set_parameter(&params.synth_pll_sync, data.synth_pll_sync);
+    setter.set_parameter(&params.synth_sync_soft, data.synth_sync_soft);
+
+    setter.set_parameter(&params.synth_pll_topology, data.synth_pll_topology);
+    setter.set_parameter(&params.synth_pll2_track_speed, data.synth_pll2_track_speed);
+    setter.set_parameter(&params.synth_pll2_damping, data.synth_pll2_damping);
+    setter.set_parameter(&params.synth_pll2_mult, data.synth_pll2_mult);
+    setter.set_parameter(&params.synth_pll_coupling, data.synth_pll_coupling);
+    setter.set_parameter(&params.synth_pll_ref_source, data.synth_pll_ref_source);
+    setter.set_parameter(&params.synth_pll_ref_mix, data.synth_pll_ref_mix);
+
+    setter.set_parameter(&params.synth_ring_pll_enable, data.synth_ring_pll_enable);
+    setter.set_parameter(&params.synth_ring_pll, data.synth_ring_pll);
+    setter.set_parameter(&params.synth_ring_vps_saw_enable, data.synth_ring_vps_saw_enable);
+    setter.set_parameter(&params.synth_ring_vps_saw, data.synth_ring_vps_saw);
+    setter.set_parameter(&params.synth_xfm_vps_saw_enable, data.synth_xfm_vps_saw_enable);
+    setter.set_parameter(&params.synth_xfm_vps_saw, data.synth_xfm_vps_saw);
+    setter.set_parameter(&params.synth_xfm_saw_ref_enable, data.synth_xfm_saw_ref_enable);
+    setter.set_parameter(&params.synth_xfm_saw_ref, data.synth_xfm_saw_ref);
+
     setter.set_parameter(&params.synth_filter_enable, data.synth_filter_enable);
     setter.set_parameter(&params.synth_filter_cutoff, data.synth_filter_cutoff);
     setter.set_parameter(&params.synth_filter_resonance, data.synth_filter_resonance);
@@ -815,6 +961,23 @@ fn load_preset_to_params(
     setter.set_parameter(&params.synth_filter_cutoff_slew, data.synth_filter_cutoff_slew);
     setter.set_parameter(&params.synth_filter_poles, data.synth_filter_poles);
 
+    setter.set_parameter(&params.synth_filter2_enable, data.synth_filter2_enable);
+    setter.set_parameter(&params.synth_filter2_type, data.synth_filter2_type);
+    setter.set_parameter(&params.synth_filter2_cutoff, data.synth_filter2_cutoff);
+    setter.set_parameter(&params.synth_filter2_resonance, data.synth_filter2_resonance);
+    setter.set_parameter(&params.synth_filter2_morph, data.synth_filter2_morph);
+    setter.set_parameter(&params.synth_filter2_key_track, data.synth_filter2_key_track);
+    setter.set_parameter(&params.synth_filter2_env_amount, data.synth_filter2_env_amount);
+    setter.set_parameter(&params.synth_filter_routing, data.synth_filter_routing);
+
+    setter.set_parameter(&params.synth_resonator_enable, data.synth_resonator_enable);
+    setter.set_parameter(&params.synth_resonator_model, data.synth_resonator_model);
+    setter.set_parameter(&params.synth_resonator_excite, data.synth_resonator_excite);
+    setter.set_parameter(&params.synth_resonator_decay, data.synth_resonator_decay);
+    setter.set_parameter(&params.synth_resonator_brightness, data.synth_resonator_brightness);
+    setter.set_parameter(&params.synth_resonator_position, data.synth_resonator_position);
+    setter.set_parameter(&params.synth_resonator_mix, data.synth_resonator_mix);
+
     setter.set_parameter(&params.synth_vol_attack, data.synth_vol_attack);
     setter.set_parameter(&params.synth_vol_attack_shape, data.synth_vol_attack_shape);
     setter.set_parameter(&params.synth_vol_decay, data.synth_vol_decay);
@@ -863,6 +1026,65 @@ fn load_preset_to_params(
     setter.set_parameter(&params.synth_reverb_rhythm_duck_division, data.synth_reverb_rhythm_duck_division);
     setter.set_parameter(&params.synth_reverb_rhythm_duck_smooth, data.synth_reverb_rhythm_duck_smooth);
 
+    setter.set_parameter(&params.synth_delay_enable, data.synth_delay_enable);
+    setter.set_parameter(&params.synth_delay_mode, data.synth_delay_mode);
+    setter.set_parameter(&params.synth_delay_sync, data.synth_delay_sync);
+    setter.set_parameter(&params.synth_delay_division, data.synth_delay_division);
+    setter.set_parameter(&params.synth_delay_time, data.synth_delay_time);
+    setter.set_parameter(&params.synth_delay_mix, data.synth_delay_mix);
+    setter.set_parameter(&params.synth_delay_feedback, data.synth_delay_feedback);
+    setter.set_parameter(&params.synth_delay_spread, data.synth_delay_spread);
+    setter.set_parameter(&params.synth_delay_lpf, data.synth_delay_lpf);
+    setter.set_parameter(&params.synth_delay_hpf, data.synth_delay_hpf);
+    setter.set_parameter(&params.synth_delay_saturation, data.synth_delay_saturation);
+    setter.set_parameter(&params.synth_delay_mod_rate, data.synth_delay_mod_rate);
+    setter.set_parameter(&params.synth_delay_mod_depth, data.synth_delay_mod_depth);
+    setter.set_parameter(&params.synth_delay_ducking, data.synth_delay_ducking);
+    setter.set_parameter(&params.synth_delay_send_vps, data.synth_delay_send_vps);
+    setter.set_parameter(&params.synth_delay_send_pll, data.synth_delay_send_pll);
+    setter.set_parameter(&params.synth_delay_send_saw, data.synth_delay_send_saw);
+    setter.set_parameter(&params.synth_delay_send_wt, data.synth_delay_send_wt);
+    setter.set_parameter(&params.synth_delay_send_sub, data.synth_delay_send_sub);
+    setter.set_parameter(&params.synth_delay_send_filter, data.synth_delay_send_filter);
+
+    setter.set_parameter(&params.mod_fx_enable, data.mod_fx_enable);
+    setter.set_parameter(&params.mod_fx_mode, data.mod_fx_mode);
+    setter.set_parameter(&params.mod_fx_position, data.mod_fx_position);
+    setter.set_parameter(&params.mod_fx_rate, data.mod_fx_rate);
+    setter.set_parameter(&params.mod_fx_sync, data.mod_fx_sync);
+    setter.set_parameter(&params.mod_fx_division, data.mod_fx_division);
+    setter.set_parameter(&params.mod_fx_depth, data.mod_fx_depth);
+    setter.set_parameter(&params.mod_fx_feedback, data.mod_fx_feedback);
+    setter.set_parameter(&params.mod_fx_mix, data.mod_fx_mix);
+    setter.set_parameter(&params.mod_fx_voices, data.mod_fx_voices);
+    setter.set_parameter(&params.mod_fx_stages, data.mod_fx_stages);
+    setter.set_parameter(&params.mod_fx_spread, data.mod_fx_spread);
+
+    let fx_order = if data.fx_chain.is_empty() {
+        place_mod_fx(&DEFAULT_FX_ORDER, data.mod_fx_position)
+    } else {
+        sanitize_order(&data.fx_chain)
+    };
+    for (slot, module) in params.fx_slots().into_iter().zip(fx_order) {
+        setter.set_parameter(slot, module.to_index());
+    }
+    setter.set_parameter(&params.fx_send_looper, data.fx_send_looper);
+    setter.set_parameter(&params.fx_send_delay, data.fx_send_delay);
+    setter.set_parameter(&params.fx_send_reverb, data.fx_send_reverb);
+
+    setter.set_parameter(&params.lofi_enable, data.lofi_enable);
+    setter.set_parameter(&params.lofi_post_filter, data.lofi_post_filter);
+    setter.set_parameter(&params.lofi_vps, data.lofi_vps);
+    setter.set_parameter(&params.lofi_pll, data.lofi_pll);
+    setter.set_parameter(&params.lofi_saw, data.lofi_saw);
+    setter.set_parameter(&params.lofi_shaper, data.lofi_shaper);
+    setter.set_parameter(&params.lofi_bits, data.lofi_bits);
+    setter.set_parameter(&params.lofi_rate, data.lofi_rate);
+    setter.set_parameter(&params.lofi_anti_alias, data.lofi_anti_alias);
+    setter.set_parameter(&params.lofi_jitter, data.lofi_jitter);
+    setter.set_parameter(&params.lofi_drive, data.lofi_drive);
+    setter.set_parameter(&params.lofi_mix, data.lofi_mix);
+
     setter.set_parameter(&params.lfo1_rate, data.lfo1_rate);
     setter.set_parameter(&params.lfo1_waveform, data.lfo1_waveform);
     setter.set_parameter(&params.lfo1_tempo_sync, data.lfo1_tempo_sync);
@@ -965,49 +1187,84 @@ fn load_preset_to_params(
     setter.set_parameter(&params.mseq_dest2, data.mseq_dest2);
     setter.set_parameter(&params.mseq_amount2, data.mseq_amount2);
 
-    if let Ok(mut strength_values) = ui_state.strength_values.lock() {
-        for i in 0..96 {
-            if i < data.strength_values.len() {
-                strength_values[i] = data.strength_values[i] as f32 / 100.0;
-            } else {
-                strength_values[i] = 0.0;
+    setter.set_parameter(&params.mseg1_tempo_sync, data.mseg1_tempo_sync);
+    setter.set_parameter(&params.mseg1_retrigger, data.mseg1_retrigger);
+    setter.set_parameter(&params.mseg1_dest1, data.mseg1_dest1);
+    setter.set_parameter(&params.mseg1_amount1, data.mseg1_amount1);
+    setter.set_parameter(&params.mseg1_dest2, data.mseg1_dest2);
+    setter.set_parameter(&params.mseg1_amount2, data.mseg1_amount2);
+    setter.set_parameter(&params.mseg2_tempo_sync, data.mseg2_tempo_sync);
+    setter.set_parameter(&params.mseg2_retrigger, data.mseg2_retrigger);
+    setter.set_parameter(&params.mseg2_dest1, data.mseg2_dest1);
+    setter.set_parameter(&params.mseg2_amount1, data.mseg2_amount1);
+    setter.set_parameter(&params.mseg2_dest2, data.mseg2_dest2);
+    setter.set_parameter(&params.mseg2_amount2, data.mseg2_amount2);
+    setter.set_parameter(&params.mod_pitch_quantize, data.mod_pitch_quantize);
+
+    setter.set_parameter(&params.macro_1, data.macro_1);
+    setter.set_parameter(&params.macro_2, data.macro_2);
+    setter.set_parameter(&params.macro_3, data.macro_3);
+    setter.set_parameter(&params.macro_4, data.macro_4);
+    setter.set_parameter(&params.macro_5, data.macro_5);
+    setter.set_parameter(&params.macro_6, data.macro_6);
+    setter.set_parameter(&params.macro_7, data.macro_7);
+    setter.set_parameter(&params.macro_8, data.macro_8);
+}
+
+/// Writes preset data to the params and sequencer state without resetting
+/// the DSP.
+pub(super) fn apply_preset_data(
+    data: &crate::preset::PresetData,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    write_preset_params(data, params, setter);
+    // The knobs now hold the whole patch, including any scene being heard
+    ui_state.scenes.request_reset();
+
+    // A missing table file leaves the current one loaded
+    if data.wavetable_name != ui_state.wavetable_name() {
+        if let Ok(table) = crate::synth::wavetable::load_wavetable(&data.wavetable_name) {
+            if let Ok(mut name) = params.wavetable_name.write() {
+                name.clone_from(&table.name);
             }
+            ui_state.set_wavetable(table);
         }
     }
 
-    if let Ok(mut note_pool) = ui_state.note_pool.lock() {
-        note_pool.notes.clear();
-        note_pool.set_root_note(data.root_note);
-
-        for note_data in &data.notes {
-            let chance = note_data.chance as f32 / 127.0;
-            let strength_bias = (note_data.beat as f32 - 64.0) / 63.0;
-            let length_bias = (note_data.beat_length as f32 - 64.0) / 63.0;
-            note_pool.set_note_full(note_data.midi_note, note_data.octave_offset, chance, strength_bias, length_bias);
+    if let Ok(mut shapes) = params.mseg_shapes.write() {
+        for (i, shape) in shapes.iter_mut().enumerate() {
+            *shape = data.mseg_shapes.get(i).copied().unwrap_or_default();
+            shape.sanitize();
         }
     }
+    ui_state.mark_mseg_dirty();
 
-    if let Ok(mut scale) = ui_state.scale.lock() {
-        *scale = data.scale;
-    }
-
-    if let Ok(mut pattern) = ui_state.stability_pattern.lock() {
-        *pattern = data.stability_pattern;
-    }
-
-    if let Ok(mut oct_rand) = ui_state.octave_randomization.lock() {
-        oct_rand.chance = data.octave_randomization.chance;
-        oct_rand.strength_pref = data.octave_randomization.strength_pref;
-        oct_rand.length_pref = data.octave_randomization.length_pref;
-        oct_rand.direction = data.octave_randomization.direction;
+    if let Ok(mut targets) = params.macro_targets.write() {
+        for (i, list) in targets.iter_mut().enumerate() {
+            *list = data.macro_targets.get(i).cloned().unwrap_or_default();
+            list.truncate(MACRO_MAX_TARGETS);
+        }
     }
-
-    if let Ok(mut style_config) = ui_state.style_config.lock() {
-        style_config.style = data.style_config.style;
-        style_config.chance = data.style_config.chance;
-        style_config.complexity = data.style_config.complexity;
-        style_config.max_notes = data.style_config.max_notes;
-        style_config.mode = data.style_config.mode;
+    ui_state.mark_macro_dirty();
+
+    if let (Ok(mut strength_values), Ok(mut note_pool), Ok(mut scale), Ok(mut stability_pattern), Ok(mut octave_randomization), Ok(mut style_config)) = (
+        ui_state.strength_values.lock(),
+        ui_state.note_pool.lock(),
+        ui_state.scale.lock(),
+        ui_state.stability_pattern.lock(),
+        ui_state.octave_randomization.lock(),
+        ui_state.style_config.lock(),
+    ) {
+        PatternData::from_preset(data).write_into(
+            &mut strength_values,
+            &mut note_pool,
+            &mut scale,
+            &mut stability_pattern,
+            &mut octave_randomization,
+            &mut style_config,
+        );
     }
 
     if let Ok(mut multi_bar) = ui_state.multi_bar_config.lock() {
@@ -1070,293 +1327,345 @@ fn load_preset_to_params(
 
     ui_state.increment_preset_version();
     ui_state.mark_seq_dirty();
-    ui_state.request_dsp_reset();
 }
 
-fn save_params_to_preset_data(
+/// Params as the audio thread plays them once a scene recall has landed, so
+/// saving or storing keeps the recalled scene rather than the bare knobs.
+struct HeardValues(HashMap<ParamPtr, f32>);
+
+impl HeardValues {
+    fn plain<P: Param>(&self, param: &P) -> P::Plain {
+        match self.0.get(&param.as_ptr()) {
+            Some(&offset) => param.preview_plain((param.modulated_normalized_value() + offset).clamp(0.0, 1.0)),
+            None => param.modulated_plain_value(),
+        }
+    }
+}
+
+pub(super) fn save_params_to_preset_data(
     params: &Arc<DeviceParams>,
     ui_state: &Arc<SharedUiState>,
 ) -> crate::preset::PresetData {
     let mut data = crate::preset::PresetData::default();
+    let heard = HeardValues(ui_state.scenes.landed_offsets());
 
-    data.straight_1_1 = [params.div1_beat1.modulated_plain_value()];
+    data.straight_1_1 = [heard.plain(&params.div1_beat1)];
 
     data.straight_1_2 = [
-        params.div2_beat1.modulated_plain_value(),
-        params.div2_beat2.modulated_plain_value(),
+        heard.plain(&params.div2_beat1),
+        heard.plain(&params.div2_beat2),
     ];
 
     data.straight_1_4 = [
-        params.div4_beat1.modulated_plain_value(),
-        params.div4_beat2.modulated_plain_value(),
-        params.div4_beat3.modulated_plain_value(),
-        params.div4_beat4.modulated_plain_value(),
+        heard.plain(&params.div4_beat1),
+        heard.plain(&params.div4_beat2),
+        heard.plain(&params.div4_beat3),
+        heard.plain(&params.div4_beat4),
     ];
 
     data.straight_1_8 = [
-        params.div8_beat1.modulated_plain_value(),
-        params.div8_beat2.modulated_plain_value(),
-        params.div8_beat3.modulated_plain_value(),
-        params.div8_beat4.modulated_plain_value(),
-        params.div8_beat5.modulated_plain_value(),
-        params.div8_beat6.modulated_plain_value(),
-        params.div8_beat7.modulated_plain_value(),
-        params.div8_beat8.modulated_plain_value(),
+        heard.plain(&params.div8_beat1),
+        heard.plain(&params.div8_beat2),
+        heard.plain(&params.div8_beat3),
+        heard.plain(&params.div8_beat4),
+        heard.plain(&params.div8_beat5),
+        heard.plain(&params.div8_beat6),
+        heard.plain(&params.div8_beat7),
+        heard.plain(&params.div8_beat8),
     ];
 
     data.straight_1_16 = [
-        params.div16_beat1.modulated_plain_value(),
-        params.div16_beat2.modulated_plain_value(),
-        params.div16_beat3.modulated_plain_value(),
-        params.div16_beat4.modulated_plain_value(),
-        params.div16_beat5.modulated_plain_value(),
-        params.div16_beat6.modulated_plain_value(),
-        params.div16_beat7.modulated_plain_value(),
-        params.div16_beat8.modulated_plain_value(),
-        params.div16_beat9.modulated_plain_value(),
-        params.div16_beat10.modulated_plain_value(),
-        params.div16_beat11.modulated_plain_value(),
-        params.div16_beat12.modulated_plain_value(),
-        params.div16_beat13.modulated_plain_value(),
-        params.div16_beat14.modulated_plain_value(),
-        params.div16_beat15.modulated_plain_value(),
-        params.div16_beat16.modulated_plain_value(),
+        heard.plain(&params.div16_beat1),
+        heard.plain(&params.div16_beat2),
+        heard.plain(&params.div16_beat3),
+        heard.plain(&params.div16_beat4),
+        heard.plain(&params.div16_beat5),
+        heard.plain(&params.div16_beat6),
+        heard.plain(&params.div16_beat7),
+        heard.plain(&params.div16_beat8),
+        heard.plain(&params.div16_beat9),
+        heard.plain(&params.div16_beat10),
+        heard.plain(&params.div16_beat11),
+        heard.plain(&params.div16_beat12),
+        heard.plain(&params.div16_beat13),
+        heard.plain(&params.div16_beat14),
+        heard.plain(&params.div16_beat15),
+        heard.plain(&params.div16_beat16),
     ];
 
     data.straight_1_32 = [
-        params.div32_beat1.modulated_plain_value(),
-        params.div32_beat2.modulated_plain_value(),
-        params.div32_beat3.modulated_plain_value(),
-        params.div32_beat4.modulated_plain_value(),
-        params.div32_beat5.modulated_plain_value(),
-        params.div32_beat6.modulated_plain_value(),
-        params.div32_beat7.modulated_plain_value(),
-        params.div32_beat8.modulated_plain_value(),
-        params.div32_beat9.modulated_plain_value(),
-        params.div32_beat10.modulated_plain_value(),
-        params.div32_beat11.modulated_plain_value(),
-        params.div32_beat12.modulated_plain_value(),
-        params.div32_beat13.modulated_plain_value(),
-        params.div32_beat14.modulated_plain_value(),
-        params.div32_beat15.modulated_plain_value(),
-        params.div32_beat16.modulated_plain_value(),
-        params.div32_beat17.modulated_plain_value(),
-        params.div32_beat18.modulated_plain_value(),
-        params.div32_beat19.modulated_plain_value(),
-        params.div32_beat20.modulated_plain_value(),
-        params.div32_beat21.modulated_plain_value(),
-        params.div32_beat22.modulated_plain_value(),
-        params.div32_beat23.modulated_plain_value(),
-        params.div32_beat24.modulated_plain_value(),
-        params.div32_beat25.modulated_plain_value(),
-        params.div32_beat26.modulated_plain_value(),
-        params.div32_beat27.modulated_plain_value(),
-        params.div32_beat28.modulated_plain_value(),
-        params.div32_beat29.modulated_plain_value(),
-        params.div32_beat30.modulated_plain_value(),
-        params.div32_beat31.modulated_plain_value(),
-        params.div32_beat32.modulated_plain_value(),
+        heard.plain(&params.div32_beat1),
+        heard.plain(&params.div32_beat2),
+        heard.plain(&params.div32_beat3),
+        heard.plain(&params.div32_beat4),
+        heard.plain(&params.div32_beat5),
+        heard.plain(&params.div32_beat6),
+        heard.plain(&params.div32_beat7),
+        heard.plain(&params.div32_beat8),
+        heard.plain(&params.div32_beat9),
+        heard.plain(&params.div32_beat10),
+        heard.plain(&params.div32_beat11),
+        heard.plain(&params.div32_beat12),
+        heard.plain(&params.div32_beat13),
+        heard.plain(&params.div32_beat14),
+        heard.plain(&params.div32_beat15),
+        heard.plain(&params.div32_beat16),
+        heard.plain(&params.div32_beat17),
+        heard.plain(&params.div32_beat18),
+        heard.plain(&params.div32_beat19),
+        heard.plain(&params.div32_beat20),
+        heard.plain(&params.div32_beat21),
+        heard.plain(&params.div32_beat22),
+        heard.plain(&params.div32_beat23),
+        heard.plain(&params.div32_beat24),
+        heard.plain(&params.div32_beat25),
+        heard.plain(&params.div32_beat26),
+        heard.plain(&params.div32_beat27),
+        heard.plain(&params.div32_beat28),
+        heard.plain(&params.div32_beat29),
+        heard.plain(&params.div32_beat30),
+        heard.plain(&params.div32_beat31),
+        heard.plain(&params.div32_beat32),
     ];
 
     data.triplet_1_2t = [
-        params.div3t_beat1.modulated_plain_value(),
-        params.div3t_beat2.modulated_plain_value(),
-        params.div3t_beat3.modulated_plain_value(),
+        heard.plain(&params.div3t_beat1),
+        heard.plain(&params.div3t_beat2),
+        heard.plain(&params.div3t_beat3),
     ];
 
     data.triplet_1_4t = [
-        params.div6t_beat1.modulated_plain_value(),
-        params.div6t_beat2.modulated_plain_value(),
-        params.div6t_beat3.modulated_plain_value(),
-        params.div6t_beat4.modulated_plain_value(),
-        params.div6t_beat5.modulated_plain_value(),
-        params.div6t_beat6.modulated_plain_value(),
+        heard.plain(&params.div6t_beat1),
+        heard.plain(&params.div6t_beat2),
+        heard.plain(&params.div6t_beat3),
+        heard.plain(&params.div6t_beat4),
+        heard.plain(&params.div6t_beat5),
+        heard.plain(&params.div6t_beat6),
     ];
 
     data.triplet_1_8t = [
-        params.div12t_beat1.modulated_plain_value(),
-        params.div12t_beat2.modulated_plain_value(),
-        params.div12t_beat3.modulated_plain_value(),
-        params.div12t_beat4.modulated_plain_value(),
-        params.div12t_beat5.modulated_plain_value(),
-        params.div12t_beat6.modulated_plain_value(),
-        params.div12t_beat7.modulated_plain_value(),
-        params.div12t_beat8.modulated_plain_value(),
-        params.div12t_beat9.modulated_plain_value(),
-        params.div12t_beat10.modulated_plain_value(),
-        params.div12t_beat11.modulated_plain_value(),
-        params.div12t_beat12.modulated_plain_value(),
+        heard.plain(&params.div12t_beat1),
+        heard.plain(&params.div12t_beat2),
+        heard.plain(&params.div12t_beat3),
+        heard.plain(&params.div12t_beat4),
+        heard.plain(&params.div12t_beat5),
+        heard.plain(&params.div12t_beat6),
+        heard.plain(&params.div12t_beat7),
+        heard.plain(&params.div12t_beat8),
+        heard.plain(&params.div12t_beat9),
+        heard.plain(&params.div12t_beat10),
+        heard.plain(&params.div12t_beat11),
+        heard.plain(&params.div12t_beat12),
     ];
 
     data.triplet_1_16t = [
-        params.div24t_beat1.modulated_plain_value(),
-        params.div24t_beat2.modulated_plain_value(),
-        params.div24t_beat3.modulated_plain_value(),
-        params.div24t_beat4.modulated_plain_value(),
-        params.div24t_beat5.modulated_plain_value(),
-        params.div24t_beat6.modulated_plain_value(),
-        params.div24t_beat7.modulated_plain_value(),
-        params.div24t_beat8.modulated_plain_value(),
-        params.div24t_beat9.modulated_plain_value(),
-        params.div24t_beat10.modulated_plain_value(),
-        params.div24t_beat11.modulated_plain_value(),
-        params.div24t_beat12.modulated_plain_value(),
-        params.div24t_beat13.modulated_plain_value(),
-        params.div24t_beat14.modulated_plain_value(),
-        params.div24t_beat15.modulated_plain_value(),
-        params.div24t_beat16.modulated_plain_value(),
-        params.div24t_beat17.modulated_plain_value(),
-        params.div24t_beat18.modulated_plain_value(),
-        params.div24t_beat19.modulated_plain_value(),
-        params.div24t_beat20.modulated_plain_value(),
-        params.div24t_beat21.modulated_plain_value(),
-        params.div24t_beat22.modulated_plain_value(),
-        params.div24t_beat23.modulated_plain_value(),
-        params.div24t_beat24.modulated_plain_value(),
+        heard.plain(&params.div24t_beat1),
+        heard.plain(&params.div24t_beat2),
+        heard.plain(&params.div24t_beat3),
+        heard.plain(&params.div24t_beat4),
+        heard.plain(&params.div24t_beat5),
+        heard.plain(&params.div24t_beat6),
+        heard.plain(&params.div24t_beat7),
+        heard.plain(&params.div24t_beat8),
+        heard.plain(&params.div24t_beat9),
+        heard.plain(&params.div24t_beat10),
+        heard.plain(&params.div24t_beat11),
+        heard.plain(&params.div24t_beat12),
+        heard.plain(&params.div24t_beat13),
+        heard.plain(&params.div24t_beat14),
+        heard.plain(&params.div24t_beat15),
+        heard.plain(&params.div24t_beat16),
+        heard.plain(&params.div24t_beat17),
+        heard.plain(&params.div24t_beat18),
+        heard.plain(&params.div24t_beat19),
+        heard.plain(&params.div24t_beat20),
+        heard.plain(&params.div24t_beat21),
+        heard.plain(&params.div24t_beat22),
+        heard.plain(&params.div24t_beat23),
+        heard.plain(&params.div24t_beat24),
     ];
 
     data.dotted_1_2d = [
-        params.div2d_beat1.modulated_plain_value(),
-        params.div2d_beat2.modulated_plain_value(),
+        heard.plain(&params.div2d_beat1),
+        heard.plain(&params.div2d_beat2),
     ];
 
     data.dotted_1_4d = [
-        params.div3d_beat1.modulated_plain_value(),
-        params.div3d_beat2.modulated_plain_value(),
-        params.div3d_beat3.modulated_plain_value(),
+        heard.plain(&params.div3d_beat1),
+        heard.plain(&params.div3d_beat2),
+        heard.plain(&params.div3d_beat3),
     ];
 
     data.dotted_1_8d = [
-        params.div6d_beat1.modulated_plain_value(),
-        params.div6d_beat2.modulated_plain_value(),
-        params.div6d_beat3.modulated_plain_value(),
-        params.div6d_beat4.modulated_plain_value(),
-        params.div6d_beat5.modulated_plain_value(),
-        params.div6d_beat6.modulated_plain_value(),
+        heard.plain(&params.div6d_beat1),
+        heard.plain(&params.div6d_beat2),
+        heard.plain(&params.div6d_beat3),
+        heard.plain(&params.div6d_beat4),
+        heard.plain(&params.div6d_beat5),
+        heard.plain(&params.div6d_beat6),
     ];
 
     data.dotted_1_16d = [
-        params.div11d_beat1.modulated_plain_value(),
-        params.div11d_beat2.modulated_plain_value(),
-        params.div11d_beat3.modulated_plain_value(),
-        params.div11d_beat4.modulated_plain_value(),
-        params.div11d_beat5.modulated_plain_value(),
-        params.div11d_beat6.modulated_plain_value(),
-        params.div11d_beat7.modulated_plain_value(),
-        params.div11d_beat8.modulated_plain_value(),
-        params.div11d_beat9.modulated_plain_value(),
-        params.div11d_beat10.modulated_plain_value(),
-        params.div11d_beat11.modulated_plain_value(),
+        heard.plain(&params.div11d_beat1),
+        heard.plain(&params.div11d_beat2),
+        heard.plain(&params.div11d_beat3),
+        heard.plain(&params.div11d_beat4),
+        heard.plain(&params.div11d_beat5),
+        heard.plain(&params.div11d_beat6),
+        heard.plain(&params.div11d_beat7),
+        heard.plain(&params.div11d_beat8),
+        heard.plain(&params.div11d_beat9),
+        heard.plain(&params.div11d_beat10),
+        heard.plain(&params.div11d_beat11),
     ];
 
     data.dotted_1_32d = [
-        params.div22d_beat1.modulated_plain_value(),
-        params.div22d_beat2.modulated_plain_value(),
-        params.div22d_beat3.modulated_plain_value(),
-        params.div22d_beat4.modulated_plain_value(),
-        params.div22d_beat5.modulated_plain_value(),
-        params.div22d_beat6.modulated_plain_value(),
-        params.div22d_beat7.modulated_plain_value(),
-        params.div22d_beat8.modulated_plain_value(),
-        params.div22d_beat9.modulated_plain_value(),
-        params.div22d_beat10.modulated_plain_value(),
-        params.div22d_beat11.modulated_plain_value(),
-        params.div22d_beat12.modulated_plain_value(),
-        params.div22d_beat13.modulated_plain_value(),
-        params.div22d_beat14.modulated_plain_value(),
-        params.div22d_beat15.modulated_plain_value(),
-        params.div22d_beat16.modulated_plain_value(),
-        params.div22d_beat17.modulated_plain_value(),
-        params.div22d_beat18.modulated_plain_value(),
-        params.div22d_beat19.modulated_plain_value(),
-        params.div22d_beat20.modulated_plain_value(),
-        params.div22d_beat21.modulated_plain_value(),
-        params.div22d_beat22.modulated_plain_value(),
+        heard.plain(&params.div22d_beat1),
+        heard.plain(&params.div22d_beat2),
+        heard.plain(&params.div22d_beat3),
+        heard.plain(&params.div22d_beat4),
+        heard.plain(&params.div22d_beat5),
+        heard.plain(&params.div22d_beat6),
+        heard.plain(&params.div22d_beat7),
+        heard.plain(&params.div22d_beat8),
+        heard.plain(&params.div22d_beat9),
+        heard.plain(&params.div22d_beat10),
+        heard.plain(&params.div22d_beat11),
+        heard.plain(&params.div22d_beat12),
+        heard.plain(&params.div22d_beat13),
+        heard.plain(&params.div22d_beat14),
+        heard.plain(&params.div22d_beat15),
+        heard.plain(&params.div22d_beat16),
+        heard.plain(&params.div22d_beat17),
+        heard.plain(&params.div22d_beat18),
+        heard.plain(&params.div22d_beat19),
+        heard.plain(&params.div22d_beat20),
+        heard.plain(&params.div22d_beat21),
+        heard.plain(&params.div22d_beat22),
     ];
 
-    data.synth_pll_track_speed = params.synth_pll_track_speed.modulated_plain_value();
-    data.synth_pll_damping = params.synth_pll_damping.modulated_plain_value();
-    data.synth_pll_influence = params.synth_pll_influence.modulated_plain_value();
+    data.synth_pll_track_speed = heard.plain(&params.synth_pll_track_speed);
+    data.synth_pll_damping = heard.plain(&params.synth_pll_damping);
+    data.synth_pll_influence = heard.plain(&params.synth_pll_influence);
     data.synth_pll_mult = params.synth_pll_mult.value();
     data.synth_pll_colored = params.synth_pll_colored.value();
     data.synth_pll_mode = params.synth_pll_mode.value();
     data.synth_pll_ref_octave = params.synth_pll_ref_octave.value();
     data.synth_pll_ref_tune = params.synth_pll_ref_tune.value();
-    data.synth_pll_ref_fine = params.synth_pll_ref_fine.modulated_plain_value();
-    data.synth_pll_ref_pulse_width = params.synth_pll_ref_pulse_width.modulated_plain_value();
-    data.synth_pll_feedback = params.synth_pll_feedback.modulated_plain_value();
-    data.synth_pll_volume = params.synth_pll_volume.modulated_plain_value();
-    data.synth_pll_stereo_damp_offset = params.synth_pll_stereo_damp_offset.modulated_plain_value();
-    data.synth_pll_glide = params.synth_pll_glide.modulated_plain_value();
-    data.synth_pll_fm_amount = params.synth_pll_fm_amount.modulated_plain_value();
+    data.synth_pll_ref_fine = heard.plain(&params.synth_pll_ref_fine);
+    data.synth_pll_ref_pulse_width = heard.plain(&params.synth_pll_ref_pulse_width);
+    data.synth_pll_feedback = heard.plain(&params.synth_pll_feedback);
+    data.synth_pll_volume = heard.plain(&params.synth_pll_volume);
+    data.synth_pll_stereo_damp_offset = heard.plain(&params.synth_pll_stereo_damp_offset);
+    data.synth_pll_glide = heard.plain(&params.synth_pll_glide);
+    data.synth_pll_fm_amount = heard.plain(&params.synth_pll_fm_amount);
     data.synth_pll_fm_ratio = params.synth_pll_fm_ratio.value();
     data.synth_pll_fm_ratio_free = params.synth_pll_fm_ratio_free.value();
-    data.synth_pll_fm_ratio_float = params.synth_pll_fm_ratio_float.modulated_plain_value();
+    data.synth_pll_fm_ratio_float = heard.plain(&params.synth_pll_fm_ratio_float);
     data.synth_pll_fm_expand = params.synth_pll_fm_expand.value();
-    data.synth_pll_retrigger = params.synth_pll_retrigger.modulated_plain_value();
-    data.synth_pll_burst_threshold = params.synth_pll_burst_threshold.modulated_plain_value();
-    data.synth_pll_burst_amount = params.synth_pll_burst_amount.modulated_plain_value();
-    data.synth_pll_loop_saturation = params.synth_pll_loop_saturation.modulated_plain_value();
-    data.synth_pll_color_amount = params.synth_pll_color_amount.modulated_plain_value();
-    data.synth_pll_edge_sensitivity = params.synth_pll_edge_sensitivity.modulated_plain_value();
-    data.synth_pll_range = params.synth_pll_range.modulated_plain_value();
-    data.synth_pll_stereo_track_offset = params.synth_pll_stereo_track_offset.modulated_plain_value();
-    data.synth_pll_stereo_phase = params.synth_pll_stereo_phase.modulated_plain_value();
-    data.synth_pll_fm_env_amount = params.synth_pll_fm_env_amount.modulated_plain_value();
+    data.synth_pll_retrigger = heard.plain(&params.synth_pll_retrigger);
+    data.synth_pll_burst_threshold = heard.plain(&params.synth_pll_burst_threshold);
+    data.synth_pll_burst_amount = heard.plain(&params.synth_pll_burst_amount);
+    data.synth_pll_loop_saturation = heard.plain(&params.synth_pll_loop_saturation);
+    data.synth_pll_color_amount = heard.plain(&params.synth_pll_color_amount);
+    data.synth_pll_edge_sensitivity = heard.plain(&params.synth_pll_edge_sensitivity);
+    data.synth_pll_range = heard.plain(&params.synth_pll_range);
+    data.synth_pll_stereo_track_offset = heard.plain(&params.synth_pll_stereo_track_offset);
+    data.synth_pll_stereo_phase = heard.plain(&params.synth_pll_stereo_phase);
+    data.synth_pll_fm_env_amount = heard.plain(&params.synth_pll_fm_env_amount);
     data.synth_pll_precision = params.synth_pll_precision.value();
     data.synth_pll_enable = params.synth_pll_enable.value();
-    data.synth_pll_mult_slew_time = params.synth_pll_mult_slew_time.modulated_plain_value();
+    data.synth_pll_mult_slew_time = heard.plain(&params.synth_pll_mult_slew_time);
     data.synth_pll_anti_alias = params.synth_pll_anti_alias.value();
-    data.synth_pll_injection_amount = params.synth_pll_injection_amount.modulated_plain_value();
+    data.synth_pll_injection_amount = heard.plain(&params.synth_pll_injection_amount);
     data.synth_pll_injection_x4 = params.synth_pll_injection_x4.value();
     data.synth_pll_feedback_div = params.synth_pll_feedback_div.value();
 
     data.synth_osc_octave = params.synth_osc_octave.value();
     data.synth_osc_tune = params.synth_osc_tune.value();
-    data.synth_osc_fine = params.synth_osc_fine.modulated_plain_value();
-    data.synth_osc_fold = params.synth_osc_fold.modulated_plain_value();
-    data.synth_osc_d = params.synth_osc_d.modulated_plain_value();
-    data.synth_osc_v = params.synth_osc_v.modulated_plain_value();
-    data.synth_osc_stereo_v_offset = params.synth_osc_stereo_v_offset.modulated_plain_value();
-    data.synth_osc_stereo_d_offset = params.synth_osc_stereo_d_offset.modulated_plain_value();
+    data.synth_osc_fine = heard.plain(&params.synth_osc_fine);
+    data.synth_osc_fold = heard.plain(&params.synth_osc_fold);
+    data.synth_osc_d = heard.plain(&params.synth_osc_d);
+    data.synth_osc_v = heard.plain(&params.synth_osc_v);
+    data.synth_osc_stereo_v_offset = heard.plain(&params.synth_osc_stereo_v_offset);
+    data.synth_osc_stereo_d_offset = heard.plain(&params.synth_osc_stereo_d_offset);
     data.synth_vps_shape_type = params.synth_vps_shape_type.value();
-    data.synth_vps_shape_amount = params.synth_vps_shape_amount.modulated_plain_value();
+    data.synth_vps_shape_amount = heard.plain(&params.synth_vps_shape_amount);
     data.synth_vps_fold_range = params.synth_vps_fold_range.value();
     data.synth_vps_formant = params.synth_vps_formant.value();
-    data.synth_osc_volume = params.synth_osc_volume.modulated_plain_value();
+    data.synth_osc_volume = heard.plain(&params.synth_osc_volume);
 
-    data.synth_sub_volume = params.synth_sub_volume.modulated_plain_value();
+    data.synth_sub_volume = heard.plain(&params.synth_sub_volume);
     data.synth_sub_filter_route = params.synth_sub_filter_route.value();
 
     data.synth_saw_enable = params.synth_saw_enable.value();
-    data.synth_saw_volume = params.synth_saw_volume.modulated_plain_value();
+    data.synth_saw_volume = heard.plain(&params.synth_saw_volume);
     data.synth_saw_octave = params.synth_saw_octave.value();
     data.synth_saw_tune = params.synth_saw_tune.value();
-    data.synth_saw_fine = params.synth_saw_fine.modulated_plain_value();
-    data.synth_saw_fold = params.synth_saw_fold.modulated_plain_value();
+    data.synth_saw_fine = heard.plain(&params.synth_saw_fine);
+    data.synth_saw_fold = heard.plain(&params.synth_saw_fold);
     data.synth_saw_fold_range = params.synth_saw_fold_range.value();
-    data.synth_saw_tight = params.synth_saw_tight.modulated_plain_value();
+    data.synth_saw_tight = heard.plain(&params.synth_saw_tight);
     data.synth_saw_shape_type = params.synth_saw_shape_type.value();
-    data.synth_saw_shape_amount = params.synth_saw_shape_amount.modulated_plain_value();
+    data.synth_saw_shape_amount = heard.plain(&params.synth_saw_shape_amount);
+
+    data.wavetable_name = ui_state.wavetable_name();
+    data.synth_wt_volume = heard.plain(&params.synth_wt_volume);
+    data.synth_wt_octave = params.synth_wt_octave.value();
+    data.synth_wt_tune = params.synth_wt_tune.value();
+    data.synth_wt_fine = heard.plain(&params.synth_wt_fine);
+    data.synth_wt_position = heard.plain(&params.synth_wt_position);
+
+    data.synth_noise_level = heard.plain(&params.synth_noise_level);
+    data.synth_noise_type = params.synth_noise_type.value();
+    data.synth_noise_rate = heard.plain(&params.synth_noise_rate);
+    data.synth_noise_lpf = heard.plain(&params.synth_noise_lpf);
+    data.synth_noise_hpf = heard.plain(&params.synth_noise_hpf);
+    data.synth_noise_env = params.synth_noise_env.value();
+    data.synth_noise_inject = params.synth_noise_inject.value();
+    data.synth_noise_inject_amount = heard.plain(&params.synth_noise_inject_amount);
+
+    data.synth_sync_source = params.synth_sync_source.value();
+    data.synth_vps_sync = params.synth_vps_sync.value();
+    data.synth_saw_sync = params.synth_saw_sync.value();
+    data.synth_pll_sync = params.synth_pll_sync.value();
+    data.synth_sync_soft = heard.plain(&params.synth_sync_soft);
+
+    data.synth_pll_topology = params.synth_pll_topology.value();
+    data.synth_pll2_track_speed = heard.plain(&params.synth_pll2_track_speed);
+    data.synth_pll2_damping = heard.plain(&params.synth_pll2_damping);
+    data.synth_pll2_mult = params.synth_pll2_mult.value();
+    data.synth_pll_coupling = heard.plain(&params.synth_pll_coupling);
+    data.synth_pll_ref_source = params.synth_pll_ref_source.value();
+    data.synth_pll_ref_mix = heard.plain(&params.synth_pll_ref_mix);
+
+    data.synth_ring_pll_enable = params.synth_ring_pll_enable.value();
+    data.synth_ring_pll = heard.plain(&params.synth_ring_pll);
+    data.synth_ring_vps_saw_enable = params.synth_ring_vps_saw_enable.value();
+    data.synth_ring_vps_saw = heard.plain(&params.synth_ring_vps_saw);
+    data.synth_xfm_vps_saw_enable = params.synth_xfm_vps_saw_enable.value();
+    data.synth_xfm_vps_saw = heard.plain(&params.synth_xfm_vps_saw);
+    data.synth_xfm_saw_ref_enable = params.synth_xfm_saw_ref_enable.value();
+    data.synth_xfm_saw_ref = heard.plain(&params.synth_xfm_saw_ref);
 
     data.synth_filter_enable = params.synth_filter_enable.value();
-    data.synth_filter_cutoff = params.synth_filter_cutoff.modulated_plain_value();
-    data.synth_filter_resonance = params.synth_filter_resonance.modulated_plain_value();
-    data.synth_filter_drive = params.synth_filter_drive.modulated_plain_value();
+    data.synth_filter_cutoff = heard.plain(&params.synth_filter_cutoff);
+    data.synth_filter_resonance = heard.plain(&params.synth_filter_resonance);
+    data.synth_filter_drive = heard.plain(&params.synth_filter_drive);
     data.synth_filter_mode = params.synth_filter_mode.value();
-    data.synth_filter_key_track = params.synth_filter_key_track.modulated_plain_value();
-    data.synth_filter_env_amount = params.synth_filter_env_amount.modulated_plain_value();
-    data.synth_filter_stereo_sep = params.synth_filter_stereo_sep.modulated_plain_value();
-    data.synth_filter_env_attack = params.synth_filter_env_attack.modulated_plain_value();
-    data.synth_filter_env_attack_shape = params.synth_filter_env_attack_shape.modulated_plain_value();
-    data.synth_filter_env_decay = params.synth_filter_env_decay.modulated_plain_value();
-    data.synth_filter_env_decay_shape = params.synth_filter_env_decay_shape.modulated_plain_value();
-    data.synth_filter_env_sustain = params.synth_filter_env_sustain.modulated_plain_value();
-    data.synth_filter_env_release = params.synth_filter_env_release.modulated_plain_value();
-    data.synth_filter_env_release_shape = params.synth_filter_env_release_shape.modulated_plain_value();
-    data.synth_filter_env_dip = params.synth_filter_env_dip.modulated_plain_value();
-    data.synth_filter_env_range = params.synth_filter_env_range.modulated_plain_value();
-    data.synth_filter_env_hold = params.synth_filter_env_hold.modulated_plain_value();
+    data.synth_filter_key_track = heard.plain(&params.synth_filter_key_track);
+    data.synth_filter_env_amount = heard.plain(&params.synth_filter_env_amount);
+    data.synth_filter_stereo_sep = heard.plain(&params.synth_filter_stereo_sep);
+    data.synth_filter_env_attack = heard.plain(&params.synth_filter_env_attack);
+    data.synth_filter_env_attack_shape = heard.plain(&params.synth_filter_env_attack_shape);
+    data.synth_filter_env_decay = heard.plain(&params.synth_filter_env_decay);
+    data.synth_filter_env_decay_shape = heard.plain(&params.synth_filter_env_decay_shape);
+    data.synth_filter_env_sustain = heard.plain(&params.synth_filter_env_sustain);
+    data.synth_filter_env_release = heard.plain(&params.synth_filter_env_release);
+    data.synth_filter_env_release_shape = heard.plain(&params.synth_filter_env_release_shape);
+    data.synth_filter_env_dip = heard.plain(&params.synth_filter_env_dip);
+    data.synth_filter_env_range = heard.plain(&params.synth_filter_env_range);
+    data.synth_filter_env_hold = heard.plain(&params.synth_filter_env_hold);
     data.synth_filter_env_loop_mode = params.synth_filter_env_loop_mode.value();
     data.synth_filter_env_attack_s = params.synth_filter_env_attack_s.value();
     data.synth_filter_env_decay_s = params.synth_filter_env_decay_s.value();
@@ -1371,33 +1680,50 @@ fn save_params_to_preset_data(
     data.synth_filter_env_release_div = params.synth_filter_env_release_div.value();
     data.synth_filter_drive_boost = params.synth_filter_drive_boost.value();
     data.synth_filter_sat_type = params.synth_filter_sat_type.value();
-    data.synth_filter_morph = params.synth_filter_morph.modulated_plain_value();
-    data.synth_filter_fm = params.synth_filter_fm.modulated_plain_value();
-    data.synth_filter_feedback = params.synth_filter_feedback.modulated_plain_value();
-    data.synth_filter_bass_lock = params.synth_filter_bass_lock.modulated_plain_value();
-    data.synth_filter_pole_spread = params.synth_filter_pole_spread.modulated_plain_value();
-    data.synth_filter_res_character = params.synth_filter_res_character.modulated_plain_value();
-    data.synth_filter_res_tilt = params.synth_filter_res_tilt.modulated_plain_value();
-    data.synth_filter_cutoff_slew = params.synth_filter_cutoff_slew.modulated_plain_value();
+    data.synth_filter_morph = heard.plain(&params.synth_filter_morph);
+    data.synth_filter_fm = heard.plain(&params.synth_filter_fm);
+    data.synth_filter_feedback = heard.plain(&params.synth_filter_feedback);
+    data.synth_filter_bass_lock = heard.plain(&params.synth_filter_bass_lock);
+    data.synth_filter_pole_spread = heard.plain(&params.synth_filter_pole_spread);
+    data.synth_filter_res_character = heard.plain(&params.synth_filter_res_character);
+    data.synth_filter_res_tilt = heard.plain(&params.synth_filter_res_tilt);
+    data.synth_filter_cutoff_slew = heard.plain(&params.synth_filter_cutoff_slew);
     data.synth_filter_poles = params.synth_filter_poles.value();
 
-    data.synth_vol_attack = params.synth_vol_attack.modulated_plain_value();
-    data.synth_vol_attack_shape = params.synth_vol_attack_shape.modulated_plain_value();
-    data.synth_vol_decay = params.synth_vol_decay.modulated_plain_value();
-    data.synth_vol_decay_shape = params.synth_vol_decay_shape.modulated_plain_value();
-    data.synth_vol_sustain = params.synth_vol_sustain.modulated_plain_value();
-    data.synth_vol_release = params.synth_vol_release.modulated_plain_value();
-    data.synth_vol_release_shape = params.synth_vol_release_shape.modulated_plain_value();
+    data.synth_filter2_enable = params.synth_filter2_enable.value();
+    data.synth_filter2_type = params.synth_filter2_type.value();
+    data.synth_filter2_cutoff = heard.plain(&params.synth_filter2_cutoff);
+    data.synth_filter2_resonance = heard.plain(&params.synth_filter2_resonance);
+    data.synth_filter2_morph = heard.plain(&params.synth_filter2_morph);
+    data.synth_filter2_key_track = heard.plain(&params.synth_filter2_key_track);
+    data.synth_filter2_env_amount = heard.plain(&params.synth_filter2_env_amount);
+    data.synth_filter_routing = params.synth_filter_routing.value();
+
+    data.synth_resonator_enable = params.synth_resonator_enable.value();
+    data.synth_resonator_model = params.synth_resonator_model.value();
+    data.synth_resonator_excite = params.synth_resonator_excite.value();
+    data.synth_resonator_decay = heard.plain(&params.synth_resonator_decay);
+    data.synth_resonator_brightness = heard.plain(&params.synth_resonator_brightness);
+    data.synth_resonator_position = heard.plain(&params.synth_resonator_position);
+    data.synth_resonator_mix = heard.plain(&params.synth_resonator_mix);
+
+    data.synth_vol_attack = heard.plain(&params.synth_vol_attack);
+    data.synth_vol_attack_shape = heard.plain(&params.synth_vol_attack_shape);
+    data.synth_vol_decay = heard.plain(&params.synth_vol_decay);
+    data.synth_vol_decay_shape = heard.plain(&params.synth_vol_decay_shape);
+    data.synth_vol_sustain = heard.plain(&params.synth_vol_sustain);
+    data.synth_vol_release = heard.plain(&params.synth_vol_release);
+    data.synth_vol_release_shape = heard.plain(&params.synth_vol_release_shape);
     data.synth_vol_attack_s = params.synth_vol_attack_s.value();
     data.synth_vol_decay_s = params.synth_vol_decay_s.value();
     data.synth_vol_release_s = params.synth_vol_release_s.value();
-    data.synth_vol_hold = params.synth_vol_hold.modulated_plain_value();
-    data.synth_vol_depth = params.synth_vol_depth.modulated_plain_value();
+    data.synth_vol_hold = heard.plain(&params.synth_vol_hold);
+    data.synth_vol_depth = heard.plain(&params.synth_vol_depth);
     data.synth_vol_loop_mode = params.synth_vol_loop_mode.value();
-    data.synth_env_key_track = params.synth_env_key_track.modulated_plain_value();
-    data.synth_env_vel_to_attack = params.synth_env_vel_to_attack.modulated_plain_value();
-    data.synth_env_vel_to_decay = params.synth_env_vel_to_decay.modulated_plain_value();
-    data.synth_env_vel_to_sustain = params.synth_env_vel_to_sustain.modulated_plain_value();
+    data.synth_env_key_track = heard.plain(&params.synth_env_key_track);
+    data.synth_env_vel_to_attack = heard.plain(&params.synth_env_vel_to_attack);
+    data.synth_env_vel_to_decay = heard.plain(&params.synth_env_vel_to_decay);
+    data.synth_env_vel_to_sustain = heard.plain(&params.synth_env_vel_to_sustain);
     data.synth_vol_attack_sync = params.synth_vol_attack_sync.value();
     data.synth_vol_attack_div = params.synth_vol_attack_div.value();
     data.synth_vol_hold_sync = params.synth_vol_hold_sync.value();
@@ -1406,141 +1732,227 @@ fn save_params_to_preset_data(
     data.synth_vol_decay_div = params.synth_vol_decay_div.value();
     data.synth_vol_release_sync = params.synth_vol_release_sync.value();
     data.synth_vol_release_div = params.synth_vol_release_div.value();
-    data.synth_retrigger_dip = params.synth_retrigger_dip.modulated_plain_value();
-    data.synth_env_range = params.synth_env_range.modulated_plain_value();
-    data.synth_pll_tail_time = params.synth_pll_tail_time.modulated_plain_value();
-    data.synth_pll_tail_amount = params.synth_pll_tail_amount.modulated_plain_value();
-
-    data.synth_reverb_mix = params.synth_reverb_mix.modulated_plain_value();
-    data.synth_reverb_time_scale = params.synth_reverb_time_scale.modulated_plain_value();
-    data.synth_reverb_decay = params.synth_reverb_decay.modulated_plain_value();
-    data.synth_reverb_diffusion = params.synth_reverb_diffusion.modulated_plain_value();
-    data.synth_reverb_pre_delay = params.synth_reverb_pre_delay.modulated_plain_value();
-    data.synth_reverb_mod_depth = params.synth_reverb_mod_depth.modulated_plain_value();
-    data.synth_reverb_hpf = params.synth_reverb_hpf.modulated_plain_value();
-    data.synth_reverb_lpf = params.synth_reverb_lpf.modulated_plain_value();
-    data.synth_reverb_ducking = params.synth_reverb_ducking.modulated_plain_value();
-    data.synth_reverb_input_hpf = params.synth_reverb_input_hpf.modulated_plain_value();
-    data.synth_reverb_input_lpf = params.synth_reverb_input_lpf.modulated_plain_value();
-    data.synth_reverb_mod_shape = params.synth_reverb_mod_shape.modulated_plain_value();
-    data.synth_reverb_stereo_width = params.synth_reverb_stereo_width.modulated_plain_value();
-    data.synth_reverb_saturation = params.synth_reverb_saturation.modulated_plain_value();
-    data.synth_reverb_rhythm_duck_depth = params.synth_reverb_rhythm_duck_depth.modulated_plain_value();
+    data.synth_retrigger_dip = heard.plain(&params.synth_retrigger_dip);
+    data.synth_env_range = heard.plain(&params.synth_env_range);
+    data.synth_pll_tail_time = heard.plain(&params.synth_pll_tail_time);
+    data.synth_pll_tail_amount = heard.plain(&params.synth_pll_tail_amount);
+
+    data.synth_reverb_mix = heard.plain(&params.synth_reverb_mix);
+    data.synth_reverb_time_scale = heard.plain(&params.synth_reverb_time_scale);
+    data.synth_reverb_decay = heard.plain(&params.synth_reverb_decay);
+    data.synth_reverb_diffusion = heard.plain(&params.synth_reverb_diffusion);
+    data.synth_reverb_pre_delay = heard.plain(&params.synth_reverb_pre_delay);
+    data.synth_reverb_mod_depth = heard.plain(&params.synth_reverb_mod_depth);
+    data.synth_reverb_hpf = heard.plain(&params.synth_reverb_hpf);
+    data.synth_reverb_lpf = heard.plain(&params.synth_reverb_lpf);
+    data.synth_reverb_ducking = heard.plain(&params.synth_reverb_ducking);
+    data.synth_reverb_input_hpf = heard.plain(&params.synth_reverb_input_hpf);
+    data.synth_reverb_input_lpf = heard.plain(&params.synth_reverb_input_lpf);
+    data.synth_reverb_mod_shape = heard.plain(&params.synth_reverb_mod_shape);
+    data.synth_reverb_stereo_width = heard.plain(&params.synth_reverb_stereo_width);
+    data.synth_reverb_saturation = heard.plain(&params.synth_reverb_saturation);
+    data.synth_reverb_rhythm_duck_depth = heard.plain(&params.synth_reverb_rhythm_duck_depth);
     data.synth_reverb_rhythm_duck_division = params.synth_reverb_rhythm_duck_division.value();
-    data.synth_reverb_rhythm_duck_smooth = params.synth_reverb_rhythm_duck_smooth.modulated_plain_value();
-
-    data.lfo1_rate = params.lfo1_rate.modulated_plain_value();
+    data.synth_reverb_rhythm_duck_smooth = heard.plain(&params.synth_reverb_rhythm_duck_smooth);
+
+    data.synth_delay_enable = params.synth_delay_enable.value();
+    data.synth_delay_mode = params.synth_delay_mode.value();
+    data.synth_delay_sync = params.synth_delay_sync.value();
+    data.synth_delay_division = params.synth_delay_division.value();
+    data.synth_delay_time = heard.plain(&params.synth_delay_time);
+    data.synth_delay_mix = heard.plain(&params.synth_delay_mix);
+    data.synth_delay_feedback = heard.plain(&params.synth_delay_feedback);
+    data.synth_delay_spread = heard.plain(&params.synth_delay_spread);
+    data.synth_delay_lpf = heard.plain(&params.synth_delay_lpf);
+    data.synth_delay_hpf = heard.plain(&params.synth_delay_hpf);
+    data.synth_delay_saturation = heard.plain(&params.synth_delay_saturation);
+    data.synth_delay_mod_rate = heard.plain(&params.synth_delay_mod_rate);
+    data.synth_delay_mod_depth = heard.plain(&params.synth_delay_mod_depth);
+    data.synth_delay_ducking = heard.plain(&params.synth_delay_ducking);
+    data.synth_delay_send_vps = params.synth_delay_send_vps.value();
+    data.synth_delay_send_pll = params.synth_delay_send_pll.value();
+    data.synth_delay_send_saw = params.synth_delay_send_saw.value();
+    data.synth_delay_send_wt = params.synth_delay_send_wt.value();
+    data.synth_delay_send_sub = params.synth_delay_send_sub.value();
+    data.synth_delay_send_filter = params.synth_delay_send_filter.value();
+
+    data.mod_fx_enable = params.mod_fx_enable.value();
+    data.mod_fx_mode = params.mod_fx_mode.value();
+    data.mod_fx_position = params.mod_fx_position.value();
+    data.mod_fx_rate = heard.plain(&params.mod_fx_rate);
+    data.mod_fx_sync = params.mod_fx_sync.value();
+    data.mod_fx_division = params.mod_fx_division.value();
+    data.mod_fx_depth = heard.plain(&params.mod_fx_depth);
+    data.mod_fx_feedback = heard.plain(&params.mod_fx_feedback);
+    data.mod_fx_mix = heard.plain(&params.mod_fx_mix);
+    data.mod_fx_voices = params.mod_fx_voices.value();
+    data.mod_fx_stages = params.mod_fx_stages.value();
+    data.mod_fx_spread = heard.plain(&params.mod_fx_spread);
+
+    data.fx_chain = params.fx_order().iter().map(|m| m.to_index()).collect();
+    data.fx_send_looper = params.fx_send_looper.value();
+    data.fx_send_delay = params.fx_send_delay.value();
+    data.fx_send_reverb = params.fx_send_reverb.value();
+
+    data.lofi_enable = params.lofi_enable.value();
+    data.lofi_post_filter = params.lofi_post_filter.value();
+    data.lofi_vps = params.lofi_vps.value();
+    data.lofi_pll = params.lofi_pll.value();
+    data.lofi_saw = params.lofi_saw.value();
+    data.lofi_shaper = params.lofi_shaper.value();
+    data.lofi_bits = heard.plain(&params.lofi_bits);
+    data.lofi_rate = heard.plain(&params.lofi_rate);
+    data.lofi_anti_alias = params.lofi_anti_alias.value();
+    data.lofi_jitter = heard.plain(&params.lofi_jitter);
+    data.lofi_drive = heard.plain(&params.lofi_drive);
+    data.lofi_mix = heard.plain(&params.lofi_mix);
+
+    data.lfo1_rate = heard.plain(&params.lfo1_rate);
     data.lfo1_waveform = params.lfo1_waveform.value();
     data.lfo1_tempo_sync = params.lfo1_tempo_sync.value();
     data.lfo1_sync_division = params.lfo1_sync_division.value();
     data.lfo1_sync_source = params.lfo1_sync_source.value();
-    data.lfo1_phase_mod = params.lfo1_phase_mod.modulated_plain_value();
+    data.lfo1_phase_mod = heard.plain(&params.lfo1_phase_mod);
     data.lfo1_dest1 = params.lfo1_dest1.value();
-    data.lfo1_amount1 = params.lfo1_amount1.modulated_plain_value();
+    data.lfo1_amount1 = heard.plain(&params.lfo1_amount1);
     data.lfo1_dest2 = params.lfo1_dest2.value();
-    data.lfo1_amount2 = params.lfo1_amount2.modulated_plain_value();
+    data.lfo1_amount2 = heard.plain(&params.lfo1_amount2);
 
-    data.lfo2_rate = params.lfo2_rate.modulated_plain_value();
+    data.lfo2_rate = heard.plain(&params.lfo2_rate);
     data.lfo2_waveform = params.lfo2_waveform.value();
     data.lfo2_tempo_sync = params.lfo2_tempo_sync.value();
     data.lfo2_sync_division = params.lfo2_sync_division.value();
     data.lfo2_sync_source = params.lfo2_sync_source.value();
-    data.lfo2_phase_mod = params.lfo2_phase_mod.modulated_plain_value();
+    data.lfo2_phase_mod = heard.plain(&params.lfo2_phase_mod);
     data.lfo2_dest1 = params.lfo2_dest1.value();
-    data.lfo2_amount1 = params.lfo2_amount1.modulated_plain_value();
+    data.lfo2_amount1 = heard.plain(&params.lfo2_amount1);
     data.lfo2_dest2 = params.lfo2_dest2.value();
-    data.lfo2_amount2 = params.lfo2_amount2.modulated_plain_value();
+    data.lfo2_amount2 = heard.plain(&params.lfo2_amount2);
 
-    data.lfo3_rate = params.lfo3_rate.modulated_plain_value();
+    data.lfo3_rate = heard.plain(&params.lfo3_rate);
     data.lfo3_waveform = params.lfo3_waveform.value();
     data.lfo3_tempo_sync = params.lfo3_tempo_sync.value();
     data.lfo3_sync_division = params.lfo3_sync_division.value();
     data.lfo3_sync_source = params.lfo3_sync_source.value();
-    data.lfo3_phase_mod = params.lfo3_phase_mod.modulated_plain_value();
+    data.lfo3_phase_mod = heard.plain(&params.lfo3_phase_mod);
     data.lfo3_dest1 = params.lfo3_dest1.value();
-    data.lfo3_amount1 = params.lfo3_amount1.modulated_plain_value();
+    data.lfo3_amount1 = heard.plain(&params.lfo3_amount1);
     data.lfo3_dest2 = params.lfo3_dest2.value();
-    data.lfo3_amount2 = params.lfo3_amount2.modulated_plain_value();
+    data.lfo3_amount2 = heard.plain(&params.lfo3_amount2);
 
-    data.swing_amount = params.swing_amount.modulated_plain_value();
-    data.note_length_percent = params.note_length_percent.modulated_plain_value();
+    data.swing_amount = heard.plain(&params.swing_amount);
+    data.note_length_percent = heard.plain(&params.note_length_percent);
 
     data.legato_mode = params.legato_mode.value();
-    data.legato_time = params.legato_time.modulated_plain_value();
+    data.legato_time = heard.plain(&params.legato_time);
     data.legato_velocity_lock = params.legato_velocity_lock.value();
     data.vca_mode = params.vca_mode.value();
     data.note_priority = params.note_priority.value();
 
-    data.len_mod_1_target = params.len_mod_1_target.modulated_plain_value();
-    data.len_mod_1_amount = params.len_mod_1_amount.modulated_plain_value();
-    data.len_mod_1_prob = params.len_mod_1_prob.modulated_plain_value();
+    data.len_mod_1_target = heard.plain(&params.len_mod_1_target);
+    data.len_mod_1_amount = heard.plain(&params.len_mod_1_amount);
+    data.len_mod_1_prob = heard.plain(&params.len_mod_1_prob);
 
-    data.len_mod_2_target = params.len_mod_2_target.modulated_plain_value();
-    data.len_mod_2_amount = params.len_mod_2_amount.modulated_plain_value();
-    data.len_mod_2_prob = params.len_mod_2_prob.modulated_plain_value();
+    data.len_mod_2_target = heard.plain(&params.len_mod_2_target);
+    data.len_mod_2_amount = heard.plain(&params.len_mod_2_amount);
+    data.len_mod_2_prob = heard.plain(&params.len_mod_2_prob);
 
-    data.vel_strength_target = params.vel_strength_target.modulated_plain_value();
-    data.vel_strength_amount = params.vel_strength_amount.modulated_plain_value();
-    data.vel_strength_prob = params.vel_strength_prob.modulated_plain_value();
-    data.vel_length_target = params.vel_length_target.modulated_plain_value();
-    data.vel_length_amount = params.vel_length_amount.modulated_plain_value();
-    data.vel_length_prob = params.vel_length_prob.modulated_plain_value();
+    data.vel_strength_target = heard.plain(&params.vel_strength_target);
+    data.vel_strength_amount = heard.plain(&params.vel_strength_amount);
+    data.vel_strength_prob = heard.plain(&params.vel_strength_prob);
+    data.vel_length_target = heard.plain(&params.vel_length_target);
+    data.vel_length_amount = heard.plain(&params.vel_length_amount);
+    data.vel_length_prob = heard.plain(&params.vel_length_prob);
 
-    data.pos_mod_1_target = params.pos_mod_1_target.modulated_plain_value();
-    data.pos_mod_1_shift = params.pos_mod_1_shift.modulated_plain_value();
-    data.pos_mod_1_prob = params.pos_mod_1_prob.modulated_plain_value();
+    data.pos_mod_1_target = heard.plain(&params.pos_mod_1_target);
+    data.pos_mod_1_shift = heard.plain(&params.pos_mod_1_shift);
+    data.pos_mod_1_prob = heard.plain(&params.pos_mod_1_prob);
 
-    data.pos_mod_2_target = params.pos_mod_2_target.modulated_plain_value();
-    data.pos_mod_2_shift = params.pos_mod_2_shift.modulated_plain_value();
-    data.pos_mod_2_prob = params.pos_mod_2_prob.modulated_plain_value();
+    data.pos_mod_2_target = heard.plain(&params.pos_mod_2_target);
+    data.pos_mod_2_shift = heard.plain(&params.pos_mod_2_shift);
+    data.pos_mod_2_prob = heard.plain(&params.pos_mod_2_prob);
 
-    data.synth_drift_amount = params.synth_drift_amount.modulated_plain_value();
-    data.synth_drift_rate = params.synth_drift_rate.modulated_plain_value();
+    data.synth_drift_amount = heard.plain(&params.synth_drift_amount);
+    data.synth_drift_rate = heard.plain(&params.synth_drift_rate);
     data.synth_noise_amount = 0.0;
-    data.synth_tube_drive = params.synth_tube_drive.modulated_plain_value();
+    data.synth_tube_drive = heard.plain(&params.synth_tube_drive);
     data.synth_color_distortion_amount = 0.0;
     data.synth_color_distortion_threshold = 0.7;
     data.synth_vps_enable = params.synth_vps_enable.value();
     data.synth_reverb_enable = params.synth_reverb_enable.value();
     data.synth_pll_mult_slew = params.synth_pll_mult_slew.value();
-    data.synth_noise_amount = params.synth_noise_amount.modulated_plain_value();
-    data.synth_color_distortion_amount = params.synth_color_distortion_amount.modulated_plain_value();
-    data.synth_color_distortion_threshold = params.synth_color_distortion_threshold.modulated_plain_value();
+    data.synth_noise_amount = heard.plain(&params.synth_noise_amount);
+    data.synth_color_distortion_amount = heard.plain(&params.synth_color_distortion_amount);
+    data.synth_color_distortion_threshold = heard.plain(&params.synth_color_distortion_threshold);
 
     data.master_hpf = params.master_hpf.value();
     data.master_hpf_boost = params.master_hpf_boost.value();
     data.master_hpf_sub = params.master_hpf_sub.value();
     data.box_cut_mode = params.box_cut_mode.value();
-    data.brilliance_amount = params.brilliance_amount.modulated_plain_value();
-    data.brilliance_drive = params.brilliance_drive.modulated_plain_value();
-    data.stereo_mono_bass = params.stereo_mono_bass.modulated_plain_value();
-    data.stereo_width = params.stereo_width.modulated_plain_value();
+    data.brilliance_amount = heard.plain(&params.brilliance_amount);
+    data.brilliance_drive = heard.plain(&params.brilliance_drive);
+    data.stereo_mono_bass = heard.plain(&params.stereo_mono_bass);
+    data.stereo_width = heard.plain(&params.stereo_width);
 
     data.mseq_steps = vec![
-        params.mseq_step_1.modulated_plain_value(),
-        params.mseq_step_2.modulated_plain_value(),
-        params.mseq_step_3.modulated_plain_value(),
-        params.mseq_step_4.modulated_plain_value(),
-        params.mseq_step_5.modulated_plain_value(),
-        params.mseq_step_6.modulated_plain_value(),
-        params.mseq_step_7.modulated_plain_value(),
-        params.mseq_step_8.modulated_plain_value(),
-        params.mseq_step_9.modulated_plain_value(),
-        params.mseq_step_10.modulated_plain_value(),
-        params.mseq_step_11.modulated_plain_value(),
-        params.mseq_step_12.modulated_plain_value(),
-        params.mseq_step_13.modulated_plain_value(),
-        params.mseq_step_14.modulated_plain_value(),
-        params.mseq_step_15.modulated_plain_value(),
-        params.mseq_step_16.modulated_plain_value(),
+        heard.plain(&params.mseq_step_1),
+        heard.plain(&params.mseq_step_2),
+        heard.plain(&params.mseq_step_3),
+        heard.plain(&params.mseq_step_4),
+        heard.plain(&params.mseq_step_5),
+        heard.plain(&params.mseq_step_6),
+        heard.plain(&params.mseq_step_7),
+        heard.plain(&params.mseq_step_8),
+        heard.plain(&params.mseq_step_9),
+        heard.plain(&params.mseq_step_10),
+        heard.plain(&params.mseq_step_11),
+        heard.plain(&params.mseq_step_12),
+        heard.plain(&params.mseq_step_13),
+        heard.plain(&params.mseq_step_14),
+        heard.plain(&params.mseq_step_15),
+        heard.plain(&params.mseq_step_16),
     ];
     data.mseq_ties = params.mseq_ties.value();
     data.mseq_division = params.mseq_division.value();
-    data.mseq_slew = params.mseq_slew.modulated_plain_value();
+    data.mseq_slew = heard.plain(&params.mseq_slew);
     data.mseq_dest1 = params.mseq_dest1.value();
-    data.mseq_amount1 = params.mseq_amount1.modulated_plain_value();
+    data.mseq_amount1 = heard.plain(&params.mseq_amount1);
     data.mseq_dest2 = params.mseq_dest2.value();
-    data.mseq_amount2 = params.mseq_amount2.modulated_plain_value();
+    data.mseq_amount2 = heard.plain(&params.mseq_amount2);
+
+    if let Ok(shapes) = params.mseg_shapes.read() {
+        data.mseg_shapes = shapes.to_vec();
+    }
+    data.mseg1_tempo_sync = params.mseg1_tempo_sync.value();
+    data.mseg1_retrigger = params.mseg1_retrigger.value();
+    data.mseg1_dest1 = params.mseg1_dest1.value();
+    data.mseg1_amount1 = heard.plain(&params.mseg1_amount1);
+    data.mseg1_dest2 = params.mseg1_dest2.value();
+    data.mseg1_amount2 = heard.plain(&params.mseg1_amount2);
+    data.mseg2_tempo_sync = params.mseg2_tempo_sync.value();
+    data.mseg2_retrigger = params.mseg2_retrigger.value();
+    data.mseg2_dest1 = params.mseg2_dest1.value();
+    data.mseg2_amount1 = heard.plain(&params.mseg2_amount1);
+    data.mseg2_dest2 = params.mseg2_dest2.value();
+    data.mseg2_amount2 = heard.plain(&params.mseg2_amount2);
+    data.mod_pitch_quantize = params.mod_pitch_quantize.value();
+
+    data.macro_1 = heard.plain(&params.macro_1);
+    data.macro_2 = heard.plain(&params.macro_2);
+    data.macro_3 = heard.plain(&params.macro_3);
+    data.macro_4 = heard.plain(&params.macro_4);
+    data.macro_5 = heard.plain(&params.macro_5);
+    data.macro_6 = heard.plain(&params.macro_6);
+    data.macro_7 = heard.plain(&params.macro_7);
+    data.macro_8 = heard.plain(&params.macro_8);
+    if let Ok(targets) = params.macro_targets.read() {
+        data.macro_targets = targets.to_vec();
+    }
+    if let Ok(scenes) = params.scenes.read() {
+        if scenes.iter().any(Option::is_some) {
+            data.scenes = scenes.clone();
+        }
+    }
 
     if let Ok(strength_values) = ui_state.strength_values.lock() {
         for (i, &v) in strength_values.iter().enumerate() {
@@ -1,7 +1,9 @@
 use crate::params::DeviceParams;
 use crate::ui::SharedUiState;
 use crate::midi_modes::MidiInputMode;
-use crate::midi_devices::MidiChannel;
+use crate::sequencer::TransposeQuantize;
+use crate::midi_devices::{MidiChannel, MidiDeviceManager, MidiRoles, CLOCK_PPQN_OPTIONS};
+use crate::tempo::{TempoMode, TempoSource};
 use egui_taffy::TuiBuilderLogic;
 use nih_plug::prelude::ParamSetter;
 use nih_plug_egui::egui;
@@ -15,6 +17,7 @@ const HINT_FONT: f32 = 13.0;
 const SECTION_GAP: f32 = 12.0;
 const COL_GAP: f32 = 32.0;
 const MARGIN: f32 = 20.0;
+const RAMP_BAR_OPTIONS: [u8; 5] = [1, 2, 4, 8, 16];
 
 pub fn render(
     tui: &mut egui_taffy::Tui,
@@ -67,6 +70,8 @@ pub fn render(
         });
 
         ui.allocate_new_ui(egui::UiBuilder::new().max_rect(right_rect), |ui| {
+            render_transport_section(ui, ui_state);
+            section_separator(ui);
             render_midi_learn_section(ui, ui_state);
             section_separator(ui);
             render_performance_section(ui, params, setter, ui_state);
@@ -94,123 +99,245 @@ fn render_midi_devices_section(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>)
             .min_size(egui::vec2(140.0, 36.0))
     ).clicked() {
         mgr.refresh_devices();
+        mgr.reconnect_saved_devices();
     }
 
     ui.add_space(8.0);
 
-    let input_devices: Vec<String> = mgr.input_devices().iter().map(|d| d.name.clone()).collect();
-    let output_devices: Vec<String> = mgr.output_devices().iter().map(|d| d.name.clone()).collect();
-    let current_input = mgr.connected_input_name().map(|s| s.to_string());
-    let current_output = mgr.connected_output_name().map(|s| s.to_string());
-    let current_in_channel = mgr.input_channel().clone();
-    let current_out_channel = mgr.output_channel();
+    let inputs = mgr.inputs().to_vec();
+    let outputs = mgr.outputs().to_vec();
+    let available_inputs: Vec<String> = mgr.input_devices().iter()
+        .map(|d| d.name.clone())
+        .filter(|name| !inputs.iter().any(|p| &p.name == name))
+        .collect();
+    let available_outputs: Vec<String> = mgr.output_devices().iter()
+        .map(|d| d.name.clone())
+        .filter(|name| !outputs.iter().any(|p| &p.name == name))
+        .collect();
     let feedback_risk = mgr.has_feedback_risk();
 
-    let input_label = current_input.as_deref().unwrap_or("None");
-    ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Input:").size(UI_FONT));
-        egui::ComboBox::from_id_salt("midi_input_device")
-            .width(260.0)
-            .selected_text(egui::RichText::new(input_label).size(UI_FONT))
-            .show_ui(ui, |ui| {
-                let none_btn = egui::Button::new(egui::RichText::new("None").size(UI_FONT))
-                    .min_size(egui::vec2(240.0, 36.0))
-                    .selected(current_input.is_none());
-                if ui.add(none_btn).clicked() {
-                    mgr.disconnect_input();
-                    mgr.save_config();
-                    ui.close_menu();
-                }
-                for name in &input_devices {
-                    let selected = current_input.as_deref() == Some(name.as_str());
-                    let btn = egui::Button::new(egui::RichText::new(name).size(UI_FONT))
-                        .min_size(egui::vec2(240.0, 36.0))
-                        .selected(selected);
-                    if ui.add(btn).clicked() {
-                        mgr.connect_input(name);
-                        mgr.save_config();
-                        ui.close_menu();
-                    }
-                }
-            });
-        ui.add_space(12.0);
-        ui.label(egui::RichText::new("Ch:").size(UI_FONT));
-        egui::ComboBox::from_id_salt("midi_input_channel")
-            .width(80.0)
-            .selected_text(egui::RichText::new(current_in_channel.label()).size(UI_FONT))
-            .show_ui(ui, |ui| {
-                for ch in MidiChannel::all_options() {
-                    let selected = ch == current_in_channel;
-                    let btn = egui::Button::new(egui::RichText::new(ch.label()).size(UI_FONT))
-                        .min_size(egui::vec2(70.0, 36.0))
-                        .selected(selected);
-                    if ui.add(btn).clicked() {
-                        mgr.set_input_channel(ch);
-                        mgr.save_config();
-                        ui.close_menu();
+    ui.label(egui::RichText::new("Inputs").size(UI_FONT));
+    for (i, port) in inputs.iter().enumerate() {
+        let connected = mgr.is_input_connected(&port.name);
+        ui.horizontal(|ui| {
+            port_status_label(ui, &port.name, connected);
+            ui.add_space(8.0);
+            ui.label(egui::RichText::new("Ch:").size(UI_FONT));
+            egui::ComboBox::from_id_salt(("midi_input_channel", i))
+                .width(60.0)
+                .selected_text(egui::RichText::new(port.channel.label()).size(UI_FONT))
+                .show_ui(ui, |ui| {
+                    for ch in MidiChannel::all_options() {
+                        let selected = ch == port.channel;
+                        let btn = egui::Button::new(egui::RichText::new(ch.label()).size(UI_FONT))
+                            .min_size(egui::vec2(70.0, 36.0))
+                            .selected(selected);
+                        if ui.add(btn).clicked() {
+                            mgr.set_input_channel(&port.name, ch);
+                            mgr.save_config();
+                            ui.close_menu();
+                        }
                     }
-                }
-            });
-    });
+                });
+            let mut roles = port.roles;
+            if role_buttons(ui, &mut roles) {
+                mgr.set_input_roles(&port.name, roles);
+                mgr.save_config();
+            }
+            if remove_button(ui) {
+                mgr.disconnect_input(&port.name);
+                mgr.save_config();
+            }
+        });
+    }
+    if !available_inputs.is_empty() {
+        add_device_combo(ui, "midi_input_add", "Add input...", &available_inputs, |name| {
+            mgr.connect_input(name);
+            mgr.save_config();
+        });
+    }
 
-    ui.add_space(4.0);
+    ui.add_space(8.0);
 
-    let output_label = current_output.as_deref().unwrap_or("None");
-    ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Output:").size(UI_FONT));
-        egui::ComboBox::from_id_salt("midi_output_device")
-            .width(260.0)
-            .selected_text(egui::RichText::new(output_label).size(UI_FONT))
-            .show_ui(ui, |ui| {
-                let none_btn = egui::Button::new(egui::RichText::new("None").size(UI_FONT))
-                    .min_size(egui::vec2(240.0, 36.0))
-                    .selected(current_output.is_none());
-                if ui.add(none_btn).clicked() {
-                    mgr.disconnect_output();
-                    mgr.save_config();
-                    ui.close_menu();
-                }
-                for name in &output_devices {
-                    let selected = current_output.as_deref() == Some(name.as_str());
-                    let btn = egui::Button::new(egui::RichText::new(name).size(UI_FONT))
-                        .min_size(egui::vec2(240.0, 36.0))
-                        .selected(selected);
-                    if ui.add(btn).clicked() {
-                        mgr.connect_output(name);
-                        mgr.save_config();
-                        ui.close_menu();
-                    }
-                }
-            });
-        ui.add_space(12.0);
-        ui.label(egui::RichText::new("Ch:").size(UI_FONT));
-        egui::ComboBox::from_id_salt("midi_output_channel")
-            .width(80.0)
-            .selected_text(egui::RichText::new(format!("{}", current_out_channel + 1)).size(UI_FONT))
-            .show_ui(ui, |ui| {
-                for ch in 0u8..16 {
-                    let btn = egui::Button::new(egui::RichText::new(format!("{}", ch + 1)).size(UI_FONT))
-                        .min_size(egui::vec2(70.0, 36.0))
-                        .selected(ch == current_out_channel);
-                    if ui.add(btn).clicked() {
-                        mgr.set_output_channel(ch);
-                        mgr.save_config();
-                        ui.close_menu();
+    ui.label(egui::RichText::new("Outputs").size(UI_FONT));
+    for (i, port) in outputs.iter().enumerate() {
+        let connected = mgr.is_output_connected(&port.name);
+        ui.horizontal(|ui| {
+            port_status_label(ui, &port.name, connected);
+            ui.add_space(8.0);
+            ui.label(egui::RichText::new("Ch:").size(UI_FONT));
+            egui::ComboBox::from_id_salt(("midi_output_channel", i))
+                .width(60.0)
+                .selected_text(egui::RichText::new(format!("{}", port.channel + 1)).size(UI_FONT))
+                .show_ui(ui, |ui| {
+                    for ch in 0u8..16 {
+                        let btn = egui::Button::new(egui::RichText::new(format!("{}", ch + 1)).size(UI_FONT))
+                            .min_size(egui::vec2(70.0, 36.0))
+                            .selected(ch == port.channel);
+                        if ui.add(btn).clicked() {
+                            mgr.set_output_channel(&port.name, ch);
+                            mgr.save_config();
+                            ui.close_menu();
+                        }
                     }
-                }
-            });
-    });
+                });
+            let mut roles = port.roles;
+            if role_buttons(ui, &mut roles) {
+                mgr.set_output_roles(&port.name, roles);
+                mgr.save_config();
+            }
+            if port.roles.clock {
+                egui::ComboBox::from_id_salt(("midi_output_ppqn", i))
+                    .width(70.0)
+                    .selected_text(egui::RichText::new(format!("{} PPQN", port.clock_ppqn)).size(HINT_FONT))
+                    .show_ui(ui, |ui| {
+                        for ppqn in CLOCK_PPQN_OPTIONS {
+                            let btn = egui::Button::new(egui::RichText::new(format!("{}", ppqn)).size(UI_FONT))
+                                .min_size(egui::vec2(70.0, 36.0))
+                                .selected(ppqn == port.clock_ppqn);
+                            if ui.add(btn).clicked() {
+                                mgr.set_output_clock_ppqn(&port.name, ppqn);
+                                mgr.save_config();
+                                ui.close_menu();
+                            }
+                        }
+                    });
+            }
+            if remove_button(ui) {
+                mgr.disconnect_output(&port.name);
+                mgr.save_config();
+            }
+        });
+    }
+    if !available_outputs.is_empty() {
+        add_device_combo(ui, "midi_output_add", "Add output...", &available_outputs, |name| {
+            mgr.connect_output(name);
+            mgr.save_config();
+        });
+    }
+
+    ui.add_space(8.0);
+    render_virtual_ports(ui, &mut mgr);
 
     if feedback_risk {
         ui.add_space(4.0);
         ui.label(
-            egui::RichText::new("⚠ Feedback risk: same device and overlapping channels")
+            egui::RichText::new("⚠ Feedback risk: same device, role and overlapping channels")
                 .size(UI_FONT)
                 .color(Color32::from_rgb(220, 200, 60)),
         );
     }
 }
 
+fn render_virtual_ports(ui: &mut egui::Ui, mgr: &mut MidiDeviceManager) {
+    ui.label(egui::RichText::new("Virtual Ports").size(UI_FONT));
+    if !mgr.virtual_ports_supported() {
+        ui.label(egui::RichText::new("Not available on this platform").size(HINT_FONT).weak());
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Name:").size(UI_FONT));
+        let id = egui::Id::new("midi_virtual_port_name");
+        let mut name = ui
+            .data_mut(|d| d.get_temp::<String>(id))
+            .unwrap_or_else(|| mgr.virtual_port_name().to_string());
+        let resp = ui.add(
+            egui::TextEdit::singleline(&mut name)
+                .desired_width(160.0)
+                .font(egui::FontId::proportional(UI_FONT)),
+        );
+        if resp.lost_focus() {
+            mgr.set_virtual_port_name(&name);
+            mgr.save_config();
+            ui.data_mut(|d| d.remove::<String>(id));
+        } else if resp.has_focus() {
+            ui.data_mut(|d| d.insert_temp(id, name));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        let mut input = mgr.virtual_input_enabled();
+        if ui.checkbox(&mut input, egui::RichText::new("Virtual In").size(UI_FONT)).changed() {
+            mgr.set_virtual_input(input);
+            mgr.save_config();
+        }
+        let mut output = mgr.virtual_output_enabled();
+        if ui.checkbox(&mut output, egui::RichText::new("Virtual Out").size(UI_FONT)).changed() {
+            mgr.set_virtual_output(output);
+            mgr.save_config();
+        }
+        let failed = (input && !mgr.virtual_input_active()) || (output && !mgr.virtual_output_active());
+        if failed {
+            ui.label(
+                egui::RichText::new("Could not create port")
+                    .size(HINT_FONT)
+                    .color(Color32::from_rgb(220, 200, 60)),
+            );
+        } else {
+            ui.label(egui::RichText::new("Other apps can connect to PhaseBurn").size(HINT_FONT).weak());
+        }
+    });
+}
+
+fn port_status_label(ui: &mut egui::Ui, name: &str, connected: bool) {
+    let color = if connected {
+        Color32::from_rgb(100, 200, 120)
+    } else {
+        Color32::from_gray(110)
+    };
+    ui.label(egui::RichText::new("●").size(UI_FONT).color(color));
+    let text = egui::RichText::new(name).size(UI_FONT);
+    ui.label(if connected { text } else { text.weak() })
+        .on_hover_text(if connected { "Connected" } else { "Not present — reconnects when plugged in" });
+}
+
+/// Toggles for the notes / CC / clock roles; returns true when one changed.
+fn role_buttons(ui: &mut egui::Ui, roles: &mut MidiRoles) -> bool {
+    let mut changed = false;
+    for (label, flag) in [("Notes", &mut roles.notes), ("CC", &mut roles.cc), ("Clock", &mut roles.clock)] {
+        let btn = egui::Button::new(egui::RichText::new(label).size(HINT_FONT))
+            .min_size(egui::vec2(48.0, 28.0))
+            .selected(*flag);
+        if ui.add(btn).clicked() {
+            *flag = !*flag;
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn remove_button(ui: &mut egui::Ui) -> bool {
+    ui.add(
+        egui::Button::new(egui::RichText::new("✕").size(UI_FONT))
+            .min_size(egui::vec2(28.0, 28.0)),
+    )
+    .on_hover_text("Remove")
+    .clicked()
+}
+
+fn add_device_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    placeholder: &str,
+    devices: &[String],
+    mut on_select: impl FnMut(&str),
+) {
+    egui::ComboBox::from_id_salt(id)
+        .width(260.0)
+        .selected_text(egui::RichText::new(placeholder).size(UI_FONT).weak())
+        .show_ui(ui, |ui| {
+            for name in devices {
+                let btn = egui::Button::new(egui::RichText::new(name).size(UI_FONT))
+                    .min_size(egui::vec2(240.0, 36.0));
+                if ui.add(btn).clicked() {
+                    on_select(name);
+                    ui.close_menu();
+                }
+            }
+        });
+}
+
 fn render_midi_input_section(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>) {
     ui.label(egui::RichText::new("MIDI INPUT").size(HEADER_FONT).strong());
     ui.add_space(8.0);
@@ -315,6 +442,120 @@ fn render_midi_input_section(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>) {
                 });
             }
         }
+        MidiInputMode::CallResponse => {
+            let current_bars = ui_state.call_response_bars.load(Ordering::Relaxed);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Phrase:").size(UI_FONT));
+                for bars in [1u8, 2, 4, 8] {
+                    let label = if bars == 1 { "1 bar".to_string() } else { format!("{} bars", bars) };
+                    let btn = egui::Button::new(egui::RichText::new(label).size(UI_FONT))
+                        .min_size(egui::vec2(70.0, 28.0))
+                        .selected(bars == current_bars);
+                    if ui.add(btn).clicked() {
+                        ui_state.call_response_bars.store(bars, Ordering::Relaxed);
+                        if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                            mgr.set_call_response_bars(bars);
+                            mgr.save_config();
+                        }
+                    }
+                }
+            });
+
+            ui.add_space(4.0);
+
+            if let Ok(display) = ui_state.midi_mode_display.try_lock() {
+                let status = if display.phrase_listening {
+                    format!(
+                        "Listening: bar {}/{} ({} notes)",
+                        display.phrase_bar.min(display.phrase_bars),
+                        display.phrase_bars,
+                        display.phrase_notes,
+                    )
+                } else if display.answer_notes > 0 {
+                    format!("Answering {} notes — play again to replace", display.answer_notes)
+                } else {
+                    "Play a phrase to start...".to_string()
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(status).size(UI_FONT));
+
+                    if ui
+                        .add(
+                            egui::Button::new(egui::RichText::new("Clear").size(UI_FONT))
+                                .min_size(egui::vec2(60.0, 28.0)),
+                        )
+                        .clicked()
+                    {
+                        ui_state.midi_clear_memory.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+        }
+        MidiInputMode::Transpose => {
+            let current_quantize = TransposeQuantize::from_index(
+                ui_state.transpose_quantize.load(Ordering::Relaxed),
+            );
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Quantize:").size(UI_FONT));
+                for quantize in TransposeQuantize::all() {
+                    let btn = egui::Button::new(egui::RichText::new(quantize.label()).size(UI_FONT))
+                        .min_size(egui::vec2(90.0, 28.0))
+                        .selected(quantize == current_quantize);
+                    if ui.add(btn).clicked() {
+                        ui_state.transpose_quantize.store(quantize.to_index(), Ordering::Relaxed);
+                        if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                            mgr.set_transpose_quantize(quantize.to_index());
+                            mgr.save_config();
+                        }
+                    }
+                }
+            });
+
+            ui.add_space(4.0);
+
+            let mut scale_aware = ui_state.transpose_scale_aware.load(Ordering::Relaxed);
+            ui.horizontal(|ui| {
+                let resp = ui.checkbox(&mut scale_aware, egui::RichText::new("Scale Aware").size(UI_FONT));
+                ui.label(egui::RichText::new("Shift by scale degrees to stay in key").size(HINT_FONT).weak());
+                if resp.changed() {
+                    ui_state.transpose_scale_aware.store(scale_aware, Ordering::Relaxed);
+                    if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                        mgr.set_transpose_scale_aware(scale_aware);
+                        mgr.save_config();
+                    }
+                }
+            });
+
+            let mut gate = ui_state.transpose_gate.load(Ordering::Relaxed);
+            ui.horizontal(|ui| {
+                let resp = ui.checkbox(&mut gate, egui::RichText::new("Gate").size(UI_FONT));
+                ui.label(egui::RichText::new("Play only while a key is held").size(HINT_FONT).weak());
+                if resp.changed() {
+                    ui_state.transpose_gate.store(gate, Ordering::Relaxed);
+                    if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                        mgr.set_transpose_gate(gate);
+                        mgr.save_config();
+                    }
+                }
+            });
+
+            ui.add_space(4.0);
+
+            if let Ok(display) = ui_state.midi_mode_display.try_lock() {
+                let status = match display.transpose_key {
+                    Some(note) => {
+                        let note_names = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+                        let name = note_names[(note % 12) as usize];
+                        let octave = (note / 12) as i8 - 1;
+                        let state = if display.transpose_held { "held" } else { "latched" };
+                        format!("Key: {}{} ({})", name, octave, state)
+                    }
+                    None => "Play a key to transpose from the root...".to_string(),
+                };
+                ui.label(egui::RichText::new(status).size(UI_FONT));
+            }
+        }
     }
 }
 
@@ -394,6 +635,197 @@ fn render_midi_sync_section(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>) {
             }
         }
     });
+
+    ui.add_space(4.0);
+
+    let mut offset_ms = f32::from_bits(ui_state.clock_offset_ms.load(Ordering::Relaxed));
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Clock Offset:").size(UI_FONT));
+        let resp = ui.add_sized(
+            egui::vec2(80.0, 28.0),
+            egui::DragValue::new(&mut offset_ms)
+                .range(-100.0..=100.0)
+                .speed(0.1)
+                .suffix(" ms")
+                .max_decimals(1),
+        );
+        ui.label(egui::RichText::new("Negative sends clock early").size(HINT_FONT).weak());
+        if resp.changed() {
+            ui_state.clock_offset_ms.store(offset_ms.to_bits(), Ordering::Relaxed);
+        }
+        if resp.drag_stopped() || resp.lost_focus() {
+            if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                mgr.set_clock_offset_ms(offset_ms);
+                mgr.save_config();
+            }
+        }
+    });
+
+    ui.add_space(4.0);
+
+    let mut din_sync = ui_state.clock_din_sync.load(Ordering::Relaxed);
+    ui.horizontal(|ui| {
+        let resp = ui.checkbox(&mut din_sync, egui::RichText::new("DIN Sync").size(UI_FONT));
+        ui.label(egui::RichText::new("Clock runs while stopped, reset before start").size(HINT_FONT).weak());
+        if resp.changed() {
+            ui_state.clock_din_sync.store(din_sync, Ordering::Relaxed);
+            if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                mgr.set_clock_din_sync(din_sync);
+                mgr.save_config();
+            }
+        }
+    });
+
+    ui.add_space(4.0);
+
+    let mut start_on_bar = ui_state.clock_start_on_bar.load(Ordering::Relaxed);
+    ui.horizontal(|ui| {
+        let resp = ui.checkbox(&mut start_on_bar, egui::RichText::new("Start on Bar").size(UI_FONT));
+        ui.label(egui::RichText::new("Hold start until the next downbeat").size(HINT_FONT).weak());
+        if resp.changed() {
+            ui_state.clock_start_on_bar.store(start_on_bar, Ordering::Relaxed);
+            if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                mgr.set_clock_start_on_bar(start_on_bar);
+                mgr.save_config();
+            }
+        }
+    });
+
+    ui.add_space(4.0);
+
+    let mut clock_swing = ui_state.clock_swing.load(Ordering::Relaxed);
+    ui.horizontal(|ui| {
+        let resp = ui.checkbox(&mut clock_swing, egui::RichText::new("Swing Clock").size(UI_FONT));
+        ui.label(egui::RichText::new("Apply sequencer swing to clock ticks").size(HINT_FONT).weak());
+        if resp.changed() {
+            ui_state.clock_swing.store(clock_swing, Ordering::Relaxed);
+            if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                mgr.set_clock_swing(clock_swing);
+                mgr.save_config();
+            }
+        }
+    });
+}
+
+fn render_transport_section(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>) {
+    ui.label(egui::RichText::new("TRANSPORT").size(HEADER_FONT).strong());
+    ui.add_space(8.0);
+
+    let transport = &ui_state.transport;
+    let current_mode = TempoMode::from_index(transport.mode.load(Ordering::Relaxed));
+    let source = transport.get_source();
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Tempo Source:").size(UI_FONT));
+        egui::ComboBox::from_id_salt("tempo_mode_selector")
+            .width(120.0)
+            .selected_text(egui::RichText::new(current_mode.label()).size(UI_FONT))
+            .show_ui(ui, |ui| {
+                for mode in TempoMode::all() {
+                    let btn = egui::Button::new(egui::RichText::new(mode.label()).size(UI_FONT))
+                        .min_size(egui::vec2(120.0, 36.0))
+                        .selected(mode == current_mode);
+                    if ui.add(btn).clicked() {
+                        transport.mode.store(mode.to_index(), Ordering::Relaxed);
+                        if let Ok(mut mgr) = ui_state.midi_device_manager.try_lock() {
+                            mgr.set_tempo_mode(mode.to_index());
+                            mgr.save_config();
+                        }
+                        ui.close_menu();
+                    }
+                }
+            });
+        ui.label(egui::RichText::new(format!("Following {}", source.label())).size(HINT_FONT).weak());
+    });
+
+    ui.add_space(4.0);
+
+    let locked = source != TempoSource::Manual;
+    let mut bpm = transport.get_manual_bpm();
+    ui.add_enabled_ui(!locked, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("BPM:").size(UI_FONT));
+            if ui.add_sized(egui::vec2(36.0, 28.0), egui::Button::new(egui::RichText::new("-").size(UI_FONT))).clicked() {
+                transport.request_bpm(bpm - 1.0);
+            }
+            let resp = ui.add_sized(
+                egui::vec2(80.0, 28.0),
+                egui::DragValue::new(&mut bpm)
+                    .range(20.0..=300.0)
+                    .speed(0.1)
+                    .max_decimals(1),
+            );
+            if resp.changed() {
+                transport.request_bpm(bpm);
+            }
+            if ui.add_sized(egui::vec2(36.0, 28.0), egui::Button::new(egui::RichText::new("+").size(UI_FONT))).clicked() {
+                transport.request_bpm(bpm + 1.0);
+            }
+            ui.add_space(8.0);
+            if ui.add_sized(egui::vec2(64.0, 28.0), egui::Button::new(egui::RichText::new("Tap").size(UI_FONT))).clicked() {
+                transport.request_tap();
+            }
+        });
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Nudge:").size(UI_FONT));
+            let slower = ui.add_sized(egui::vec2(44.0, 28.0), egui::Button::new(egui::RichText::new("\u{25C0}").size(UI_FONT)));
+            let faster = ui.add_sized(egui::vec2(44.0, 28.0), egui::Button::new(egui::RichText::new("\u{25B6}").size(UI_FONT)));
+            let nudge = if slower.is_pointer_button_down_on() {
+                -1
+            } else if faster.is_pointer_button_down_on() {
+                1
+            } else {
+                0
+            };
+            transport.nudge.store(nudge, Ordering::Relaxed);
+            ui.label(egui::RichText::new("Hold to push the beat back or forward").size(HINT_FONT).weak());
+        });
+
+        ui.add_space(4.0);
+
+        let ramp_id = ui.id().with("tempo_ramp_target");
+        let mut target = ui.data_mut(|d| d.get_temp::<f32>(ramp_id)).unwrap_or(bpm);
+        let bars = transport.ramp_bars.load(Ordering::Relaxed);
+        let ramping = transport.ramping.load(Ordering::Relaxed);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Ramp to:").size(UI_FONT));
+            let resp = ui.add_sized(
+                egui::vec2(80.0, 28.0),
+                egui::DragValue::new(&mut target)
+                    .range(20.0..=300.0)
+                    .speed(0.1)
+                    .max_decimals(1),
+            );
+            if resp.changed() {
+                ui.data_mut(|d| d.insert_temp(ramp_id, target));
+            }
+            egui::ComboBox::from_id_salt("tempo_ramp_bars")
+                .width(80.0)
+                .selected_text(egui::RichText::new(format!("{} bars", bars)).size(UI_FONT))
+                .show_ui(ui, |ui| {
+                    for option in RAMP_BAR_OPTIONS {
+                        let btn = egui::Button::new(egui::RichText::new(format!("{} bars", option)).size(UI_FONT))
+                            .min_size(egui::vec2(80.0, 36.0))
+                            .selected(option == bars);
+                        if ui.add(btn).clicked() {
+                            transport.ramp_bars.store(option, Ordering::Relaxed);
+                            ui.close_menu();
+                        }
+                    }
+                });
+            let label = if ramping { "Cancel" } else { "Start" };
+            if ui.add_sized(egui::vec2(72.0, 28.0), egui::Button::new(egui::RichText::new(label).size(UI_FONT))).clicked() {
+                if ramping {
+                    transport.ramp_cancel.store(true, Ordering::Relaxed);
+                } else {
+                    transport.request_ramp(target, bars);
+                }
+            }
+        });
+    });
 }
 
 fn render_midi_learn_section(ui: &mut egui::Ui, ui_state: &Arc<SharedUiState>) {
@@ -589,6 +1021,23 @@ fn render_performance_section(
 
     ui.add_space(8.0);
 
+    let mut master_volume = params.stems_post_master.value();
+    if ui
+        .checkbox(&mut master_volume, egui::RichText::new("Stems follow master volume").size(UI_FONT))
+        .on_hover_text("Scales the stems by the master volume; the master FX chain, HPF, compressor and limiter are not applied to them")
+        .changed()
+    {
+        setter.set_parameter(&params.stems_post_master, master_volume);
+    }
+    let stems_status = if ui_state.stems_active.load(Ordering::Relaxed) {
+        "Stems: 6 stereo aux outputs"
+    } else {
+        "Stems: off (choose the Stereo + Stems layout in the host)"
+    };
+    ui.label(egui::RichText::new(stems_status).size(UI_FONT).weak());
+
+    ui.add_space(8.0);
+
     let sample_rate = ui_state.sample_rate.load(Ordering::Relaxed) as f32;
     let limiter_latency = ui_state.limiter_latency_samples.load(Ordering::Relaxed);
     let comp_latency = ui_state.comp_latency_samples.load(Ordering::Relaxed);
@@ -2,6 +2,8 @@
 
 use crate::params::DeviceParams;
 use crate::ui::SharedUiState;
+use crate::synth::fx_chain::{place_mod_fx, FxModule, DEFAULT_FX_ORDER, FX_SLOT_COUNT};
+use crate::synth::wavetable;
 use crate::midi_learn::{MidiLearnState, SOUND_PARAMS};
 use egui_taffy::taffy::{prelude::*, style::{AlignItems, FlexDirection, Overflow}, geometry::Point};
 use egui_taffy::TuiBuilderLogic;
@@ -91,14 +93,26 @@ pub fn render(
         .ui(|ui| {
             match current_tab {
                 0 => render_sound_tab(ui, params, setter, ui_state),
-                1 => render_vol_env_tab(ui, params, setter, ui_state),
-                2 => render_filt_env_tab(ui, params, setter, ui_state),
-                3 => render_filter_tab(ui, params, setter, ui_state),
-                4 => render_fx_tab(ui, params, setter),
-                5 => render_lush_tab(ui, params, setter),
-                6 => render_comp_tab(ui, params, setter, ui_state),
-                7 => super::modulation::render_ui(ui, params, setter),
-                _ => super::modulation::render_step_mod_ui(ui, params, setter, ui_state),
+                1 => render_wavetable_tab(ui, params, setter, ui_state),
+                2 => render_noise_tab(ui, params, setter, ui_state),
+                3 => render_sync_tab(ui, params, setter, ui_state),
+                4 => render_topology_tab(ui, params, setter, ui_state),
+                5 => render_cross_mod_tab(ui, params, setter, ui_state),
+                6 => render_vol_env_tab(ui, params, setter, ui_state),
+                7 => render_filt_env_tab(ui, params, setter, ui_state),
+                8 => render_filter_tab(ui, params, setter, ui_state),
+                9 => render_filter2_tab(ui, params, setter, ui_state),
+                10 => render_resonator_tab(ui, params, setter, ui_state),
+                11 => render_lofi_tab(ui, params, setter),
+                12 => render_fx_tab(ui, params, setter),
+                13 => render_lush_tab(ui, params, setter),
+                14 => render_delay_tab(ui, params, setter),
+                15 => render_mod_fx_tab(ui, params, setter),
+                16 => render_comp_tab(ui, params, setter, ui_state),
+                17 => render_chain_tab(ui, params, setter),
+                18 => super::modulation::render_ui(ui, params, setter),
+                19 => super::modulation::render_step_mod_ui(ui, params, setter, ui_state),
+                _ => super::modulation::render_mseg_ui(ui, params, setter, ui_state),
             }
         });
     });
@@ -109,13 +123,16 @@ const TAB_GAP: f32 = 3.0;
 
 fn render_tab_bar(ui: &mut egui::Ui, current_tab: u8) {
     let rect = ui.max_rect();
-    let tab_names = ["OSCs", "VOLENV", "FLTENV", "FILTER", "FX", "LUSH", "COMP", "LFOs", "STEP"];
+    let tab_names = ["OSCs", "WT", "NOISE", "SYNC", "TOPO", "XMOD", "VOLENV", "FLTENV", "FILTER", "FILT2", "RESO", "LOFI", "FX", "LUSH", "DELAY", "MODFX", "COMP", "CHAIN", "LFOs", "STEP", "MSEG"];
+    // Shrink tabs to fit the bar rather than clipping the last ones
+    let count = tab_names.len() as f32;
+    let tab_height = ((rect.height() - TAB_GAP * (count - 1.0)) / count).min(TAB_HEIGHT);
 
     for (i, name) in tab_names.iter().enumerate() {
-        let y = rect.min.y + i as f32 * (TAB_HEIGHT + TAB_GAP);
+        let y = rect.min.y + i as f32 * (tab_height + TAB_GAP);
         let button_rect = egui::Rect::from_min_size(
             egui::pos2(rect.min.x, y),
-            egui::vec2(TAB_BAR_WIDTH, TAB_HEIGHT),
+            egui::vec2(TAB_BAR_WIDTH, tab_height),
         );
 
         let response = ui.interact(
@@ -138,13 +155,13 @@ fn render_tab_bar(ui: &mut egui::Ui, current_tab: u8) {
         if is_selected {
             let accent_rect = egui::Rect::from_min_size(
                 button_rect.left_top(),
-                egui::vec2(3.0, TAB_HEIGHT),
+                egui::vec2(3.0, tab_height),
             );
             ui.painter()
                 .rect_filled(accent_rect, 1.5, Color32::from_rgb(100, 140, 200));
         }
 
-        let font_id = egui::FontId::proportional(TAB_FONT);
+        let font_id = egui::FontId::proportional(TAB_FONT.min(tab_height * 0.27));
         let text_color = if is_selected {
             Color32::WHITE
         } else {
@@ -1919,103 +1936,1227 @@ fn render_env_sync_row(
     });
 }
 
-fn render_fx_tab(
+fn render_wavetable_tab(
     ui: &mut egui::Ui,
     params: &Arc<DeviceParams>,
     setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
 ) {
-    ui.horizontal(|ui| {
-        egui::Frame::NONE
-            .inner_margin(egui::Margin { left: FRAME_MARGIN.left + 5, ..FRAME_MARGIN })
-            .show(ui, |ui| {
-                render_looper_section(ui, params, setter);
-            });
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let pitch_color = Some(Color32::from_rgb(80, 80, 40));
+    let accent = Color32::from_rgb(90, 170, 200);
 
-        ui.add_space(10.0);
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
 
-        egui::Frame::NONE
-            .inner_margin(egui::Margin { left: 5, ..FRAME_MARGIN })
-            .show(ui, |ui| {
-                ui.vertical(|ui| {
-                    ui.label(egui::RichText::new("MASTER HPF").size(HEADER_FONT).strong());
-                    ui.add_space(9.0);
-                    render_hpf_buttons(ui, params, setter);
-                    ui.add_space(13.0);
-                    ui.label(egui::RichText::new("BOOST").size(LABEL_FONT).color(Color32::from_gray(140)));
-                    ui.add_space(6.0);
-                    render_hpf_boost_buttons(ui, params, setter);
-                    ui.add_space(13.0);
-                    ui.label(egui::RichText::new("BOX CUT").size(LABEL_FONT).color(Color32::from_gray(140)));
-                    ui.add_space(6.0);
-                    render_box_cut_buttons(ui, params, setter);
-                    ui.add_space(18.0);
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("SUB").size(LABEL_FONT).color(Color32::from_gray(140)));
-                        ui.add_space(6.0);
-                        render_toggle_switch(ui, params, setter, &params.master_hpf_sub, "OUT", "IN");
-                    });
-                    ui.add_space(20.0);
-                    let chart_x = ui.cursor().left();
-                    let chart_y = ui.cursor().top() + 12.0;
-                    render_filter_visualization(ui, params, chart_x, chart_y);
-                });
-            });
+    // ===== LEFT PANEL: TABLE SELECTION & PREVIEW =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("WAVETABLE").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        ui.horizontal(|ui| {
+            render_wavetable_selector(ui, params, ui_state);
+        });
 
-        ui.add_space(20.0);
+        let table = ui_state.wavetable.current();
+        let Some(table) = table else { return };
 
-        egui::Frame::NONE
-            .inner_margin(egui::Margin { left: 5, right: 0, ..FRAME_MARGIN })
-            .show(ui, |ui| {
-                ui.vertical(|ui| {
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("BRILL").size(HEADER_FONT).strong());
-                        ui.add_space(25.0);
-                        ui.label(egui::RichText::new("STEREO").size(HEADER_FONT).strong());
-                    });
-                    ui.add_space(10.0);
-                    ui.horizontal(|ui| {
-                        ui.spacing_mut().item_spacing.x = 5.0;
-                        let brill_color = Some(Color32::from_rgb(160, 130, 60));
-                        render_vertical_slider_with_ticks(
-                            ui, params, setter,
-                            &params.brilliance_amount, "BRILL",
-                            0.0, 1.0, SliderScale::Linear,
-                            brill_color,
-                            &[(0.0, "OFF"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")],
-                            None,
-                        );
-                        let stereo_color = Some(Color32::from_rgb(50, 130, 110));
-                        ui.add_space(15.0);
-                        render_vertical_slider_with_ticks(
-                            ui, params, setter,
-                            &params.stereo_mono_bass, "MON",
-                            0.0, 300.0, SliderScale::Linear,
-                            stereo_color,
-                            &[(0.0, "OFF"), (80.0, "80Hz"), (120.0, "120"), (200.0, "200"), (300.0, "300Hz")],
-                            None,
-                        );
-                        render_vertical_slider_with_ticks(
-                            ui, params, setter,
-                            &params.stereo_width, "WIDTH",
-                            0.0, 2.0, SliderScale::Linear,
-                            stereo_color,
-                            &[(0.0, "0%"), (0.5, "50%"), (1.0, "100%"), (1.5, "150%"), (2.0, "200%")],
-                            None,
-                        );
-                    });
+        ui.add_space(6.0);
+        ui.label(
+            egui::RichText::new(format!("{} FRAMES", table.frame_count()))
+                .size(LABEL_FONT)
+                .color(Color32::from_gray(140)),
+        );
+
+        // Preview of the frame under the position knob, read from the
+        // full-bandwidth level
+        ui.add_space(13.0);
+        let preview_w = (ui.available_width() - 10.0).max(100.0);
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(preview_w, 160.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 4.0, Color32::from_rgb(24, 24, 30));
+        ui.painter().line_segment(
+            [egui::pos2(rect.left(), rect.center().y), egui::pos2(rect.right(), rect.center().y)],
+            egui::Stroke::new(1.0, Color32::from_gray(50)),
+        );
+        let position = params.synth_wt_position.modulated_plain_value() as f64;
+        let steps = 256;
+        let points: Vec<egui::Pos2> = (0..=steps)
+            .map(|i| {
+                let phase = i as f64 / steps as f64;
+                let y = table.sample(phase % 1.0, position, 0.0) as f32;
+                egui::pos2(
+                    rect.left() + phase as f32 * rect.width(),
+                    rect.center().y - y.clamp(-1.0, 1.0) * rect.height() * 0.45,
+                )
+            })
+            .collect();
+        ui.painter().add(egui::Shape::line(points, egui::Stroke::new(2.0, accent)));
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            render_int_vertical_slider(
+                ui, params, setter,
+                &params.synth_wt_octave, "OCT",
+                pitch_color,
+                None, None, ml!("synth_wt_octave"),
+            );
+            render_int_vertical_slider(
+                ui, params, setter,
+                &params.synth_wt_tune, "TUNE",
+                pitch_color,
+                Some(&[-12, 0, 12]), None, ml!("synth_wt_tune"),
+            );
+            render_vertical_slider(
+                ui, params, setter,
+                &params.synth_wt_fine, "FINE",
+                -1.0, 1.0, SliderScale::Linear,
+                pitch_color,
+                ml!("synth_wt_fine"),
+            );
+            render_vertical_slider(
+                ui, params, setter,
+                &params.synth_wt_position, "POS",
+                0.0, 1.0, SliderScale::Linear,
+                Some(accent),
+                ml!("synth_wt_position"),
+            );
+            render_vertical_slider(
+                ui, params, setter,
+                &params.synth_wt_volume, "VOL",
+                0.0, 1.0, SliderScale::Linear,
+                Some(Color32::from_rgb(40, 80, 40)),
+                ml!("synth_wt_volume"),
+            );
+        });
+    });
+}
+
+fn render_wavetable_selector(ui: &mut egui::Ui, params: &Arc<DeviceParams>, ui_state: &Arc<SharedUiState>) {
+    let tables_id = egui::Id::new("wavetable_list_cache");
+    let tables: Vec<String> = ui.memory_mut(|mem| {
+        mem.data.get_temp_mut_or_insert_with(tables_id, wavetable::list_wavetables).clone()
+    });
+
+    let current_name = ui_state.wavetable_name();
+
+    egui::ComboBox::from_id_salt(egui::Id::new("wavetable_combo"))
+        .selected_text(egui::RichText::new(&current_name).size(14.0))
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            for name in &tables {
+                if ui.selectable_label(*name == current_name, egui::RichText::new(name).size(13.0)).clicked() {
+                    if let Ok(table) = wavetable::load_wavetable(name) {
+                        if let Ok(mut persisted) = params.wavetable_name.write() {
+                            *persisted = name.clone();
+                        }
+                        ui_state.set_wavetable(table);
+                    }
+                }
+            }
+        });
+
+    let refresh_btn = egui::Button::new(egui::RichText::new("Refresh").size(12.0))
+        .min_size(egui::vec2(55.0, 24.0));
+    if ui.add(refresh_btn).clicked() {
+        let fresh = wavetable::list_wavetables();
+        ui.memory_mut(|mem| {
+            mem.data.insert_temp(tables_id, fresh);
+        });
+    }
+}
+
+fn render_noise_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let accent = Color32::from_rgb(170, 170, 180);
+    let tone_color = Some(Color32::from_rgb(90, 90, 110));
+    let inject_color = Some(Color32::from_rgb(40, 40, 80));
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("NOISE").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        ui.label(egui::RichText::new("COLOR").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_noise_type,
+            &[("WHITE", 0), ("PINK", 1), ("BROWN", 2)], 80.0, accent);
+        ui.add_space(4.0);
+        render_int_choice_buttons(ui, setter, &params.synth_noise_type,
+            &[("CRKL", 3), ("VELV", 4), ("S&H", 5)], 80.0, accent);
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("ENVELOPE").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_noise_env,
+            &[("AMP", 0), ("FILT", 1)], 80.0, accent);
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("PLL INJECT").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_noise_inject,
+            &[("OFF", 0), ("REF", 1), ("LOOP", 2), ("VCO", 3)], 56.0, accent);
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            render_vertical_slider(
+                ui, params, setter,
+                &params.synth_noise_level, "LVL",
+                0.0, 1.0, SliderScale::Linear,
+                Some(Color32::from_rgb(40, 80, 40)),
+                ml!("synth_noise_level"),
+            );
+            render_vertical_slider_with_ticks(
+                ui, params, setter, &params.synth_noise_rate, "RATE",
+                1.0, 20000.0, SliderScale::Logarithmic, tone_color,
+                &[(1.0, "1"), (100.0, "100"), (1000.0, "1k"), (20000.0, "20k")], None,
+            );
+            render_vertical_slider_with_ticks(
+                ui, params, setter, &params.synth_noise_hpf, "HPF",
+                20.0, 20000.0, SliderScale::Logarithmic, tone_color,
+                &[(20.0, "OFF"), (200.0, "200"), (2000.0, "2k"), (20000.0, "20k")], None,
+            );
+            render_vertical_slider_with_ticks(
+                ui, params, setter, &params.synth_noise_lpf, "LPF",
+                20.0, 20000.0, SliderScale::Logarithmic, tone_color,
+                &[(20.0, "20"), (200.0, "200"), (2000.0, "2k"), (20000.0, "OFF")], None,
+            );
+            ui.add_space(25.0);
+            render_vertical_slider(
+                ui, params, setter,
+                &params.synth_noise_inject_amount, "INJ",
+                0.0, 1.0, SliderScale::Linear,
+                inject_color,
+                ml!("synth_noise_inject_amount"),
+            );
+        });
+    });
+}
+
+fn render_sync_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let accent = Color32::from_rgb(190, 120, 190);
+    let modes: &[(&str, i32)] = &[("OFF", 0), ("HARD", 1), ("SOFT", 2)];
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("SYNC").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        ui.label(egui::RichText::new("MASTER").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_sync_source,
+            &[("PLL REF", 0), ("PLL VCO", 1)], 100.0, accent);
+
+        for (label, param) in [
+            ("VPS", &params.synth_vps_sync),
+            ("SAW", &params.synth_saw_sync),
+        ] {
+            ui.add_space(13.0);
+            ui.label(egui::RichText::new(label).size(LABEL_FONT).color(Color32::from_gray(140)));
+            ui.add_space(6.0);
+            render_int_choice_buttons(ui, setter, param, modes, 80.0, accent);
+        }
+
+        // The VCO always follows the reference, whatever the master above
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("PLL VCO → REF").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_pll_sync, modes, 80.0, accent);
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            render_vertical_slider_with_ticks(
+                ui, params, setter, &params.synth_sync_soft, "SOFT",
+                0.0, 1.0, SliderScale::Linear, Some(accent),
+                &[(0.0, "0"), (0.5, "50%"), (1.0, "HARD")],
+                ml!("synth_sync_soft"),
+            );
+        });
+    });
+}
+
+fn render_topology_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let accent = Color32::from_rgb(80, 80, 160);
+    let topology = params.synth_pll_topology.value();
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("PLL TOPOLOGY").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        ui.label(egui::RichText::new("LOOPS").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_pll_topology,
+            &[("STEREO", 0), ("CASCADE", 1), ("DUAL", 2)], 80.0, accent);
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("REFERENCE").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_pll_ref_source,
+            &[("REF", 0), ("VPS", 1), ("SAW", 2)], 80.0, accent);
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            // Loop 2 only runs in CASCADE and DUAL
+            let loop2 = if topology != 0 { Some(accent) } else { Some(Color32::from_gray(60)) };
+            render_vertical_slider(
+                ui, params, setter, &params.synth_pll2_track_speed, "TRK2",
+                0.0, 1.0, SliderScale::Linear, loop2,
+                ml!("synth_pll2_track_speed"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_pll2_damping, "DMP2",
+                0.0, 1.0, SliderScale::Linear, loop2,
+                ml!("synth_pll2_damping"),
+            );
+            render_int_vertical_slider(
+                ui, params, setter,
+                &params.synth_pll2_mult, "MUL2",
+                loop2,
+                None,
+                Some(&["1", "2", "4", "8", "16", "32", "64"]),
+                ml!("synth_pll2_mult"),
+            );
+            let coupled = if topology == 2 { Some(accent) } else { Some(Color32::from_gray(60)) };
+            render_vertical_slider(
+                ui, params, setter, &params.synth_pll_coupling, "CPL",
+                0.0, 1.0, SliderScale::Linear, coupled,
+                ml!("synth_pll_coupling"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_pll_ref_mix, "MIX",
+                0.0, 1.0, SliderScale::Linear, Some(Color32::from_rgb(80, 80, 40)),
+                ml!("synth_pll_ref_mix"),
+            );
+        });
+    });
+}
+
+fn render_filter2_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let accent = Color32::from_rgb(130, 80, 160);
+    let filter2_on = params.synth_filter2_enable.value();
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("FILTER 2").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        ui.horizontal(|ui| {
+            let mut on = filter2_on;
+            render_toggle(ui, &mut on, "ON", ml!("synth_filter2_enable"));
+            if on != filter2_on {
+                setter.set_parameter(&params.synth_filter2_enable, on);
+            }
+        });
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("TYPE").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_filter2_type,
+            &[("NOTCH", 0), ("PEAK", 1), ("ALLP", 2), ("COMB", 3), ("VOWEL", 4)], 60.0, accent);
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("ROUTING").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_filter_routing,
+            &[("SERIAL", 0), ("PARALLEL", 1), ("SPLIT", 2)], 80.0, accent);
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            let dim = |color: Color32| if filter2_on { Some(color) } else { Some(Color32::from_gray(60)) };
+            render_vertical_slider(
+                ui, params, setter, &params.synth_filter2_cutoff, "CUT",
+                20.0, 20000.0, SliderScale::Exponential(3.0), dim(accent),
+                ml!("synth_filter2_cutoff"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_filter2_resonance, "RES",
+                0.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(80, 90, 160)),
+                ml!("synth_filter2_resonance"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_filter2_morph, "MRPH",
+                0.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(150, 70, 80)),
+                ml!("synth_filter2_morph"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_filter2_key_track, "KEY",
+                0.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(60, 120, 110)),
+                ml!("synth_filter2_key_track"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_filter2_env_amount, "ENV",
+                -1.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(140, 110, 50)),
+                ml!("synth_filter2_env_amount"),
+            );
+        });
+    });
+}
+
+fn render_resonator_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let accent = Color32::from_rgb(60, 130, 140);
+    let resonator_on = params.synth_resonator_enable.value();
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("RESONATOR").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        ui.horizontal(|ui| {
+            let mut on = resonator_on;
+            render_toggle(ui, &mut on, "ON", ml!("synth_resonator_enable"));
+            if on != resonator_on {
+                setter.set_parameter(&params.synth_resonator_enable, on);
+            }
+        });
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("MODEL").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_resonator_model,
+            &[("STRING", 0), ("BAR", 1), ("BELL", 2), ("TUBE", 3)], 60.0, accent);
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("EXCITE").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.synth_resonator_excite,
+            &[("MIX", 0), ("NOISE", 1), ("BOTH", 2)], 60.0, accent);
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            let dim = |color: Color32| if resonator_on { Some(color) } else { Some(Color32::from_gray(60)) };
+            render_vertical_slider(
+                ui, params, setter, &params.synth_resonator_decay, "DCY",
+                0.0, 1.0, SliderScale::Linear, dim(accent),
+                ml!("synth_resonator_decay"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_resonator_brightness, "BRT",
+                0.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(150, 120, 60)),
+                ml!("synth_resonator_brightness"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_resonator_position, "POS",
+                0.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(90, 80, 150)),
+                ml!("synth_resonator_position"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_resonator_mix, "MIX",
+                0.0, 1.0, SliderScale::Linear, dim(Color32::from_rgb(80, 80, 40)),
+                ml!("synth_resonator_mix"),
+            );
+        });
+    });
+}
+
+fn render_cross_mod_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+    ui_state: &Arc<SharedUiState>,
+) {
+    let ml = &*ui_state.midi_learn;
+    macro_rules! ml {
+        ($id:expr) => { Some((ml, $id)) };
+    }
+    let accent = Color32::from_rgb(170, 130, 40);
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: PATH TOGGLES =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("CROSS MOD").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        for (label, param, id) in [
+            ("RING  PLL × REF", &params.synth_ring_pll_enable, "synth_ring_pll_enable"),
+            ("RING  VPS × SAW", &params.synth_ring_vps_saw_enable, "synth_ring_vps_saw_enable"),
+            ("FM  VPS → SAW", &params.synth_xfm_vps_saw_enable, "synth_xfm_vps_saw_enable"),
+            ("FM  SAW → REF", &params.synth_xfm_saw_ref_enable, "synth_xfm_saw_ref_enable"),
+        ] {
+            ui.horizontal(|ui| {
+                let mut on = param.value();
+                render_toggle(ui, &mut on, "ON", ml!(id));
+                if on != param.value() {
+                    setter.set_parameter(param, on);
+                }
+                ui.add_space(8.0);
+                ui.label(egui::RichText::new(label).size(LABEL_FONT).color(Color32::from_gray(140)));
+            });
+            ui.add_space(10.0);
+        }
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 5.0;
+            let dim = |on: bool| if on { Some(accent) } else { Some(Color32::from_gray(60)) };
+            render_vertical_slider(
+                ui, params, setter, &params.synth_ring_pll, "RING",
+                0.0, 1.0, SliderScale::Linear, dim(params.synth_ring_pll_enable.value()),
+                ml!("synth_ring_pll"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_ring_vps_saw, "V×S",
+                0.0, 1.0, SliderScale::Linear, dim(params.synth_ring_vps_saw_enable.value()),
+                ml!("synth_ring_vps_saw"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_xfm_vps_saw, "V→S",
+                0.0, 1.0, SliderScale::Linear, dim(params.synth_xfm_vps_saw_enable.value()),
+                ml!("synth_xfm_vps_saw"),
+            );
+            render_vertical_slider(
+                ui, params, setter, &params.synth_xfm_saw_ref, "S→R",
+                0.0, 1.0, SliderScale::Linear, dim(params.synth_xfm_saw_ref_enable.value()),
+                ml!("synth_xfm_saw_ref"),
+            );
+        });
+    });
+}
+
+fn render_lofi_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+) {
+    let accent = Color32::from_rgb(200, 170, 90);
+    let crush_color = Some(accent);
+    let shape_color = Some(Color32::from_rgb(200, 120, 90));
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS & TOGGLES =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("LO-FI").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        {
+            let on = params.lofi_enable.value();
+            let btn_w = 80.0;
+            let btn_h = 48.0;
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 5.0;
+                for (label, active) in &[("OFF", false), ("ON", true)] {
+                    let is_selected = on == *active;
+                    let (bg, text_col) = if is_selected {
+                        if *active {
+                            (Color32::from_rgb(80, 160, 80), Color32::WHITE)
+                        } else {
+                            (Color32::from_rgb(180, 60, 60), Color32::WHITE)
+                        }
+                    } else {
+                        (Color32::from_rgb(40, 40, 48), Color32::from_gray(160))
+                    };
+                    let (rect, response) = ui.allocate_exact_size(
+                        egui::vec2(btn_w, btn_h),
+                        egui::Sense::click(),
+                    );
+                    let hover_bg = if response.hovered() && !is_selected {
+                        Color32::from_rgb(55, 55, 65)
+                    } else {
+                        bg
+                    };
+                    ui.painter().rect_filled(rect, 4.0, hover_bg);
+                    if is_selected {
+                        let stroke_col = if *active {
+                            Color32::from_rgb(100, 190, 100)
+                        } else {
+                            Color32::from_rgb(210, 80, 80)
+                        };
+                        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, stroke_col), egui::epaint::StrokeKind::Inside);
+                    }
+                    let font = egui::FontId::proportional(LABEL_FONT);
+                    let galley = ui.painter().layout_no_wrap(label.to_string(), font, text_col);
+                    let text_pos = rect.center() - galley.size() / 2.0;
+                    ui.painter().galley(text_pos, galley, text_col);
+                    if response.clicked() {
+                        setter.set_parameter(&params.lofi_enable, *active);
+                    }
+                }
+            });
+        }
+
+        ui.add_space(13.0);
+        render_route_toggle(ui, setter, &params.lofi_post_filter, "POST FILTER");
+
+        // Source selection only applies ahead of the filter, where the
+        // oscillators are still separate
+        let pre_filter = !params.lofi_post_filter.value();
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("SOURCES").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 8.0;
+            render_route_toggle_dimmed(ui, setter, &params.lofi_vps, "VPS", !pre_filter);
+            render_route_toggle_dimmed(ui, setter, &params.lofi_pll, "PLL", !pre_filter);
+            render_route_toggle_dimmed(ui, setter, &params.lofi_saw, "SAW", !pre_filter);
+        });
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("SHAPER").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.lofi_shaper,
+            &[("OFF", 0), ("SOFT", 1), ("ASYM", 2), ("FOLD", 3)], 56.0, accent);
+        ui.add_space(4.0);
+        render_int_choice_buttons(ui, setter, &params.lofi_shaper,
+            &[("SIN", 4), ("PI", 5), ("CLIP", 6), ("TUBE", 7)], 56.0, accent);
+
+        ui.add_space(13.0);
+        render_route_toggle(ui, setter, &params.lofi_anti_alias, "ANTI-ALIAS");
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("CRUSH").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.lofi_bits, "BITS",
+                        1.0, 16.0, SliderScale::Linear, crush_color,
+                        &[(1.0, "1"), (4.0, "4"), (8.0, "8"), (12.0, "12"), (16.0, "16")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.lofi_rate, "RATE",
+                        100.0, 48000.0, SliderScale::Logarithmic, crush_color,
+                        &[(100.0, "100"), (1000.0, "1k"), (8000.0, "8k"), (22050.0, "22k"), (48000.0, "OFF")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.lofi_jitter, "JITR",
+                        0.0, 1.0, SliderScale::Linear, crush_color,
+                        &[(0.0, "OFF"), (0.5, "50%"), (1.0, "100%")], None,
+                    );
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("SHAPE").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.lofi_drive, "DRV",
+                        0.0, 1.0, SliderScale::Linear, shape_color,
+                        &[(0.0, "0"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.lofi_mix, "MIX",
+                        0.0, 1.0, SliderScale::Linear, shape_color,
+                        &[(0.0, "DRY"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "WET")], None,
+                    );
+                });
+            });
+        });
+    });
+}
+
+fn render_fx_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+) {
+    ui.horizontal(|ui| {
+        egui::Frame::NONE
+            .inner_margin(egui::Margin { left: FRAME_MARGIN.left + 5, ..FRAME_MARGIN })
+            .show(ui, |ui| {
+                render_looper_section(ui, params, setter);
+            });
+
+        ui.add_space(10.0);
+
+        egui::Frame::NONE
+            .inner_margin(egui::Margin { left: 5, ..FRAME_MARGIN })
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("MASTER HPF").size(HEADER_FONT).strong());
+                    ui.add_space(9.0);
+                    render_hpf_buttons(ui, params, setter);
+                    ui.add_space(13.0);
+                    ui.label(egui::RichText::new("BOOST").size(LABEL_FONT).color(Color32::from_gray(140)));
+                    ui.add_space(6.0);
+                    render_hpf_boost_buttons(ui, params, setter);
+                    ui.add_space(13.0);
+                    ui.label(egui::RichText::new("BOX CUT").size(LABEL_FONT).color(Color32::from_gray(140)));
+                    ui.add_space(6.0);
+                    render_box_cut_buttons(ui, params, setter);
+                    ui.add_space(18.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("SUB").size(LABEL_FONT).color(Color32::from_gray(140)));
+                        ui.add_space(6.0);
+                        render_toggle_switch(ui, params, setter, &params.master_hpf_sub, "OUT", "IN");
+                    });
+                    ui.add_space(20.0);
+                    let chart_x = ui.cursor().left();
+                    let chart_y = ui.cursor().top() + 12.0;
+                    render_filter_visualization(ui, params, chart_x, chart_y);
+                });
+            });
+
+        ui.add_space(20.0);
+
+        egui::Frame::NONE
+            .inner_margin(egui::Margin { left: 5, right: 0, ..FRAME_MARGIN })
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("BRILL").size(HEADER_FONT).strong());
+                        ui.add_space(25.0);
+                        ui.label(egui::RichText::new("STEREO").size(HEADER_FONT).strong());
+                    });
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 5.0;
+                        let brill_color = Some(Color32::from_rgb(160, 130, 60));
+                        render_vertical_slider_with_ticks(
+                            ui, params, setter,
+                            &params.brilliance_amount, "BRILL",
+                            0.0, 1.0, SliderScale::Linear,
+                            brill_color,
+                            &[(0.0, "OFF"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")],
+                            None,
+                        );
+                        let stereo_color = Some(Color32::from_rgb(50, 130, 110));
+                        ui.add_space(15.0);
+                        render_vertical_slider_with_ticks(
+                            ui, params, setter,
+                            &params.stereo_mono_bass, "MON",
+                            0.0, 300.0, SliderScale::Linear,
+                            stereo_color,
+                            &[(0.0, "OFF"), (80.0, "80Hz"), (120.0, "120"), (200.0, "200"), (300.0, "300Hz")],
+                            None,
+                        );
+                        render_vertical_slider_with_ticks(
+                            ui, params, setter,
+                            &params.stereo_width, "WIDTH",
+                            0.0, 2.0, SliderScale::Linear,
+                            stereo_color,
+                            &[(0.0, "0%"), (0.5, "50%"), (1.0, "100%"), (1.5, "150%"), (2.0, "200%")],
+                            None,
+                        );
+                    });
+                });
+            });
+    });
+}
+
+fn render_lush_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+) {
+    let reverb_color = Some(Color32::from_rgb(90, 110, 190));
+    let tone_color = Some(Color32::from_rgb(80, 140, 140));
+    let input_color = Some(Color32::from_rgb(140, 100, 160));
+    let mod_color = Some(Color32::from_rgb(130, 130, 80));
+    let duck_color = Some(Color32::from_rgb(160, 120, 80));
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS & TOGGLES =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("REVERB").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        {
+            let on = params.synth_reverb_enable.value();
+            let btn_w = 80.0;
+            let btn_h = 48.0;
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 5.0;
+                for (label, active) in &[("OFF", false), ("ON", true)] {
+                    let is_selected = on == *active;
+                    let (bg, text_col) = if is_selected {
+                        if *active {
+                            (Color32::from_rgb(80, 160, 80), Color32::WHITE)
+                        } else {
+                            (Color32::from_rgb(180, 60, 60), Color32::WHITE)
+                        }
+                    } else {
+                        (Color32::from_rgb(40, 40, 48), Color32::from_gray(160))
+                    };
+                    let (rect, response) = ui.allocate_exact_size(
+                        egui::vec2(btn_w, btn_h),
+                        egui::Sense::click(),
+                    );
+                    let hover_bg = if response.hovered() && !is_selected {
+                        Color32::from_rgb(55, 55, 65)
+                    } else {
+                        bg
+                    };
+                    ui.painter().rect_filled(rect, 4.0, hover_bg);
+                    if is_selected {
+                        let stroke_col = if *active {
+                            Color32::from_rgb(100, 190, 100)
+                        } else {
+                            Color32::from_rgb(210, 80, 80)
+                        };
+                        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, stroke_col), egui::epaint::StrokeKind::Inside);
+                    }
+                    let font = egui::FontId::proportional(LABEL_FONT);
+                    let galley = ui.painter().layout_no_wrap(label.to_string(), font, text_col);
+                    let text_pos = rect.center() - galley.size() / 2.0;
+                    ui.painter().galley(text_pos, galley, text_col);
+                    if response.clicked() {
+                        setter.set_parameter(&params.synth_reverb_enable, *active);
+                    }
+                }
+            });
+        }
+
+        ui.add_space(13.0);
+        let rev_filter_on = params.synth_reverb_send_filter.value();
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 16.0;
+            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_vps, "VPS", rev_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_pll, "PLL", rev_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_saw, "SAW", rev_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_wt, "WT", rev_filter_on);
+        });
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 16.0;
+            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_sub, "SUB", rev_filter_on);
+            render_route_toggle(ui, setter, &params.synth_reverb_send_filter, "FLTR");
+            render_route_toggle(ui, setter, &params.synth_reverb_send_looper, "LOOP");
+        });
+
+        ui.add_space(13.0);
+        render_route_toggle(ui, setter, &params.synth_reverb_pre_delay_sync, "PRE-DELAY SYNC");
+        if params.synth_reverb_pre_delay_sync.value() {
+            ui.add_space(6.0);
+            render_looper_division_combo(ui, setter, &params.synth_reverb_pre_delay_division,
+                "predly_div", 100.0, Color32::from_rgb(90, 110, 190));
+        }
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("DUCKING").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("REL").size(12.0).color(Color32::from_gray(120)));
+            ui.add_space(2.0);
+            render_looper_division_combo(ui, setter, &params.synth_reverb_duck_division,
+                "duck_div", 80.0, Color32::from_rgb(160, 120, 80));
+        });
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("SIDECHAIN").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("RATE").size(12.0).color(Color32::from_gray(120)));
+            ui.add_space(2.0);
+            render_looper_division_combo(ui, setter, &params.synth_reverb_rhythm_duck_division,
+                "rhythm_duck_div", 80.0, Color32::from_rgb(160, 120, 80));
+        });
+    });
+
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        // ===== ROW 1: REVERB + TONE =====
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("REVERB").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_mix, "MIX",
+                        0.0, 1.0, SliderScale::Linear, reverb_color,
+                        &[(0.0, "DRY"), (0.25, "-12dB"), (0.5, "-6dB"), (0.75, "-3dB"), (1.0, "WET")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_decay, "DCY",
+                        0.0, 1.0, SliderScale::Linear, reverb_color,
+                        &[(0.0, "SHORT"), (0.25, "1s"), (0.5, "3s"), (0.75, "8s"), (1.0, "INF")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_time_scale, "SIZE",
+                        0.0, 1.0, SliderScale::Linear, reverb_color,
+                        &[(0.0, "TINY"), (0.25, "SM"), (0.5, "MED"), (0.75, "LG"), (1.0, "HALL")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_stereo_width, "WDTH",
+                        0.0, 1.0, SliderScale::Linear, reverb_color,
+                        &[(0.0, "MONO"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "FULL")], None,
+                    );
+                    if !params.synth_reverb_pre_delay_sync.value() {
+                        render_vertical_slider_with_ticks(
+                            ui, params, setter, &params.synth_reverb_pre_delay, "DLY",
+                            0.0, 500.0, SliderScale::Linear, reverb_color,
+                            &[(0.0, "0ms"), (50.0, "50"), (125.0, "125"), (250.0, "250"), (500.0, "500")], None,
+                        );
+                    }
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("TONE").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_lpf, "LPF",
+                        20.0, 22000.0, SliderScale::Logarithmic, tone_color,
+                        &[(20.0, "20"), (200.0, "200"), (2000.0, "2k"), (10000.0, "10k"), (22000.0, "22k")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_hpf, "HPF",
+                        20.0, 22000.0, SliderScale::Logarithmic, tone_color,
+                        &[(20.0, "20"), (100.0, "100"), (500.0, "500"), (2000.0, "2k"), (10000.0, "10k")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_diffusion, "DIFF",
+                        0.0, 1.0, SliderScale::Linear, tone_color,
+                        &[(0.0, "CLEAN"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "LUSH")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_diffusion_mix, "DIFM",
+                        0.0, 1.0, SliderScale::Linear, tone_color,
+                        &[(0.0, "0%"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_saturation, "SAT",
+                        0.0, 1.0, SliderScale::Linear, tone_color,
+                        &[(0.0, "CLEAN"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "WARM")], None,
+                    );
+                });
+            });
+        });
+
+        ui.add_space(15.0);
+
+        // ===== ROW 2: INPUT + MOD + DUCKING + SIDECHAIN =====
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("INPUT").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_input_hpf, "IHPF",
+                        20.0, 22000.0, SliderScale::Logarithmic, input_color,
+                        &[(20.0, "20"), (100.0, "100"), (500.0, "500"), (2000.0, "2k"), (10000.0, "10k")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_input_lpf, "ILPF",
+                        20.0, 22000.0, SliderScale::Logarithmic, input_color,
+                        &[(20.0, "20"), (200.0, "200"), (2000.0, "2k"), (10000.0, "10k"), (22000.0, "22k")], None,
+                    );
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("MOD").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_mod_depth, "DPTH",
+                        0.0, 1.0, SliderScale::Linear, mod_color,
+                        &[(0.0, "OFF"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_mod_speed, "SPD",
+                        0.0, 1.0, SliderScale::Linear, mod_color,
+                        &[(0.0, "SLOW"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "FAST")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_mod_shape, "SHPE",
+                        0.0, 1.0, SliderScale::Linear, mod_color,
+                        &[(0.0, "SIN"), (0.5, "MIX"), (1.0, "RND")], None,
+                    );
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("DUCKING").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_ducking, "DUCK",
+                        0.0, 1.0, SliderScale::Linear, duck_color,
+                        &[(0.0, "OFF"), (0.25, "-3dB"), (0.5, "-6dB"), (0.75, "-12dB"), (1.0, "-∞")], None,
+                    );
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("SIDECHAIN").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_rhythm_duck_depth, "DPTH",
+                        0.0, 1.0, SliderScale::Linear, duck_color,
+                        &[(0.0, "OFF"), (0.25, "-3dB"), (0.5, "-6dB"), (0.75, "-12dB"), (1.0, "-∞")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_reverb_rhythm_duck_smooth, "SMTH",
+                        10.0, 300.0, SliderScale::Logarithmic, duck_color,
+                        &[(10.0, "10ms"), (50.0, "50"), (100.0, "100"), (200.0, "200"), (300.0, "300")], None,
+                    );
                 });
             });
+        });
     });
 }
 
-fn render_lush_tab(
+fn render_delay_tab(
     ui: &mut egui::Ui,
     params: &Arc<DeviceParams>,
     setter: &ParamSetter,
 ) {
-    let reverb_color = Some(Color32::from_rgb(90, 110, 190));
+    let delay_color = Some(Color32::from_rgb(190, 130, 70));
     let tone_color = Some(Color32::from_rgb(80, 140, 140));
-    let input_color = Some(Color32::from_rgb(140, 100, 160));
     let mod_color = Some(Color32::from_rgb(130, 130, 80));
     let duck_color = Some(Color32::from_rgb(160, 120, 80));
 
@@ -2031,10 +3172,10 @@ fn render_lush_tab(
     );
     let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
     left_ui.vertical(|ui| {
-        ui.label(egui::RichText::new("REVERB").size(HEADER_FONT).strong());
+        ui.label(egui::RichText::new("DELAY").size(HEADER_FONT).strong());
         ui.add_space(9.0);
         {
-            let on = params.synth_reverb_enable.value();
+            let on = params.synth_delay_enable.value();
             let btn_w = 80.0;
             let btn_h = 48.0;
             ui.horizontal(|ui| {
@@ -2073,55 +3214,314 @@ fn render_lush_tab(
                     let text_pos = rect.center() - galley.size() / 2.0;
                     ui.painter().galley(text_pos, galley, text_col);
                     if response.clicked() {
-                        setter.set_parameter(&params.synth_reverb_enable, *active);
+                        setter.set_parameter(&params.synth_delay_enable, *active);
                     }
                 }
             });
         }
 
         ui.add_space(13.0);
-        let rev_filter_on = params.synth_reverb_send_filter.value();
+        ui.label(egui::RichText::new("MODE").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_delay_mode_buttons(ui, params, setter);
+
+        ui.add_space(13.0);
+        let dly_filter_on = params.synth_delay_send_filter.value();
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = 16.0;
-            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_vps, "VPS", rev_filter_on);
-            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_pll, "PLL", rev_filter_on);
-            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_saw, "SAW", rev_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_delay_send_vps, "VPS", dly_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_delay_send_pll, "PLL", dly_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_delay_send_saw, "SAW", dly_filter_on);
         });
         ui.add_space(6.0);
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = 16.0;
-            render_route_toggle_dimmed(ui, setter, &params.synth_reverb_send_sub, "SUB", rev_filter_on);
-            render_route_toggle(ui, setter, &params.synth_reverb_send_filter, "FLTR");
-            render_route_toggle(ui, setter, &params.synth_reverb_send_looper, "LOOP");
+            render_route_toggle_dimmed(ui, setter, &params.synth_delay_send_wt, "WT", dly_filter_on);
+            render_route_toggle_dimmed(ui, setter, &params.synth_delay_send_sub, "SUB", dly_filter_on);
+            render_route_toggle(ui, setter, &params.synth_delay_send_filter, "FLTR");
         });
 
         ui.add_space(13.0);
-        render_route_toggle(ui, setter, &params.synth_reverb_pre_delay_sync, "PRE-DELAY SYNC");
-        if params.synth_reverb_pre_delay_sync.value() {
+        render_route_toggle(ui, setter, &params.synth_delay_sync, "TEMPO SYNC");
+        if params.synth_delay_sync.value() {
             ui.add_space(6.0);
-            render_looper_division_combo(ui, setter, &params.synth_reverb_pre_delay_division,
-                "predly_div", 100.0, Color32::from_rgb(90, 110, 190));
+            render_looper_division_combo(ui, setter, &params.synth_delay_division,
+                "delay_div", 100.0, Color32::from_rgb(190, 130, 70));
         }
+    });
 
-        ui.add_space(13.0);
-        ui.label(egui::RichText::new("DUCKING").size(LABEL_FONT).color(Color32::from_gray(140)));
-        ui.add_space(6.0);
+    // ===== SEPARATOR =====
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    // ===== RIGHT PANEL: ALL SLIDERS =====
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x - 90.0 + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        // ===== ROW 1: DELAY + TONE =====
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("REL").size(12.0).color(Color32::from_gray(120)));
-            ui.add_space(2.0);
-            render_looper_division_combo(ui, setter, &params.synth_reverb_duck_division,
-                "duck_div", 80.0, Color32::from_rgb(160, 120, 80));
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("DELAY").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_mix, "MIX",
+                        0.0, 1.0, SliderScale::Linear, delay_color,
+                        &[(0.0, "DRY"), (0.25, "-12dB"), (0.5, "-6dB"), (0.75, "-3dB"), (1.0, "WET")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_feedback, "FDBK",
+                        0.0, 1.0, SliderScale::Linear, delay_color,
+                        &[(0.0, "0%"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "INF")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_spread, "SPRD",
+                        0.0, 1.0, SliderScale::Linear, delay_color,
+                        &[(0.0, "0%"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "WIDE")], None,
+                    );
+                    if !params.synth_delay_sync.value() {
+                        render_vertical_slider_with_ticks(
+                            ui, params, setter, &params.synth_delay_time, "TIME",
+                            1.0, 2000.0, SliderScale::Logarithmic, delay_color,
+                            &[(1.0, "1ms"), (10.0, "10"), (100.0, "100"), (500.0, "500"), (2000.0, "2s")], None,
+                        );
+                    }
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("TONE").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_lpf, "LPF",
+                        20.0, 22000.0, SliderScale::Logarithmic, tone_color,
+                        &[(20.0, "20"), (200.0, "200"), (2000.0, "2k"), (10000.0, "10k"), (22000.0, "22k")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_hpf, "HPF",
+                        20.0, 22000.0, SliderScale::Logarithmic, tone_color,
+                        &[(20.0, "20"), (100.0, "100"), (500.0, "500"), (2000.0, "2k"), (10000.0, "10k")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_saturation, "SAT",
+                        0.0, 1.0, SliderScale::Linear, tone_color,
+                        &[(0.0, "CLEAN"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "WARM")], None,
+                    );
+                });
+            });
+        });
+
+        ui.add_space(15.0);
+
+        // ===== ROW 2: MOD + DUCKING =====
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("MOD").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_mod_depth, "DPTH",
+                        0.0, 1.0, SliderScale::Linear, mod_color,
+                        &[(0.0, "OFF"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
+                    );
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_mod_rate, "RATE",
+                        0.0, 1.0, SliderScale::Linear, mod_color,
+                        &[(0.0, "SLOW"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "FAST")], None,
+                    );
+                });
+            });
+
+            ui.add_space(25.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("DUCKING").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 5.0;
+                    render_vertical_slider_with_ticks(
+                        ui, params, setter, &params.synth_delay_ducking, "DUCK",
+                        0.0, 1.0, SliderScale::Linear, duck_color,
+                        &[(0.0, "OFF"), (0.25, "-3dB"), (0.5, "-6dB"), (0.75, "-12dB"), (1.0, "-∞")], None,
+                    );
+                });
+            });
         });
+    });
+}
+
+fn render_delay_mode_buttons(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+) {
+    let current = params.synth_delay_mode.value();
+    let labels = ["STEREO", "PING", "TAPE"];
+
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        for (i, label) in labels.iter().enumerate() {
+            let is_selected = current == i as i32;
+            let (bg, text_col) = if is_selected {
+                (Color32::from_rgb(85, 65, 45), Color32::WHITE)
+            } else {
+                (Color32::from_rgb(40, 40, 48), Color32::from_gray(140))
+            };
+
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(72.0, 32.0),
+                egui::Sense::click(),
+            );
+
+            let hover_bg = if response.hovered() && !is_selected {
+                Color32::from_rgb(55, 55, 65)
+            } else {
+                bg
+            };
+
+            ui.painter().rect_filled(rect, 4.0, hover_bg);
+            if is_selected {
+                ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, Color32::from_rgb(190, 130, 70)), egui::epaint::StrokeKind::Inside);
+            }
+
+            let font = egui::FontId::proportional(14.0);
+            let galley = ui.painter().layout_no_wrap(label.to_string(), font, text_col);
+            ui.painter().galley(
+                egui::pos2(rect.center().x - galley.size().x / 2.0, rect.center().y - galley.size().y / 2.0),
+                galley,
+                text_col,
+            );
+
+            if response.clicked() {
+                setter.set_parameter(&params.synth_delay_mode, i as i32);
+            }
+        }
+    });
+}
+
+fn render_mod_fx_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+) {
+    let accent = Color32::from_rgb(120, 170, 110);
+    let mfx_color = Some(accent);
+    let mod_color = Some(Color32::from_rgb(130, 130, 80));
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    // ===== LEFT PANEL: BUTTONS & TOGGLES =====
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("MOD FX").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+        {
+            let on = params.mod_fx_enable.value();
+            let btn_w = 80.0;
+            let btn_h = 48.0;
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 5.0;
+                for (label, active) in &[("OFF", false), ("ON", true)] {
+                    let is_selected = on == *active;
+                    let (bg, text_col) = if is_selected {
+                        if *active {
+                            (Color32::from_rgb(80, 160, 80), Color32::WHITE)
+                        } else {
+                            (Color32::from_rgb(180, 60, 60), Color32::WHITE)
+                        }
+                    } else {
+                        (Color32::from_rgb(40, 40, 48), Color32::from_gray(160))
+                    };
+                    let (rect, response) = ui.allocate_exact_size(
+                        egui::vec2(btn_w, btn_h),
+                        egui::Sense::click(),
+                    );
+                    let hover_bg = if response.hovered() && !is_selected {
+                        Color32::from_rgb(55, 55, 65)
+                    } else {
+                        bg
+                    };
+                    ui.painter().rect_filled(rect, 4.0, hover_bg);
+                    if is_selected {
+                        let stroke_col = if *active {
+                            Color32::from_rgb(100, 190, 100)
+                        } else {
+                            Color32::from_rgb(210, 80, 80)
+                        };
+                        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, stroke_col), egui::epaint::StrokeKind::Inside);
+                    }
+                    let font = egui::FontId::proportional(LABEL_FONT);
+                    let galley = ui.painter().layout_no_wrap(label.to_string(), font, text_col);
+                    let text_pos = rect.center() - galley.size() / 2.0;
+                    ui.painter().galley(text_pos, galley, text_col);
+                    if response.clicked() {
+                        setter.set_parameter(&params.mod_fx_enable, *active);
+                    }
+                }
+            });
+        }
+
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("MODE").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        render_int_choice_buttons(ui, setter, &params.mod_fx_mode,
+            &[("CHORUS", 0), ("FLANGE", 1), ("PHASE", 2)], 72.0, accent);
+
+        // Shortcut for the CHAIN tab: moves Mod FX next to the chosen module
+        ui.add_space(13.0);
+        ui.label(egui::RichText::new("POSITION").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        if let Some(position) = render_int_choice_buttons(ui, setter, &params.mod_fx_position,
+            &[("PRE LP", 0), ("POST LP", 1), ("POST RV", 2), ("POST CP", 3)], 72.0, accent)
+        {
+            let order = place_mod_fx(&params.fx_order(), position);
+            for (slot, module) in params.fx_slots().into_iter().zip(order) {
+                setter.set_parameter(slot, module.to_index());
+            }
+        }
+
+        ui.add_space(13.0);
+        match params.mod_fx_mode.value() {
+            0 => {
+                ui.label(egui::RichText::new("VOICES").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                render_int_choice_buttons(ui, setter, &params.mod_fx_voices,
+                    &[("1", 1), ("2", 2), ("3", 3), ("4", 4)], 48.0, accent);
+            }
+            2 => {
+                ui.label(egui::RichText::new("STAGES").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.add_space(6.0);
+                render_int_choice_buttons(ui, setter, &params.mod_fx_stages,
+                    &[("4", 4), ("6", 6), ("8", 8), ("10", 10), ("12", 12)], 48.0, accent);
+            }
+            _ => {}
+        }
 
         ui.add_space(13.0);
-        ui.label(egui::RichText::new("SIDECHAIN").size(LABEL_FONT).color(Color32::from_gray(140)));
-        ui.add_space(6.0);
-        ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("RATE").size(12.0).color(Color32::from_gray(120)));
-            ui.add_space(2.0);
-            render_looper_division_combo(ui, setter, &params.synth_reverb_rhythm_duck_division,
-                "rhythm_duck_div", 80.0, Color32::from_rgb(160, 120, 80));
-        });
+        render_route_toggle(ui, setter, &params.mod_fx_sync, "TEMPO SYNC");
+        if params.mod_fx_sync.value() {
+            ui.add_space(6.0);
+            render_looper_division_combo(ui, setter, &params.mod_fx_division,
+                "mod_fx_div", 100.0, accent);
+        }
     });
 
     // ===== SEPARATOR =====
@@ -2137,162 +3537,109 @@ fn render_lush_tab(
     );
     let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
     right_ui.vertical(|ui| {
-        // ===== ROW 1: REVERB + TONE =====
         ui.horizontal(|ui| {
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("REVERB").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.label(egui::RichText::new("MOD FX").size(LABEL_FONT).color(Color32::from_gray(140)));
                 ui.add_space(6.0);
                 ui.horizontal(|ui| {
                     ui.spacing_mut().item_spacing.x = 5.0;
                     render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_mix, "MIX",
-                        0.0, 1.0, SliderScale::Linear, reverb_color,
-                        &[(0.0, "DRY"), (0.25, "-12dB"), (0.5, "-6dB"), (0.75, "-3dB"), (1.0, "WET")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_decay, "DCY",
-                        0.0, 1.0, SliderScale::Linear, reverb_color,
-                        &[(0.0, "SHORT"), (0.25, "1s"), (0.5, "3s"), (0.75, "8s"), (1.0, "INF")], None,
+                        ui, params, setter, &params.mod_fx_mix, "MIX",
+                        0.0, 1.0, SliderScale::Linear, mfx_color,
+                        &[(0.0, "DRY"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "WET")], None,
                     );
                     render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_time_scale, "SIZE",
-                        0.0, 1.0, SliderScale::Linear, reverb_color,
-                        &[(0.0, "TINY"), (0.25, "SM"), (0.5, "MED"), (0.75, "LG"), (1.0, "HALL")], None,
+                        ui, params, setter, &params.mod_fx_feedback, "FDBK",
+                        -1.0, 1.0, SliderScale::Linear, mfx_color,
+                        &[(-1.0, "-100"), (-0.5, "-50"), (0.0, "0"), (0.5, "+50"), (1.0, "+100")], None,
                     );
                     render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_stereo_width, "WDTH",
-                        0.0, 1.0, SliderScale::Linear, reverb_color,
-                        &[(0.0, "MONO"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "FULL")], None,
+                        ui, params, setter, &params.mod_fx_spread, "SPRD",
+                        0.0, 1.0, SliderScale::Linear, mfx_color,
+                        &[(0.0, "MONO"), (0.5, "90°"), (1.0, "180°")], None,
                     );
-                    if !params.synth_reverb_pre_delay_sync.value() {
-                        render_vertical_slider_with_ticks(
-                            ui, params, setter, &params.synth_reverb_pre_delay, "DLY",
-                            0.0, 500.0, SliderScale::Linear, reverb_color,
-                            &[(0.0, "0ms"), (50.0, "50"), (125.0, "125"), (250.0, "250"), (500.0, "500")], None,
-                        );
-                    }
                 });
             });
 
             ui.add_space(25.0);
 
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("TONE").size(LABEL_FONT).color(Color32::from_gray(140)));
+                ui.label(egui::RichText::new("MOD").size(LABEL_FONT).color(Color32::from_gray(140)));
                 ui.add_space(6.0);
                 ui.horizontal(|ui| {
                     ui.spacing_mut().item_spacing.x = 5.0;
                     render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_lpf, "LPF",
-                        20.0, 22000.0, SliderScale::Logarithmic, tone_color,
-                        &[(20.0, "20"), (200.0, "200"), (2000.0, "2k"), (10000.0, "10k"), (22000.0, "22k")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_hpf, "HPF",
-                        20.0, 22000.0, SliderScale::Logarithmic, tone_color,
-                        &[(20.0, "20"), (100.0, "100"), (500.0, "500"), (2000.0, "2k"), (10000.0, "10k")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_diffusion, "DIFF",
-                        0.0, 1.0, SliderScale::Linear, tone_color,
-                        &[(0.0, "CLEAN"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "LUSH")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_diffusion_mix, "DIFM",
-                        0.0, 1.0, SliderScale::Linear, tone_color,
-                        &[(0.0, "0%"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_saturation, "SAT",
-                        0.0, 1.0, SliderScale::Linear, tone_color,
-                        &[(0.0, "CLEAN"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "WARM")], None,
+                        ui, params, setter, &params.mod_fx_depth, "DPTH",
+                        0.0, 1.0, SliderScale::Linear, mod_color,
+                        &[(0.0, "OFF"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
                     );
+                    if !params.mod_fx_sync.value() {
+                        render_vertical_slider_with_ticks(
+                            ui, params, setter, &params.mod_fx_rate, "RATE",
+                            0.01, 10.0, SliderScale::Logarithmic, mod_color,
+                            &[(0.01, ".01"), (0.1, "0.1"), (0.5, "0.5"), (2.0, "2Hz"), (10.0, "10")], None,
+                        );
+                    }
                 });
             });
         });
+    });
+}
 
-        ui.add_space(15.0);
-
-        // ===== ROW 2: INPUT + MOD + DUCKING + SIDECHAIN =====
-        ui.horizontal(|ui| {
-            ui.vertical(|ui| {
-                ui.label(egui::RichText::new("INPUT").size(LABEL_FONT).color(Color32::from_gray(140)));
-                ui.add_space(6.0);
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 5.0;
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_input_hpf, "IHPF",
-                        20.0, 22000.0, SliderScale::Logarithmic, input_color,
-                        &[(20.0, "20"), (100.0, "100"), (500.0, "500"), (2000.0, "2k"), (10000.0, "10k")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_input_lpf, "ILPF",
-                        20.0, 22000.0, SliderScale::Logarithmic, input_color,
-                        &[(20.0, "20"), (200.0, "200"), (2000.0, "2k"), (10000.0, "10k"), (22000.0, "22k")], None,
-                    );
-                });
-            });
+/// Row of exclusive buttons writing `value` into an IntParam.
+pub(super) fn render_int_choice_buttons(
+    ui: &mut egui::Ui,
+    setter: &ParamSetter,
+    param: &nih_plug::prelude::IntParam,
+    options: &[(&str, i32)],
+    btn_w: f32,
+    accent: Color32,
+) -> Option<i32> {
+    let current = param.value();
+    let mut clicked = None;
 
-            ui.add_space(25.0);
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        for (label, value) in options {
+            let is_selected = current == *value;
+            let (bg, text_col) = if is_selected {
+                let dim = |c: u8| (c as f32 * 0.45) as u8;
+                (Color32::from_rgb(dim(accent.r()), dim(accent.g()), dim(accent.b())), Color32::WHITE)
+            } else {
+                (Color32::from_rgb(40, 40, 48), Color32::from_gray(140))
+            };
 
-            ui.vertical(|ui| {
-                ui.label(egui::RichText::new("MOD").size(LABEL_FONT).color(Color32::from_gray(140)));
-                ui.add_space(6.0);
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 5.0;
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_mod_depth, "DPTH",
-                        0.0, 1.0, SliderScale::Linear, mod_color,
-                        &[(0.0, "OFF"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "100%")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_mod_speed, "SPD",
-                        0.0, 1.0, SliderScale::Linear, mod_color,
-                        &[(0.0, "SLOW"), (0.25, "25%"), (0.5, "50%"), (0.75, "75%"), (1.0, "FAST")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_mod_shape, "SHPE",
-                        0.0, 1.0, SliderScale::Linear, mod_color,
-                        &[(0.0, "SIN"), (0.5, "MIX"), (1.0, "RND")], None,
-                    );
-                });
-            });
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(btn_w, 32.0),
+                egui::Sense::click(),
+            );
 
-            ui.add_space(25.0);
+            let hover_bg = if response.hovered() && !is_selected {
+                Color32::from_rgb(55, 55, 65)
+            } else {
+                bg
+            };
 
-            ui.vertical(|ui| {
-                ui.label(egui::RichText::new("DUCKING").size(LABEL_FONT).color(Color32::from_gray(140)));
-                ui.add_space(6.0);
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 5.0;
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_ducking, "DUCK",
-                        0.0, 1.0, SliderScale::Linear, duck_color,
-                        &[(0.0, "OFF"), (0.25, "-3dB"), (0.5, "-6dB"), (0.75, "-12dB"), (1.0, "-∞")], None,
-                    );
-                });
-            });
+            ui.painter().rect_filled(rect, 4.0, hover_bg);
+            if is_selected {
+                ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, accent), egui::epaint::StrokeKind::Inside);
+            }
 
-            ui.add_space(25.0);
+            let font = egui::FontId::proportional(14.0);
+            let galley = ui.painter().layout_no_wrap(label.to_string(), font, text_col);
+            ui.painter().galley(
+                egui::pos2(rect.center().x - galley.size().x / 2.0, rect.center().y - galley.size().y / 2.0),
+                galley,
+                text_col,
+            );
 
-            ui.vertical(|ui| {
-                ui.label(egui::RichText::new("SIDECHAIN").size(LABEL_FONT).color(Color32::from_gray(140)));
-                ui.add_space(6.0);
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 5.0;
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_rhythm_duck_depth, "DPTH",
-                        0.0, 1.0, SliderScale::Linear, duck_color,
-                        &[(0.0, "OFF"), (0.25, "-3dB"), (0.5, "-6dB"), (0.75, "-12dB"), (1.0, "-∞")], None,
-                    );
-                    render_vertical_slider_with_ticks(
-                        ui, params, setter, &params.synth_reverb_rhythm_duck_smooth, "SMTH",
-                        10.0, 300.0, SliderScale::Logarithmic, duck_color,
-                        &[(10.0, "10ms"), (50.0, "50"), (100.0, "100"), (200.0, "200"), (300.0, "300")], None,
-                    );
-                });
-            });
-        });
+            if response.clicked() {
+                setter.set_parameter(param, *value);
+                clicked = Some(*value);
+            }
+        }
     });
+    clicked
 }
 
 fn render_comp_tab(
@@ -2375,16 +3722,6 @@ fn render_comp_tab(
         ui.add_space(6.0);
         render_comp_lookahead_buttons(ui, params, setter);
 
-        ui.add_space(13.0);
-        ui.label(egui::RichText::new("ROUTE").size(LABEL_FONT).color(Color32::from_gray(140)));
-        ui.add_space(6.0);
-        ui.horizontal(|ui| {
-            ui.spacing_mut().item_spacing.x = 16.0;
-            render_route_toggle(ui, setter, &params.comp_route_master, "MSTR");
-            render_route_toggle(ui, setter, &params.comp_route_looper, "LOOP");
-            render_route_toggle(ui, setter, &params.comp_route_reverb, "VERB");
-        });
-
         ui.add_space(10.0);
         render_comp_status(ui, ui_state);
     });
@@ -2462,6 +3799,141 @@ fn render_comp_tab(
     });
 }
 
+fn render_chain_tab(
+    ui: &mut egui::Ui,
+    params: &Arc<DeviceParams>,
+    setter: &ParamSetter,
+) {
+    let accent = Color32::from_rgb(200, 150, 80);
+    let order = params.fx_order();
+
+    let content_rect = ui.max_rect();
+    let half_w = content_rect.width() / 2.0;
+    let sep_x = content_rect.left() + half_w;
+    let margin = FRAME_MARGIN;
+
+    let write_order = |order: &[FxModule; FX_SLOT_COUNT]| {
+        for (slot, module) in params.fx_slots().into_iter().zip(order) {
+            setter.set_parameter(slot, module.to_index());
+        }
+    };
+
+    let left_rect = egui::Rect::from_min_max(
+        egui::pos2(content_rect.left() + margin.left as f32 + 5.0, content_rect.top() + margin.top as f32),
+        egui::pos2(sep_x - 10.0, content_rect.bottom()),
+    );
+    let mut left_ui = ui.new_child(egui::UiBuilder::new().max_rect(left_rect));
+    left_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("MASTER CHAIN").size(HEADER_FONT).strong());
+        ui.add_space(9.0);
+
+        ui.spacing_mut().item_spacing.y = 4.0;
+        for (slot, module) in order.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 4.0;
+                ui.add_sized(
+                    egui::vec2(24.0, 32.0),
+                    egui::Label::new(egui::RichText::new(format!("{}", slot + 1)).size(14.0).color(Color32::from_gray(120))),
+                );
+                ui.add_sized(
+                    egui::vec2(130.0, 32.0),
+                    egui::Label::new(egui::RichText::new(module.label()).size(14.0).color(Color32::from_gray(210))),
+                );
+                if render_chain_button(ui, "\u{25B2}", 32.0, slot > 0, accent) {
+                    let mut reordered = order;
+                    reordered.swap(slot, slot - 1);
+                    write_order(&reordered);
+                }
+                if render_chain_button(ui, "\u{25BC}", 32.0, slot + 1 < FX_SLOT_COUNT, accent) {
+                    let mut reordered = order;
+                    reordered.swap(slot, slot + 1);
+                    write_order(&reordered);
+                }
+                if let Some(send_param) = params.fx_send_param(*module) {
+                    ui.add_space(8.0);
+                    let label = if send_param.value() { "SEND" } else { "INS" };
+                    if render_chain_button(ui, label, 56.0, true, accent) {
+                        setter.set_parameter(send_param, !send_param.value());
+                    }
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.add_sized(
+                egui::vec2(24.0, 32.0),
+                egui::Label::new(egui::RichText::new("-").size(14.0).color(Color32::from_gray(80))),
+            );
+            ui.add_sized(
+                egui::vec2(130.0, 32.0),
+                egui::Label::new(egui::RichText::new("LIMITER").size(14.0).color(Color32::from_gray(100))),
+            );
+        });
+
+        ui.add_space(13.0);
+        if render_chain_button(ui, "RESET", 80.0, order != DEFAULT_FX_ORDER, accent) {
+            write_order(&DEFAULT_FX_ORDER);
+        }
+    });
+
+    ui.painter().line_segment(
+        [egui::pos2(sep_x, content_rect.top()), egui::pos2(sep_x, content_rect.bottom())],
+        egui::Stroke::new(1.0, Color32::BLACK),
+    );
+
+    let right_rect = egui::Rect::from_min_max(
+        egui::pos2(sep_x + margin.left as f32, content_rect.top() + margin.top as f32),
+        egui::pos2(content_rect.right() - margin.right as f32, content_rect.bottom()),
+    );
+    let mut right_ui = ui.new_child(egui::UiBuilder::new().max_rect(right_rect));
+    right_ui.vertical(|ui| {
+        ui.label(egui::RichText::new("ROUTING").size(LABEL_FONT).color(Color32::from_gray(140)));
+        ui.add_space(6.0);
+        let text_col = Color32::from_gray(170);
+        ui.label(egui::RichText::new(
+            "Modules run top to bottom on the master bus. The limiter always runs last."
+        ).size(14.0).color(text_col));
+        ui.add_space(6.0);
+        ui.label(egui::RichText::new(
+            "INS processes the bus in place. SEND taps the bus at its slot and adds \
+             only the wet signal back after the last slot, so later modules do not \
+             process it."
+        ).size(14.0).color(text_col));
+        ui.add_space(6.0);
+        ui.label(egui::RichText::new(
+            "The reverb's LOOP send only hears the looper when the looper sits above it."
+        ).size(14.0).color(text_col));
+    });
+}
+
+fn render_chain_button(
+    ui: &mut egui::Ui,
+    label: &str,
+    btn_w: f32,
+    enabled: bool,
+    accent: Color32,
+) -> bool {
+    let sense = if enabled { egui::Sense::click() } else { egui::Sense::hover() };
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(btn_w, 32.0), sense);
+
+    let bg = if enabled && response.hovered() {
+        Color32::from_rgb(55, 55, 65)
+    } else {
+        Color32::from_rgb(40, 40, 48)
+    };
+    let text_col = if enabled { accent } else { Color32::from_gray(70) };
+
+    ui.painter().rect_filled(rect, 4.0, bg);
+    let font = egui::FontId::proportional(14.0);
+    let galley = ui.painter().layout_no_wrap(label.to_string(), font, text_col);
+    ui.painter().galley(
+        egui::pos2(rect.center().x - galley.size().x / 2.0, rect.center().y - galley.size().y / 2.0),
+        galley,
+        text_col,
+    );
+
+    enabled && response.clicked()
+}
+
 fn render_comp_auto_makeup_toggle(
     ui: &mut egui::Ui,
     params: &Arc<DeviceParams>,
@@ -2839,6 +4311,7 @@ fn render_looper_section(
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = 16.0;
             render_route_toggle_dimmed(ui, setter, &params.looper_input_saw, "SAW", oscs_dimmed);
+            render_route_toggle_dimmed(ui, setter, &params.looper_input_wt, "WT", oscs_dimmed);
             render_route_toggle_dimmed(ui, setter, &params.looper_input_filter, "FLTR", lp_premaster);
         });
         ui.add_space(6.0);
@@ -3228,13 +4701,13 @@ fn is_selected_param(ml: &MidiLearnState, param_id: &str) -> bool {
     idx < SOUND_PARAMS.len() && SOUND_PARAMS[idx] == param_id
 }
 
-enum SliderScale {
+pub(super) enum SliderScale {
     Linear,
     Logarithmic,
     Exponential(f32),
 }
 
-fn render_vertical_slider<P: Param>(
+pub(super) fn render_vertical_slider<P: Param>(
     ui: &mut egui::Ui,
     params: &Arc<DeviceParams>,
     setter: &ParamSetter,
@@ -3779,6 +5252,20 @@ fn param_id_to_mod_dest_index(param_id: &str) -> Option<i32> {
         "synth_saw_fold" => Some(28),
         "synth_saw_shape_amount" => Some(29),
         "synth_saw_volume" => Some(30),
+        "synth_wt_position" => Some(67),
+        "synth_wt_volume" => Some(68),
+        "synth_noise_level" => Some(69),
+        "synth_sync_soft" => Some(70),
+        "synth_ring_pll" => Some(71),
+        "synth_ring_vps_saw" => Some(72),
+        "synth_xfm_vps_saw" => Some(73),
+        "synth_xfm_saw_ref" => Some(74),
+        "synth_filter2_cutoff" => Some(75),
+        "synth_filter2_resonance" => Some(76),
+        "synth_filter2_morph" => Some(77),
+        "synth_osc_tune" => Some(78),
+        "synth_saw_tune" => Some(79),
+        "synth_pll_ref_tune" => Some(80),
         "synth_env_range" => Some(39),
         "synth_pll_tail_amount" => Some(40),
         "synth_pll_tail_time" => Some(41),
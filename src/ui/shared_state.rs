@@ -7,11 +7,15 @@ use crate::sequencer::styles::StyleConfig;
 use crate::sequencer::multi_bar::MultiBarConfig;
 use crate::sequencer::melodic_engine::MelodicConfig;
 use crate::sequencer::ml_dataset::MlDataset;
+use crate::synth::wavetable::{Wavetable, WavetableExchange};
+use crate::synth::mseg::MSEG_COUNT;
+use crate::scenes::SceneControls;
 use crate::sequencer::BeatLinks;
-use crate::preset::PresetManager;
+use crate::preset::{MorphSnapshots, PatternData, PresetManager};
 use crate::midi_modes::MidiModeDisplay;
 use crate::midi_devices::{MidiDeviceManager, MidiInputQueue, MidiOutputQueue};
 use crate::midi_learn::MidiLearnState;
+use crate::tempo::{TempoMode, TransportControls};
 
 #[derive(Clone)]
 pub struct SharedUiState {
@@ -29,15 +33,23 @@ pub struct SharedUiState {
     pub melodic_config: Arc<Mutex<MelodicConfig>>,
     pub ml_dataset: Arc<Mutex<Arc<MlDataset>>>,
     pub ml_dataset_dirty: Arc<AtomicBool>,
+    pub wavetable: Arc<WavetableExchange>,
+    pub wavetable_dirty: Arc<AtomicBool>,
     pub request_dsp_reset: Arc<AtomicBool>,
     pub seq_data_dirty: Arc<AtomicBool>,
     pub midi_mode: Arc<AtomicU8>,
     pub midi_mode_display: Arc<Mutex<MidiModeDisplay>>,
     pub midi_clear_memory: Arc<AtomicBool>,
+    pub call_response_bars: Arc<AtomicU8>,
+    pub transpose_scale_aware: Arc<AtomicBool>,
+    pub transpose_quantize: Arc<AtomicU8>,
+    pub transpose_gate: Arc<AtomicBool>,
     pub sample_rate: Arc<AtomicU32>,
     pub limiter_latency_samples: Arc<AtomicU32>,
     pub comp_latency_samples: Arc<AtomicU32>,
     pub comp_gr_db: Arc<AtomicU32>,
+    /// The host opened the stem layout with its aux outputs.
+    pub stems_active: Arc<AtomicBool>,
     pub midi_device_manager: Arc<Mutex<MidiDeviceManager>>,
     pub midi_device_input_queue: MidiInputQueue,
     pub midi_device_output_queue: MidiOutputQueue,
@@ -46,13 +58,27 @@ pub struct SharedUiState {
     pub midi_clock_out: Arc<AtomicBool>,
     pub midi_transport_in: Arc<AtomicBool>,
     pub midi_transport_out: Arc<AtomicBool>,
+    pub clock_offset_ms: Arc<AtomicU32>,
+    pub clock_din_sync: Arc<AtomicBool>,
+    pub clock_start_on_bar: Arc<AtomicBool>,
+    pub clock_swing: Arc<AtomicBool>,
     pub midi_transport_start: Arc<AtomicBool>,
     pub midi_transport_stop: Arc<AtomicBool>,
     pub soft_takeover: Arc<AtomicBool>,
     pub beat_links: Arc<Mutex<BeatLinks>>,
     pub restored_oversampling: Arc<AtomicI32>,
     pub mod_seq_step: Arc<AtomicU8>,
+    pub mseg_dirty: Arc<AtomicBool>,
+    /// Playhead of each MSEG in points, as f32 bits.
+    pub mseg_position: Arc<[AtomicU32; MSEG_COUNT]>,
+    pub macro_dirty: Arc<AtomicBool>,
+    /// A/B morph endpoints handed to the audio thread, which swaps them for
+    /// its previous set so the old one is freed here.
+    pub morph_snapshots: Arc<Mutex<MorphSnapshots>>,
+    pub morph_dirty: Arc<AtomicBool>,
     pub current_tempo: Arc<AtomicU32>,
+    pub transport: Arc<TransportControls>,
+    pub scenes: Arc<SceneControls>,
 }
 
 impl SharedUiState {
@@ -69,6 +95,8 @@ impl SharedUiState {
 
         let input_queue = midi_mgr.input_queue();
         let output_queue = midi_mgr.output_queue();
+        let midi_device_manager = Arc::new(Mutex::new(midi_mgr));
+        MidiDeviceManager::start_background_thread(&midi_device_manager);
         let restored_midi_mode = cfg.midi_mode;
         let midi_learn = Arc::new(MidiLearnState::with_mappings(
             cfg.midi_learn_mappings_data(),
@@ -91,16 +119,23 @@ impl SharedUiState {
             melodic_config: Arc::new(Mutex::new(MelodicConfig::default())),
             ml_dataset: Arc::new(Mutex::new(Arc::new(MlDataset::builtin()))),
             ml_dataset_dirty: Arc::new(AtomicBool::new(true)),
+            wavetable: Arc::new(WavetableExchange::new(Arc::new(Wavetable::builtin()))),
+            wavetable_dirty: Arc::new(AtomicBool::new(true)),
             request_dsp_reset: Arc::new(AtomicBool::new(false)),
             seq_data_dirty: Arc::new(AtomicBool::new(true)),
             midi_mode: Arc::new(AtomicU8::new(restored_midi_mode)),
             midi_mode_display: Arc::new(Mutex::new(MidiModeDisplay::default())),
             midi_clear_memory: Arc::new(AtomicBool::new(false)),
+            call_response_bars: Arc::new(AtomicU8::new(cfg.call_response_bars)),
+            transpose_scale_aware: Arc::new(AtomicBool::new(cfg.transpose_scale_aware)),
+            transpose_quantize: Arc::new(AtomicU8::new(cfg.transpose_quantize)),
+            transpose_gate: Arc::new(AtomicBool::new(cfg.transpose_gate)),
             sample_rate: Arc::new(AtomicU32::new(44100)),
             limiter_latency_samples: Arc::new(AtomicU32::new(0)),
             comp_latency_samples: Arc::new(AtomicU32::new(0)),
             comp_gr_db: Arc::new(AtomicU32::new(0)),
-            midi_device_manager: Arc::new(Mutex::new(midi_mgr)),
+            stems_active: Arc::new(AtomicBool::new(false)),
+            midi_device_manager,
             midi_device_input_queue: input_queue,
             midi_device_output_queue: output_queue,
             midi_learn,
@@ -108,16 +143,54 @@ impl SharedUiState {
             midi_clock_out: Arc::new(AtomicBool::new(cfg.midi_clock_out)),
             midi_transport_in: Arc::new(AtomicBool::new(cfg.midi_transport_in)),
             midi_transport_out: Arc::new(AtomicBool::new(cfg.midi_transport_out)),
+            clock_offset_ms: Arc::new(AtomicU32::new(cfg.clock_offset_ms.to_bits())),
+            clock_din_sync: Arc::new(AtomicBool::new(cfg.clock_din_sync)),
+            clock_start_on_bar: Arc::new(AtomicBool::new(cfg.clock_start_on_bar)),
+            clock_swing: Arc::new(AtomicBool::new(cfg.clock_swing)),
             midi_transport_start: Arc::new(AtomicBool::new(false)),
             midi_transport_stop: Arc::new(AtomicBool::new(false)),
             soft_takeover: Arc::new(AtomicBool::new(cfg.soft_takeover)),
             beat_links: Arc::new(Mutex::new(BeatLinks::new())),
             restored_oversampling: Arc::new(AtomicI32::new(cfg.oversampling)),
             mod_seq_step: Arc::new(AtomicU8::new(0)),
+            mseg_dirty: Arc::new(AtomicBool::new(true)),
+            mseg_position: Arc::new(std::array::from_fn(|_| AtomicU32::new(0))),
+            macro_dirty: Arc::new(AtomicBool::new(true)),
+            morph_snapshots: Arc::new(Mutex::new(MorphSnapshots::default())),
+            morph_dirty: Arc::new(AtomicBool::new(false)),
             current_tempo: Arc::new(AtomicU32::new(12000)),
+            transport: Arc::new(TransportControls::new(TempoMode::from_index(cfg.tempo_mode), cfg.manual_bpm)),
+            scenes: Arc::new(SceneControls::new()),
         }
     }
 
+    /// Writes a sequencer pattern from the audio thread and flags it for the
+    /// sequencer. Writes nothing and returns false while the editor holds one
+    /// of the locks.
+    pub fn try_store_pattern(&self, pattern: &PatternData) -> bool {
+        let (Ok(mut strength_values), Ok(mut note_pool), Ok(mut scale), Ok(mut stability_pattern), Ok(mut octave_randomization), Ok(mut style_config)) = (
+            self.strength_values.try_lock(),
+            self.note_pool.try_lock(),
+            self.scale.try_lock(),
+            self.stability_pattern.try_lock(),
+            self.octave_randomization.try_lock(),
+            self.style_config.try_lock(),
+        ) else {
+            return false;
+        };
+        pattern.write_into(
+            &mut strength_values,
+            &mut note_pool,
+            &mut scale,
+            &mut stability_pattern,
+            &mut octave_randomization,
+            &mut style_config,
+        );
+        self.increment_preset_version();
+        self.mark_seq_dirty();
+        true
+    }
+
     pub fn increment_preset_version(&self) {
         self.preset_version.fetch_add(1, Ordering::SeqCst);
     }
@@ -144,6 +217,15 @@ impl SharedUiState {
         self.output_level.load(Ordering::Relaxed) as f32 / 1000.0
     }
 
+    pub fn set_wavetable(&self, table: Arc<Wavetable>) {
+        self.wavetable.set(table);
+        self.wavetable_dirty.store(true, Ordering::Release);
+    }
+
+    pub fn wavetable_name(&self) -> String {
+        self.wavetable.current().map(|t| t.name.clone()).unwrap_or_default()
+    }
+
     pub fn mark_seq_dirty(&self) {
         self.seq_data_dirty.store(true, Ordering::Release);
     }
@@ -152,6 +234,22 @@ impl SharedUiState {
         self.seq_data_dirty.swap(false, Ordering::AcqRel)
     }
 
+    pub fn mark_mseg_dirty(&self) {
+        self.mseg_dirty.store(true, Ordering::Release);
+    }
+
+    pub fn mark_macro_dirty(&self) {
+        self.macro_dirty.store(true, Ordering::Release);
+    }
+
+    pub fn mark_morph_dirty(&self) {
+        self.morph_dirty.store(true, Ordering::Release);
+    }
+
+    pub fn mseg_position(&self, idx: usize) -> f32 {
+        f32::from_bits(self.mseg_position[idx].load(Ordering::Relaxed))
+    }
+
     pub fn request_dsp_reset(&self) {
         self.request_dsp_reset.store(true, Ordering::SeqCst);
     }